@@ -22,11 +22,16 @@ pub enum FactoryError {
     InvalidTickRange = 14,
     InvalidInitialPrice = 15,
     InvalidCreatorFee = 16,
+    InvalidProtocolFee = 17,
+    PoolNotFound = 18,
+    InitialPriceDeviation = 19,
     
     // Liquidity
     InsufficientInitialLiquidity = 20,
     InvalidLockDuration = 21,
     LiquidityStillLocked = 22,
+    PermanentLock = 23,
+    InvalidToken = 24,
     
     // Creator
     NotPoolCreator = 30,
@@ -2,6 +2,8 @@
 
 use soroban_sdk::{Address, Env, Symbol};
 
+use crate::types::PoolInfo;
+
 /// Emitted when factory is initialized
 pub fn emit_initialized(env: &Env, admin: &Address) {
     env.events().publish(
@@ -19,17 +21,18 @@ pub fn emit_router_set(env: &Env, router: &Address) {
 }
 
 /// Emitted when a new pool is created
-pub fn emit_pool_created(
-    env: &Env,
-    pool: &Address,
-    token0: &Address,
-    token1: &Address,
-    creator: &Address,
-    fee_bps: u32,
-) {
+pub fn emit_pool_created(env: &Env, info: &PoolInfo) {
     env.events().publish(
         (Symbol::new(env, "PoolCreated"),),
-        (pool.clone(), token0.clone(), token1.clone(), creator.clone(), fee_bps),
+        (
+            info.pool.clone(),
+            info.token0.clone(),
+            info.token1.clone(),
+            info.creator.clone(),
+            info.fee_bps,
+            info.created_at_ledger,
+            info.created_at_ts,
+        ),
     );
 }
 
@@ -72,6 +75,22 @@ pub fn emit_fee_tier_updated(env: &Env, fee_bps: u32, tick_spacing: i32, enabled
     );
 }
 
+/// Emitted when the protocol fee for new pools is updated
+pub fn emit_protocol_fee_updated(env: &Env, protocol_fee_bps: u32) {
+    env.events().publish(
+        (Symbol::new(env, "ProtocolFeeUpdated"),),
+        (protocol_fee_bps,),
+    );
+}
+
+/// Emitted when a pool is enabled or disabled for routing
+pub fn emit_pool_enabled_updated(env: &Env, pool: &Address, enabled: bool) {
+    env.events().publish(
+        (Symbol::new(env, "PoolEnabledUpdated"),),
+        (pool.clone(), enabled),
+    );
+}
+
 /// Emitted when admin is updated
 pub fn emit_admin_updated(env: &Env, old_admin: &Address, new_admin: &Address) {
     env.events().publish(
@@ -21,6 +21,8 @@ use soroban_sdk::{
     contract, contractimpl, vec, Address, BytesN, Env, IntoVal, Symbol, Vec,
     token, xdr::ToXdr,
 };
+use belugaswap_math::constants::{BPS_DENOMINATOR, MAX_PROTOCOL_FEE_BPS, MIN_TICK, MAX_TICK};
+use belugaswap_math::{mul_div, sort_tokens};
 
 mod error;
 mod events;
@@ -45,6 +47,9 @@ const MIN_INITIAL_LIQUIDITY: i128 = 1_000_000;
 /// Maximum fee in bps (1%)
 const MAX_FEE_BPS: u32 = 100;
 
+/// Maximum pools returned by one `get_pool_addresses_page` call
+const MAX_POOL_PAGE_SIZE: u32 = 100;
+
 // ============================================================
 // CONTRACT
 // ============================================================
@@ -76,6 +81,7 @@ impl BelugaFactory {
             admin: admin.clone(),
             pool_wasm_hash,
             router: None,
+            protocol_fee_bps: 0,
         };
         write_config(&env, &config);
         set_initialized(&env);
@@ -162,11 +168,11 @@ impl BelugaFactory {
         }
         
         // Sort tokens
-        let (token0, token1) = sort_tokens(&token_a, &token_b);
-        let (amt0, amt1) = if token_a < token_b {
-            (amount0_desired, amount1_desired)
-        } else {
+        let (token0, token1, swapped) = sort_tokens(&token_a, &token_b);
+        let (amt0, amt1) = if swapped {
             (amount1_desired, amount0_desired)
+        } else {
+            (amount0_desired, amount1_desired)
         };
         
         // Check pool doesn't exist
@@ -193,6 +199,33 @@ impl BelugaFactory {
             return Err(FactoryError::InsufficientInitialLiquidity);
         }
         
+        // Validate extra seed ranges, reordered like the primary amounts
+        let mut ranges = vec![&env, (lower_tick, upper_tick, amt0, amt1)];
+        let (mut total0, mut total1) = (amt0, amt1);
+        for seed in params.extra_ranges.iter() {
+            if seed.lower_tick >= seed.upper_tick {
+                return Err(FactoryError::InvalidTickRange);
+            }
+            if seed.lower_tick % tick_spacing != 0 || seed.upper_tick % tick_spacing != 0 {
+                return Err(FactoryError::InvalidTickSpacing);
+            }
+            // Each range must be a distinct position
+            if ranges.iter().any(|(l, u, _, _)| l == seed.lower_tick && u == seed.upper_tick) {
+                return Err(FactoryError::InvalidTickRange);
+            }
+            let (seed0, seed1) = if swapped {
+                (seed.amount1_desired, seed.amount0_desired)
+            } else {
+                (seed.amount0_desired, seed.amount1_desired)
+            };
+            if seed0 < 0 || seed1 < 0 || (seed0 == 0 && seed1 == 0) {
+                return Err(FactoryError::InsufficientInitialLiquidity);
+            }
+            total0 = total0.checked_add(seed0).ok_or(FactoryError::InsufficientInitialLiquidity)?;
+            total1 = total1.checked_add(seed1).ok_or(FactoryError::InsufficientInitialLiquidity)?;
+            ranges.push_back((seed.lower_tick, seed.upper_tick, seed0, seed1));
+        }
+        
         // Validate lock duration
         let is_permanent = lock_duration == 0;
         if !is_permanent && lock_duration < MIN_LOCK_DURATION {
@@ -200,13 +233,45 @@ impl BelugaFactory {
         }
         
         // Validate creator fee (0.1% - 10%)
-        if creator_fee_bps < 10 || creator_fee_bps > 1000 {
+        if !(10..=1000).contains(&creator_fee_bps) {
             return Err(FactoryError::InvalidCreatorFee);
         }
         
-        // Validate price
-        if initial_sqrt_price_x64 == 0 {
-            return Err(FactoryError::InvalidInitialPrice);
+        // Validate price: exactly one of sqrt price or tick
+        let (initial_sqrt_price_x64, current_tick) = match params.initial_tick {
+            Some(_) if initial_sqrt_price_x64 != 0 => {
+                return Err(FactoryError::InvalidInitialPrice);
+            }
+            Some(tick) => {
+                if !(MIN_TICK..=MAX_TICK).contains(&tick) {
+                    return Err(FactoryError::InvalidInitialPrice);
+                }
+                if tick % tick_spacing != 0 {
+                    return Err(FactoryError::InvalidTickSpacing);
+                }
+                (belugaswap_math::get_sqrt_ratio_at_tick(tick), tick)
+            }
+            None => {
+                if initial_sqrt_price_x64 == 0 {
+                    return Err(FactoryError::InvalidInitialPrice);
+                }
+                (initial_sqrt_price_x64, belugaswap_math::get_tick_at_sqrt_ratio(initial_sqrt_price_x64))
+            }
+        };
+        
+        // Guard against initializing far from a reference price
+        if let Some(reference) = params.reference_sqrt_price_x64 {
+            if reference == 0 {
+                return Err(FactoryError::InvalidInitialPrice);
+            }
+            if exceeds_deviation(&env, initial_sqrt_price_x64, reference, params.max_init_deviation_bps) {
+                return Err(FactoryError::InitialPriceDeviation);
+            }
+        }
+        
+        // Both tokens must answer a token call before anything is deployed
+        if !is_token(&env, &token0) || !is_token(&env, &token1) {
+            return Err(FactoryError::InvalidToken);
         }
         
         // === DEPLOY POOL ===
@@ -215,7 +280,6 @@ impl BelugaFactory {
         // === INITIALIZE POOL ===
         let factory_address = env.current_contract_address();
         let router_address = config.router.unwrap(); // Safe: checked above
-        let current_tick = belugaswap_math::get_tick_at_sqrt_ratio(initial_sqrt_price_x64);
         
         let _: () = env.invoke_contract(
             &pool_address,
@@ -235,28 +299,41 @@ impl BelugaFactory {
             ],
         );
         
+        // Protocol fee switch is off by default in the pool
+        if config.protocol_fee_bps > 0 {
+            let _: () = env.invoke_contract(
+                &pool_address,
+                &Symbol::new(&env, "set_protocol_fee"),
+                vec![
+                    &env,
+                    factory_address.into_val(&env),
+                    config.protocol_fee_bps.into_val(&env),
+                ],
+            );
+        }
+        
         // === TRANSFER TOKENS ===
-        token::Client::new(&env, &token0).transfer(&creator, &pool_address, &amt0);
-        token::Client::new(&env, &token1).transfer(&creator, &pool_address, &amt1);
+        // Exactly the summed amounts of every range; the pool refunds what
+        // the positions do not use
+        token::Client::new(&env, &token0).transfer(&creator, &pool_address, &total0);
+        token::Client::new(&env, &token1).transfer(&creator, &pool_address, &total1);
         
         // === ADD LIQUIDITY ===
-        let liquidity: i128 = env.invoke_contract(
+        let liquidities: Vec<i128> = env.invoke_contract(
             &pool_address,
-            &Symbol::new(&env, "mint"),
+            &Symbol::new(&env, "mint_ranges"),
             vec![
                 &env,
                 creator.clone().into_val(&env),
-                lower_tick.into_val(&env),
-                upper_tick.into_val(&env),
-                amt0.into_val(&env),
-                amt1.into_val(&env),
+                ranges.into_val(&env),
             ],
         );
         
-        if liquidity <= 0 {
+        // Only the primary position is locked
+        let liquidity = liquidities.get(0).unwrap_or(0);
+        if liquidities.iter().any(|l| l <= 0) {
             return Err(FactoryError::InsufficientInitialLiquidity);
         }
-        
         // === REGISTER LOCK ===
         let current_ledger = env.ledger().sequence();
         let lock_end = if is_permanent {
@@ -280,18 +357,31 @@ impl BelugaFactory {
         write_creator_lock(&env, &pool_address, &creator, &creator_lock);
         
         // === REGISTER POOL ===
+        let info = PoolInfo {
+            pool: pool_address.clone(),
+            token0: token0.clone(),
+            token1: token1.clone(),
+            fee_bps,
+            creator: creator.clone(),
+            created_at_ledger: current_ledger,
+            created_at_ts: env.ledger().timestamp(),
+        };
         write_pool(&env, &token0, &token1, fee_bps, &pool_address);
+        write_pool_info(&env, &info);
         add_to_pool_list(&env, &pool_address);
         increment_pool_count(&env);
         
         // === EMIT EVENTS ===
-        emit_pool_created(&env, &pool_address, &token0, &token1, &creator, fee_bps);
+        emit_pool_created(&env, &info);
         emit_creator_locked(&env, &pool_address, &creator, liquidity, lock_end, is_permanent);
         
         Ok(pool_address)
     }
     
     /// Unlock creator liquidity (REVOKES CREATOR FEE PERMANENTLY!)
+    /// 
+    /// Timed locks unlock in full once past `lock_end`. Permanent locks fail
+    /// with `PermanentLock`.
     pub fn unlock_creator_liquidity(
         env: Env,
         pool_address: Address,
@@ -299,37 +389,62 @@ impl BelugaFactory {
     ) -> Result<i128, FactoryError> {
         creator.require_auth();
         
-        let mut lock = read_creator_lock(&env, &pool_address, &creator)
-            .ok_or(FactoryError::CreatorLockNotFound)?;
+        let lock = Self::read_unlockable_lock(&env, &pool_address, &creator)?;
         
-        if lock.creator != creator {
-            return Err(FactoryError::NotPoolCreator);
-        }
+        Ok(Self::release_creator_lock(&env, &pool_address, &creator, lock))
+    }
+    
+    /// Request an unlock of creator liquidity (REVOKES CREATOR FEE PERMANENTLY!)
+    /// 
+    /// Unlocks like `unlock_creator_liquidity` and records the request ledger
+    /// (see `get_unlock_requested_at`).
+    pub fn request_unlock(
+        env: Env,
+        pool_address: Address,
+        creator: Address,
+    ) -> Result<i128, FactoryError> {
+        let liquidity = Self::unlock_creator_liquidity(env.clone(), pool_address.clone(), creator.clone())?;
         
-        if lock.is_unlocked {
-            return Err(FactoryError::CreatorFeeRevoked);
-        }
+        write_unlock_requested_at(&env, &pool_address, &creator, env.ledger().sequence());
         
-        // Check if permanent lock
-        if lock.is_permanent {
-            return Err(FactoryError::LiquidityStillLocked);
-        }
+        Ok(liquidity)
+    }
+    
+    // ========================================================
+    // POOL REPORTING
+    // ========================================================
+    
+    /// Record a pool's current token balances for the factory TVL
+    /// 
+    /// Called by pools with TVL reporting on (see the pool's
+    /// `set_tvl_reporting`); the pool must authorize and be registered here.
+    /// The pool's previous report is replaced, so each token's total moves by
+    /// the difference. Negative balances count as zero.
+    pub fn report_reserves(
+        env: Env,
+        pool_address: Address,
+        reserve0: i128,
+        reserve1: i128,
+    ) -> Result<(), FactoryError> {
+        pool_address.require_auth();
         
-        // Check if lock period has ended
-        let current_ledger = env.ledger().sequence();
-        if current_ledger < lock.lock_end {
-            return Err(FactoryError::LiquidityStillLocked);
-        }
+        let info = read_pool_info(&env, &pool_address).ok_or(FactoryError::PoolNotFound)?;
         
-        // REVOKE PERMANENTLY
-        lock.is_unlocked = true;
-        lock.fee_revoked = true;
-        write_creator_lock(&env, &pool_address, &creator, &lock);
+        let reserves = (reserve0.max(0) as u128, reserve1.max(0) as u128);
+        let previous = read_pool_reserves(&env, &pool_address);
         
-        emit_creator_unlocked(&env, &pool_address, &creator, lock.liquidity);
-        emit_creator_fee_revoked(&env, &pool_address, &creator);
+        for (token, previous, value) in [
+            (&info.token0, previous.0, reserves.0),
+            (&info.token1, previous.1, reserves.1),
+        ] {
+            let total = read_token_locked_value(&env, token)
+                .saturating_sub(previous)
+                .saturating_add(value);
+            write_token_locked_value(&env, token, total);
+        }
+        write_pool_reserves(&env, &pool_address, reserves);
         
-        Ok(lock.liquidity)
+        Ok(())
     }
     
     // ========================================================
@@ -337,14 +452,47 @@ impl BelugaFactory {
     // ========================================================
     
     /// Get pool contract address by token pair and fee tier
+    /// 
+    /// Returns `None` for pools disabled by the admin, so the router stops
+    /// discovering them.
     pub fn get_pool_address(
         env: Env, 
         token_a: Address, 
         token_b: Address, 
         fee_bps: u32
     ) -> Option<Address> {
-        let (token0, token1) = sort_tokens(&token_a, &token_b);
+        let (token0, token1, _) = sort_tokens(&token_a, &token_b);
         read_pool(&env, &token0, &token1, fee_bps)
+            .filter(|pool| !is_pool_disabled(&env, pool))
+    }
+    
+    /// Every deployed fee tier for a pair, with its pool address
+    /// 
+    /// One call instead of a `get_pool_address` per tier. Disabled pools are
+    /// left out, as in `get_pool_address`. Tiers come in the order they were
+    /// first configured.
+    pub fn get_pools_for_pair(env: Env, token_a: Address, token_b: Address) -> Vec<(u32, Address)> {
+        let (token0, token1, _) = sort_tokens(&token_a, &token_b);
+        let mut pools = Vec::new(&env);
+        for fee_bps in read_fee_tier_list(&env).iter() {
+            if let Some(pool) = read_pool(&env, &token0, &token1, fee_bps)
+                .filter(|pool| !is_pool_disabled(&env, pool))
+            {
+                pools.push_back((fee_bps, pool));
+            }
+        }
+        pools
+    }
+    
+    /// Registration record of a factory-created pool: tokens, fee tier,
+    /// creator and the ledger and timestamp of creation
+    pub fn get_pool_info(env: Env, pool_address: Address) -> Option<PoolInfo> {
+        read_pool_info(&env, &pool_address)
+    }
+    
+    /// Check if a deployed pool is enabled for routing
+    pub fn is_pool_enabled(env: Env, pool_address: Address) -> bool {
+        is_pool_registered(&env, &pool_address) && !is_pool_disabled(&env, &pool_address)
     }
     
     /// Check if pool is already deployed for this pair+fee
@@ -354,7 +502,7 @@ impl BelugaFactory {
         token_b: Address, 
         fee_bps: u32
     ) -> bool {
-        let (token0, token1) = sort_tokens(&token_a, &token_b);
+        let (token0, token1, _) = sort_tokens(&token_a, &token_b);
         pool_exists(&env, &token0, &token1, fee_bps)
     }
     
@@ -363,11 +511,38 @@ impl BelugaFactory {
         read_pool_count(&env)
     }
     
+    /// Total amount of `token` locked across reporting pools, in its raw units
+    /// 
+    /// Sum of the token's balance in every pool holding it, as each pool last
+    /// reported through `report_reserves`. Pools report on deposits and
+    /// withdrawals only, so swaps since a pool's last liquidity change are not
+    /// included.
+    pub fn get_tvl(env: Env, token: Address) -> u128 {
+        read_token_locked_value(&env, &token)
+    }
+    
     /// Get all deployed pool addresses
+    /// 
+    /// Use `get_pool_addresses_page` once the registry grows large.
     pub fn get_all_pool_addresses(env: Env) -> Vec<Address> {
         read_pool_list(&env)
     }
     
+    /// Get up to `limit` pool addresses starting at index `start`
+    /// 
+    /// `limit` is clamped to MAX_POOL_PAGE_SIZE. A `start` past the end
+    /// returns an empty list.
+    pub fn get_pool_addresses_page(env: Env, start: u32, limit: u32) -> Vec<Address> {
+        let list = read_pool_list(&env);
+        let end = start
+            .saturating_add(limit.min(MAX_POOL_PAGE_SIZE))
+            .min(list.len());
+        if start >= end {
+            return Vec::new(&env);
+        }
+        list.slice(start..end)
+    }
+    
     /// Get fee tier configuration
     pub fn get_fee_tier(env: Env, fee_bps: u32) -> Option<FeeTier> {
         read_fee_tier(&env, fee_bps)
@@ -408,6 +583,37 @@ impl BelugaFactory {
         }
     }
     
+    /// Ledgers until the creator's lock on [lower_tick, upper_tick] expires
+    /// 
+    /// `None` without a lock on that range, 0 once unlocked or expired and
+    /// `u32::MAX` for a permanent lock.
+    pub fn get_remaining_lock_time(
+        env: Env,
+        pool_address: Address,
+        creator: Address,
+        lower_tick: i32,
+        upper_tick: i32,
+    ) -> Option<u32> {
+        let lock = read_creator_lock(&env, &pool_address, &creator)
+            .filter(|lock| lock.lower_tick == lower_tick && lock.upper_tick == upper_tick)?;
+        if lock.is_unlocked {
+            return Some(0);
+        }
+        if lock.is_permanent {
+            return Some(u32::MAX);
+        }
+        Some(lock.lock_end.saturating_sub(env.ledger().sequence()))
+    }
+    
+    /// Ledger of the creator's last `request_unlock` for a pool, if any
+    pub fn get_unlock_requested_at(
+        env: Env,
+        pool_address: Address,
+        creator: Address,
+    ) -> Option<u32> {
+        read_unlock_requested_at(&env, &pool_address, &creator)
+    }
+    
     /// Check if creator fee is still active (not revoked)
     pub fn is_creator_fee_active(
         env: Env,
@@ -420,6 +626,16 @@ impl BelugaFactory {
         }
     }
     
+    /// Get factory admin (also the recipient of protocol fees)
+    pub fn get_admin(env: Env) -> Address {
+        read_config(&env).admin
+    }
+    
+    /// Get protocol fee applied to newly created pools
+    pub fn get_protocol_fee(env: Env) -> u32 {
+        read_config(&env).protocol_fee_bps
+    }
+    
     /// Check if factory is ready (initialized + router set)
     pub fn is_ready(env: Env) -> bool {
         if !is_initialized(&env) {
@@ -457,6 +673,47 @@ impl BelugaFactory {
         Ok(())
     }
     
+    /// Set protocol's share of the LP fee for pools created from now on
+    /// (0 = off, max 2500 bps = 25% of the LP fee)
+    pub fn set_protocol_fee(env: Env, fee_bps: u32) -> Result<(), FactoryError> {
+        let mut config = read_config(&env);
+        config.admin.require_auth();
+        
+        if fee_bps > MAX_PROTOCOL_FEE_BPS {
+            return Err(FactoryError::InvalidProtocolFee);
+        }
+        
+        config.protocol_fee_bps = fee_bps;
+        write_config(&env, &config);
+        
+        emit_protocol_fee_updated(&env, fee_bps);
+        
+        Ok(())
+    }
+    
+    /// Enable or disable a deployed pool for routing
+    /// 
+    /// A disabled pool is hidden from `get_pool_address` but keeps running:
+    /// LPs can still remove liquidity and collect fees directly on the pool.
+    pub fn set_pool_enabled(
+        env: Env,
+        pool_address: Address,
+        enabled: bool,
+    ) -> Result<(), FactoryError> {
+        let config = read_config(&env);
+        config.admin.require_auth();
+        
+        if !is_pool_registered(&env, &pool_address) {
+            return Err(FactoryError::PoolNotFound);
+        }
+        
+        set_pool_disabled(&env, &pool_address, !enabled);
+        
+        emit_pool_enabled_updated(&env, &pool_address, enabled);
+        
+        Ok(())
+    }
+    
     /// Add or update fee tier configuration
     pub fn set_fee_tier(
         env: Env,
@@ -529,7 +786,77 @@ impl BelugaFactory {
         
         env.deployer()
             .with_current_contract(salt)
-            .deploy(config.pool_wasm_hash.clone())
+            .deploy_v2(config.pool_wasm_hash.clone(), ())
     }
     
+    /// Creator lock that may be unlocked now: it exists, belongs to
+    /// `creator`, is still locked, is not permanent and is past `lock_end`
+    fn read_unlockable_lock(
+        env: &Env,
+        pool_address: &Address,
+        creator: &Address,
+    ) -> Result<CreatorLock, FactoryError> {
+        let lock = read_creator_lock(env, pool_address, creator)
+            .ok_or(FactoryError::CreatorLockNotFound)?;
+        
+        if &lock.creator != creator {
+            return Err(FactoryError::NotPoolCreator);
+        }
+        
+        if lock.is_unlocked {
+            return Err(FactoryError::CreatorFeeRevoked);
+        }
+        
+        if lock.is_permanent {
+            return Err(FactoryError::PermanentLock);
+        }
+        
+        if env.ledger().sequence() < lock.lock_end {
+            return Err(FactoryError::LiquidityStillLocked);
+        }
+        
+        Ok(lock)
+    }
+    
+    /// Mark a lock unlocked and revoke the creator fee for good
+    fn release_creator_lock(
+        env: &Env,
+        pool_address: &Address,
+        creator: &Address,
+        mut lock: CreatorLock,
+    ) -> i128 {
+        lock.is_unlocked = true;
+        lock.fee_revoked = true;
+        write_creator_lock(env, pool_address, creator, &lock);
+        
+        emit_creator_unlocked(env, pool_address, creator, lock.liquidity);
+        emit_creator_fee_revoked(env, pool_address, creator);
+        
+        lock.liquidity
+    }
+    
+}
+
+// ============================================================
+// HELPERS
+// ============================================================
+
+/// Whether `token` is a contract that answers `decimals`, as every token
+/// interface implementation (including SACs) does
+fn is_token(env: &Env, token: &Address) -> bool {
+    matches!(
+        env.try_invoke_contract::<u32, soroban_sdk::Error>(
+            token,
+            &Symbol::new(env, "decimals"),
+            Vec::new(env),
+        ),
+        Ok(Ok(_))
+    )
+}
+
+/// Whether `price` is more than `max_bps` away from `reference` (relative to
+/// the reference)
+fn exceeds_deviation(env: &Env, price: u128, reference: u128, max_bps: u32) -> bool {
+    let allowed = mul_div(env, reference, max_bps as u128, BPS_DENOMINATOR);
+    price.abs_diff(reference) > allowed
 }
\ No newline at end of file
@@ -2,7 +2,7 @@
 
 use soroban_sdk::{Address, Env, Vec};
 
-use crate::types::{CreatorLock, DataKey, FactoryConfig, FeeTier};
+use crate::types::{CreatorLock, DataKey, FactoryConfig, FeeTier, PoolInfo};
 
 // ============================================================
 // TTL CONFIG
@@ -51,6 +51,21 @@ pub fn write_fee_tier(env: &Env, fee_bps: u32, tier: &FeeTier) {
     let key = DataKey::FeeTier(fee_bps);
     env.storage().persistent().set(&key, tier);
     extend_ttl(env, &key);
+
+    let mut list = read_fee_tier_list(env);
+    if !list.contains(fee_bps) {
+        list.push_back(fee_bps);
+        env.storage().persistent().set(&DataKey::FeeTierList, &list);
+    }
+    extend_ttl(env, &DataKey::FeeTierList);
+}
+
+/// Every fee tier ever configured, enabled or not
+pub fn read_fee_tier_list(env: &Env) -> Vec<u32> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::FeeTierList)
+        .unwrap_or(Vec::new(env))
 }
 
 pub fn read_fee_tier(env: &Env, fee_bps: u32) -> Option<FeeTier> {
@@ -66,15 +81,6 @@ pub fn read_fee_tier(env: &Env, fee_bps: u32) -> Option<FeeTier> {
 // POOL REGISTRY
 // ============================================================
 
-/// Get canonical token order (sorted)
-pub fn sort_tokens(token_a: &Address, token_b: &Address) -> (Address, Address) {
-    if token_a < token_b {
-        (token_a.clone(), token_b.clone())
-    } else {
-        (token_b.clone(), token_a.clone())
-    }
-}
-
 pub fn pool_exists(env: &Env, token0: &Address, token1: &Address, fee_bps: u32) -> bool {
     env.storage()
         .persistent()
@@ -87,6 +93,21 @@ pub fn write_pool(env: &Env, token0: &Address, token1: &Address, fee_bps: u32, p
     extend_ttl(env, &key);
 }
 
+pub fn write_pool_info(env: &Env, info: &PoolInfo) {
+    let key = DataKey::PoolInfo(info.pool.clone());
+    env.storage().persistent().set(&key, info);
+    extend_ttl(env, &key);
+}
+
+pub fn read_pool_info(env: &Env, pool: &Address) -> Option<PoolInfo> {
+    let key = DataKey::PoolInfo(pool.clone());
+    let result = env.storage().persistent().get(&key);
+    if result.is_some() {
+        extend_ttl(env, &key);
+    }
+    result
+}
+
 pub fn read_pool(env: &Env, token0: &Address, token1: &Address, fee_bps: u32) -> Option<Address> {
     let key = DataKey::Pool(token0.clone(), token1.clone(), fee_bps);
     let result = env.storage().persistent().get(&key);
@@ -121,6 +142,26 @@ pub fn add_to_pool_list(env: &Env, pool: &Address) {
     env.storage().persistent().set(&DataKey::PoolList, &list);
 }
 
+pub fn is_pool_registered(env: &Env, pool: &Address) -> bool {
+    read_pool_list(env).contains(pool)
+}
+
+pub fn is_pool_disabled(env: &Env, pool: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::PoolDisabled(pool.clone()))
+}
+
+pub fn set_pool_disabled(env: &Env, pool: &Address, disabled: bool) {
+    let key = DataKey::PoolDisabled(pool.clone());
+    if disabled {
+        env.storage().persistent().set(&key, &true);
+        extend_ttl(env, &key);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
 pub fn init_pool_list(env: &Env) {
     env.storage()
         .persistent()
@@ -145,4 +186,41 @@ pub fn read_creator_lock(env: &Env, pool: &Address, creator: &Address) -> Option
         extend_ttl(env, &key);
     }
     result
-}
\ No newline at end of file
+}
+
+pub fn write_unlock_requested_at(env: &Env, pool: &Address, creator: &Address, ledger: u32) {
+    let key = DataKey::UnlockRequestedAt(pool.clone(), creator.clone());
+    env.storage().persistent().set(&key, &ledger);
+    extend_ttl(env, &key);
+}
+
+pub fn read_unlock_requested_at(env: &Env, pool: &Address, creator: &Address) -> Option<u32> {
+    let key = DataKey::UnlockRequestedAt(pool.clone(), creator.clone());
+    env.storage().persistent().get(&key)
+}
+
+// ============================================================
+// TVL
+// ============================================================
+
+pub fn read_pool_reserves(env: &Env, pool: &Address) -> (u128, u128) {
+    let key = DataKey::PoolReserves(pool.clone());
+    env.storage().persistent().get(&key).unwrap_or((0, 0))
+}
+
+pub fn write_pool_reserves(env: &Env, pool: &Address, reserves: (u128, u128)) {
+    let key = DataKey::PoolReserves(pool.clone());
+    env.storage().persistent().set(&key, &reserves);
+    extend_ttl(env, &key);
+}
+
+pub fn read_token_locked_value(env: &Env, token: &Address) -> u128 {
+    let key = DataKey::TokenLockedValue(token.clone());
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+pub fn write_token_locked_value(env: &Env, token: &Address, value: u128) {
+    let key = DataKey::TokenLockedValue(token.clone());
+    env.storage().persistent().set(&key, &value);
+    extend_ttl(env, &key);
+}
@@ -1,6 +1,6 @@
 //! Factory type definitions
 
-use soroban_sdk::{contracttype, Address, BytesN};
+use soroban_sdk::{contracttype, Address, BytesN, Vec};
 
 // ============================================================
 // FACTORY CONFIG
@@ -13,6 +13,7 @@ pub struct FactoryConfig {
     pub admin: Address,
     pub pool_wasm_hash: BytesN<32>,
     pub router: Option<Address>,  // Set after router deployment
+    pub protocol_fee_bps: u32,    // Applied to new pools (0 = off)
 }
 
 // ============================================================
@@ -21,6 +22,9 @@ pub struct FactoryConfig {
 
 /// Parameters for creating a new pool
 /// Bundled into struct to stay within 10 param limit
+///
+/// Amounts follow the `token_a`/`token_b` order of the params. Prices and
+/// ticks are always in the pool's sorted order (token1 per token0).
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct CreatePoolParams {
@@ -32,11 +36,18 @@ pub struct CreatePoolParams {
     pub fee_bps: u32,
     /// Creator fee share (10-1000 bps = 0.1%-10%)
     pub creator_fee_bps: u32,
-    /// Initial price as sqrt(price) * 2^64
+    /// Initial price as sqrt(price) * 2^64 (must be 0 when `initial_tick` is set)
     pub initial_sqrt_price_x64: u128,
-    /// Amount of token0 to deposit
+    /// Initial price as a tick, aligned to the fee tier's tick spacing
+    pub initial_tick: Option<i32>,
+    /// Reference sqrt price the initial price is checked against (None = no check)
+    pub reference_sqrt_price_x64: Option<u128>,
+    /// Max deviation of the initial sqrt price from the reference, in bps
+    pub max_init_deviation_bps: u32,
+    /// Amount of `token_a` to deposit (reordered with the tokens, like
+    /// `amount1_desired`, so reversed pairs need no special handling)
     pub amount0_desired: i128,
-    /// Amount of token1 to deposit
+    /// Amount of `token_b` to deposit
     pub amount1_desired: i128,
     /// Lower tick boundary
     pub lower_tick: i32,
@@ -44,6 +55,24 @@ pub struct CreatePoolParams {
     pub upper_tick: i32,
     /// Lock duration in ledgers (0 = permanent)
     pub lock_duration: u32,
+    /// Further positions minted for the creator after the primary one (not locked)
+    pub extra_ranges: Vec<SeedRange>,
+}
+
+/// Additional initial position seeded by `create_pool`
+///
+/// Amounts follow the `token_a`/`token_b` order, like the primary amounts.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SeedRange {
+    /// Lower tick boundary
+    pub lower_tick: i32,
+    /// Upper tick boundary
+    pub upper_tick: i32,
+    /// Amount of `token_a` to deposit
+    pub amount0_desired: i128,
+    /// Amount of `token_b` to deposit
+    pub amount1_desired: i128,
 }
 
 // ============================================================
@@ -87,6 +116,30 @@ pub struct CreatorLock {
     pub fee_revoked: bool,      // true = PERMANENT revocation
 }
 
+// ============================================================
+// POOL INFO
+// ============================================================
+
+/// Registration record of a pool created by the factory
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PoolInfo {
+    /// Pool contract address
+    pub pool: Address,
+    /// Sorted first token
+    pub token0: Address,
+    /// Sorted second token
+    pub token1: Address,
+    /// Fee tier
+    pub fee_bps: u32,
+    /// Address that created the pool
+    pub creator: Address,
+    /// Ledger sequence of creation
+    pub created_at_ledger: u32,
+    /// Ledger timestamp of creation
+    pub created_at_ts: u64,
+}
+
 // ============================================================
 // STORAGE KEYS
 // ============================================================
@@ -107,4 +160,16 @@ pub enum DataKey {
     PoolCount,
     /// Creator lock by (pool, creator)
     CreatorLock(Address, Address),
+    /// Set while a pool is hidden from routing
+    PoolDisabled(Address),
+    /// Ledger of the creator's last `request_unlock` by (pool, creator)
+    UnlockRequestedAt(Address, Address),
+    /// (token0, token1) balances a pool last reported
+    PoolReserves(Address),
+    /// Sum of the last reported balances of a token across pools
+    TokenLockedValue(Address),
+    /// Registration record by pool address
+    PoolInfo(Address),
+    /// Every fee_bps ever configured, in the order first written
+    FeeTierList,
 }
\ No newline at end of file
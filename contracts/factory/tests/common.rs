@@ -1,32 +1,83 @@
-use soroban_sdk::{testutils::Address as _, Address, BytesN, Env};
+#![allow(dead_code)]
+
+use soroban_sdk::{testutils::Address as _, token::StellarAssetClient, Address, BytesN, Env, Vec};
 use beluga_factory::{BelugaFactory, BelugaFactoryClient, CreatePoolParams};
 
 pub fn setup_factory(env: &Env) -> (BelugaFactoryClient<'_>, Address) {
     let admin = Address::generate(env);
-    let factory_id = env.register_contract(None, BelugaFactory);
+    let factory_id = env.register(BelugaFactory, ());
     let client = BelugaFactoryClient::new(env, &factory_id);
     let pool_wasm_hash = BytesN::from_array(env, &[0u8; 32]);
     client.initialize(&admin, &pool_wasm_hash);
     (client, admin)
 }
 
+/// Pool contract build, from `make pool-fixture`
+#[allow(clippy::too_many_arguments)]
+pub mod pool {
+    soroban_sdk::contractimport!(file = "tests/fixtures/belugaswap_pool.wasm");
+}
+
+/// Factory that deploys the real pool WASM, with a router set so
+/// `create_pool` goes through
+pub fn setup_factory_with_pool(env: &Env) -> (BelugaFactoryClient<'_>, Address) {
+    // Deploying and running the pool WASM exceeds the default test budget
+    env.cost_estimate().budget().reset_unlimited();
+
+    let (client, admin) = setup_factory(env);
+    client.set_pool_wasm_hash(&env.deployer().upload_contract_wasm(pool::WASM));
+    client.set_router(&Address::generate(env));
+    (client, admin)
+}
+
+/// Token with `amount` minted to each of `holders`
+pub fn create_funded_token(env: &Env, holders: &[&Address], amount: i128) -> Address {
+    let token = create_token(env);
+    for holder in holders {
+        StellarAssetClient::new(env, &token).mint(holder, &amount);
+    }
+    token
+}
+
+/// Create a pool for the pair through `create_pool`, with a fresh creator
+/// funded for the default initial position; returns (pool, creator)
+pub fn create_pool(
+    env: &Env,
+    client: &BelugaFactoryClient,
+    token_a: &Address,
+    token_b: &Address,
+    fee_bps: u32,
+) -> (Address, Address) {
+    let creator = Address::generate(env);
+    for token in [token_a, token_b] {
+        StellarAssetClient::new(env, token).mint(&creator, &1_000_000_000);
+    }
+    let mut params = default_pool_params(env, token_a, token_b);
+    params.fee_bps = fee_bps;
+    (client.create_pool(&creator, &params), creator)
+}
+
 pub fn create_token(env: &Env) -> Address {
     let admin = Address::generate(env);
     let token_id = env.register_stellar_asset_contract_v2(admin.clone());
     token_id.address()
 }
 
-pub fn default_pool_params(_env: &Env, token_a: &Address, token_b: &Address) -> CreatePoolParams {
+pub fn default_pool_params(env: &Env, token_a: &Address, token_b: &Address) -> CreatePoolParams {
     CreatePoolParams {
         token_a: token_a.clone(),
         token_b: token_b.clone(),
         fee_bps: 30,
         creator_fee_bps: 100,
         initial_sqrt_price_x64: 1u128 << 64,
+        initial_tick: None,
+        reference_sqrt_price_x64: None,
+        max_init_deviation_bps: 0,
         amount0_desired: 10_000_000,
         amount1_desired: 10_000_000,
         lower_tick: -600,
         upper_tick: 600,
         lock_duration: 120_960,
+        extra_ranges: Vec::new(env),
     }
 }
\ No newline at end of file
@@ -1,5 +1,6 @@
 mod common;
 
+use beluga_factory::FactoryError;
 use soroban_sdk::{testutils::Address as _, Address, BytesN, Env};
 
 #[test]
@@ -76,4 +77,52 @@ fn test_non_admin_cannot_update_wasm_hash() {
     env.mock_auths(&[]);
     
     client.set_pool_wasm_hash(&new_hash);
-}
\ No newline at end of file
+}
+#[test]
+fn test_set_protocol_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _admin) = common::setup_factory(&env);
+    assert_eq!(client.get_protocol_fee(), 0);
+    
+    client.set_protocol_fee(&2500);
+    assert_eq!(client.get_protocol_fee(), 2500);
+    
+    // Switch back off
+    client.set_protocol_fee(&0);
+    assert_eq!(client.get_protocol_fee(), 0);
+}
+
+#[test]
+fn test_set_protocol_fee_above_max_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _admin) = common::setup_factory(&env);
+    
+    let result = client.try_set_protocol_fee(&2501);
+    assert_eq!(result, Err(Ok(FactoryError::InvalidProtocolFee)));
+    assert_eq!(client.get_protocol_fee(), 0);
+}
+
+#[test]
+#[should_panic]
+fn test_non_admin_cannot_set_protocol_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _admin) = common::setup_factory(&env);
+    env.mock_auths(&[]);
+    
+    client.set_protocol_fee(&1000);
+}
+
+#[test]
+fn test_get_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, admin) = common::setup_factory(&env);
+    assert_eq!(client.get_admin(), admin);
+}
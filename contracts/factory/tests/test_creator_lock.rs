@@ -1,6 +1,7 @@
 mod common;
 
-use soroban_sdk::{testutils::Address as _, Address, Env};
+use beluga_factory::{BelugaFactoryClient, FactoryError};
+use soroban_sdk::{testutils::{Address as _, Ledger}, Address, Env};
 
 #[test]
 fn test_nonexistent_creator_lock() {
@@ -26,4 +27,125 @@ fn test_unlock_nonexistent_lock() {
     let creator = Address::generate(&env);
     
     client.unlock_creator_liquidity(&fake_pool, &creator); // Should fail
-}
\ No newline at end of file
+}
+/// Shortest timed lock `create_pool` accepts
+const LOCK_LEDGERS: u32 = 120_960;
+
+/// Factory with a pool whose creator position over [-600, 600] is locked for
+/// `lock_duration` ledgers (0 locks it permanently)
+fn setup_locked_pool(env: &Env, lock_duration: u32) -> (BelugaFactoryClient<'_>, Address, Address) {
+    env.mock_all_auths();
+    // Keep the contract instances alive past the lock
+    env.ledger().with_mut(|li| {
+        li.min_persistent_entry_ttl = LOCK_LEDGERS * 2;
+        li.max_entry_ttl = LOCK_LEDGERS * 4;
+    });
+
+    let (client, _) = common::setup_factory_with_pool(env);
+    let creator = Address::generate(env);
+    let token_a = common::create_funded_token(env, &[&creator], 1_000_000_000);
+    let token_b = common::create_funded_token(env, &[&creator], 1_000_000_000);
+
+    let mut params = common::default_pool_params(env, &token_a, &token_b);
+    params.lock_duration = lock_duration;
+    let pool = client.create_pool(&creator, &params);
+    (client, pool, creator)
+}
+
+#[test]
+fn test_request_unlock_after_timed_lock_expiry() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, pool, creator) = setup_locked_pool(&env, LOCK_LEDGERS);
+    let lock = client.get_creator_lock(&pool, &creator).unwrap();
+    let lock_end = env.ledger().sequence() + LOCK_LEDGERS;
+    assert_eq!(lock.lock_end, lock_end);
+    
+    // Not yet expired
+    assert_eq!(client.try_request_unlock(&pool, &creator), Err(Ok(FactoryError::LiquidityStillLocked)));
+    assert_eq!(client.get_unlock_requested_at(&pool, &creator), None);
+    
+    env.ledger().set_sequence_number(lock_end);
+    assert_eq!(client.request_unlock(&pool, &creator), lock.liquidity);
+    assert_eq!(client.get_unlock_requested_at(&pool, &creator), Some(lock_end));
+    
+    let lock = client.get_creator_lock(&pool, &creator).unwrap();
+    assert!(lock.is_unlocked && lock.fee_revoked);
+    assert!(!client.is_liquidity_locked(&pool, &creator, &-600, &600));
+    assert!(!client.is_creator_fee_active(&pool, &creator));
+    
+    // Only once
+    assert_eq!(client.try_request_unlock(&pool, &creator), Err(Ok(FactoryError::CreatorFeeRevoked)));
+}
+
+#[test]
+fn test_request_unlock_rejects_permanent_lock() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, pool, creator) = setup_locked_pool(&env, 0);
+    
+    env.ledger().set_sequence_number(env.ledger().sequence() + 1_000);
+    assert_eq!(client.try_request_unlock(&pool, &creator), Err(Ok(FactoryError::PermanentLock)));
+    
+    // Nothing recorded, still locked with the fee active
+    assert_eq!(client.get_unlock_requested_at(&pool, &creator), None);
+    assert!(client.is_liquidity_locked(&pool, &creator, &-600, &600));
+    assert!(client.is_creator_fee_active(&pool, &creator));
+}
+
+#[test]
+fn test_unlock_creator_liquidity_rejects_permanent_lock() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, pool, creator) = setup_locked_pool(&env, 0);
+    
+    env.ledger().set_sequence_number(env.ledger().sequence() + 1_000);
+    assert_eq!(
+        client.try_unlock_creator_liquidity(&pool, &creator),
+        Err(Ok(FactoryError::PermanentLock))
+    );
+    assert!(client.is_creator_fee_active(&pool, &creator));
+}
+
+#[test]
+fn test_request_unlock_nonexistent_lock() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _) = common::setup_factory(&env);
+    let pool = Address::generate(&env);
+    let creator = Address::generate(&env);
+    
+    assert_eq!(client.try_request_unlock(&pool, &creator), Err(Ok(FactoryError::CreatorLockNotFound)));
+}
+
+#[test]
+fn test_remaining_lock_time() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, pool, creator) = setup_locked_pool(&env, LOCK_LEDGERS);
+    let lock_end = env.ledger().sequence() + LOCK_LEDGERS;
+    
+    assert_eq!(client.get_remaining_lock_time(&pool, &creator, &-600, &600), Some(LOCK_LEDGERS));
+    assert_eq!(client.get_remaining_lock_time(&pool, &creator, &-600, &1200), None);
+    
+    env.ledger().with_mut(|li| li.sequence_number = lock_end - 1);
+    assert_eq!(client.get_remaining_lock_time(&pool, &creator, &-600, &600), Some(1));
+    
+    env.ledger().with_mut(|li| li.sequence_number = lock_end + 50);
+    assert_eq!(client.get_remaining_lock_time(&pool, &creator, &-600, &600), Some(0));
+    
+}
+
+#[test]
+fn test_remaining_lock_time_permanent() {
+    let env = Env::default();
+    let (client, pool, creator) = setup_locked_pool(&env, 0);
+    
+    env.ledger().set_sequence_number(env.ledger().sequence() + 1_000);
+    assert_eq!(client.get_remaining_lock_time(&pool, &creator, &-600, &600), Some(u32::MAX));
+}
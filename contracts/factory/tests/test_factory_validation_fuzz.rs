@@ -11,6 +11,7 @@ use proptest::prelude::*;
 
 const MIN_LOCK_DURATION: u32 = 120_960; // ~7 days
 const MIN_INITIAL_LIQUIDITY: i128 = 1_000_000;
+#[allow(dead_code)]
 const MAX_FEE_BPS: u32 = 100;
 
 fn validate_tokens(token_a: &str, token_b: &str) -> bool {
@@ -38,7 +39,7 @@ fn validate_lock_duration(duration: u32) -> bool {
 }
 
 fn validate_creator_fee(fee_bps: u32) -> bool {
-    fee_bps >= 10 && fee_bps <= 1000
+    (10..=1000).contains(&fee_bps)
 }
 
 fn validate_sqrt_price(price: u128) -> bool {
@@ -425,10 +426,8 @@ fn statistics_fee_tier_distribution() {
     // Verify random values are mostly invalid
     let mut invalid_count = 0;
     for i in 1..=200 {
-        if i != 5 && i != 30 && i != 100 {
-            if !validate_fee_tier(i) {
-                invalid_count += 1;
-            }
+        if i != 5 && i != 30 && i != 100 && !validate_fee_tier(i) {
+            invalid_count += 1;
         }
     }
     
@@ -0,0 +1,123 @@
+mod common;
+
+use beluga_factory::FactoryError;
+use belugaswap_math::{get_sqrt_ratio_at_tick, get_tick_at_sqrt_ratio};
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+// create_pool derives the pool's initial tick with get_tick_at_sqrt_ratio.
+// Most tests pin the conversion itself: the stored tick must bracket the
+// initial price. One deploys a real pool and reads its state back.
+
+fn assert_same_bucket(sqrt_price_x64: u128) {
+    let tick = get_tick_at_sqrt_ratio(sqrt_price_x64);
+    assert!(
+        get_sqrt_ratio_at_tick(tick) <= sqrt_price_x64
+            && sqrt_price_x64 < get_sqrt_ratio_at_tick(tick + 1),
+        "tick {} does not bracket sqrt price {}",
+        tick,
+        sqrt_price_x64
+    );
+}
+
+#[test]
+fn test_initial_tick_at_price_one() {
+    assert_eq!(get_tick_at_sqrt_ratio(1u128 << 64), 0);
+}
+
+#[test]
+fn test_initial_tick_non_trivial_prices() {
+    let one = 1u128 << 64;
+    for sqrt_price in [
+        one + one / 3,          // price ~1.78
+        one - one / 7,          // price ~0.73
+        one * 10,               // price 100
+        one / 10,               // price 0.01
+        one * 1000 + 12_345,    // price ~1e6, off-bucket
+        one / 1000 + 6_789,     // price ~1e-6, off-bucket
+    ] {
+        assert_same_bucket(sqrt_price);
+    }
+}
+
+#[test]
+fn test_initial_tick_not_ilog2_approximation() {
+    // sqrt price 2.0 (price 4.0) sits at tick ~13862; the old ilog2 * 6932
+    // heuristic landed on 6932.
+    let tick = get_tick_at_sqrt_ratio(2u128 << 64);
+    assert!((13_862..=13_863).contains(&tick), "got tick {}", tick);
+}
+
+#[test]
+fn test_tick_initialized_price_matches_sqrt_ratio() {
+    // With `initial_tick` set, create_pool hands the pool
+    // (get_sqrt_ratio_at_tick(tick), tick) without converting back, so the
+    // pair matches a sqrt-price creation at the same price. (Near MIN_TICK the
+    // Q64 price is too coarse to round-trip, which the tick path sidesteps.)
+    for tick in [0, 60, -60, 6_960, -6_960, 200_040, -200_040, 887_220] {
+        let sqrt_price = get_sqrt_ratio_at_tick(tick);
+        assert_eq!(get_tick_at_sqrt_ratio(sqrt_price), tick, "tick {}", tick);
+    }
+}
+
+/// Factory with a router set, so create_pool reaches price validation
+fn setup_with_router(env: &Env) -> (beluga_factory::BelugaFactoryClient<'_>, Address, Address, Address) {
+    let (client, _) = common::setup_factory(env);
+    client.set_router(&Address::generate(env));
+    (client, Address::generate(env), common::create_token(env), common::create_token(env))
+}
+
+#[test]
+fn test_initial_tick_excludes_sqrt_price() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, creator, token_a, token_b) = setup_with_router(&env);
+    let mut params = common::default_pool_params(&env, &token_a, &token_b);
+    params.initial_tick = Some(60);
+
+    let result = client.try_create_pool(&creator, &params);
+    assert_eq!(result.err(), Some(Ok(FactoryError::InvalidInitialPrice)));
+}
+
+#[test]
+fn test_initial_tick_must_be_aligned() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, creator, token_a, token_b) = setup_with_router(&env);
+    let mut params = common::default_pool_params(&env, &token_a, &token_b);
+    params.initial_sqrt_price_x64 = 0;
+    params.initial_tick = Some(61); // 30 bps tier: spacing 60
+
+    let result = client.try_create_pool(&creator, &params);
+    assert_eq!(result.err(), Some(Ok(FactoryError::InvalidTickSpacing)));
+
+    params.initial_tick = Some(887_280); // aligned, but past MAX_TICK
+    let result = client.try_create_pool(&creator, &params);
+    assert_eq!(result.err(), Some(Ok(FactoryError::InvalidInitialPrice)));
+}
+
+#[test]
+fn test_created_pool_tick_matches_unaligned_sqrt_price() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = common::setup_factory_with_pool(&env);
+    let creator = Address::generate(&env);
+    let token_a = common::create_funded_token(&env, &[&creator], 1_000_000_000);
+    let token_b = common::create_funded_token(&env, &[&creator], 1_000_000_000);
+
+    // Between ticks, and not on a multiple of the tick spacing
+    let one = 1u128 << 64;
+    let sqrt_price = one + one / 3;
+    let expected = get_tick_at_sqrt_ratio(sqrt_price);
+    assert!(get_sqrt_ratio_at_tick(expected) < sqrt_price && expected % 60 != 0);
+
+    let mut params = common::default_pool_params(&env, &token_a, &token_b);
+    params.initial_sqrt_price_x64 = sqrt_price;
+    let pool_address = client.create_pool(&creator, &params);
+
+    let state = common::pool::Client::new(&env, &pool_address).get_pool_state();
+    assert_eq!(state.current_tick, expected);
+    assert_eq!(state.sqrt_price_x64, sqrt_price);
+}
@@ -22,7 +22,7 @@ fn test_double_initialization() {
     env.mock_all_auths();
     
     let admin = Address::generate(&env);
-    let factory_id = env.register_contract(None, BelugaFactory);
+    let factory_id = env.register(BelugaFactory, ());
     let client = BelugaFactoryClient::new(&env, &factory_id);
     let hash = BytesN::from_array(&env, &[0u8; 32]);
     
@@ -37,7 +37,7 @@ fn test_create_pool_not_initialized() {
     env.mock_all_auths();
     
     // Don't initialize factory
-    let factory_id = env.register_contract(None, BelugaFactory);
+    let factory_id = env.register(BelugaFactory, ());
     let client = BelugaFactoryClient::new(&env, &factory_id);
     
     let creator = Address::generate(&env);
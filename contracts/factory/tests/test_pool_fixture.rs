@@ -0,0 +1,64 @@
+use std::{fs, path::Path};
+
+use soroban_sdk::{Bytes, Env};
+
+// The factory tests deploy a prebuilt pool WASM (tests/fixtures). Its
+// `.sources.sha256` digest records the pool and package sources it was built
+// from; once they change, the fixture is stale and must be rebuilt with
+// `make pool-fixture`.
+
+/// Crates the pool WASM is built from, relative to the workspace root
+const POOL_SOURCES: [&str; 5] = [
+    "contracts/pool",
+    "packages/math",
+    "packages/position",
+    "packages/swap",
+    "packages/tick",
+];
+
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<String>) {
+    for entry in fs::read_dir(root.join(dir)).unwrap() {
+        let path = dir.join(entry.unwrap().file_name());
+        if root.join(&path).is_dir() {
+            collect_files(root, &path, files);
+        } else {
+            files.push(path.to_str().unwrap().to_string());
+        }
+    }
+}
+
+/// Digest `make pool-fixture` records: each manifest and `src` file in byte
+/// order of its path, as the path, a newline, then the file contents
+fn sources_digest(env: &Env, root: &Path) -> String {
+    let mut files = Vec::new();
+    for krate in POOL_SOURCES {
+        files.push(format!("{}/Cargo.toml", krate));
+        collect_files(root, &Path::new(krate).join("src"), &mut files);
+    }
+    files.sort();
+
+    let mut data = Vec::new();
+    for file in &files {
+        data.extend_from_slice(file.as_bytes());
+        data.push(b'\n');
+        data.extend(fs::read(root.join(file)).unwrap());
+    }
+    let hash = env.crypto().sha256(&Bytes::from_slice(env, &data));
+    hash.to_array().iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[test]
+fn test_pool_fixture_matches_sources() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let root = manifest_dir.join("../..");
+    let recorded = fs::read_to_string(manifest_dir.join("tests/fixtures/belugaswap_pool.wasm.sources.sha256")).unwrap();
+
+    assert_eq!(
+        recorded.trim(),
+        sources_digest(&env, &root),
+        "pool WASM fixture is stale, rebuild it with `make pool-fixture`"
+    );
+}
@@ -1,6 +1,7 @@
 mod common;
 
-use soroban_sdk::Env;
+use beluga_factory::FactoryError;
+use soroban_sdk::{testutils::{Address as _, Ledger}, vec, Address, Env, Vec};
 
 #[test]
 fn test_initial_pool_state() {
@@ -57,4 +58,176 @@ fn test_different_fee_tiers_different_pools() {
     assert!(!client.is_pool_deployed(&token_a, &token_b, &5));
     assert!(!client.is_pool_deployed(&token_a, &token_b, &30));
     assert!(!client.is_pool_deployed(&token_a, &token_b, &100));
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_disabled_pool_hidden_from_lookup() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _) = common::setup_factory_with_pool(&env);
+    let token_a = common::create_token(&env);
+    let token_b = common::create_token(&env);
+    let (pool, _) = common::create_pool(&env, &client, &token_a, &token_b, 30);
+    
+    assert!(client.is_pool_enabled(&pool));
+    assert_eq!(client.get_pool_address(&token_a, &token_b, &30), Some(pool.clone()));
+    
+    client.set_pool_enabled(&pool, &false);
+    assert!(!client.is_pool_enabled(&pool));
+    assert!(client.get_pool_address(&token_a, &token_b, &30).is_none());
+    // Still deployed, so the pair cannot be recreated
+    assert!(client.is_pool_deployed(&token_a, &token_b, &30));
+    
+    client.set_pool_enabled(&pool, &true);
+    assert_eq!(client.get_pool_address(&token_b, &token_a, &30), Some(pool));
+}
+
+#[test]
+fn test_get_pools_for_pair_returns_every_tier() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _) = common::setup_factory_with_pool(&env);
+    let token_a = common::create_token(&env);
+    let token_b = common::create_token(&env);
+    assert_eq!(client.get_pools_for_pair(&token_a, &token_b).len(), 0);
+    
+    // Two of the default tiers, plus a tier the admin adds later
+    client.set_fee_tier(&50, &50, &true);
+    let (pool_5, _) = common::create_pool(&env, &client, &token_a, &token_b, 5);
+    let (pool_100, _) = common::create_pool(&env, &client, &token_b, &token_a, 100);
+    let (pool_50, _) = common::create_pool(&env, &client, &token_a, &token_b, 50);
+    // Another pair's pool does not leak in
+    common::create_pool(&env, &client, &token_a, &common::create_token(&env), 30);
+    
+    let expected = vec![&env, (5, pool_5.clone()), (100, pool_100.clone()), (50, pool_50.clone())];
+    assert_eq!(client.get_pools_for_pair(&token_a, &token_b), expected);
+    // Either token order
+    assert_eq!(client.get_pools_for_pair(&token_b, &token_a), expected);
+    for (fee_bps, pool) in expected.iter() {
+        assert_eq!(client.get_pool_address(&token_a, &token_b, &fee_bps), Some(pool));
+    }
+    
+    // Disabled pools drop out, like in get_pool_address
+    client.set_pool_enabled(&pool_100, &false);
+    assert_eq!(
+        client.get_pools_for_pair(&token_b, &token_a),
+        vec![&env, (5, pool_5), (50, pool_50)]
+    );
+}
+
+#[test]
+fn test_set_pool_enabled_unknown_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _) = common::setup_factory(&env);
+    let pool = Address::generate(&env);
+    
+    assert!(!client.is_pool_enabled(&pool));
+    assert_eq!(
+        client.try_set_pool_enabled(&pool, &false),
+        Err(Ok(FactoryError::PoolNotFound))
+    );
+}
+
+#[test]
+fn test_pool_addresses_pagination() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _) = common::setup_factory_with_pool(&env);
+    for _ in 0..7 {
+        let token_a = common::create_token(&env);
+        let token_b = common::create_token(&env);
+        common::create_pool(&env, &client, &token_a, &token_b, 30);
+    }
+    let all = client.get_all_pool_addresses();
+    assert_eq!(all.len(), 7);
+    
+    // Consecutive pages cover the registry exactly once, in order
+    let mut paged = Vec::new(&env);
+    let mut start = 0;
+    loop {
+        let page = client.get_pool_addresses_page(&start, &3);
+        if page.is_empty() {
+            break;
+        }
+        assert!(page.len() <= 3);
+        start += page.len();
+        paged.append(&page);
+    }
+    assert_eq!(paged, all);
+    
+    assert_eq!(client.get_pool_addresses_page(&5, &10).len(), 2);
+    assert_eq!(client.get_pool_addresses_page(&0, &0).len(), 0);
+    assert_eq!(client.get_pool_addresses_page(&7, &3).len(), 0);
+    assert_eq!(client.get_pool_addresses_page(&u32::MAX, &u32::MAX).len(), 0);
+}
+
+#[test]
+fn test_report_reserves_replaces_previous_report() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _) = common::setup_factory_with_pool(&env);
+    // Sorted, so the shared token is token0 of both pools
+    let mut tokens = [0; 3].map(|_| common::create_token(&env));
+    tokens.sort();
+    let pools = [&tokens[1], &tokens[2]]
+        .map(|other| common::create_pool(&env, &client, &tokens[0], other, 30).0);
+    let tvl = || tokens.clone().map(|token| client.get_tvl(&token));
+    assert_eq!(tvl(), [0, 0, 0]);
+    
+    // Totals are kept per token, never mixing units
+    client.report_reserves(&pools[0], &100, &50);
+    client.report_reserves(&pools[1], &1_000, &7);
+    assert_eq!(tvl(), [1_100, 50, 7]);
+    
+    // A new report moves each token's total by the difference
+    client.report_reserves(&pools[0], &40, &20);
+    assert_eq!(tvl(), [1_040, 20, 7]);
+    client.report_reserves(&pools[1], &0, &0);
+    assert_eq!(tvl(), [40, 20, 0]);
+}
+
+#[test]
+fn test_report_reserves_rejects_unregistered_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _) = common::setup_factory(&env);
+    assert_eq!(
+        client.try_report_reserves(&Address::generate(&env), &100, &100),
+        Err(Ok(FactoryError::PoolNotFound))
+    );
+}
+
+#[test]
+fn test_pool_info_records_creation_metadata() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 4_242;
+        li.timestamp = 1_700_000_000;
+    });
+    
+    let (client, _) = common::setup_factory_with_pool(&env);
+    assert!(client.get_pool_info(&Address::generate(&env)).is_none());
+    
+    let (token_a, token_b) = (common::create_token(&env), common::create_token(&env));
+    let (pool, creator) = common::create_pool(&env, &client, &token_a, &token_b, 30);
+    
+    // Later ledgers do not move the recorded creation point
+    env.ledger().with_mut(|li| {
+        li.sequence_number += 100;
+        li.timestamp += 500;
+    });
+    let (token0, token1) = if token_a < token_b { (token_a, token_b) } else { (token_b, token_a) };
+    let stored = client.get_pool_info(&pool).unwrap();
+    assert_eq!(stored.pool, pool);
+    assert_eq!(stored.creator, creator);
+    assert_eq!((stored.token0, stored.token1, stored.fee_bps), (token0, token1, 30));
+    assert_eq!((stored.created_at_ledger, stored.created_at_ts), (4_242, 1_700_000_000));
+}
@@ -1,6 +1,7 @@
 mod common;
 
-use soroban_sdk::{testutils::Address as _, Address, Env};
+use beluga_factory::{FactoryError, SeedRange};
+use soroban_sdk::{contract, contractimpl, testutils::Address as _, vec, Address, Env};
 
 // Note: All validation errors return Error(Contract, #5) because the factory
 // wraps errors from sub-contract calls. The specific validation still works,
@@ -335,4 +336,185 @@ fn test_zero_initial_price() {
     params.initial_sqrt_price_x64 = 0;
     
     client.create_pool(&creator, &params);
-}
\ No newline at end of file
+}
+
+/// Factory with a router set, so create_pool reaches price validation
+fn setup_with_router(env: &Env) -> (beluga_factory::BelugaFactoryClient<'_>, Address, Address, Address) {
+    let (client, _) = common::setup_factory(env);
+    client.set_router(&Address::generate(env));
+    (client, Address::generate(env), common::create_token(env), common::create_token(env))
+}
+
+#[test]
+fn test_initial_price_deviation_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, creator, token_a, token_b) = setup_with_router(&env);
+    let mut params = common::default_pool_params(&env, &token_a, &token_b);
+    let reference = 1u128 << 64;
+    params.reference_sqrt_price_x64 = Some(reference);
+    params.max_init_deviation_bps = 100; // 1%
+    
+    for price in [reference + reference / 50, reference - reference / 50, reference * 4] {
+        params.initial_sqrt_price_x64 = price;
+        let result = client.try_create_pool(&creator, &params);
+        assert_eq!(result.err(), Some(Ok(FactoryError::InitialPriceDeviation)));
+    }
+    
+    params.reference_sqrt_price_x64 = Some(0);
+    let result = client.try_create_pool(&creator, &params);
+    assert_eq!(result.err(), Some(Ok(FactoryError::InvalidInitialPrice)));
+}
+
+#[test]
+fn test_initial_price_within_deviation_passes_guard() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _) = common::setup_factory_with_pool(&env);
+    let creator = Address::generate(&env);
+    let reference = 1u128 << 64;
+    
+    // Exactly at the bound, both sides. A fresh pair each time, since a pair
+    // and fee tier only take one pool
+    for price in [reference + reference / 100, reference - reference / 100, reference] {
+        let token_a = common::create_funded_token(&env, &[&creator], 1_000_000_000);
+        let token_b = common::create_funded_token(&env, &[&creator], 1_000_000_000);
+        let mut params = common::default_pool_params(&env, &token_a, &token_b);
+        params.reference_sqrt_price_x64 = Some(reference);
+        params.max_init_deviation_bps = 100;
+        params.initial_sqrt_price_x64 = price;
+        
+        let pool = client.create_pool(&creator, &params);
+        assert_eq!(client.get_pool_address(&token_a, &token_b, &params.fee_bps), Some(pool));
+    }
+    assert_eq!(client.get_total_pools(), 3);
+}
+
+/// Contract with no token interface
+#[contract]
+pub struct NotAToken;
+
+#[contractimpl]
+impl NotAToken {
+    pub fn hello(_env: Env) -> u32 {
+        1
+    }
+}
+
+#[test]
+fn test_non_token_rejected_before_deploy() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, creator, token, _) = setup_with_router(&env);
+    let not_a_token = env.register(NotAToken, ());
+    
+    // A plain account and a contract without `decimals`, on either side
+    for other in [Address::generate(&env), not_a_token] {
+        for (a, b) in [(&token, &other), (&other, &token)] {
+            let params = common::default_pool_params(&env, a, b);
+            let result = client.try_create_pool(&creator, &params);
+            assert_eq!(result.err(), Some(Ok(FactoryError::InvalidToken)));
+            assert_eq!(client.get_pool_address(a, b, &params.fee_bps), None);
+        }
+    }
+    assert_eq!(client.get_total_pools(), 0);
+}
+
+// ============================================================
+// EXTRA SEED RANGES
+// ============================================================
+
+fn seed(lower_tick: i32, upper_tick: i32, amount0_desired: i128, amount1_desired: i128) -> SeedRange {
+    SeedRange { lower_tick, upper_tick, amount0_desired, amount1_desired }
+}
+
+#[test]
+fn test_invalid_extra_ranges_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _) = common::setup_factory_with_pool(&env);
+    let creator = Address::generate(&env);
+    let token_a = common::create_funded_token(&env, &[&creator], 1_000_000_000);
+    let token_b = common::create_funded_token(&env, &[&creator], 1_000_000_000);
+    // Sorted, so the seed amounts below are in token0/token1 order
+    let (token_a, token_b) = if token_a < token_b { (token_a, token_b) } else { (token_b, token_a) };
+    let cases = [
+        (seed(1200, -1200, 1_000_000, 1_000_000), FactoryError::InvalidTickRange),
+        (seed(-1201, 1200, 1_000_000, 1_000_000), FactoryError::InvalidTickSpacing),
+        // Same ticks as the primary range
+        (seed(-600, 600, 1_000_000, 1_000_000), FactoryError::InvalidTickRange),
+        (seed(-1200, 1200, 0, 0), FactoryError::InsufficientInitialLiquidity),
+        (seed(-1200, 1200, -1, 1_000_000), FactoryError::InsufficientInitialLiquidity),
+    ];
+    for (range, expected) in cases {
+        let mut params = common::default_pool_params(&env, &token_a, &token_b);
+        params.extra_ranges = vec![&env, range];
+        let result = client.try_create_pool(&creator, &params);
+        assert_eq!(result.err(), Some(Ok(expected)));
+    }
+    
+    // Two extras over the same ticks
+    let mut params = common::default_pool_params(&env, &token_a, &token_b);
+    params.extra_ranges = vec![&env, seed(0, 1200, 0, 1_000_000), seed(0, 1200, 0, 1_000_000)];
+    assert_eq!(client.try_create_pool(&creator, &params).err(), Some(Ok(FactoryError::InvalidTickRange)));
+    
+    assert_eq!(client.get_total_pools(), 0);
+    
+    // Valid extras are seeded next to the primary range, each out of range
+    // on its own side so it takes only one token
+    params.extra_ranges = vec![&env, seed(600, 1200, 1_000_000, 0), seed(-1200, -600, 0, 1_000_000)];
+    let pool = common::pool::Client::new(&env, &client.create_pool(&creator, &params));
+    assert_eq!(client.get_total_pools(), 1);
+    for (lower_tick, upper_tick) in [(600, 1200), (-1200, -600)] {
+        assert!(pool.get_position(&creator, &lower_tick, &upper_tick).liquidity > 0);
+    }
+}
+
+// ============================================================
+// REVERSED PAIRS
+// ============================================================
+
+#[test]
+fn test_reversed_pair_amounts_follow_their_tokens() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _) = common::setup_factory_with_pool(&env);
+    let creator = Address::generate(&env);
+    let token_x = common::create_funded_token(&env, &[&creator], 100_000_000);
+    let token_y = common::create_funded_token(&env, &[&creator], 100_000_000);
+    let (token0, token1) = if token_x < token_y { (token_x, token_y) } else { (token_y, token_x) };
+    
+    // token_a sorts after token_b, so amount0_desired is token1's amount.
+    // [-1200, 600] around price 1.0 takes about twice as much token1 as
+    // token0; unswapped amounts would only fill half of each.
+    let mut params = common::default_pool_params(&env, &token1, &token0);
+    params.amount0_desired = 20_000_000;
+    params.amount1_desired = 10_000_000;
+    params.lower_tick = -1200;
+    let pool_address = client.create_pool(&creator, &params);
+    let pool = common::pool::Client::new(&env, &pool_address);
+    
+    let state = pool.get_pool_state();
+    assert_eq!((state.token0, state.token1), (token0.clone(), token1.clone()));
+    
+    let (reserve0, reserve1) = pool.get_reserves();
+    assert!((9_900_000..=10_000_000).contains(&reserve0), "reserve0 {}", reserve0);
+    assert!((19_000_000..=20_000_000).contains(&reserve1), "reserve1 {}", reserve1);
+    
+    // The creator paid exactly the reserves; the rest was refunded
+    let balance = |token: &Address| soroban_sdk::token::Client::new(&env, token).balance(&creator);
+    assert_eq!(balance(&token0), 100_000_000 - reserve0);
+    assert_eq!(balance(&token1), 100_000_000 - reserve1);
+    
+    // The locked creator position holds the sorted amounts
+    let position = pool.get_position(&creator, &-1200, &600);
+    let lock = client.get_creator_lock(&pool_address, &creator).unwrap();
+    assert_eq!(position.liquidity, lock.liquidity);
+    assert!(position.amount0 <= reserve0 && position.amount0 > 9_900_000);
+    assert!(position.amount1 <= reserve1 && position.amount1 > 19_000_000);
+}
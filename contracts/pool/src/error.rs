@@ -18,12 +18,14 @@ use soroban_sdk::{contracterror, symbol_short, Symbol};
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
-pub enum BelugaError {
+pub enum PoolError {
     // Initialization errors (100-199)
     /// Pool has already been initialized
     AlreadyInitialized = 100,
     /// Pool has not been initialized
     NotInitialized = 101,
+    /// Pool is paused: swaps and deposits are disabled
+    PoolPaused = 102,
 
     // Configuration errors (200-299)
     /// Invalid fee: must be 1-10000 bps
@@ -36,12 +38,22 @@ pub enum BelugaError {
     InvalidTickRange = 203,
     /// Tick out of valid range
     InvalidTick = 204,
+    /// Invalid protocol fee: must be 0-2500 bps
+    InvalidProtocolFee = 205,
+    /// No creator fee change has been proposed
+    NoPendingCreatorFee = 206,
+    /// Proposed creator fee increase is still timelocked
+    CreatorFeeTimelocked = 207,
+    /// Swap iteration budget must be 1-MAX_SWAP_ITERATIONS
+    InvalidSwapIterations = 208,
 
     // Token errors (300-399)
     /// Invalid token for this pool
     InvalidToken = 300,
     /// Input and output tokens are the same
     SameToken = 301,
+    /// Token0 and token1 back the reserves and cannot be rescued
+    ReserveToken = 302,
 
     // Liquidity errors (400-499)
     /// Liquidity amount too low (minimum required)
@@ -50,28 +62,61 @@ pub enum BelugaError {
     InsufficientLiquidity = 401,
     /// Liquidity amount must be positive
     InvalidLiquidityAmount = 402,
+    /// Creator position is still locked by the factory
+    PositionLocked = 403,
+    /// Percentage must be 1-10000 bps
+    InvalidBps = 404,
+    /// Position has no liquidity
+    PositionNotFound = 405,
+    /// Positions to merge must be distinct and share a tick range
+    PositionMismatch = 406,
+    /// Range order must lie entirely on the supplied token's side of the price
+    InvalidRangeOrder = 407,
+    /// Ledger timestamp is past the caller's deadline
+    DeadlineExpired = 408,
 
     // Swap errors (500-599)
     /// Swap amount too small
+    /// (also raised by the swap engine as `SwapError::SwapAmountTooSmall`)
     SwapAmountTooSmall = 500,
     /// Slippage tolerance exceeded
     SlippageExceeded = 501,
     /// Output amount too small (dust)
+    /// (raised by the swap engine as `SwapError::OutputDust`)
     OutputDust = 502,
     /// No liquidity available for swap
     NoLiquidity = 503,
     /// Maximum slippage exceeded
     MaxSlippageExceeded = 504,
+    /// Payout below the caller's minimum
+    InsufficientOutput = 505,
+    /// Spot tick too far from the TWAP tick
+    PriceDeviation = 506,
+    /// Swap ran out of its iteration budget with input left
+    /// (raised by the swap engine as `SwapError::IterationLimit`)
+    IterationLimit = 507,
+    /// Swap price impact above the pool's `max_price_impact_bps`
+    PriceImpactTooHigh = 508,
+    /// Price limit at or past the current price in the swap direction
+    InvalidPriceLimit = 509,
 
     // Authorization errors (600-699)
     /// Unauthorized: only pool creator can perform this action
     Unauthorized = 600,
+    /// Unauthorized: only factory admin can perform this action
+    NotFactoryAdmin = 601,
+    /// Caller does not own this position id
+    NotPositionOwner = 602,
 
     // Math errors (700-799)
     /// Division by zero
     DivisionByZero = 700,
     /// Arithmetic overflow
     Overflow = 701,
+
+    // Accounting errors (800-899)
+    /// Token balances no longer cover principal and fees owed
+    InvariantViolated = 800,
 }
 
 // ============================================================
@@ -113,6 +158,18 @@ impl ErrorSymbol {
         symbol_short!("SLIP_MAX")
     }
 
+    /// Price impact above the pool's limit
+    #[inline]
+    pub fn impact_hi() -> Symbol {
+        symbol_short!("IMPACT_HI")
+    }
+
+    /// Price limit on the wrong side of the current price
+    #[inline]
+    pub fn bad_limit() -> Symbol {
+        symbol_short!("BAD_LIMIT")
+    }
+
     /// Invalid token
     #[inline]
     pub fn bad_token() -> Symbol {
@@ -128,11 +185,12 @@ impl ErrorSymbol {
 
 // ============================================================
 // ERROR MESSAGES (Legacy - for panic messages)
-// Kept for backward compatibility but prefer BelugaError
+// Kept for backward compatibility but prefer PoolError
 // ============================================================
 
 pub struct ErrorMsg;
 
+#[allow(dead_code)]
 impl ErrorMsg {
     pub const ALREADY_INITIALIZED: &'static str = "pool already initialized";
     pub const INVALID_FEE: &'static str = "invalid fee: must be 1-10000 bps";
@@ -146,23 +204,27 @@ impl ErrorMsg {
     pub const INSUFFICIENT_LIQUIDITY: &'static str = "insufficient liquidity in position";
     pub const INVALID_LIQUIDITY_AMOUNT: &'static str = "liquidity amount must be positive";
     pub const UNAUTHORIZED: &'static str = "unauthorized: only pool creator can perform this action";
+    pub const INVALID_PROTOCOL_FEE: &'static str = "invalid protocol fee: must be 0-2500 bps";
+    pub const NOT_FACTORY_ADMIN: &'static str = "unauthorized: only factory admin can perform this action";
 }
 
 // ============================================================
 // ERROR CONVERSION HELPERS
 // ============================================================
 
-impl BelugaError {
+impl PoolError {
     /// Convert error to Symbol for use in PreviewResult
-    pub fn to_symbol(&self) -> Symbol {
+    pub fn to_symbol(self) -> Symbol {
         match self {
-            BelugaError::SwapAmountTooSmall => ErrorSymbol::amt_low(),
-            BelugaError::NoLiquidity => ErrorSymbol::no_liq(),
-            BelugaError::SlippageExceeded => ErrorSymbol::slip_hi(),
-            BelugaError::OutputDust => ErrorSymbol::out_dust(),
-            BelugaError::MaxSlippageExceeded => ErrorSymbol::slip_max(),
-            BelugaError::InvalidToken => ErrorSymbol::bad_token(),
-            BelugaError::SameToken => ErrorSymbol::same_token(),
+            PoolError::SwapAmountTooSmall => ErrorSymbol::amt_low(),
+            PoolError::NoLiquidity => ErrorSymbol::no_liq(),
+            PoolError::SlippageExceeded => ErrorSymbol::slip_hi(),
+            PoolError::OutputDust => ErrorSymbol::out_dust(),
+            PoolError::MaxSlippageExceeded => ErrorSymbol::slip_max(),
+            PoolError::PriceImpactTooHigh => ErrorSymbol::impact_hi(),
+            PoolError::InvalidPriceLimit => ErrorSymbol::bad_limit(),
+            PoolError::InvalidToken => ErrorSymbol::bad_token(),
+            PoolError::SameToken => ErrorSymbol::same_token(),
             _ => symbol_short!("ERROR"),
         }
     }
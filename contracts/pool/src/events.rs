@@ -5,7 +5,7 @@
 // - Consistent naming convention
 // - Event topics for indexing
 
-use soroban_sdk::{contracttype, Env, Symbol, Address};
+use soroban_sdk::{contracttype, Address, BytesN, Env, Symbol};
 
 // ============================================================
 // EVENT TOPICS (OpenZeppelin Style)
@@ -52,17 +52,137 @@ impl EventTopic {
         Symbol::new(env, "FeesCollected")
     }
 
+    /// Position token minted event topic
+    #[inline]
+    pub fn position_minted(env: &Env) -> Symbol {
+        Symbol::new(env, "PositionMinted")
+    }
+
+    /// Position token transferred event topic
+    #[inline]
+    pub fn position_transferred(env: &Env) -> Symbol {
+        Symbol::new(env, "PositionTransferred")
+    }
+
+    /// Position tokens merged event topic
+    #[inline]
+    pub fn positions_merged(env: &Env) -> Symbol {
+        Symbol::new(env, "PositionsMerged")
+    }
+
+    /// Range order placed event topic
+    #[inline]
+    pub fn range_order(env: &Env) -> Symbol {
+        Symbol::new(env, "RangeOrder")
+    }
+
     /// Tick synced event topic (for debugging/indexing)
     #[inline]
     pub fn tick_synced(env: &Env) -> Symbol {
         Symbol::new(env, "TickSynced")
     }
 
+    /// Tick crossed during a swap event topic
+    #[inline]
+    pub fn tick_crossed(env: &Env) -> Symbol {
+        Symbol::new(env, "TickCrossed")
+    }
+
     /// Creator fees claimed event topic
     #[inline]
     pub fn creator_fees_claimed(env: &Env) -> Symbol {
         Symbol::new(env, "CreatorFeesClaimed")
     }
+
+    /// Creator fee accrued during a swap event topic
+    #[inline]
+    pub fn creator_fee_accrued(env: &Env) -> Symbol {
+        Symbol::new(env, "CreatorFeeAccrued")
+    }
+
+    /// Protocol fee changed event topic
+    #[inline]
+    pub fn protocol_fee_set(env: &Env) -> Symbol {
+        Symbol::new(env, "ProtocolFeeSet")
+    }
+
+    /// Protocol fees collected event topic
+    #[inline]
+    pub fn protocol_fees_collected(env: &Env) -> Symbol {
+        Symbol::new(env, "ProtocolCollected")
+    }
+
+    /// Protocol fee recipient changed event topic
+    #[inline]
+    pub fn protocol_fee_recipient_set(env: &Env) -> Symbol {
+        Symbol::new(env, "ProtocolRecipientSet")
+    }
+
+    /// Creator fee recipient changed event topic
+    #[inline]
+    pub fn creator_fee_recipient_set(env: &Env) -> Symbol {
+        Symbol::new(env, "CreatorRecipientSet")
+    }
+
+    /// Creator fee increase proposed event topic
+    #[inline]
+    pub fn creator_fee_proposed(env: &Env) -> Symbol {
+        Symbol::new(env, "CreatorFeeProposed")
+    }
+
+    /// Creator fee changed event topic
+    #[inline]
+    pub fn creator_fee_set(env: &Env) -> Symbol {
+        Symbol::new(env, "CreatorFeeSet")
+    }
+
+    /// Pause flag changed event topic
+    #[inline]
+    pub fn paused_set(env: &Env) -> Symbol {
+        Symbol::new(env, "PausedSet")
+    }
+
+    /// Swap iteration budget changed event topic
+    #[inline]
+    pub fn swap_iterations_set(env: &Env) -> Symbol {
+        Symbol::new(env, "SwapIterationsSet")
+    }
+
+    pub fn max_price_impact_set(env: &Env) -> Symbol {
+        Symbol::new(env, "MaxPriceImpactSet")
+    }
+
+    pub fn creator_fee_cap_set(env: &Env) -> Symbol {
+        Symbol::new(env, "CreatorFeeCapSet")
+    }
+
+    /// Post-swap solvency check toggled event topic
+    #[inline]
+    pub fn invariant_checks_set(env: &Env) -> Symbol {
+        Symbol::new(env, "InvariantChecksSet")
+    }
+
+    /// Pool code upgraded event topic
+    #[inline]
+    pub fn upgraded(env: &Env) -> Symbol {
+        Symbol::new(env, "Upgraded")
+    }
+
+    pub fn tvl_reporting_set(env: &Env) -> Symbol {
+        Symbol::new(env, "TvlReportingSet")
+    }
+
+    /// Stray token rescued event topic
+    #[inline]
+    pub fn token_rescued(env: &Env) -> Symbol {
+        Symbol::new(env, "TokenRescued")
+    }
+
+    /// Observation cardinality increased event topic
+    #[inline]
+    pub fn observation_cardinality(env: &Env) -> Symbol {
+        Symbol::new(env, "ObsCardinality")
+    }
 }
 
 // ============================================================
@@ -187,11 +307,35 @@ pub fn emit_remove_liquidity(env: &Env, liquidity: i128, amount0: i128, amount1:
     );
 }
 
-/// Emit swap event (simple version for backward compatibility)
-pub fn emit_swap(env: &Env, amount_in: i128, amount_out: i128, zero_for_one: bool) {
+/// Emit swap event
+///
+/// Data: `(amount_in, amount_out, zero_for_one, sqrt_price_x64, current_tick,
+/// lp_fee, creator_fee, protocol_fee)`. The first three fields keep their
+/// original positions; price is post-swap and fees are in the input token.
+#[allow(clippy::too_many_arguments)]
+pub fn emit_swap(
+    env: &Env,
+    amount_in: i128,
+    amount_out: i128,
+    zero_for_one: bool,
+    sqrt_price_x64: u128,
+    current_tick: i32,
+    lp_fee: i128,
+    creator_fee: i128,
+    protocol_fee: i128,
+) {
     env.events().publish(
         (EventTopic::swap(env),),
-        (amount_in, amount_out, zero_for_one),
+        (
+            amount_in,
+            amount_out,
+            zero_for_one,
+            sqrt_price_x64,
+            current_tick,
+            lp_fee,
+            creator_fee,
+            protocol_fee,
+        ),
     );
 }
 
@@ -211,10 +355,199 @@ pub fn emit_sync_tick(env: &Env, tick: i32, sqrt_price_x64: u128) {
     );
 }
 
+/// Emit tick crossed event
+///
+/// Data: `(tick, liquidity)`, where `liquidity` is the active liquidity after
+/// the crossing.
+pub fn emit_cross_tick(env: &Env, tick: i32, liquidity: i128) {
+    env.events().publish(
+        (EventTopic::tick_crossed(env),),
+        (tick, liquidity),
+    );
+}
+
 /// Emit claim creator fees event (simple version for backward compatibility)
 pub fn emit_claim_creator_fees(env: &Env, amount0: u128, amount1: u128) {
     env.events().publish(
         (EventTopic::creator_fees_claimed(env),),
         (amount0, amount1),
     );
-}
\ No newline at end of file
+}
+
+/// Emit creator fee accrual event
+///
+/// Data: `(token, amount, unclaimed)`, where `unclaimed` is the creator's
+/// unclaimed balance of `token` after this swap.
+pub fn emit_creator_fee_accrued(
+    env: &Env,
+    creator: &Address,
+    token: &Address,
+    amount: u128,
+    unclaimed: u128,
+) {
+    env.events().publish(
+        (EventTopic::creator_fee_accrued(env), creator.clone()),
+        (token.clone(), amount, unclaimed),
+    );
+}
+
+/// Emit protocol fee change event
+pub fn emit_protocol_fee_set(env: &Env, old_fee_bps: u32, new_fee_bps: u32) {
+    env.events().publish(
+        (EventTopic::protocol_fee_set(env),),
+        (old_fee_bps, new_fee_bps),
+    );
+}
+
+/// Emit creator fee increase proposal event
+pub fn emit_creator_fee_proposed(env: &Env, new_fee_bps: u32, effective_ledger: u32) {
+    env.events().publish(
+        (EventTopic::creator_fee_proposed(env),),
+        (new_fee_bps, effective_ledger),
+    );
+}
+
+/// Emit creator fee change event
+pub fn emit_creator_fee_set(env: &Env, old_fee_bps: u32, new_fee_bps: u32) {
+    env.events().publish(
+        (EventTopic::creator_fee_set(env),),
+        (old_fee_bps, new_fee_bps),
+    );
+}
+
+/// Emit pause flag change event
+pub fn emit_paused_set(env: &Env, admin: &Address, paused: bool) {
+    env.events().publish(
+        (EventTopic::paused_set(env), admin.clone()),
+        paused,
+    );
+}
+
+/// Emit protocol fees collected event
+pub fn emit_collect_protocol(env: &Env, recipient: &Address, amount0: u128, amount1: u128) {
+    env.events().publish(
+        (EventTopic::protocol_fees_collected(env),),
+        (recipient.clone(), amount0, amount1),
+    );
+}
+
+/// Emit protocol fee recipient change event
+pub fn emit_protocol_fee_recipient_set(env: &Env, admin: &Address, recipient: Option<Address>) {
+    env.events().publish(
+        (EventTopic::protocol_fee_recipient_set(env), admin.clone()),
+        recipient,
+    );
+}
+
+/// Emit creator fee recipient change event
+pub fn emit_creator_fee_recipient_set(env: &Env, creator: &Address, recipient: &Address) {
+    env.events().publish(
+        (EventTopic::creator_fee_recipient_set(env), creator.clone()),
+        recipient.clone(),
+    );
+}
+
+/// Emit swap iteration budget change event
+pub fn emit_swap_iterations_set(env: &Env, admin: &Address, max_iterations: u32) {
+    env.events().publish(
+        (EventTopic::swap_iterations_set(env), admin.clone()),
+        max_iterations,
+    );
+}
+
+/// Emit price impact limit update event
+pub fn emit_max_price_impact_set(env: &Env, admin: &Address, max_price_impact_bps: u32) {
+    env.events().publish(
+        (EventTopic::max_price_impact_set(env), admin.clone()),
+        max_price_impact_bps,
+    );
+}
+
+/// Emit creator fee cap update event
+pub fn emit_creator_fee_cap_set(env: &Env, admin: &Address, creator_fee_cap_bps: u32) {
+    env.events().publish(
+        (EventTopic::creator_fee_cap_set(env), admin.clone()),
+        creator_fee_cap_bps,
+    );
+}
+
+/// Emit solvency check toggle event
+pub fn emit_invariant_checks_set(env: &Env, admin: &Address, enabled: bool) {
+    env.events().publish(
+        (EventTopic::invariant_checks_set(env), admin.clone()),
+        enabled,
+    );
+}
+
+/// Emit pool code upgrade event
+pub fn emit_upgraded(env: &Env, admin: &Address, new_wasm_hash: &BytesN<32>) {
+    env.events().publish(
+        (EventTopic::upgraded(env), admin.clone()),
+        new_wasm_hash.clone(),
+    );
+}
+
+/// Emit TVL reporting toggle event
+pub fn emit_tvl_reporting_set(env: &Env, admin: &Address, enabled: bool) {
+    env.events().publish(
+        (EventTopic::tvl_reporting_set(env), admin.clone()),
+        enabled,
+    );
+}
+
+/// Emit stray token rescue event
+pub fn emit_token_rescued(env: &Env, admin: &Address, token: &Address, to: &Address, amount: i128) {
+    env.events().publish(
+        (EventTopic::token_rescued(env), admin.clone()),
+        (token.clone(), to.clone(), amount),
+    );
+}
+
+/// Emit observation cardinality increase event
+pub fn emit_observation_cardinality(env: &Env, old_cardinality: u32, new_cardinality: u32) {
+    env.events().publish(
+        (EventTopic::observation_cardinality(env),),
+        (old_cardinality, new_cardinality),
+    );
+}
+
+/// Emit position token minted event
+pub fn emit_position_minted(env: &Env, position_id: u64, owner: &Address, lower_tick: i32, upper_tick: i32) {
+    env.events().publish(
+        (EventTopic::position_minted(env), owner.clone()),
+        (position_id, lower_tick, upper_tick),
+    );
+}
+
+/// Emit position token transfer event
+pub fn emit_position_transferred(env: &Env, position_id: u64, from: &Address, to: &Address) {
+    env.events().publish(
+        (EventTopic::position_transferred(env), from.clone(), to.clone()),
+        position_id,
+    );
+}
+
+/// Emit position tokens merged event (`merged_id` no longer exists)
+pub fn emit_positions_merged(env: &Env, position_id: u64, merged_id: u64, liquidity: i128) {
+    env.events().publish(
+        (EventTopic::positions_merged(env),),
+        (position_id, merged_id, liquidity),
+    );
+}
+
+/// Emit range order placed event
+///
+/// `target_tick` is where the order is fully converted to the other token.
+pub fn emit_range_order(
+    env: &Env,
+    owner: &Address,
+    lower_tick: i32,
+    upper_tick: i32,
+    token_is_zero: bool,
+    target_tick: i32,
+) {
+    env.events().publish(
+        (EventTopic::range_order(env), owner.clone()),
+        (lower_tick, upper_tick, token_is_zero, target_tick),
+    );
+}
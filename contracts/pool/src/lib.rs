@@ -1,105 +1,41 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, token, vec, Address, Env, IntoVal, Symbol};
+use soroban_sdk::{contract, contractimpl, token, vec, Address, BytesN, Env, IntoVal, Symbol, Vec, U256};
 
 // External packages
 use belugaswap_math::{
-    get_amounts_for_liquidity, get_liquidity_for_amounts, snap_tick_to_spacing, 
-    MIN_LIQUIDITY, get_sqrt_ratio_at_tick, 
-    constants::{MAX_FEE_BPS, MIN_CREATOR_FEE_BPS, MAX_CREATOR_FEE_BPS}
+    get_amounts_for_liquidity, get_liquidity_for_amount0, get_liquidity_for_amount1, get_liquidity_for_amounts, snap_tick_to_spacing, snap_tick_to_spacing_ceil, i128_to_u128_safe, mul_bps,
+    MIN_LIQUIDITY, get_sqrt_ratio_at_tick, sort_tokens,
+    constants::{
+        MAX_FEE_BPS, MIN_CREATOR_FEE_BPS, MAX_CREATOR_FEE_BPS, MAX_PROTOCOL_FEE_BPS,
+        MINIMUM_LOCKED_LIQUIDITY, CREATOR_FEE_TIMELOCK_LEDGERS, MIN_TICK, MAX_TICK,
+        TWAP_DEVIATION_WINDOW, MAX_LIQUIDITY_BUCKETS, MAX_DEPTH_AMOUNT_IN, MAX_DEPTH_TICKS, MAX_SWAP_ITERATIONS, MIN_SWAP_AMOUNT, DUST_REFERENCE_DECIMALS,
+    }
+};
+use belugaswap_tick::TickInfo;
+use belugaswap_position::{Position, PositionInfo, PositionToken, merge_into, modify_position, update_position, calculate_pending_fees};
+use belugaswap_swap::{
+    SwapState, SwapResult, PreviewResult, engine_swap, quote_swap, quote_swap_exact_output, quote_swap_fees, validate_and_preview_swap, price_impact_bps,
 };
-use belugaswap_position::{PositionInfo, modify_position, update_position, calculate_pending_fees};
-use belugaswap_swap::{SwapState, SwapResult, PreviewResult, engine_swap, validate_and_preview_swap};
 
 // Local modules
 mod error;
 mod events;
 mod storage;
+mod twap;
 pub mod types;
 
+pub use error::PoolError;
 use error::ErrorMsg;
 use events::*;
 use storage::*;
-use types::{PoolConfig, PoolState, CreatorFeesInfo};
+use types::{PoolConfig, PoolState, PositionKey, CreatorFeesInfo, PendingCreatorFee, ProtocolFeesInfo, QuoteResult};
 
 #[contract]
 pub struct BelugaPool;
 
 #[contractimpl]
 impl BelugaPool {
-    // ========================================================
-    // INITIALIZATION
-    // ========================================================
-    
-    /// Initialize pool
-    /// 
-    /// # Arguments
-    /// * `factory` - Factory contract address
-    /// * `router` - Router contract address (for authorized swaps)
-    /// * `creator` - Pool creator address
-    /// * `token_a` - First token
-    /// * `token_b` - Second token
-    /// * `fee_bps` - Trading fee in basis points
-    /// * `creator_fee_bps` - Creator's share of fees
-    /// * `sqrt_price_x64` - Initial sqrt price
-    /// * `current_tick` - Initial tick
-    /// * `tick_spacing` - Tick spacing for this fee tier
-    pub fn initialize(
-        env: Env,
-        factory: Address,
-        router: Address,      // NEW: router parameter
-        creator: Address,
-        token_a: Address,
-        token_b: Address,
-        fee_bps: u32,
-        creator_fee_bps: u32,
-        sqrt_price_x64: u128,
-        current_tick: i32,
-        tick_spacing: i32,
-    ) {
-        // Factory must authorize (proves caller is factory contract)
-        factory.require_auth();
-        
-        if is_initialized(&env) {
-            panic!("{}", ErrorMsg::ALREADY_INITIALIZED);
-        }
-        
-        if fee_bps == 0 || fee_bps > MAX_FEE_BPS {
-            panic!("{}", ErrorMsg::INVALID_FEE);
-        }
-        
-        if creator_fee_bps < MIN_CREATOR_FEE_BPS || creator_fee_bps > MAX_CREATOR_FEE_BPS {
-            panic!("{}", ErrorMsg::INVALID_CREATOR_FEE);
-        }
-        
-        if tick_spacing <= 0 {
-            panic!("{}", ErrorMsg::INVALID_TICK_SPACING);
-        }
-        
-        let (token0, token1) = if token_a < token_b {
-            (token_a.clone(), token_b.clone())
-        } else {
-            (token_b.clone(), token_a.clone())
-        };
-        
-        let config = PoolConfig {
-            factory,
-            router,           // NEW: store router
-            creator,
-            token_a,
-            token_b,
-            fee_bps,
-            creator_fee_bps,
-        };
-        
-        write_pool_config(&env, &config);
-        init_pool_state(&env, sqrt_price_x64, current_tick, tick_spacing, token0, token1);
-        set_initialized(&env);
-        
-        emit_initialized(&env, fee_bps, creator_fee_bps, tick_spacing);
-        emit_pool_init(&env, sqrt_price_x64, current_tick, tick_spacing);
-    }
-    
     // ========================================================
     // VIEW FUNCTIONS 
     // ========================================================
@@ -114,6 +50,125 @@ impl BelugaPool {
         read_pool_state(&env)
     }
     
+    /// Get the current sqrt price (Q64.64)
+    pub fn get_sqrt_price(env: Env) -> u128 {
+        read_pool_state(&env).sqrt_price_x64
+    }
+    
+    /// Get the current price in human units of token1 per token0
+    /// 
+    /// Returned as a reduced `(numerator, denominator)` rational: the square
+    /// of `sqrt_price_x64`, scaled by `10^(decimals0 - decimals1)`. Exact
+    /// whenever both terms fit in i128.
+    pub fn get_price(env: Env) -> (i128, i128) {
+        let state = read_pool_state(&env);
+        let decimals0 = token::Client::new(&env, &state.token0).decimals();
+        let decimals1 = token::Client::new(&env, &state.token1).decimals();
+        price_ratio(&env, state.sqrt_price_x64, decimals0, decimals1)
+    }
+    
+    /// Get the pool's (token0, token1) addresses
+    pub fn get_tokens(env: Env) -> (Address, Address) {
+        let state = read_pool_state(&env);
+        (state.token0, state.token1)
+    }
+    
+    /// Get the pool's actual (token0, token1) balances
+    /// 
+    /// Includes accrued but unclaimed creator/protocol fees and LP fees owed.
+    pub fn get_reserves(env: Env) -> (i128, i128) {
+        let state = read_pool_state(&env);
+        let pool_addr = env.current_contract_address();
+        (
+            token::Client::new(&env, &state.token0).balance(&pool_addr),
+            token::Client::new(&env, &state.token1).balance(&pool_addr),
+        )
+    }
+    
+    /// Get cumulative swap volume as (token0, token1)
+    /// 
+    /// Each swap adds its input and output amounts to the respective token's
+    /// total. Counters saturate at `u128::MAX`.
+    pub fn get_volume(env: Env) -> (u128, u128) {
+        let state = read_pool_state(&env);
+        (state.volume_token0, state.volume_token1)
+    }
+    
+    /// Get the global fee growth accumulators as (token0, token1)
+    /// 
+    /// Q64.64 LP fees per unit of liquidity, wrapping modulo 2^128. Together
+    /// with the tick data behind `get_position` this lets off-chain tools
+    /// recompute a position's fees.
+    pub fn get_fee_growth_global(env: Env) -> (u128, u128) {
+        let state = read_pool_state(&env);
+        (state.fee_growth_global_0, state.fee_growth_global_1)
+    }
+    
+    /// Seconds the price has spent inside [lower_tick, upper_tick)
+    /// 
+    /// Tracked through each tick's `seconds_outside`, flipped on crossings
+    /// like fee growth. The absolute value depends on when each tick was
+    /// initialized and may wrap, so incentive programs should take the
+    /// wrapping difference of two readings. Returns 0 unless both ticks are
+    /// initialized.
+    pub fn get_seconds_inside(env: Env, lower_tick: i32, upper_tick: i32) -> u64 {
+        if lower_tick >= upper_tick
+            || !read_tick_info(&env, lower_tick).initialized
+            || !read_tick_info(&env, upper_tick).initialized
+        {
+            return 0;
+        }
+        
+        let state = read_pool_state(&env);
+        belugaswap_tick::get_seconds_inside(
+            &env,
+            read_tick_info,
+            lower_tick,
+            upper_tick,
+            state.current_tick,
+        )
+    }
+    
+    /// Stored info for a tick
+    /// 
+    /// Ticks no position references read as `TickInfo::default()`, with
+    /// `initialized` false; a tick is initialized while its gross liquidity
+    /// is positive.
+    pub fn get_tick_info(env: Env, tick: i32) -> TickInfo {
+        read_tick_info(&env, tick)
+    }
+    
+    /// Whether a position over [lower_tick, upper_tick) is earning fees now
+    /// 
+    /// Snaps the range the way deposits do and applies the liquidity
+    /// accounting's rule: in range while `lower <= current_tick < upper`.
+    pub fn is_position_in_range(env: Env, lower_tick: i32, upper_tick: i32) -> bool {
+        let state = read_pool_state(&env);
+        let lower_aligned = snap_tick_to_spacing(lower_tick, state.tick_spacing);
+        let upper_aligned = snap_tick_to_spacing_ceil(upper_tick, state.tick_spacing);
+        state.current_tick >= lower_aligned && state.current_tick < upper_aligned
+    }
+    
+    /// Next initialized tick from `from_tick` in a swap direction
+    /// 
+    /// Same bitmap search the swap engine uses: with `zero_for_one` it looks
+    /// at or below `from_tick`, otherwise strictly above it. Returns `None`
+    /// when no initialized tick lies within the search limit.
+    pub fn next_initialized_tick(env: Env, from_tick: i32, zero_for_one: bool) -> Option<i32> {
+        let state = read_pool_state(&env);
+        let next = belugaswap_tick::find_next_initialized_tick(
+            &env,
+            read_tick_word,
+            from_tick,
+            state.tick_spacing,
+            zero_for_one,
+        );
+        
+        // The search returns `from_tick` itself when it finds nothing
+        let found = if zero_for_one { next <= from_tick } else { next > from_tick };
+        (found && read_tick_info(&env, next).initialized).then_some(next)
+    }
+    
     /// Get pool configuration (tokens, fees, creator, router)
     pub fn get_pool_config(env: Env) -> PoolConfig {
         read_pool_config(&env)
@@ -124,6 +179,49 @@ impl BelugaPool {
         read_pool_config(&env).router
     }
     
+    /// Tick cumulative at each lookback point (seconds before now)
+    /// 
+    /// The arithmetic-mean tick over a window is
+    /// `(cumulative[now] - cumulative[now - window]) / window`. Lookbacks older
+    /// than the oldest stored observation return that observation's cumulative.
+    pub fn observe(env: Env, seconds_ago: Vec<u32>) -> Vec<i64> {
+        let state = read_pool_state(&env);
+        twap::observe(&env, &seconds_ago, state.current_tick, state.liquidity)
+    }
+    
+    /// Seconds-per-liquidity cumulative (Q64.64) at each lookback point
+    /// 
+    /// The difference between two points, times a position's liquidity, is how
+    /// many seconds that liquidity was in range over the window.
+    pub fn observe_liquidity(env: Env, seconds_ago: Vec<u32>) -> Vec<u128> {
+        let state = read_pool_state(&env);
+        twap::observe_liquidity(&env, &seconds_ago, state.current_tick, state.liquidity)
+    }
+    
+    /// Arithmetic-mean tick over the last `window_secs` seconds
+    /// 
+    /// Divides by the time the stored history actually covers, so an oracle
+    /// younger than the window averages over what it has. Returns the current
+    /// tick when there is no history yet.
+    pub fn get_twap_tick(env: Env, window_secs: u32) -> i32 {
+        let state = read_pool_state(&env);
+        twap::average_tick(&env, window_secs, state.current_tick, state.liquidity)
+    }
+    
+    /// Grow the TWAP observation ring to retain at least `new_cardinality`
+    /// observations (capped at `MAX_TWAP_OBSERVATIONS`)
+    /// 
+    /// Anyone may call this; the caller pays for the extra storage as it is
+    /// written. Returns the pending cardinality.
+    pub fn increase_observation_cardinality(env: Env, new_cardinality: u32) -> u32 {
+        let old = get_twap_cardinality_next(&env);
+        let new = twap::increase_observation_cardinality(&env, new_cardinality);
+        if new > old {
+            emit_observation_cardinality(&env, old, new);
+        }
+        new
+    }
+    
     /// Get position info for an LP
     pub fn get_position(
         env: Env,
@@ -133,15 +231,21 @@ impl BelugaPool {
     ) -> PositionInfo {
         let state = read_pool_state(&env);
         let pos = read_position(&env, &owner, lower_tick, upper_tick);
-        
-        let (amount0, amount1) = get_amounts_for_liquidity(
-            &env,
-            pos.liquidity,
-            get_sqrt_ratio_at_tick(lower_tick),
-            get_sqrt_ratio_at_tick(upper_tick),
-            state.sqrt_price_x64,
-        );
-        
+        Self::position_info(&env, &state, lower_tick, upper_tick, &pos)
+    }
+    
+    /// Fees a position has earned over its lifetime, collected or not
+    /// 
+    /// Settled lifetime totals plus fees pending since the last checkpoint;
+    /// unlike `fees_owed_*` these never drop on collect.
+    pub fn get_position_lifetime_fees(
+        env: Env,
+        owner: Address,
+        lower_tick: i32,
+        upper_tick: i32,
+    ) -> (u128, u128) {
+        let state = read_pool_state(&env);
+        let pos = read_position(&env, &owner, lower_tick, upper_tick);
         let fee_growth_inside = get_fee_growth_inside_local(
             &env,
             lower_tick,
@@ -150,16 +254,170 @@ impl BelugaPool {
             state.fee_growth_global_0,
             state.fee_growth_global_1,
         );
-        
         let (pending0, pending1) = calculate_pending_fees(&env, &pos, fee_growth_inside.0, fee_growth_inside.1);
+        (
+            pos.lifetime_fees_0.saturating_add(pending0),
+            pos.lifetime_fees_1.saturating_add(pending1),
+        )
+    }
+    
+    /// Ledgers left on `owner`'s creator lock over [lower_tick, upper_tick]
+    /// 
+    /// Forwards to the factory's `get_remaining_lock_time`; `None` if there
+    /// is no such lock or the factory call fails.
+    pub fn get_lock_remaining(
+        env: Env,
+        owner: Address,
+        lower_tick: i32,
+        upper_tick: i32,
+    ) -> Option<u32> {
+        let config = read_pool_config(&env);
+        let result = env.try_invoke_contract::<Option<u32>, soroban_sdk::Error>(
+            &config.factory,
+            &Symbol::new(&env, "get_remaining_lock_time"),
+            vec![
+                &env,
+                env.current_contract_address().into_val(&env),
+                owner.into_val(&env),
+                lower_tick.into_val(&env),
+                upper_tick.into_val(&env),
+            ],
+        );
         
-        PositionInfo {
-            liquidity: pos.liquidity,
-            amount0,
-            amount1,
-            fees_owed_0: pos.tokens_owed_0.saturating_add(pending0),
-            fees_owed_1: pos.tokens_owed_1.saturating_add(pending1),
+        match result {
+            Ok(Ok(remaining)) => remaining,
+            _ => None,
+        }
+    }
+    
+    /// Get position info for a position token (empty if the id does not exist)
+    pub fn get_position_by_id(env: Env, position_id: u64) -> PositionInfo {
+        match read_position_token(&env, position_id) {
+            Some(token) => {
+                let state = read_pool_state(&env);
+                Self::position_info(&env, &state, token.lower_tick, token.upper_tick, &token.position)
+            }
+            None => PositionInfo::default(),
+        }
+    }
+    
+    /// Get a position token's owner, range and raw position state
+    pub fn get_position_token(env: Env, position_id: u64) -> Option<PositionToken> {
+        read_position_token(&env, position_id)
+    }
+    
+    /// Token amounts `liquidity` over [lower_tick, upper_tick] is worth at a
+    /// hypothetical `sqrt_price_x64` (not necessarily the pool's price)
+    /// 
+    /// Rounds down, like `get_position`. Returns (0, 0) for an empty or
+    /// out-of-bounds range.
+    pub fn quote_position_amounts(
+        env: Env,
+        liquidity: i128,
+        lower_tick: i32,
+        upper_tick: i32,
+        sqrt_price_x64: u128,
+    ) -> (i128, i128) {
+        if lower_tick >= upper_tick || lower_tick < MIN_TICK || upper_tick > MAX_TICK {
+            return (0, 0);
+        }
+        
+        get_amounts_for_liquidity(
+            &env,
+            liquidity,
+            get_sqrt_ratio_at_tick(lower_tick),
+            get_sqrt_ratio_at_tick(upper_tick),
+            sqrt_price_x64,
+        )
+    }
+    
+    /// What `owner`'s position would be worth at `sqrt_price_x64`, fees
+    /// included
+    /// 
+    /// Principal is `quote_position_amounts` at the hypothetical price; fees
+    /// are those owed today, since a price move alone earns nothing.
+    pub fn position_value_at_price(
+        env: Env,
+        owner: Address,
+        lower_tick: i32,
+        upper_tick: i32,
+        sqrt_price_x64: u128,
+    ) -> (i128, i128) {
+        let state = read_pool_state(&env);
+        let pos = read_position(&env, &owner, lower_tick, upper_tick);
+        let info = Self::position_info(&env, &state, lower_tick, upper_tick, &pos);
+        let (amount0, amount1) =
+            Self::quote_position_amounts(env, pos.liquidity, lower_tick, upper_tick, sqrt_price_x64);
+        (
+            amount0.saturating_add(safe_u128_to_i128(info.fees_owed_0)),
+            amount1.saturating_add(safe_u128_to_i128(info.fees_owed_1)),
+        )
+    }
+    
+    /// Active liquidity across [lower_tick, upper_tick), split into equal buckets
+    /// 
+    /// Each entry is the tick-weighted average of the liquidity that would be
+    /// in range at each tick of its bucket (rounded down), which is what a
+    /// depth chart plots. Initialized ticks are found through the tick bitmap.
+    /// `num_buckets` is clamped to [1, MAX_LIQUIDITY_BUCKETS] and to the range
+    /// width. Returns an empty vector for an empty or out-of-bounds range.
+    pub fn get_liquidity_distribution(
+        env: Env,
+        lower_tick: i32,
+        upper_tick: i32,
+        num_buckets: u32,
+    ) -> Vec<i128> {
+        let mut result = Vec::new(&env);
+        if lower_tick >= upper_tick || lower_tick < MIN_TICK || upper_tick > MAX_TICK {
+            return result;
+        }
+        
+        let state = read_pool_state(&env);
+        let spacing = state.tick_spacing;
+        
+        // Liquidity in range at lower_tick: undo or apply every tick crossed
+        // between the current tick and it
+        let mut liquidity = state.liquidity;
+        if lower_tick <= state.current_tick {
+            for tick in initialized_ticks_between(&env, lower_tick, state.current_tick, spacing).iter() {
+                liquidity = liquidity.saturating_sub(read_tick_info(&env, tick).liquidity_net);
+            }
+        } else {
+            for tick in initialized_ticks_between(&env, state.current_tick, lower_tick, spacing).iter() {
+                liquidity = liquidity.saturating_add(read_tick_info(&env, tick).liquidity_net);
+            }
+        }
+        
+        let ticks = initialized_ticks_between(&env, lower_tick, upper_tick - 1, spacing);
+        let width = upper_tick as i64 - lower_tick as i64;
+        let buckets = num_buckets.clamp(1, MAX_LIQUIDITY_BUCKETS).min(width as u32) as i64;
+        let mut next = 0;
+        
+        for i in 0..buckets {
+            let start = (lower_tick as i64 + width * i / buckets) as i32;
+            let end = (lower_tick as i64 + width * (i + 1) / buckets) as i32;
+            
+            let mut weighted: i128 = 0;
+            let mut from = start;
+            while next < ticks.len() && ticks.get_unchecked(next) < end {
+                let tick = ticks.get_unchecked(next);
+                weighted = weighted.saturating_add(liquidity.saturating_mul((tick - from) as i128));
+                liquidity = liquidity.saturating_add(read_tick_info(&env, tick).liquidity_net);
+                from = tick;
+                next += 1;
+            }
+            weighted = weighted.saturating_add(liquidity.saturating_mul((end - from) as i128));
+            result.push_back(weighted / (end - start) as i128);
         }
+        
+        result
+    }
+    
+    /// List the tick ranges `owner` holds a position in
+    /// 
+    /// A range stays listed until its liquidity is removed and its fees collected.
+    pub fn get_owner_positions(env: Env, owner: Address) -> Vec<PositionKey> {
+        read_owner_positions(&env, &owner)
     }
     
     /// Get accumulated creator fees
@@ -171,6 +429,24 @@ impl BelugaPool {
         }
     }
     
+    /// Get accumulated protocol fees
+    pub fn get_protocol_fees(env: Env) -> ProtocolFeesInfo {
+        let pool = read_pool_state(&env);
+        ProtocolFeesInfo {
+            fees_token0: pool.protocol_fees_0,
+            fees_token1: pool.protocol_fees_1,
+        }
+    }
+    
+    /// Fee components as `(fee_bps, creator_fee_bps, protocol_fee_bps)`
+    /// 
+    /// `fee_bps` is the total swap fee; the creator takes `creator_fee_bps`
+    /// of it and the protocol `protocol_fee_bps` of what remains for LPs.
+    pub fn get_fee_config(env: Env) -> (u32, u32, u32) {
+        let config = read_pool_config(&env);
+        (config.fee_bps, config.creator_fee_bps, config.protocol_fee_bps)
+    }
+    
     /// Get swap direction based on input token
     pub fn get_swap_direction(env: Env, token_in: Address) -> bool {
         let pool = read_pool_state(&env);
@@ -189,7 +465,7 @@ impl BelugaPool {
         token_in: Address,
         amount_in: i128,
         min_amount_out: i128,
-        sqrt_price_limit_x64: u128,
+        sqrt_price_limit_x64: Option<u128>,
     ) -> PreviewResult {
         let config = read_pool_config(&env);
         let pool = read_pool_state(&env);
@@ -203,136 +479,471 @@ impl BelugaPool {
             return PreviewResult::invalid(Symbol::new(&env, "BAD_TOKEN"));
         };
         
-        let swap_state = SwapState {
-            sqrt_price_x64: pool.sqrt_price_x64,
-            current_tick: pool.current_tick,
-            liquidity: pool.liquidity,
-            tick_spacing: pool.tick_spacing,
-            fee_growth_global_0: pool.fee_growth_global_0,
-            fee_growth_global_1: pool.fee_growth_global_1,
-        };
+        if !is_valid_price_limit(&pool, zero_for_one, sqrt_price_limit_x64) {
+            return PreviewResult::invalid(PoolError::InvalidPriceLimit.to_symbol());
+        }
+        
+        let swap_state = swap_state(&pool, &config, zero_for_one);
         
         match validate_and_preview_swap(
             &env,
             &swap_state,
-            |e, t| read_tick_info(e, t),
+            read_tick_info,
+            read_tick_word,
             amount_in,
             min_amount_out,
             zero_for_one,
             sqrt_price_limit_x64,
             config.fee_bps as i128,
         ) {
-            Ok((amount_in_used, amount_out, fee_paid, price_impact_bps, _final_price)) => {
-                PreviewResult::valid(amount_in_used, amount_out, fee_paid, price_impact_bps)
+            Ok((_, _, _, price_impact_bps, _, _)) if exceeds_price_impact(&config, price_impact_bps) => {
+                PreviewResult::invalid(PoolError::PriceImpactTooHigh.to_symbol())
+            }
+            Ok((amount_in_used, amount_out, fee_paid, price_impact_bps, _final_price, steps)) => {
+                PreviewResult::valid(amount_in_used, amount_out, fee_paid, price_impact_bps, steps)
             }
             Err(error_symbol) => PreviewResult::invalid(error_symbol),
         }
     }
     
-    // ========================================================
-    // SWAP FUNCTION
-    // ========================================================
-    
-    /// Execute a swap
+    /// Preview the input needed to receive an exact output
     /// 
-    /// Can be called by:
-    /// - Direct user (sender.require_auth)
-    /// - Router contract (for multi-hop swaps)
-    pub fn swap(
+    /// `amount_in_used` is the smallest input whose swap delivers at least
+    /// `amount_out`; `amount_out_expected` is what that input actually yields.
+    pub fn preview_swap_exact_output(
         env: Env,
-        sender: Address,
         token_in: Address,
-        amount_in: i128,
-        amount_out_min: i128,
-        sqrt_price_limit_x64: u128,
-    ) -> SwapResult {
-        sender.require_auth();
-        
-        // Validate amount_in early
-        if amount_in <= 0 {
-            panic!("amount_in must be positive");
-        }
-        
-        let pool_state = read_pool_state(&env);
+        amount_out: i128,
+        amount_in_max: i128,
+        sqrt_price_limit_x64: Option<u128>,
+    ) -> PreviewResult {
         let config = read_pool_config(&env);
+        let pool = read_pool_state(&env);
         
-        let zero_for_one = if token_in == pool_state.token0 {
+        let zero_for_one = if token_in == pool.token0 {
             true
-        } else if token_in == pool_state.token1 {
+        } else if token_in == pool.token1 {
             false
         } else {
-            panic!("{}", ErrorMsg::INVALID_TOKEN);
+            return PreviewResult::invalid(Symbol::new(&env, "BAD_TOKEN"));
         };
         
-        // Safe creator fee check - won't panic if factory call fails
-        let creator_fee_bps = Self::get_active_creator_fee_bps_safe(&env, &config);
+        if !is_valid_price_limit(&pool, zero_for_one, sqrt_price_limit_x64) {
+            return PreviewResult::invalid(PoolError::InvalidPriceLimit.to_symbol());
+        }
         
-        let mut swap_state = SwapState {
-            sqrt_price_x64: pool_state.sqrt_price_x64,
-            current_tick: pool_state.current_tick,
-            liquidity: pool_state.liquidity,
-            tick_spacing: pool_state.tick_spacing,
-            fee_growth_global_0: pool_state.fee_growth_global_0,
-            fee_growth_global_1: pool_state.fee_growth_global_1,
-        };
+        let swap_state = swap_state(&pool, &config, zero_for_one);
         
-        let (amount_in_used, amount_out, creator_fee) = engine_swap(
+        let (amount_in, amount_out_expected, _final_price, _final_tick, steps) = quote_swap_exact_output(
             &env,
-            &mut swap_state,
-            |e, t| read_tick_info(e, t),
-            |e, t, info| write_tick_info(e, t, info),
-            |e, tick, price| emit_sync_tick(e, tick, price),
-            amount_in,
+            &swap_state,
+            read_tick_info,
+            read_tick_word,
+            amount_out,
             zero_for_one,
             sqrt_price_limit_x64,
             config.fee_bps as i128,
-            creator_fee_bps,
         );
         
-        if amount_out < amount_out_min {
-            panic!("{}", ErrorMsg::SLIPPAGE_EXCEEDED);
+        if amount_in <= 0 {
+            let error = if pool.liquidity <= 0 { "NO_LIQ" } else { "OUT_HIGH" };
+            return PreviewResult::invalid(Symbol::new(&env, error));
         }
         
-        // Update pool state
-        let mut updated_pool = pool_state.clone();
-        updated_pool.sqrt_price_x64 = swap_state.sqrt_price_x64;
-        updated_pool.current_tick = swap_state.current_tick;
-        updated_pool.liquidity = swap_state.liquidity;
-        updated_pool.fee_growth_global_0 = swap_state.fee_growth_global_0;
-        updated_pool.fee_growth_global_1 = swap_state.fee_growth_global_1;
-
-        // Credit the creator fee. It is carved out of the LP fee inside the swap
-        // engine and is denominated in the input token, and the swapper already
-        // transferred it in as part of amount_in_used — so we only need to record
-        // the claim here (claim_creator_fees pays it out later).
-        if creator_fee > 0 {
-            let cf = creator_fee as u128;
-            if zero_for_one {
-                updated_pool.creator_fees_0 = updated_pool.creator_fees_0.saturating_add(cf);
-            } else {
-                updated_pool.creator_fees_1 = updated_pool.creator_fees_1.saturating_add(cf);
-            }
+        if amount_in_max > 0 && amount_in > amount_in_max {
+            return PreviewResult::invalid(Symbol::new(&env, "SLIP_HI"));
         }
-        write_pool_state(&env, &updated_pool);
         
-        // Transfer tokens
-        let (token_in_addr, token_out_addr) = if zero_for_one {
-            (&pool_state.token0, &pool_state.token1)
-        } else {
-            (&pool_state.token1, &pool_state.token0)
-        };
+        let fee_paid = mul_bps(&env, amount_in, config.fee_bps);
         
-        token::Client::new(&env, token_in_addr).transfer(&sender, &env.current_contract_address(), &amount_in_used);
-        token::Client::new(&env, token_out_addr).transfer(&env.current_contract_address(), &sender, &amount_out);
+        PreviewResult::valid(amount_in, amount_out_expected, fee_paid, 0, steps)
+    }
+    
+    /// Fees a swap of `amount_in` would pay right now, in `token_in`
+    /// 
+    /// Returns `(lp_fee, creator_fee, protocol_fee)` from a dry run with the
+    /// active creator fee and the protocol share, charged on the input the
+    /// swap would actually consume. Returns (0, 0, 0) for an unknown token.
+    pub fn estimate_fees(env: Env, token_in: Address, amount_in: i128) -> (i128, i128, i128) {
+        let config = read_pool_config(&env);
+        let pool = read_pool_state(&env);
+        
+        let zero_for_one = if token_in == pool.token0 {
+            true
+        } else if token_in == pool.token1 {
+            false
+        } else {
+            return (0, 0, 0);
+        };
+        
+        let swap_state = swap_state(&pool, &config, zero_for_one);
+        
+        let (_amount_in_used, creator_fee, protocol_fee, lp_fee) = quote_swap_fees(
+            &env,
+            &swap_state,
+            read_tick_info,
+            read_tick_word,
+            amount_in,
+            zero_for_one,
+            None,
+            config.fee_bps as i128,
+            Self::get_active_creator_fee_bps_safe(&env, &config),
+            config.protocol_fee_bps as i128,
+        );
+        
+        (lp_fee, creator_fee, protocol_fee)
+    }
+    
+    /// Quote a swap of up to `amount_in_max` that stops at a target price
+    /// 
+    /// Runs the dry-run engine with `sqrt_price_target_x64` as the price
+    /// limit and reports the input consumed and output produced on the way.
+    /// A target at or on the wrong side of the current price quotes nothing.
+    pub fn quote_to_price(
+        env: Env,
+        amount_in_max: i128,
+        zero_for_one: bool,
+        sqrt_price_target_x64: u128,
+    ) -> QuoteResult {
+        let config = read_pool_config(&env);
+        let pool = read_pool_state(&env);
+        
+        if !is_valid_price_limit(&pool, zero_for_one, Some(sqrt_price_target_x64)) || sqrt_price_target_x64 == 0 {
+            return QuoteResult {
+                amount_in_used: 0,
+                amount_out: 0,
+                sqrt_price_x64: pool.sqrt_price_x64,
+                current_tick: pool.current_tick,
+                reached_target: false,
+                steps: 0,
+            };
+        }
+        
+        let swap_state = swap_state(&pool, &config, zero_for_one);
+        
+        let (amount_in_used, amount_out, sqrt_price_x64, current_tick, steps) = quote_swap(
+            &env,
+            &swap_state,
+            read_tick_info,
+            read_tick_word,
+            amount_in_max,
+            zero_for_one,
+            Some(sqrt_price_target_x64),
+            config.fee_bps as i128,
+        );
+        
+        QuoteResult {
+            amount_in_used,
+            amount_out,
+            sqrt_price_x64,
+            current_tick,
+            reached_target: sqrt_price_x64 == sqrt_price_target_x64,
+            steps,
+        }
+    }
+    
+    /// Cumulative input needed to move the price to each of `ticks`
+    /// 
+    /// Depth chart data: entry `i` is the `token_in` amount, fee included,
+    /// that a swap in the given direction consumes to bring the price from
+    /// where it is now to `ticks[i]`. Each tick is a separate dry run of the
+    /// swap engine, so the order of `ticks` does not matter and further ticks
+    /// cost more. A tick at or behind the current price needs 0; past the
+    /// last liquidity, the entry is the input the remaining liquidity can
+    /// absorb, up to `MAX_DEPTH_AMOUNT_IN`. Only the first `MAX_DEPTH_TICKS`
+    /// ticks are priced.
+    pub fn amounts_to_reach_ticks(env: Env, ticks: Vec<i32>, zero_for_one: bool) -> Vec<i128> {
+        let config = read_pool_config(&env);
+        let pool = read_pool_state(&env);
+        let swap_state = swap_state(&pool, &config, zero_for_one);
+        
+        let mut amounts = Vec::new(&env);
+        for tick in ticks.iter().take(MAX_DEPTH_TICKS as usize) {
+            let target = get_sqrt_ratio_at_tick(tick.clamp(MIN_TICK, MAX_TICK));
+            if !is_valid_price_limit(&pool, zero_for_one, Some(target)) {
+                amounts.push_back(0);
+                continue;
+            }
+            let (amount_in_used, _, _, _, _) = quote_swap(
+                &env,
+                &swap_state,
+                read_tick_info,
+                read_tick_word,
+                MAX_DEPTH_AMOUNT_IN,
+                zero_for_one,
+                Some(target),
+                config.fee_bps as i128,
+            );
+            amounts.push_back(amount_in_used);
+        }
+        amounts
+    }
+    
+    // ========================================================
+    // SWAP FUNCTION
+    // ========================================================
+    
+    /// Execute a swap
+    /// 
+    /// Can be called by:
+    /// - Direct user (sender.require_auth)
+    /// - Router contract (for multi-hop swaps)
+    /// 
+    /// A nonzero `max_tick_deviation` rejects the swap when the spot tick is
+    /// more than that many ticks from the TWAP over `TWAP_DEVIATION_WINDOW`
+    /// (sandwich protection).
+    pub fn swap(
+        env: Env,
+        sender: Address,
+        token_in: Address,
+        amount_in: i128,
+        amount_out_min: i128,
+        sqrt_price_limit_x64: Option<u128>,
+        max_tick_deviation: u32,
+    ) -> Result<SwapResult, PoolError> {
+        sender.require_auth();
+        Self::do_swap(
+            env,
+            &sender,
+            None,
+            &sender,
+            token_in,
+            amount_in,
+            amount_out_min,
+            sqrt_price_limit_x64,
+            max_tick_deviation,
+        )
+    }
+    
+    #[allow(clippy::too_many_arguments)]
+    fn do_swap(
+        env: Env,
+        payer: &Address,
+        spender: Option<&Address>,
+        recipient: &Address,
+        token_in: Address,
+        amount_in: i128,
+        amount_out_min: i128,
+        sqrt_price_limit_x64: Option<u128>,
+        max_tick_deviation: u32,
+    ) -> Result<SwapResult, PoolError> {
+        // Validate amount_in early
+        if amount_in <= 0 {
+            return Err(PoolError::SwapAmountTooSmall);
+        }
+        
+        let pool_state = read_pool_state(&env);
+        let config = read_pool_config(&env);
+        
+        if pool_state.paused {
+            return Err(PoolError::PoolPaused);
+        }
+        
+        let zero_for_one = if token_in == pool_state.token0 {
+            true
+        } else if token_in == pool_state.token1 {
+            false
+        } else {
+            return Err(PoolError::InvalidToken);
+        };
+        
+        if !is_valid_price_limit(&pool_state, zero_for_one, sqrt_price_limit_x64) {
+            return Err(PoolError::InvalidPriceLimit);
+        }
+        
+        let mut swap_state = swap_state(&pool_state, &config, zero_for_one);
+        
+        // No active liquidity is fine as long as the swap reaches an
+        // initialized range before its price limit
+        if pool_state.liquidity <= 0 {
+            let (reachable_in, _, _, _, _) = quote_swap(
+                &env,
+                &swap_state,
+                read_tick_info,
+                read_tick_word,
+                amount_in,
+                zero_for_one,
+                sqrt_price_limit_x64,
+                config.fee_bps as i128,
+            );
+            if reachable_in <= 0 {
+                return Err(PoolError::NoLiquidity);
+            }
+        }
+        
+        if max_tick_deviation > 0 {
+            let twap_tick = twap::average_tick(
+                &env,
+                TWAP_DEVIATION_WINDOW,
+                pool_state.current_tick,
+                pool_state.liquidity,
+            );
+            if pool_state.current_tick.abs_diff(twap_tick) > max_tick_deviation {
+                return Err(PoolError::PriceDeviation);
+            }
+        }
+        
+        let (token_in_addr, token_out_addr) = if zero_for_one {
+            (&pool_state.token0, &pool_state.token1)
+        } else {
+            (&pool_state.token1, &pool_state.token0)
+        };
+        let pool_addr = env.current_contract_address();
+        let token_in_client = token::Client::new(&env, token_in_addr);
+        
+        // Pull the full input first and swap only what actually arrived, so
+        // fee-on-transfer tokens cannot leave the pool under-collateralized
+        let balance_before = token_in_client.balance(&pool_addr);
+        match spender {
+            Some(spender) => token_in_client.transfer_from(spender, payer, &pool_addr, &amount_in),
+            None => token_in_client.transfer(payer, &pool_addr, &amount_in),
+        }
+        let amount_received = token_in_client.balance(&pool_addr).saturating_sub(balance_before);
+        if amount_received < swap_state.min_amount_in.max(MIN_SWAP_AMOUNT) {
+            return Err(PoolError::SwapAmountTooSmall);
+        }
+        
+        // Safe creator fee check - won't panic if factory call fails
+        let creator_fee_bps = Self::get_active_creator_fee_bps_safe(&env, &config);
+        
+        let (amount_in_used, amount_out, creator_fee, protocol_fee, lp_fee) = engine_swap(
+            &env,
+            &mut swap_state,
+            read_tick_info,
+            read_tick_word,
+            write_tick_info,
+            emit_sync_tick,
+            emit_cross_tick,
+            amount_received,
+            zero_for_one,
+            sqrt_price_limit_x64,
+            config.fee_bps as i128,
+            creator_fee_bps,
+            config.protocol_fee_bps as i128,
+        );
+        
+        if amount_out < amount_out_min {
+            return Err(PoolError::SlippageExceeded);
+        }
+        
+        // Circuit breaker for thin pools, measured like preview_swap
+        let impact = price_impact_bps(
+            &env,
+            pool_state.sqrt_price_x64,
+            amount_in_used,
+            amount_out,
+            zero_for_one,
+            config.fee_bps as i128,
+        );
+        if exceeds_price_impact(&config, impact) {
+            return Err(PoolError::PriceImpactTooHigh);
+        }
+        
+        // Record the pre-swap tick for the oracle
+        twap::update_twap(&env, pool_state.current_tick, pool_state.liquidity);
+        
+        // Update pool state
+        let mut updated_pool = pool_state.clone();
+        updated_pool.sqrt_price_x64 = swap_state.sqrt_price_x64;
+        updated_pool.current_tick = swap_state.current_tick;
+        updated_pool.liquidity = swap_state.liquidity;
+        updated_pool.fee_growth_global_0 = swap_state.fee_growth_global_0;
+        updated_pool.fee_growth_global_1 = swap_state.fee_growth_global_1;
+
+        // Credit the creator fee. It is carved out of the LP fee inside the swap
+        // engine and is denominated in the input token, and the swapper already
+        // transferred it in as part of amount_in_used — so we only need to record
+        // the claim here (claim_creator_fees pays it out later).
+        if creator_fee > 0 {
+            let cf = creator_fee as u128;
+            if zero_for_one {
+                updated_pool.creator_fees_0 = updated_pool.creator_fees_0.saturating_add(cf);
+            } else {
+                updated_pool.creator_fees_1 = updated_pool.creator_fees_1.saturating_add(cf);
+            }
+        }
+
+        // Same for the protocol fee (carved out of what remains for LPs)
+        if protocol_fee > 0 {
+            let pf = protocol_fee as u128;
+            if zero_for_one {
+                updated_pool.protocol_fees_0 = updated_pool.protocol_fees_0.saturating_add(pf);
+            } else {
+                updated_pool.protocol_fees_1 = updated_pool.protocol_fees_1.saturating_add(pf);
+            }
+        }
+
+        // Lifetime totals feed the creator fee cap; the LP share stays owed
+        // until positions collect it
+        let fees = creator_fee.saturating_add(protocol_fee).saturating_add(lp_fee) as u128;
+        if zero_for_one {
+            updated_pool.lifetime_fees_0 = updated_pool.lifetime_fees_0.saturating_add(fees);
+            updated_pool.lifetime_creator_fees_0 =
+                updated_pool.lifetime_creator_fees_0.saturating_add(creator_fee as u128);
+            updated_pool.lp_fees_owed_0 = updated_pool.lp_fees_owed_0.saturating_add(lp_fee as u128);
+        } else {
+            updated_pool.lifetime_fees_1 = updated_pool.lifetime_fees_1.saturating_add(fees);
+            updated_pool.lifetime_creator_fees_1 =
+                updated_pool.lifetime_creator_fees_1.saturating_add(creator_fee as u128);
+            updated_pool.lp_fees_owed_1 = updated_pool.lp_fees_owed_1.saturating_add(lp_fee as u128);
+        }
+
+        // Volume counts the input actually used and the output paid
+        let (volume_in, volume_out) = (amount_in_used as u128, amount_out as u128);
+        if zero_for_one {
+            updated_pool.volume_token0 = updated_pool.volume_token0.saturating_add(volume_in);
+            updated_pool.volume_token1 = updated_pool.volume_token1.saturating_add(volume_out);
+        } else {
+            updated_pool.volume_token1 = updated_pool.volume_token1.saturating_add(volume_in);
+            updated_pool.volume_token0 = updated_pool.volume_token0.saturating_add(volume_out);
+        }
+        write_pool_state(&env, &updated_pool);
+        
+        // Return unspent input (price limit hit) and pay out
+        let amount_in_remaining = amount_received.saturating_sub(amount_in_used);
+        if amount_in_remaining > 0 {
+            token_in_client.transfer(&pool_addr, payer, &amount_in_remaining);
+        }
+        token::Client::new(&env, token_out_addr).transfer(&pool_addr, recipient, &amount_out);
+        
+        if updated_pool.invariant_checks {
+            Self::check_solvency(&env, &updated_pool)?;
+        }
+
+        if creator_fee > 0 {
+            let unclaimed = if zero_for_one {
+                updated_pool.creator_fees_0
+            } else {
+                updated_pool.creator_fees_1
+            };
+            emit_creator_fee_accrued(
+                &env,
+                &config.creator,
+                token_in_addr,
+                creator_fee as u128,
+                unclaimed,
+            );
+        }
         
-        emit_swap(&env, amount_in_used, amount_out, zero_for_one);
+        emit_swap(
+            &env,
+            amount_in_used,
+            amount_out,
+            zero_for_one,
+            swap_state.sqrt_price_x64,
+            swap_state.current_tick,
+            lp_fee,
+            creator_fee,
+            protocol_fee,
+        );
         
-        SwapResult {
+        Ok(SwapResult {
             amount_in: amount_in_used,
             amount_out,
             current_tick: swap_state.current_tick,
             sqrt_price_x64: swap_state.sqrt_price_x64,
-        }
+            amount_in_remaining,
+        })
     }
     
     // ========================================================
@@ -340,6 +951,7 @@ impl BelugaPool {
     // ========================================================
     
     /// Add liquidity to a position
+    #[allow(clippy::too_many_arguments)]
     pub fn add_liquidity(
         env: Env,
         owner: Address,
@@ -349,295 +961,1486 @@ impl BelugaPool {
         amount1_desired: i128,
         amount0_min: i128,
         amount1_min: i128,
-    ) -> (i128, i128, i128) {
+    ) -> Result<(i128, i128, i128), PoolError> {
+        owner.require_auth();
+        Self::do_add_liquidity(&env, &owner, lower_tick, upper_tick, amount0_desired, amount1_desired, amount0_min, amount1_min)
+    }
+    
+    /// Add liquidity with the minimums given as a tolerance in bps
+    /// 
+    /// Each minimum is `desired * (10000 - slippage_bps) / 10000`, then
+    /// checked exactly like `add_liquidity`'s absolute minimums.
+    /// `slippage_bps` above 10000 fails with `InvalidBps`.
+    pub fn add_liquidity_with_slippage(
+        env: Env,
+        owner: Address,
+        lower_tick: i32,
+        upper_tick: i32,
+        amount0_desired: i128,
+        amount1_desired: i128,
+        slippage_bps: u32,
+    ) -> Result<(i128, i128, i128), PoolError> {
         owner.require_auth();
         
+        if slippage_bps > 10_000 {
+            return Err(PoolError::InvalidBps);
+        }
+        let amount0_min = mul_bps(&env, amount0_desired, 10_000 - slippage_bps);
+        let amount1_min = mul_bps(&env, amount1_desired, 10_000 - slippage_bps);
+        
+        Self::do_add_liquidity(&env, &owner, lower_tick, upper_tick, amount0_desired, amount1_desired, amount0_min, amount1_min)
+    }
+    
+    /// Shared body of `add_liquidity` and `add_liquidity_with_slippage`; the
+    /// caller has checked `owner`'s auth
+    #[allow(clippy::too_many_arguments)]
+    fn do_add_liquidity(
+        env: &Env,
+        owner: &Address,
+        lower_tick: i32,
+        upper_tick: i32,
+        amount0_desired: i128,
+        amount1_desired: i128,
+        amount0_min: i128,
+        amount1_min: i128,
+    ) -> Result<(i128, i128, i128), PoolError> {
         // Delegate to internal function
         let (liquidity, amount0, amount1) = Self::internal_add_liquidity(
-            &env,
-            &owner,
+            env,
+            owner,
+            None,
             lower_tick,
             upper_tick,
             amount0_desired,
             amount1_desired,
             amount0_min,
             amount1_min,
-        );
+        )?;
         
         // Transfer tokens from owner to pool
-        let state = read_pool_state(&env);
+        let state = read_pool_state(env);
         if amount0 > 0 {
-            token::Client::new(&env, &state.token0).transfer(&owner, &env.current_contract_address(), &amount0);
+            token::Client::new(env, &state.token0).transfer(owner, &env.current_contract_address(), &amount0);
         }
         if amount1 > 0 {
-            token::Client::new(&env, &state.token1).transfer(&owner, &env.current_contract_address(), &amount1);
+            token::Client::new(env, &state.token1).transfer(owner, &env.current_contract_address(), &amount1);
         }
         
-        emit_add_liquidity(&env, liquidity, amount0, amount1);
+        emit_add_liquidity(env, liquidity, amount0, amount1);
+        Self::report_reserves(env);
         
-        (liquidity, amount0, amount1)
+        Ok((liquidity, amount0, amount1))
     }
     
-    /// Mint liquidity (called by factory during pool creation)
-    /// Tokens must already be transferred to pool
-    pub fn mint(
+    /// Preview `add_liquidity` without changing state or moving tokens
+    /// 
+    /// Applies the same tick alignment and liquidity math, so the result is
+    /// exactly `(liquidity, amount0, amount1)` an `add_liquidity` call with
+    /// these arguments would return at the current price. Returns (0, 0, 0)
+    /// when that call would fail (range empty after alignment, too little
+    /// liquidity, pool paused).
+    pub fn simulate_add_liquidity(
         env: Env,
-        owner: Address,
         lower_tick: i32,
         upper_tick: i32,
         amount0_desired: i128,
         amount1_desired: i128,
-    ) -> i128 {
-        // SECURITY: mint credits a position WITHOUT pulling tokens (the factory
-        // transfers them beforehand during create_pool). It must therefore only
-        // be callable by the factory, otherwise anyone could mint a free position
-        // and drain the pool via remove_liquidity. Require the factory to
-        // authorize this call (mirrors `initialize`).
-        read_pool_config(&env).factory.require_auth();
-        // Tokens already transferred by factory
-
-        let (liquidity, amount0, amount1) = Self::internal_add_liquidity(
-            &env,
-            &owner,
-            lower_tick,
-            upper_tick,
-            amount0_desired,
-            amount1_desired,
-            0, // No slippage check for factory mint
-            0,
-        );
-        
-        emit_add_liquidity(&env, liquidity, amount0, amount1);
-        
-        liquidity
+    ) -> (i128, i128, i128) {
+        let state = read_pool_state(&env);
+        match Self::quote_deposit(&env, &state, lower_tick, upper_tick, amount0_desired, amount1_desired) {
+            Ok(quote) => (quote.liquidity - quote.locked_liquidity, quote.amount0, quote.amount1),
+            Err(_) => (0, 0, 0),
+        }
     }
     
-    /// Remove liquidity from a position
-    pub fn remove_liquidity(
+    /// Place a range order: single-sided liquidity away from the current price
+    /// 
+    /// A token0 order must sit entirely above the current tick and fills as
+    /// the price rises through it; a token1 order sits entirely below and
+    /// fills as the price falls. Liquidity is computed from `amount` of the
+    /// supplied token alone and credited as a normal range position.
+    pub fn add_range_order(
         env: Env,
         owner: Address,
         lower_tick: i32,
         upper_tick: i32,
-        liquidity: i128,
-        amount0_min: i128,
-        amount1_min: i128,
-    ) -> (i128, i128) {
-        owner.require_auth();
-        
-        if liquidity <= 0 {
-            panic!("{}", ErrorMsg::INVALID_LIQUIDITY_AMOUNT);
+        amount: i128,
+        token_is_zero: bool,
+    ) -> Result<(i128, i128, i128), PoolError> {
+        if amount <= 0 {
+            return Err(PoolError::InvalidLiquidityAmount);
         }
         
-        let config = read_pool_config(&env);
-        let mut state = read_pool_state(&env);
-        
+        let state = read_pool_state(&env);
         let lower_aligned = snap_tick_to_spacing(lower_tick, state.tick_spacing);
-        let upper_aligned = snap_tick_to_spacing(upper_tick, state.tick_spacing);
+        let upper_aligned = snap_tick_to_spacing_ceil(upper_tick, state.tick_spacing);
         
-        // Check if position is locked (only affects creator's locked position)
-        if Self::is_position_locked(&env, &config, &owner, lower_aligned, upper_aligned) {
-            panic!("position is locked");
+        if lower_tick >= upper_tick {
+            return Err(PoolError::InvalidTickRange);
         }
         
-        let mut pos = read_position(&env, &owner, lower_aligned, upper_aligned);
-        
-        if pos.liquidity < liquidity {
-            panic!("{}", ErrorMsg::INSUFFICIENT_LIQUIDITY);
-        }
-        
-        // Calculate amounts to withdraw
-        let (amount0, amount1) = get_amounts_for_liquidity(
-            &env,
-            liquidity,
-            get_sqrt_ratio_at_tick(lower_aligned),
-            get_sqrt_ratio_at_tick(upper_aligned),
-            state.sqrt_price_x64,
-        );
-        
-        // Slippage check
-        if amount0 < amount0_min || amount1 < amount1_min {
-            panic!("{}", ErrorMsg::SLIPPAGE_EXCEEDED);
+        let one_sided = if token_is_zero {
+            state.current_tick < lower_aligned
+        } else {
+            upper_aligned <= state.current_tick
+        };
+        if !one_sided {
+            return Err(PoolError::InvalidRangeOrder);
         }
         
-        // Update fee growth
-        let fee_growth_inside = get_fee_growth_inside_local(
-            &env,
+        let (amount0_desired, amount1_desired) = if token_is_zero { (amount, 0) } else { (0, amount) };
+        let result = Self::add_liquidity(
+            env.clone(),
+            owner.clone(),
             lower_aligned,
             upper_aligned,
-            state.current_tick,
-            state.fee_growth_global_0,
-            state.fee_growth_global_1,
-        );
+            amount0_desired,
+            amount1_desired,
+            0,
+            0,
+        )?;
         
-        update_position(&env, &mut pos, fee_growth_inside.0, fee_growth_inside.1);
+        let target_tick = if token_is_zero { upper_aligned } else { lower_aligned };
+        emit_range_order(&env, &owner, lower_aligned, upper_aligned, token_is_zero, target_tick);
         
-        // Subtract liquidity from position
-        pos.liquidity = pos.liquidity.saturating_sub(liquidity);
+        Ok(result)
+    }
+    
+    /// Add liquidity to a range from `token_in` alone
+    /// 
+    /// Swaps the share of `amount_in` that balances the deposit for the range
+    /// at the post-swap price (`amount_out_min` guards that swap), then adds
+    /// liquidity from the remaining `token_in` and the swap output. Whatever
+    /// the deposit cannot use stays with `owner`.
+    /// 
+    /// Returns `(liquidity, amount0, amount1)` of the deposit.
+    pub fn add_liquidity_single_sided(
+        env: Env,
+        owner: Address,
+        lower_tick: i32,
+        upper_tick: i32,
+        token_in: Address,
+        amount_in: i128,
+        amount_out_min: i128,
+    ) -> Result<(i128, i128, i128), PoolError> {
+        owner.require_auth();
         
-        // Update ticks
-        belugaswap_tick::update_tick(
-            &env,
-            |e, t| read_tick_info(e, t),
-            |e, t, info| write_tick_info(e, t, info),
-            lower_aligned,
-            state.current_tick,
-            -liquidity,
-            state.fee_growth_global_0,
-            state.fee_growth_global_1,
-            false,
-        );
+        if amount_in <= 0 {
+            return Err(PoolError::InvalidLiquidityAmount);
+        }
+        if lower_tick >= upper_tick {
+            return Err(PoolError::InvalidTickRange);
+        }
         
-        belugaswap_tick::update_tick(
-            &env,
-            |e, t| read_tick_info(e, t),
-            |e, t, info| write_tick_info(e, t, info),
-            upper_aligned,
-            state.current_tick,
-            -liquidity,
-            state.fee_growth_global_0,
-            state.fee_growth_global_1,
-            true,
-        );
+        let state = read_pool_state(&env);
+        let zero_for_one = if token_in == state.token0 {
+            true
+        } else if token_in == state.token1 {
+            false
+        } else {
+            return Err(PoolError::InvalidToken);
+        };
         
-        // Update pool liquidity if in range
-        if state.current_tick >= lower_aligned && state.current_tick < upper_aligned {
-            state.liquidity = state.liquidity.saturating_sub(liquidity);
-        }
+        let swap_amount = Self::single_sided_swap_amount(&env, &state, lower_tick, upper_tick, zero_for_one, amount_in);
+        let (amount_in_spent, amount_out) = if swap_amount > 0 {
+            // Measure the swap by the owner's balances, like do_swap measures
+            // its input: fee-on-transfer tokens cost the owner more than the
+            // pool swaps and deliver less than the pool pays
+            let token_in_client = token::Client::new(&env, &token_in);
+            let token_out = if zero_for_one { &state.token1 } else { &state.token0 };
+            let token_out_client = token::Client::new(&env, token_out);
+            let in_before = token_in_client.balance(&owner);
+            let out_before = token_out_client.balance(&owner);
+            Self::do_swap(
+                env.clone(),
+                &owner,
+                None,
+                &owner,
+                token_in.clone(),
+                swap_amount,
+                amount_out_min,
+                None,
+                0,
+            )?;
+            (
+                in_before.saturating_sub(token_in_client.balance(&owner)),
+                token_out_client.balance(&owner).saturating_sub(out_before),
+            )
+        } else {
+            (0, 0)
+        };
         
-        write_pool_state(&env, &state);
-        write_position(&env, &owner, lower_aligned, upper_aligned, &pos);
+        let amount_left = amount_in.saturating_sub(amount_in_spent).max(0);
+        let (amount0_desired, amount1_desired) = if zero_for_one {
+            (amount_left, amount_out)
+        } else {
+            (amount_out, amount_left)
+        };
+        let (liquidity, amount0, amount1) = Self::internal_add_liquidity(
+            &env,
+            &owner,
+            None,
+            lower_tick,
+            upper_tick,
+            amount0_desired,
+            amount1_desired,
+            0,
+            0,
+        )?;
         
-        // Transfer tokens to owner
+        let state = read_pool_state(&env);
         if amount0 > 0 {
-            token::Client::new(&env, &state.token0).transfer(&env.current_contract_address(), &owner, &amount0);
+            token::Client::new(&env, &state.token0).transfer(&owner, &env.current_contract_address(), &amount0);
         }
         if amount1 > 0 {
-            token::Client::new(&env, &state.token1).transfer(&env.current_contract_address(), &owner, &amount1);
+            token::Client::new(&env, &state.token1).transfer(&owner, &env.current_contract_address(), &amount1);
         }
         
-        emit_remove_liquidity(&env, liquidity, amount0, amount1);
+        emit_add_liquidity(&env, liquidity, amount0, amount1);
+        Self::report_reserves(&env);
         
-        (amount0, amount1)
+        Ok((liquidity, amount0, amount1))
     }
     
-    /// Collect accumulated fees from a position
-    pub fn collect_fees(
+    /// Add liquidity as a transferable position token
+    /// 
+    /// Works like `add_liquidity`, but the position is keyed by the returned
+    /// id instead of `(owner, lower_tick, upper_tick)`: it never merges with
+    /// the owner's range positions and can be moved with `transfer_position`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_position(
         env: Env,
         owner: Address,
         lower_tick: i32,
         upper_tick: i32,
-    ) -> (u128, u128) {
+        amount0_desired: i128,
+        amount1_desired: i128,
+        amount0_min: i128,
+        amount1_min: i128,
+    ) -> Result<u64, PoolError> {
         owner.require_auth();
         
+        let position_id = next_position_id(&env);
+        let (liquidity, amount0, amount1) = Self::internal_add_liquidity(
+            &env,
+            &owner,
+            Some(position_id),
+            lower_tick,
+            upper_tick,
+            amount0_desired,
+            amount1_desired,
+            amount0_min,
+            amount1_min,
+        )?;
+        
+        // Transfer tokens from owner to pool
         let state = read_pool_state(&env);
+        if amount0 > 0 {
+            token::Client::new(&env, &state.token0).transfer(&owner, &env.current_contract_address(), &amount0);
+        }
+        if amount1 > 0 {
+            token::Client::new(&env, &state.token1).transfer(&owner, &env.current_contract_address(), &amount1);
+        }
         
-        let lower_aligned = snap_tick_to_spacing(lower_tick, state.tick_spacing);
-        let upper_aligned = snap_tick_to_spacing(upper_tick, state.tick_spacing);
+        let minted = read_position_token(&env, position_id).unwrap();
+        emit_add_liquidity(&env, liquidity, amount0, amount1);
+        emit_position_minted(&env, position_id, &owner, minted.lower_tick, minted.upper_tick);
+        Self::report_reserves(&env);
         
-        let mut pos = read_position(&env, &owner, lower_aligned, upper_aligned);
+        Ok(position_id)
+    }
+    
+    /// Hand a position token to a new owner
+    /// 
+    /// Liquidity and uncollected fees move with the position.
+    pub fn transfer_position(
+        env: Env,
+        from: Address,
+        to: Address,
+        position_id: u64,
+    ) -> Result<(), PoolError> {
+        from.require_auth();
         
-        // Update fee growth
+        let mut token = Self::owned_position_token(&env, &from, position_id)?;
+        token.owner = to.clone();
+        write_position_token(&env, position_id, &token);
+        
+        emit_position_transferred(&env, position_id, &from, &to);
+        
+        Ok(())
+    }
+    
+    /// Merge position token `id_b` into `id_a`
+    /// 
+    /// Both must belong to `owner` and cover the same tick range. Fees of both
+    /// are settled first, then liquidity and owed fees are summed into `id_a`
+    /// and `id_b` is deleted. Ticks and pool liquidity are unchanged.
+    /// 
+    /// Returns the merged liquidity.
+    pub fn merge_positions(
+        env: Env,
+        owner: Address,
+        id_a: u64,
+        id_b: u64,
+    ) -> Result<i128, PoolError> {
+        owner.require_auth();
+        
+        if id_a == id_b {
+            return Err(PoolError::PositionMismatch);
+        }
+        
+        let mut token_a = Self::owned_position_token(&env, &owner, id_a)?;
+        let token_b = Self::owned_position_token(&env, &owner, id_b)?;
+        if token_a.lower_tick != token_b.lower_tick || token_a.upper_tick != token_b.upper_tick {
+            return Err(PoolError::PositionMismatch);
+        }
+        
+        let state = read_pool_state(&env);
         let fee_growth_inside = get_fee_growth_inside_local(
             &env,
-            lower_aligned,
-            upper_aligned,
+            token_a.lower_tick,
+            token_a.upper_tick,
             state.current_tick,
             state.fee_growth_global_0,
             state.fee_growth_global_1,
         );
         
-        let (pending0, pending1) = calculate_pending_fees(&env, &pos, fee_growth_inside.0, fee_growth_inside.1);
+        merge_into(&env, &mut token_a.position, &token_b.position, fee_growth_inside.0, fee_growth_inside.1);
+        write_position_token(&env, id_a, &token_a);
+        remove_position_token(&env, id_b);
+        
+        emit_positions_merged(&env, id_a, id_b, token_a.position.liquidity);
         
-        let fees0 = pos.tokens_owed_0.saturating_add(pending0);
-        let fees1 = pos.tokens_owed_1.saturating_add(pending1);
+        Ok(token_a.position.liquidity)
+    }
+    
+    /// Mint liquidity (called by factory during pool creation)
+    /// Tokens must already be transferred to pool; what the liquidity does
+    /// not use is refunded to `owner`
+    pub fn mint(
+        env: Env,
+        owner: Address,
+        lower_tick: i32,
+        upper_tick: i32,
+        amount0_desired: i128,
+        amount1_desired: i128,
+    ) -> Result<i128, PoolError> {
+        let ranges = vec![&env, (lower_tick, upper_tick, amount0_desired, amount1_desired)];
+        Ok(Self::do_mint(&env, &owner, &ranges)?.get_unchecked(0))
+    }
+    
+    /// Mint several positions for `owner` from one prefunded transfer
+    /// (called by factory during pool creation)
+    /// 
+    /// Each entry is `(lower_tick, upper_tick, amount0_desired, amount1_desired)`
+    /// and mints in order, so on a new pool the first range is the one that
+    /// locks `MINIMUM_LOCKED_LIQUIDITY`. The unused part of the summed desired
+    /// amounts is refunded once, as in `mint`. Returns each range's liquidity.
+    pub fn mint_ranges(
+        env: Env,
+        owner: Address,
+        ranges: Vec<(i32, i32, i128, i128)>,
+    ) -> Result<Vec<i128>, PoolError> {
+        Self::do_mint(&env, &owner, &ranges)
+    }
+    
+    /// Shared body of `mint` and `mint_ranges`
+    fn do_mint(
+        env: &Env,
+        owner: &Address,
+        ranges: &Vec<(i32, i32, i128, i128)>,
+    ) -> Result<Vec<i128>, PoolError> {
+        // SECURITY: mint credits a position WITHOUT pulling tokens (the factory
+        // transfers them beforehand during create_pool). It must therefore only
+        // be callable by the factory, otherwise anyone could mint a free position
+        // and drain the pool via remove_liquidity. Require the factory to
+        // authorize this call (mirrors `initialize`).
+        read_pool_config(env).factory.require_auth();
+        if ranges.is_empty() {
+            return Err(PoolError::InvalidLiquidityAmount);
+        }
+        // Tokens already transferred by factory
+        let first_deposit = !read_pool_state(env).min_liquidity_locked;
+
+        let mut liquidities = Vec::new(env);
+        let (mut desired0, mut desired1, mut used0, mut used1) = (0i128, 0i128, 0i128, 0i128);
+        for (lower_tick, upper_tick, amount0_desired, amount1_desired) in ranges.iter() {
+            let (liquidity, amount0, amount1) = Self::internal_add_liquidity(
+                env,
+                owner,
+                None,
+                lower_tick,
+                upper_tick,
+                amount0_desired,
+                amount1_desired,
+                0, // No slippage check for factory mint
+                0,
+            )?;
+            desired0 = desired0.checked_add(amount0_desired).ok_or(PoolError::Overflow)?;
+            desired1 = desired1.checked_add(amount1_desired).ok_or(PoolError::Overflow)?;
+            used0 += amount0;
+            used1 += amount1;
+            liquidities.push_back(liquidity);
+            emit_add_liquidity(env, liquidity, amount0, amount1);
+        }
         
-        // Reset owed tokens
-        pos.tokens_owed_0 = 0;
-        pos.tokens_owed_1 = 0;
-        pos.fee_growth_inside_last_0 = fee_growth_inside.0;
-        pos.fee_growth_inside_last_1 = fee_growth_inside.1;
+        // Refund the unused part of the prefunded tokens. Before the first
+        // deposit the pool holds nothing else, so its whole balance beyond
+        // what was used is the minter's; later, only the desired amounts are.
+        let state = read_pool_state(env);
+        let pool_addr = env.current_contract_address();
+        for (token, desired, used) in [
+            (&state.token0, desired0, used0),
+            (&state.token1, desired1, used1),
+        ] {
+            let token_client = token::Client::new(env, token);
+            let excess = if first_deposit {
+                let balance = token_client.balance(&pool_addr);
+                // The positions must be backed by what was transferred
+                if balance < used {
+                    return Err(PoolError::InsufficientLiquidity);
+                }
+                balance - used
+            } else {
+                desired.saturating_sub(used)
+            };
+            if excess > 0 {
+                token_client.transfer(&pool_addr, owner, &excess);
+            }
+        }
         
-        write_position(&env, &owner, lower_aligned, upper_aligned, &pos);
+        Self::report_reserves(env);
         
-        // Transfer fees
-        if fees0 > 0 {
-            token::Client::new(&env, &state.token0).transfer(
-                &env.current_contract_address(),
-                &owner,
-                &safe_u128_to_i128(fees0),
-            );
+        Ok(liquidities)
+    }
+    
+    /// Remove liquidity from a position
+    pub fn remove_liquidity(
+        env: Env,
+        owner: Address,
+        lower_tick: i32,
+        upper_tick: i32,
+        liquidity: i128,
+        amount0_min: i128,
+        amount1_min: i128,
+    ) -> Result<(i128, i128), PoolError> {
+        owner.require_auth();
+        Self::do_remove_liquidity(env, &owner, &owner, lower_tick, upper_tick, liquidity, amount0_min, amount1_min)
+    }
+    
+    /// Shared body of `remove_liquidity` and `remove_liquidity_to`; the
+    /// caller has checked `owner`'s auth
+    #[allow(clippy::too_many_arguments)]
+    fn do_remove_liquidity(
+        env: Env,
+        owner: &Address,
+        recipient: &Address,
+        lower_tick: i32,
+        upper_tick: i32,
+        liquidity: i128,
+        amount0_min: i128,
+        amount1_min: i128,
+    ) -> Result<(i128, i128), PoolError> {
+        if liquidity <= 0 {
+            return Err(PoolError::InvalidLiquidityAmount);
         }
-        if fees1 > 0 {
-            token::Client::new(&env, &state.token1).transfer(
-                &env.current_contract_address(),
-                &owner,
-                &safe_u128_to_i128(fees1),
-            );
+        
+        // The minimum liquidity locked by the first deposit is never withdrawable
+        if *owner == env.current_contract_address() {
+            return Err(PoolError::PositionLocked);
         }
         
-        emit_collect(&env, fees0, fees1);
+        let config = read_pool_config(&env);
+        let mut state = read_pool_state(&env);
         
-        (fees0, fees1)
+        let lower_aligned = snap_tick_to_spacing(lower_tick, state.tick_spacing);
+        let upper_aligned = snap_tick_to_spacing_ceil(upper_tick, state.tick_spacing);
+        
+        // Check if position is locked (only affects creator's locked position)
+        if Self::is_position_locked(&env, &config, owner, lower_aligned, upper_aligned) {
+            return Err(PoolError::PositionLocked);
+        }
+        
+        let mut pos = read_position(&env, owner, lower_aligned, upper_aligned);
+        
+        if pos.liquidity < liquidity {
+            return Err(PoolError::InsufficientLiquidity);
+        }
+        
+        let (amount0, amount1) = Self::internal_remove_liquidity(
+            &env,
+            &mut state,
+            &mut pos,
+            lower_aligned,
+            upper_aligned,
+            liquidity,
+            amount0_min,
+            amount1_min,
+        )?;
+        
+        write_position(&env, owner, lower_aligned, upper_aligned, &pos);
+        if is_position_empty(&pos) {
+            remove_owner_position(&env, owner, lower_aligned, upper_aligned);
+        }
+        
+        // Transfer tokens to recipient
+        if amount0 > 0 {
+            token::Client::new(&env, &state.token0).transfer(&env.current_contract_address(), recipient, &amount0);
+        }
+        if amount1 > 0 {
+            token::Client::new(&env, &state.token1).transfer(&env.current_contract_address(), recipient, &amount1);
+        }
+        
+        emit_remove_liquidity(&env, liquidity, amount0, amount1);
+        Self::report_reserves(&env);
+        
+        Ok((amount0, amount1))
     }
     
-    /// Claim creator fees (only creator can call)
-    pub fn claim_creator_fees(env: Env) -> (u128, u128) {
-        let config = read_pool_config(&env);
-        config.creator.require_auth();
+    /// Exit a position in one call: withdraw all liquidity and collect all fees
+    /// 
+    /// Ticks left without liquidity and the position entry itself are removed
+    /// from storage. Returns the total (token0, token1) paid out, principal
+    /// plus fees.
+    pub fn close_position(
+        env: Env,
+        owner: Address,
+        lower_tick: i32,
+        upper_tick: i32,
+    ) -> Result<(i128, i128), PoolError> {
+        owner.require_auth();
+        
+        // The minimum liquidity locked by the first deposit is never withdrawable
+        if owner == env.current_contract_address() {
+            return Err(PoolError::PositionLocked);
+        }
         
+        let config = read_pool_config(&env);
         let mut state = read_pool_state(&env);
         
-        let fees0 = state.creator_fees_0;
-        let fees1 = state.creator_fees_1;
+        let lower_aligned = snap_tick_to_spacing(lower_tick, state.tick_spacing);
+        let upper_aligned = snap_tick_to_spacing_ceil(upper_tick, state.tick_spacing);
+        
+        if Self::is_position_locked(&env, &config, &owner, lower_aligned, upper_aligned) {
+            return Err(PoolError::PositionLocked);
+        }
+        
+        let mut pos = read_position(&env, &owner, lower_aligned, upper_aligned);
+        if is_position_empty(&pos) {
+            return Err(PoolError::PositionNotFound);
+        }
+        
+        let liquidity = pos.liquidity;
+        let (amount0, amount1) = if liquidity > 0 {
+            Self::internal_remove_liquidity(
+                &env,
+                &mut state,
+                &mut pos,
+                lower_aligned,
+                upper_aligned,
+                liquidity,
+                0,
+                0,
+            )?
+        } else {
+            (0, 0)
+        };
+        
+        let (fees0, fees1) = Self::internal_collect(
+            &env,
+            &state,
+            &mut pos,
+            lower_aligned,
+            upper_aligned,
+            u128::MAX,
+            u128::MAX,
+        );
+        
+        remove_position(&env, &owner, lower_aligned, upper_aligned);
+        remove_owner_position(&env, &owner, lower_aligned, upper_aligned);
+        
+        let total0 = amount0.saturating_add(safe_u128_to_i128(fees0));
+        let total1 = amount1.saturating_add(safe_u128_to_i128(fees1));
+        if total0 > 0 {
+            token::Client::new(&env, &state.token0).transfer(&env.current_contract_address(), &owner, &total0);
+        }
+        if total1 > 0 {
+            token::Client::new(&env, &state.token1).transfer(&env.current_contract_address(), &owner, &total1);
+        }
+        
+        if liquidity > 0 {
+            emit_remove_liquidity(&env, liquidity, amount0, amount1);
+        }
+        emit_collect(&env, fees0, fees1);
+        Self::report_reserves(&env);
+        
+        Ok((total0, total1))
+    }
+    
+    /// Remove a percentage (in bps) of a position's liquidity
+    /// 
+    /// Reads the position and withdraws `liquidity * bps / 10000` through
+    /// `remove_liquidity`, with no minimum amounts.
+    pub fn remove_liquidity_bps(
+        env: Env,
+        owner: Address,
+        lower_tick: i32,
+        upper_tick: i32,
+        bps: u32,
+    ) -> Result<(i128, i128), PoolError> {
+        if bps == 0 || bps > 10_000 {
+            return Err(PoolError::InvalidBps);
+        }
+        
+        let state = read_pool_state(&env);
+        let lower_aligned = snap_tick_to_spacing(lower_tick, state.tick_spacing);
+        let upper_aligned = snap_tick_to_spacing_ceil(upper_tick, state.tick_spacing);
+        
+        let pos = read_position(&env, &owner, lower_aligned, upper_aligned);
+        if pos.liquidity <= 0 {
+            return Err(PoolError::PositionNotFound);
+        }
+        
+        let liquidity = mul_bps(&env, pos.liquidity, bps);
+        Self::remove_liquidity(env, owner, lower_aligned, upper_aligned, liquidity, 0, 0)
+    }
+    
+    /// Remove liquidity from a position token (owner only)
+    pub fn remove_position_liquidity(
+        env: Env,
+        owner: Address,
+        position_id: u64,
+        liquidity: i128,
+        amount0_min: i128,
+        amount1_min: i128,
+    ) -> Result<(i128, i128), PoolError> {
+        owner.require_auth();
+        
+        if liquidity <= 0 {
+            return Err(PoolError::InvalidLiquidityAmount);
+        }
+        
+        let mut token = Self::owned_position_token(&env, &owner, position_id)?;
+        if token.position.liquidity < liquidity {
+            return Err(PoolError::InsufficientLiquidity);
+        }
+        
+        let mut state = read_pool_state(&env);
+        let (amount0, amount1) = Self::internal_remove_liquidity(
+            &env,
+            &mut state,
+            &mut token.position,
+            token.lower_tick,
+            token.upper_tick,
+            liquidity,
+            amount0_min,
+            amount1_min,
+        )?;
+        write_position_token(&env, position_id, &token);
+        
+        // Transfer tokens to owner
+        if amount0 > 0 {
+            token::Client::new(&env, &state.token0).transfer(&env.current_contract_address(), &owner, &amount0);
+        }
+        if amount1 > 0 {
+            token::Client::new(&env, &state.token1).transfer(&env.current_contract_address(), &owner, &amount1);
+        }
+        
+        emit_remove_liquidity(&env, liquidity, amount0, amount1);
+        Self::report_reserves(&env);
+        
+        Ok((amount0, amount1))
+    }
+    
+    /// Collect accumulated fees from a position
+    pub fn collect_fees(
+        env: Env,
+        owner: Address,
+        lower_tick: i32,
+        upper_tick: i32,
+    ) -> (u128, u128) {
+        Self::collect_amounts(env, owner, lower_tick, upper_tick, u128::MAX, u128::MAX)
+    }
+    
+    /// Collect up to the requested amounts of accumulated fees from a position.
+    /// Anything above the maxima stays in `tokens_owed` for a later call.
+    pub fn collect_amounts(
+        env: Env,
+        owner: Address,
+        lower_tick: i32,
+        upper_tick: i32,
+        amount0_max: u128,
+        amount1_max: u128,
+    ) -> (u128, u128) {
+        owner.require_auth();
+        Self::do_collect(env, &owner, &owner, lower_tick, upper_tick, amount0_max, amount1_max)
+    }
+    
+    /// `collect_amounts` paying the fees to `recipient`
+    /// 
+    /// Fails with `DeadlineExpired` once the ledger timestamp passes
+    /// `deadline_ts` (0 means no deadline).
+    #[allow(clippy::too_many_arguments)]
+    pub fn collect_amounts_to(
+        env: Env,
+        owner: Address,
+        lower_tick: i32,
+        upper_tick: i32,
+        amount0_max: u128,
+        amount1_max: u128,
+        recipient: Address,
+        deadline_ts: u64,
+    ) -> Result<(u128, u128), PoolError> {
+        owner.require_auth();
+        check_deadline(&env, deadline_ts)?;
+        Ok(Self::do_collect(env, &owner, &recipient, lower_tick, upper_tick, amount0_max, amount1_max))
+    }
+    
+    /// Shared body of `collect_amounts` and `collect_amounts_to`; the caller
+    /// has checked `owner`'s auth
+    fn do_collect(
+        env: Env,
+        owner: &Address,
+        recipient: &Address,
+        lower_tick: i32,
+        upper_tick: i32,
+        amount0_max: u128,
+        amount1_max: u128,
+    ) -> (u128, u128) {
+        let state = read_pool_state(&env);
+        
+        let lower_aligned = snap_tick_to_spacing(lower_tick, state.tick_spacing);
+        let upper_aligned = snap_tick_to_spacing_ceil(upper_tick, state.tick_spacing);
+        
+        let mut pos = read_position(&env, owner, lower_aligned, upper_aligned);
+        let (fees0, fees1) = Self::internal_collect(
+            &env,
+            &state,
+            &mut pos,
+            lower_aligned,
+            upper_aligned,
+            amount0_max,
+            amount1_max,
+        );
+        
+        write_position(&env, owner, lower_aligned, upper_aligned, &pos);
+        if is_position_empty(&pos) {
+            remove_owner_position(&env, owner, lower_aligned, upper_aligned);
+        }
+        
+        // Transfer fees
+        if fees0 > 0 {
+            token::Client::new(&env, &state.token0).transfer(
+                &env.current_contract_address(),
+                recipient,
+                &safe_u128_to_i128(fees0),
+            );
+        }
+        if fees1 > 0 {
+            token::Client::new(&env, &state.token1).transfer(
+                &env.current_contract_address(),
+                recipient,
+                &safe_u128_to_i128(fees1),
+            );
+        }
+        
+        emit_collect(&env, fees0, fees1);
+        
+        (fees0, fees1)
+    }
+    
+    /// Collect all accumulated fees from several of `owner`'s positions,
+    /// paying the totals out in one transfer per token
+    /// 
+    /// Ranges are aligned like `collect_fees`; empty positions are skipped.
+    pub fn collect_batch(
+        env: Env,
+        owner: Address,
+        ranges: Vec<(i32, i32)>,
+    ) -> Result<(u128, u128), PoolError> {
+        owner.require_auth();
+        
+        let state = read_pool_state(&env);
+        let mut total0: u128 = 0;
+        let mut total1: u128 = 0;
+        
+        for (lower_tick, upper_tick) in ranges.iter() {
+            if lower_tick >= upper_tick {
+                return Err(PoolError::InvalidTickRange);
+            }
+            let lower_aligned = snap_tick_to_spacing(lower_tick, state.tick_spacing);
+            let upper_aligned = snap_tick_to_spacing_ceil(upper_tick, state.tick_spacing);
+            
+            let mut pos = read_position(&env, &owner, lower_aligned, upper_aligned);
+            if is_position_empty(&pos) {
+                continue;
+            }
+            let (fees0, fees1) = Self::internal_collect(
+                &env,
+                &state,
+                &mut pos,
+                lower_aligned,
+                upper_aligned,
+                u128::MAX,
+                u128::MAX,
+            );
+            
+            write_position(&env, &owner, lower_aligned, upper_aligned, &pos);
+            if is_position_empty(&pos) {
+                remove_owner_position(&env, &owner, lower_aligned, upper_aligned);
+            }
+            total0 = total0.saturating_add(fees0);
+            total1 = total1.saturating_add(fees1);
+        }
+        
+        if total0 > 0 {
+            token::Client::new(&env, &state.token0).transfer(
+                &env.current_contract_address(),
+                &owner,
+                &safe_u128_to_i128(total0),
+            );
+        }
+        if total1 > 0 {
+            token::Client::new(&env, &state.token1).transfer(
+                &env.current_contract_address(),
+                &owner,
+                &safe_u128_to_i128(total1),
+            );
+        }
+        
+        emit_collect(&env, total0, total1);
+        
+        Ok((total0, total1))
+    }
+    
+    /// Collect up to the requested fee amounts from a position token (owner only)
+    pub fn collect_position(
+        env: Env,
+        owner: Address,
+        position_id: u64,
+        amount0_max: u128,
+        amount1_max: u128,
+    ) -> Result<(u128, u128), PoolError> {
+        owner.require_auth();
+        
+        let mut token = Self::owned_position_token(&env, &owner, position_id)?;
+        let state = read_pool_state(&env);
+        let (fees0, fees1) = Self::internal_collect(
+            &env,
+            &state,
+            &mut token.position,
+            token.lower_tick,
+            token.upper_tick,
+            amount0_max,
+            amount1_max,
+        );
+        write_position_token(&env, position_id, &token);
+        
+        // Transfer fees
+        if fees0 > 0 {
+            token::Client::new(&env, &state.token0).transfer(
+                &env.current_contract_address(),
+                &owner,
+                &safe_u128_to_i128(fees0),
+            );
+        }
+        if fees1 > 0 {
+            token::Client::new(&env, &state.token1).transfer(
+                &env.current_contract_address(),
+                &owner,
+                &safe_u128_to_i128(fees1),
+            );
+        }
+        
+        emit_collect(&env, fees0, fees1);
+        
+        Ok((fees0, fees1))
+    }
+    
+    /// Claim creator fees (only creator can call)
+    /// 
+    /// Each payout is capped at the pool's balance of that token; whatever
+    /// cannot be paid stays accrued for a later claim. Reverts with
+    /// `InsufficientOutput` if a capped payout is below its minimum.
+    pub fn claim_creator_fees(
+        env: Env,
+        min_amount0: u128,
+        min_amount1: u128,
+    ) -> Result<(u128, u128), PoolError> {
+        let config = read_pool_config(&env);
+        config.creator.require_auth();
+        
+        let mut state = read_pool_state(&env);
+        let pool_addr = env.current_contract_address();
+        let token0 = token::Client::new(&env, &state.token0);
+        let token1 = token::Client::new(&env, &state.token1);
+        
+        let fees0 = state.creator_fees_0.min(i128_to_u128_safe(token0.balance(&pool_addr)));
+        let fees1 = state.creator_fees_1.min(i128_to_u128_safe(token1.balance(&pool_addr)));
+        
+        if fees0 < min_amount0 || fees1 < min_amount1 {
+            return Err(PoolError::InsufficientOutput);
+        }
+        
+        // Keep any shortfall claimable
+        state.creator_fees_0 -= fees0;
+        state.creator_fees_1 -= fees1;
+        write_pool_state(&env, &state);
+        
+        // Transfer fees to the creator's recipient
+        if fees0 > 0 {
+            token0.transfer(&pool_addr, &state.creator_fee_recipient, &safe_u128_to_i128(fees0));
+        }
+        if fees1 > 0 {
+            token1.transfer(&pool_addr, &state.creator_fee_recipient, &safe_u128_to_i128(fees1));
+        }
+        
+        emit_claim_creator_fees(&env, fees0, fees1);
+        
+        Ok((fees0, fees1))
+    }
+    
+    /// Send creator fees to an address other than the creator (creator only)
+    /// 
+    /// The creator still authorizes `claim_creator_fees`; the tokens go to
+    /// `recipient`. Pass the creator's own address to undo.
+    pub fn set_creator_fee_recipient(env: Env, creator: Address, recipient: Address) -> Result<(), PoolError> {
+        creator.require_auth();
+        
+        let config = read_pool_config(&env);
+        if creator != config.creator {
+            return Err(PoolError::Unauthorized);
+        }
+        
+        let mut state = read_pool_state(&env);
+        state.creator_fee_recipient = recipient.clone();
+        write_pool_state(&env, &state);
+        
+        emit_creator_fee_recipient_set(&env, &creator, &recipient);
+        Ok(())
+    }
+    
+    /// Propose a new creator fee rate (creator only)
+    /// 
+    /// A decrease (or no change) takes effect immediately and cancels any
+    /// pending increase. An increase is stored as pending and can be applied
+    /// with `apply_creator_fee` after `CREATOR_FEE_TIMELOCK_LEDGERS`.
+    /// 
+    /// Returns the ledger from which the new rate applies.
+    pub fn propose_creator_fee(env: Env, creator: Address, creator_fee_bps: u32) -> Result<u32, PoolError> {
+        creator.require_auth();
+        
+        let mut config = read_pool_config(&env);
+        if creator != config.creator {
+            return Err(PoolError::Unauthorized);
+        }
+        if !(MIN_CREATOR_FEE_BPS..=MAX_CREATOR_FEE_BPS).contains(&creator_fee_bps) {
+            return Err(PoolError::InvalidCreatorFee);
+        }
+        
+        let now = env.ledger().sequence();
+        
+        if creator_fee_bps <= config.creator_fee_bps {
+            let old_fee_bps = config.creator_fee_bps;
+            config.creator_fee_bps = creator_fee_bps;
+            write_pool_config(&env, &config);
+            remove_pending_creator_fee(&env);
+            
+            emit_creator_fee_set(&env, old_fee_bps, creator_fee_bps);
+            return Ok(now);
+        }
+        
+        let effective_ledger = now.saturating_add(CREATOR_FEE_TIMELOCK_LEDGERS);
+        write_pending_creator_fee(&env, &PendingCreatorFee { creator_fee_bps, effective_ledger });
+        
+        emit_creator_fee_proposed(&env, creator_fee_bps, effective_ledger);
+        Ok(effective_ledger)
+    }
+    
+    /// Apply a pending creator fee increase once its timelock has passed
+    /// 
+    /// Returns the new creator fee in basis points.
+    pub fn apply_creator_fee(env: Env, creator: Address) -> Result<u32, PoolError> {
+        creator.require_auth();
+        
+        let mut config = read_pool_config(&env);
+        if creator != config.creator {
+            return Err(PoolError::Unauthorized);
+        }
+        
+        let pending = read_pending_creator_fee(&env).ok_or(PoolError::NoPendingCreatorFee)?;
+        if env.ledger().sequence() < pending.effective_ledger {
+            return Err(PoolError::CreatorFeeTimelocked);
+        }
+        
+        let old_fee_bps = config.creator_fee_bps;
+        config.creator_fee_bps = pending.creator_fee_bps;
+        write_pool_config(&env, &config);
+        remove_pending_creator_fee(&env);
+        
+        emit_creator_fee_set(&env, old_fee_bps, pending.creator_fee_bps);
+        Ok(pending.creator_fee_bps)
+    }
+    
+    /// Pending creator fee increase, if any
+    pub fn get_pending_creator_fee(env: Env) -> Option<PendingCreatorFee> {
+        read_pending_creator_fee(&env)
+    }
+    
+    // ========================================================
+    // PROTOCOL FEE FUNCTIONS
+    // ========================================================
+    
+    /// Set the protocol's share of the LP fee (factory or factory admin only)
+    /// 
+    /// The factory sets it when creating the pool; the factory admin can
+    /// change it on a live pool. Applies to swaps from now on; fees already
+    /// accrued are unaffected.
+    pub fn set_protocol_fee(env: Env, caller: Address, protocol_fee_bps: u32) -> Result<(), PoolError> {
+        caller.require_auth();
+        
+        let mut config = read_pool_config(&env);
+        if caller != config.factory && !Self::is_factory_admin(&env, &config, &caller) {
+            return Err(PoolError::NotFactoryAdmin);
+        }
+        
+        if protocol_fee_bps > MAX_PROTOCOL_FEE_BPS {
+            return Err(PoolError::InvalidProtocolFee);
+        }
+        
+        let old_fee_bps = config.protocol_fee_bps;
+        config.protocol_fee_bps = protocol_fee_bps;
+        write_pool_config(&env, &config);
+        
+        emit_protocol_fee_set(&env, old_fee_bps, protocol_fee_bps);
+        Ok(())
+    }
+    
+    /// Collect accrued protocol fees (factory admin only)
+    /// 
+    /// `admin` must match the factory's current admin. The fees go to the
+    /// protocol fee recipient, or to `admin` when none is set.
+    pub fn collect_protocol(env: Env, admin: Address) -> Result<(u128, u128), PoolError> {
+        Self::do_collect_protocol(env, &admin, u128::MAX, u128::MAX)
+    }
+    
+    /// Collect up to `amount0` / `amount1` of the accrued protocol fees
+    /// (factory admin only)
+    /// 
+    /// Requests above what has accrued are capped; the rest stays claimable.
+    /// Paid out like `collect_protocol`.
+    pub fn collect_protocol_amounts(env: Env, admin: Address, amount0: u128, amount1: u128) -> Result<(u128, u128), PoolError> {
+        Self::do_collect_protocol(env, &admin, amount0, amount1)
+    }
+    
+    /// Send protocol fees to a recipient other than the factory admin
+    /// (factory admin only)
+    /// 
+    /// `None` pays the admin who collects again.
+    pub fn set_protocol_fee_recipient(env: Env, caller: Address, recipient: Option<Address>) -> Result<(), PoolError> {
+        caller.require_auth();
+        
+        let mut config = read_pool_config(&env);
+        if !Self::is_factory_admin(&env, &config, &caller) {
+            return Err(PoolError::NotFactoryAdmin);
+        }
+        
+        config.protocol_fee_recipient = recipient.clone();
+        write_pool_config(&env, &config);
+        
+        emit_protocol_fee_recipient_set(&env, &caller, recipient);
+        Ok(())
+    }
+    
+    /// Shared body of `collect_protocol` and `collect_protocol_amounts`
+    fn do_collect_protocol(env: Env, admin: &Address, amount0: u128, amount1: u128) -> Result<(u128, u128), PoolError> {
+        admin.require_auth();
+        
+        let config = read_pool_config(&env);
+        if !Self::is_factory_admin(&env, &config, admin) {
+            return Err(PoolError::NotFactoryAdmin);
+        }
+        let recipient = config.protocol_fee_recipient.unwrap_or_else(|| admin.clone());
+        
+        let mut state = read_pool_state(&env);
+        
+        let fees0 = state.protocol_fees_0.min(amount0);
+        let fees1 = state.protocol_fees_1.min(amount1);
+        
+        // Keep whatever was not requested
+        state.protocol_fees_0 -= fees0;
+        state.protocol_fees_1 -= fees1;
+        write_pool_state(&env, &state);
+        
+        if fees0 > 0 {
+            token::Client::new(&env, &state.token0).transfer(
+                &env.current_contract_address(),
+                &recipient,
+                &safe_u128_to_i128(fees0),
+            );
+        }
+        if fees1 > 0 {
+            token::Client::new(&env, &state.token1).transfer(
+                &env.current_contract_address(),
+                &recipient,
+                &safe_u128_to_i128(fees1),
+            );
+        }
+        
+        emit_collect_protocol(&env, &recipient, fees0, fees1);
+        
+        Ok((fees0, fees1))
+    }
+    
+    // ========================================================
+    // CIRCUIT BREAKER
+    // ========================================================
+    
+    /// Pause or resume the pool (factory admin only)
+    /// 
+    /// While paused, `swap`, `add_liquidity`, `mint` and `mint_ranges` fail with `PoolPaused`.
+    /// Withdrawals and fee collection stay open so LPs can exit.
+    pub fn set_paused(env: Env, caller: Address, paused: bool) -> Result<(), PoolError> {
+        caller.require_auth();
+        
+        let config = read_pool_config(&env);
+        if !Self::is_factory_admin(&env, &config, &caller) {
+            return Err(PoolError::NotFactoryAdmin);
+        }
+        
+        let mut state = read_pool_state(&env);
+        state.paused = paused;
+        write_pool_state(&env, &state);
+        
+        emit_paused_set(&env, &caller, paused);
+        Ok(())
+    }
+    
+    /// Set the swap loop iteration budget (factory admin only)
+    /// 
+    /// Each initialized tick a swap crosses costs about one iteration. A swap
+    /// that runs out with input left fails with `IterationLimit` instead of
+    /// partially filling, so pools with many tightly spaced ticks may need a
+    /// larger budget. Must be 1 to MAX_SWAP_ITERATIONS.
+    pub fn set_max_swap_iterations(env: Env, caller: Address, max_iterations: u32) -> Result<(), PoolError> {
+        caller.require_auth();
+        
+        let config = read_pool_config(&env);
+        if !Self::is_factory_admin(&env, &config, &caller) {
+            return Err(PoolError::NotFactoryAdmin);
+        }
+        
+        if max_iterations == 0 || max_iterations > MAX_SWAP_ITERATIONS {
+            return Err(PoolError::InvalidSwapIterations);
+        }
+        
+        let mut state = read_pool_state(&env);
+        state.max_swap_iterations = max_iterations;
+        write_pool_state(&env, &state);
+        
+        emit_swap_iterations_set(&env, &caller, max_iterations);
+        Ok(())
+    }
+    
+    /// Set the per-swap price impact limit (factory admin only)
+    /// 
+    /// Swaps whose output falls more than `max_price_impact_bps` short of the
+    /// pre-swap spot price fail with `PriceImpactTooHigh`, protecting thin
+    /// pools from a single large trade. 0 turns the limit off; at most 10000.
+    pub fn set_max_price_impact(env: Env, caller: Address, max_price_impact_bps: u32) -> Result<(), PoolError> {
+        caller.require_auth();
+        
+        let mut config = read_pool_config(&env);
+        if !Self::is_factory_admin(&env, &config, &caller) {
+            return Err(PoolError::NotFactoryAdmin);
+        }
+        
+        if max_price_impact_bps > 10_000 {
+            return Err(PoolError::InvalidBps);
+        }
+        
+        config.max_price_impact_bps = max_price_impact_bps;
+        write_pool_config(&env, &config);
+        
+        emit_max_price_impact_set(&env, &caller, max_price_impact_bps);
+        Ok(())
+    }
+    
+    /// Cap the creator's lifetime share of swap fees (factory admin only)
+    /// 
+    /// Once the creator fees accrued in a token reach `creator_fee_cap_bps`
+    /// of all fees ever charged in it, further creator fee goes to LPs, so a
+    /// creator wash-trading their own pool cannot keep harvesting the cut.
+    /// 0 removes the cap; at most 10000.
+    pub fn set_creator_fee_cap(env: Env, caller: Address, creator_fee_cap_bps: u32) -> Result<(), PoolError> {
+        caller.require_auth();
+        
+        let mut config = read_pool_config(&env);
+        if !Self::is_factory_admin(&env, &config, &caller) {
+            return Err(PoolError::NotFactoryAdmin);
+        }
+        
+        if creator_fee_cap_bps > 10_000 {
+            return Err(PoolError::InvalidBps);
+        }
+        
+        config.creator_fee_cap_bps = creator_fee_cap_bps;
+        write_pool_config(&env, &config);
+        
+        emit_creator_fee_cap_set(&env, &caller, creator_fee_cap_bps);
+        Ok(())
+    }
+    
+    /// Turn TVL reporting to the factory on or off (factory admin only)
+    /// 
+    /// While on, every deposit and withdrawal reports the pool's token
+    /// balances to the factory's `report_reserves` (best effort: a failing
+    /// factory call never blocks the LP). Enabling reports the current
+    /// balances right away; disabling reports zero so the pool drops out of
+    /// the factory's TVL.
+    pub fn set_tvl_reporting(env: Env, caller: Address, enabled: bool) -> Result<(), PoolError> {
+        caller.require_auth();
+        
+        let config = read_pool_config(&env);
+        if !Self::is_factory_admin(&env, &config, &caller) {
+            return Err(PoolError::NotFactoryAdmin);
+        }
+        
+        let mut state = read_pool_state(&env);
+        state.tvl_reporting = enabled;
+        write_pool_state(&env, &state);
+        
+        if enabled {
+            Self::report_reserves(&env);
+        } else {
+            Self::try_report_reserves(&env, &config, 0, 0);
+        }
+        
+        emit_tvl_reporting_set(&env, &caller, enabled);
+        Ok(())
+    }
+    
+    /// Turn the post-swap solvency check on or off (factory admin only)
+    /// 
+    /// While on, every swap ends by checking that the pool's token balances
+    /// cover the principal behind all liquidity plus LP, creator and protocol
+    /// fees owed, and fails with `InvariantViolated` otherwise. The check
+    /// walks every initialized tick, so it is meant for test and staging pools.
+    pub fn set_invariant_checks(env: Env, caller: Address, enabled: bool) -> Result<(), PoolError> {
+        caller.require_auth();
+        
+        let config = read_pool_config(&env);
+        if !Self::is_factory_admin(&env, &config, &caller) {
+            return Err(PoolError::NotFactoryAdmin);
+        }
+        
+        let mut state = read_pool_state(&env);
+        state.invariant_checks = enabled;
+        write_pool_state(&env, &state);
+        
+        emit_invariant_checks_set(&env, &caller, enabled);
+        Ok(())
+    }
+    
+    /// Replace the pool's code with already-uploaded WASM (factory admin only)
+    /// 
+    /// The factory's `set_pool_wasm_hash` only affects pools deployed later;
+    /// this patches a live pool in place. The contract address and all of
+    /// its storage (state, config, ticks, positions, oracle) are kept, and
+    /// the new code takes over from the next invocation.
+    pub fn upgrade(env: Env, caller: Address, new_wasm_hash: BytesN<32>) -> Result<(), PoolError> {
+        caller.require_auth();
+        
+        let config = read_pool_config(&env);
+        if !Self::is_factory_admin(&env, &config, &caller) {
+            return Err(PoolError::NotFactoryAdmin);
+        }
+        
+        env.deployer().update_current_contract_wasm(new_wasm_hash.clone());
+        
+        emit_upgraded(&env, &caller, &new_wasm_hash);
+        Ok(())
+    }
+    
+    /// Send the pool's whole balance of a stray token to `to` (factory admin only)
+    /// 
+    /// For tokens sent to the pool by mistake. token0 and token1 are refused
+    /// so reserves, fees owed and pending payouts cannot be swept.
+    /// Returns the amount transferred.
+    pub fn rescue_token(env: Env, admin: Address, token: Address, to: Address) -> Result<i128, PoolError> {
+        admin.require_auth();
+        
+        let config = read_pool_config(&env);
+        if !Self::is_factory_admin(&env, &config, &admin) {
+            return Err(PoolError::NotFactoryAdmin);
+        }
+        
+        let state = read_pool_state(&env);
+        if token == state.token0 || token == state.token1 {
+            return Err(PoolError::ReserveToken);
+        }
+        
+        let token_client = token::Client::new(&env, &token);
+        let pool_addr = env.current_contract_address();
+        let amount = token_client.balance(&pool_addr);
+        if amount > 0 {
+            token_client.transfer(&pool_addr, &to, &amount);
+        }
+        
+        emit_token_rescued(&env, &admin, &token, &to, amount);
+        Ok(amount)
+    }
+    
+    // ========================================================
+    // INTERNAL HELPERS
+    // ========================================================
+    
+    /// Shared internal logic for add_liquidity and mint
+    #[allow(clippy::too_many_arguments)]
+    fn internal_add_liquidity(
+        env: &Env,
+        owner: &Address,
+        position_id: Option<u64>,
+        lower_tick: i32,
+        upper_tick: i32,
+        amount0_desired: i128,
+        amount1_desired: i128,
+        amount0_min: i128,
+        amount1_min: i128,
+    ) -> Result<(i128, i128, i128), PoolError> {
+        let mut state = read_pool_state(env);
+        
+        let DepositQuote {
+            lower_aligned,
+            upper_aligned,
+            liquidity,
+            locked_liquidity,
+            amount0,
+            amount1,
+        } = Self::quote_deposit(env, &state, lower_tick, upper_tick, amount0_desired, amount1_desired)?;
+        let owner_liquidity = liquidity - locked_liquidity;
+        
+        // Slippage check (skip if min = 0, e.g., factory mint)
+        if (amount0_min > 0 || amount1_min > 0) && (amount0 < amount0_min || amount1 < amount1_min) {
+            return Err(PoolError::SlippageExceeded);
+        }
+        
+        // Update ticks
+        belugaswap_tick::update_tick(
+            env,
+            read_tick_info,
+            write_tick_info,
+            read_tick_word,
+            write_tick_word,
+            lower_aligned,
+            state.tick_spacing,
+            state.current_tick,
+            liquidity,
+            state.fee_growth_global_0,
+            state.fee_growth_global_1,
+            false,
+        );
+        
+        belugaswap_tick::update_tick(
+            env,
+            read_tick_info,
+            write_tick_info,
+            read_tick_word,
+            write_tick_word,
+            upper_aligned,
+            state.tick_spacing,
+            state.current_tick,
+            liquidity,
+            state.fee_growth_global_0,
+            state.fee_growth_global_1,
+            true,
+        );
+        let (lowest, highest) = initialized_tick_range(env, &state).unwrap_or((lower_aligned, upper_aligned));
+        write_tick_range(env, lowest.min(lower_aligned), highest.max(upper_aligned));
+        
+        // Update pool liquidity if in range
+        if state.current_tick >= lower_aligned && state.current_tick < upper_aligned {
+            twap::update_twap(env, state.current_tick, state.liquidity);
+            state.liquidity = state.liquidity.saturating_add(liquidity);
+        }
+        if locked_liquidity > 0 {
+            state.min_liquidity_locked = true;
+        }
+        write_pool_state(env, &state);
         
-        // Reset creator fees
-        state.creator_fees_0 = 0;
-        state.creator_fees_1 = 0;
-        write_pool_state(&env, &state);
+        // Update position
+        let fee_growth_inside = get_fee_growth_inside_local(
+            env,
+            lower_aligned,
+            upper_aligned,
+            state.current_tick,
+            state.fee_growth_global_0,
+            state.fee_growth_global_1,
+        );
         
-        // Transfer fees to creator
-        if fees0 > 0 {
-            token::Client::new(&env, &state.token0).transfer(
-                &env.current_contract_address(),
-                &config.creator,
-                &safe_u128_to_i128(fees0),
-            );
-        }
-        if fees1 > 0 {
-            token::Client::new(&env, &state.token1).transfer(
-                &env.current_contract_address(),
-                &config.creator,
-                &safe_u128_to_i128(fees1),
-            );
+        match position_id {
+            Some(id) => {
+                let mut token = read_position_token(env, id).unwrap_or(PositionToken {
+                    owner: owner.clone(),
+                    lower_tick: lower_aligned,
+                    upper_tick: upper_aligned,
+                    position: Position::default(),
+                });
+                modify_position(env, &mut token.position, owner_liquidity, fee_growth_inside.0, fee_growth_inside.1, state.sqrt_price_x64);
+                write_position_token(env, id, &token);
+            }
+            None => {
+                let mut pos = read_position(env, owner, lower_aligned, upper_aligned);
+                modify_position(env, &mut pos, owner_liquidity, fee_growth_inside.0, fee_growth_inside.1, state.sqrt_price_x64);
+                write_position(env, owner, lower_aligned, upper_aligned, &pos);
+                add_owner_position(env, owner, lower_aligned, upper_aligned);
+            }
         }
         
-        emit_claim_creator_fees(&env, fees0, fees1);
+        if locked_liquidity > 0 {
+            let pool_addr = env.current_contract_address();
+            let mut locked = read_position(env, &pool_addr, lower_aligned, upper_aligned);
+            modify_position(env, &mut locked, locked_liquidity, fee_growth_inside.0, fee_growth_inside.1, state.sqrt_price_x64);
+            write_position(env, &pool_addr, lower_aligned, upper_aligned, &locked);
+        }
         
-        (fees0, fees1)
+        Ok((owner_liquidity, amount0, amount1))
     }
     
-    // ========================================================
-    // INTERNAL HELPERS
-    // ========================================================
-    
-    /// Shared internal logic for add_liquidity and mint
-    fn internal_add_liquidity(
+    /// Size a deposit: align the range, derive liquidity from the desired
+    /// amounts and the token amounts that liquidity takes
+    /// 
+    /// Shared by `internal_add_liquidity` and `simulate_add_liquidity` so the
+    /// simulation always matches what a deposit pulls.
+    fn quote_deposit(
         env: &Env,
-        owner: &Address,
+        state: &PoolState,
         lower_tick: i32,
         upper_tick: i32,
         amount0_desired: i128,
         amount1_desired: i128,
-        amount0_min: i128,
-        amount1_min: i128,
-    ) -> (i128, i128, i128) {
-        let mut state = read_pool_state(env);
+    ) -> Result<DepositQuote, PoolError> {
+        if state.paused {
+            return Err(PoolError::PoolPaused);
+        }
+        
+        // Ticks past the bounds would overflow the alignment below or panic
+        // in get_sqrt_ratio_at_tick
+        if lower_tick < MIN_TICK || upper_tick > MAX_TICK {
+            return Err(PoolError::InvalidTickRange);
+        }
         
         let lower_aligned = snap_tick_to_spacing(lower_tick, state.tick_spacing);
-        let upper_aligned = snap_tick_to_spacing(upper_tick, state.tick_spacing);
+        let upper_aligned = snap_tick_to_spacing_ceil(upper_tick, state.tick_spacing);
         
-        if lower_aligned >= upper_aligned {
-            panic!("{}", ErrorMsg::INVALID_TICK_RANGE);
+        // Alignment only widens the range, so checking the raw ticks also
+        // rejects inverted ranges that would snap into a valid one
+        if lower_tick >= upper_tick {
+            return Err(PoolError::InvalidTickRange);
+        }
+        // A tick near a bound can still be widened past it
+        if lower_aligned < MIN_TICK || upper_aligned > MAX_TICK {
+            return Err(PoolError::InvalidTickRange);
         }
         
         let liquidity = get_liquidity_for_amounts(
@@ -650,9 +2453,20 @@ impl BelugaPool {
         );
         
         if liquidity < MIN_LIQUIDITY {
-            panic!("{}", ErrorMsg::LIQUIDITY_TOO_LOW);
+            return Err(PoolError::LiquidityTooLow);
         }
         
+        // The pool's first deposit permanently locks a minimum slice of its
+        // liquidity in a position owned by the pool itself
+        let locked_liquidity = if state.min_liquidity_locked {
+            0
+        } else {
+            if liquidity < MIN_LIQUIDITY + MINIMUM_LOCKED_LIQUIDITY {
+                return Err(PoolError::LiquidityTooLow);
+            }
+            MINIMUM_LOCKED_LIQUIDITY
+        };
+        
         let (amount0, amount1) = get_amounts_for_liquidity(
             env,
             liquidity,
@@ -661,45 +2475,219 @@ impl BelugaPool {
             state.sqrt_price_x64,
         );
         
-        // Slippage check (skip if min = 0, e.g., factory mint)
-        if amount0_min > 0 || amount1_min > 0 {
-            if amount0 < amount0_min || amount1 < amount1_min {
-                panic!("{}", ErrorMsg::SLIPPAGE_EXCEEDED);
+        Ok(DepositQuote {
+            lower_aligned,
+            upper_aligned,
+            liquidity,
+            locked_liquidity,
+            amount0,
+            amount1,
+        })
+    }
+    
+    /// Input to swap so the rest of `amount_in` and the swap output fill a
+    /// range position in the right proportion
+    /// 
+    /// The deposit ratio depends on the price after the swap, so this
+    /// bisects on the swap size with dry-run quotes: swapping more is right
+    /// while the input token would still be left over, i.e. while the
+    /// liquidity it alone supports exceeds what the output token supports.
+    fn single_sided_swap_amount(
+        env: &Env,
+        state: &PoolState,
+        lower_tick: i32,
+        upper_tick: i32,
+        zero_for_one: bool,
+        amount_in: i128,
+    ) -> i128 {
+        let config = read_pool_config(env);
+        let sqrt_lower = get_sqrt_ratio_at_tick(snap_tick_to_spacing(lower_tick, state.tick_spacing));
+        let sqrt_upper = get_sqrt_ratio_at_tick(snap_tick_to_spacing_ceil(upper_tick, state.tick_spacing));
+        let swap_state = swap_state(state, &config, zero_for_one);
+        
+        let input_left_over = |swap_amount: i128| -> bool {
+            let (used, out, sqrt_price, _, _) = if swap_amount > 0 {
+                quote_swap(
+                    env,
+                    &swap_state,
+                    read_tick_info,
+                    read_tick_word,
+                    swap_amount,
+                    zero_for_one,
+                    None,
+                    config.fee_bps as i128,
+                )
+            } else {
+                (0, 0, state.sqrt_price_x64, state.current_tick, 0)
+            };
+            let (amount0, amount1) = if zero_for_one {
+                (amount_in - used, out)
+            } else {
+                (out, amount_in - used)
+            };
+            
+            // Outside the range only one token is deposited
+            if sqrt_price <= sqrt_lower {
+                return !zero_for_one;
+            }
+            if sqrt_price >= sqrt_upper {
+                return zero_for_one;
+            }
+            let liquidity0 = get_liquidity_for_amount0(env, amount0, sqrt_price, sqrt_upper);
+            let liquidity1 = get_liquidity_for_amount1(env, amount1, sqrt_lower, sqrt_price);
+            if zero_for_one { liquidity0 > liquidity1 } else { liquidity1 > liquidity0 }
+        };
+        
+        if !input_left_over(0) {
+            return 0;
+        }
+        if input_left_over(amount_in) {
+            return amount_in;
+        }
+        let (mut lo, mut hi) = (0, amount_in);
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            if input_left_over(mid) {
+                lo = mid;
+            } else {
+                hi = mid;
             }
         }
+        lo
+    }
+    
+    /// Withdraw `liquidity` from `pos` over an aligned range
+    /// 
+    /// Settles the position's fees, updates both ticks and the pool's active
+    /// liquidity and writes the pool state. The caller writes `pos` back and
+    /// pays out the returned `(amount0, amount1)`. Fails with
+    /// `InsufficientLiquidity`, touching nothing, if `liquidity` exceeds the
+    /// position or either tick's `liquidity_gross`.
+    #[allow(clippy::too_many_arguments)]
+    fn internal_remove_liquidity(
+        env: &Env,
+        state: &mut PoolState,
+        pos: &mut Position,
+        lower_aligned: i32,
+        upper_aligned: i32,
+        liquidity: i128,
+        amount0_min: i128,
+        amount1_min: i128,
+    ) -> Result<(i128, i128), PoolError> {
+        // Never take more than the position or either of its ticks holds:
+        // saturating would zero the tick's gross and leave its net corrupt
+        if liquidity > pos.liquidity
+            || liquidity > read_tick_info(env, lower_aligned).liquidity_gross
+            || liquidity > read_tick_info(env, upper_aligned).liquidity_gross
+        {
+            return Err(PoolError::InsufficientLiquidity);
+        }
+        
+        // Calculate amounts to withdraw
+        let (amount0, amount1) = get_amounts_for_liquidity(
+            env,
+            liquidity,
+            get_sqrt_ratio_at_tick(lower_aligned),
+            get_sqrt_ratio_at_tick(upper_aligned),
+            state.sqrt_price_x64,
+        );
+        
+        // Slippage check
+        if amount0 < amount0_min || amount1 < amount1_min {
+            return Err(PoolError::SlippageExceeded);
+        }
+        
+        // Update fee growth
+        let fee_growth_inside = get_fee_growth_inside_local(
+            env,
+            lower_aligned,
+            upper_aligned,
+            state.current_tick,
+            state.fee_growth_global_0,
+            state.fee_growth_global_1,
+        );
+        
+        update_position(env, pos, fee_growth_inside.0, fee_growth_inside.1);
+        
+        // Subtract liquidity from position
+        pos.liquidity -= liquidity;
         
         // Update ticks
-        belugaswap_tick::update_tick(
+        let lower_flipped = belugaswap_tick::update_tick(
             env,
-            |e, t| read_tick_info(e, t),
-            |e, t, info| write_tick_info(e, t, info),
+            read_tick_info,
+            write_tick_info,
+            read_tick_word,
+            write_tick_word,
             lower_aligned,
+            state.tick_spacing,
             state.current_tick,
-            liquidity,
+            -liquidity,
             state.fee_growth_global_0,
             state.fee_growth_global_1,
             false,
         );
         
-        belugaswap_tick::update_tick(
+        let upper_flipped = belugaswap_tick::update_tick(
             env,
-            |e, t| read_tick_info(e, t),
-            |e, t, info| write_tick_info(e, t, info),
+            read_tick_info,
+            write_tick_info,
+            read_tick_word,
+            write_tick_word,
             upper_aligned,
+            state.tick_spacing,
             state.current_tick,
-            liquidity,
+            -liquidity,
             state.fee_growth_global_0,
             state.fee_growth_global_1,
             true,
         );
         
+        // A tick nobody references any more is cleared (Uniswap V3 `clear`);
+        // its fee growth outside is re-seeded if it is initialized again
+        if lower_flipped {
+            remove_tick_info(env, lower_aligned);
+        }
+        if upper_flipped {
+            remove_tick_info(env, upper_aligned);
+        }
+        
+        // Clearing the lowest or highest tick narrows the range the solvency
+        // check walks
+        if let Some((lowest, highest)) = initialized_tick_range(env, state) {
+            if (lower_flipped && lower_aligned == lowest) || (upper_flipped && upper_aligned == highest) {
+                let ticks = initialized_ticks_between(env, lowest - 1, highest, state.tick_spacing);
+                if let (Some(lowest), Some(highest)) = (ticks.first(), ticks.last()) {
+                    write_tick_range(env, lowest, highest);
+                }
+            }
+        }
+        
         // Update pool liquidity if in range
         if state.current_tick >= lower_aligned && state.current_tick < upper_aligned {
-            state.liquidity = state.liquidity.saturating_add(liquidity);
+            twap::update_twap(env, state.current_tick, state.liquidity);
+            state.liquidity = state.liquidity.saturating_sub(liquidity);
         }
-        write_pool_state(env, &state);
         
-        // Update position
+        write_pool_state(env, state);
+        
+        Ok((amount0, amount1))
+    }
+    
+    /// Bring `pos` fees up to date and take up to the requested amounts
+    /// 
+    /// Anything above the maxima stays in `tokens_owed`. The caller writes
+    /// `pos` back and pays out the returned `(fees0, fees1)`.
+    fn internal_collect(
+        env: &Env,
+        state: &PoolState,
+        pos: &mut Position,
+        lower_aligned: i32,
+        upper_aligned: i32,
+        amount0_max: u128,
+        amount1_max: u128,
+    ) -> (u128, u128) {
+        // Update fee growth
         let fee_growth_inside = get_fee_growth_inside_local(
             env,
             lower_aligned,
@@ -709,11 +2697,71 @@ impl BelugaPool {
             state.fee_growth_global_1,
         );
         
-        let mut pos = read_position(env, owner, lower_aligned, upper_aligned);
-        modify_position(env, &mut pos, liquidity, fee_growth_inside.0, fee_growth_inside.1);
-        write_position(env, owner, lower_aligned, upper_aligned, &pos);
+        update_position(env, pos, fee_growth_inside.0, fee_growth_inside.1);
+        
+        let fees0 = pos.tokens_owed_0.min(amount0_max);
+        let fees1 = pos.tokens_owed_1.min(amount1_max);
+        
+        // Keep whatever was not collected
+        pos.tokens_owed_0 -= fees0;
+        pos.tokens_owed_1 -= fees1;
+        
+        // What is paid out is no longer owed. Fee growth rounds down, so the
+        // pool-wide total can only run ahead of what positions collect
+        if fees0 > 0 || fees1 > 0 {
+            let mut pool = read_pool_state(env);
+            pool.lp_fees_owed_0 = pool.lp_fees_owed_0.saturating_sub(fees0);
+            pool.lp_fees_owed_1 = pool.lp_fees_owed_1.saturating_sub(fees1);
+            write_pool_state(env, &pool);
+        }
+        
+        (fees0, fees1)
+    }
+    
+    /// Position liquidity valued at the pool price plus fees owed to date
+    fn position_info(
+        env: &Env,
+        state: &PoolState,
+        lower_tick: i32,
+        upper_tick: i32,
+        pos: &Position,
+    ) -> PositionInfo {
+        let (amount0, amount1) = get_amounts_for_liquidity(
+            env,
+            pos.liquidity,
+            get_sqrt_ratio_at_tick(lower_tick),
+            get_sqrt_ratio_at_tick(upper_tick),
+            state.sqrt_price_x64,
+        );
+        
+        let fee_growth_inside = get_fee_growth_inside_local(
+            env,
+            lower_tick,
+            upper_tick,
+            state.current_tick,
+            state.fee_growth_global_0,
+            state.fee_growth_global_1,
+        );
+        
+        let (pending0, pending1) = calculate_pending_fees(env, pos, fee_growth_inside.0, fee_growth_inside.1);
         
-        (liquidity, amount0, amount1)
+        PositionInfo {
+            liquidity: pos.liquidity,
+            amount0,
+            amount1,
+            fees_owed_0: pos.tokens_owed_0.saturating_add(pending0),
+            fees_owed_1: pos.tokens_owed_1.saturating_add(pending1),
+            cost_basis_sqrt_price_x64: pos.cost_basis_sqrt_price_x64,
+        }
+    }
+    
+    /// Load a position token, checking that `owner` holds it
+    fn owned_position_token(env: &Env, owner: &Address, position_id: u64) -> Result<PositionToken, PoolError> {
+        let token = read_position_token(env, position_id).ok_or(PoolError::PositionNotFound)?;
+        if &token.owner != owner {
+            return Err(PoolError::NotPositionOwner);
+        }
+        Ok(token)
     }
     
     /// Check if a position is locked by querying factory
@@ -750,6 +2798,49 @@ impl BelugaPool {
         }
     }
     
+    /// Whether `caller` is the factory's current admin (false if the call fails)
+    fn is_factory_admin(env: &Env, config: &PoolConfig, caller: &Address) -> bool {
+        let result = env.try_invoke_contract::<Address, soroban_sdk::Error>(
+            &config.factory,
+            &Symbol::new(env, "get_admin"),
+            vec![env],
+        );
+        
+        match result {
+            Ok(Ok(admin)) => &admin == caller,
+            _ => false,
+        }
+    }
+    
+    /// Fail with `InvariantViolated` unless the pool's balances cover what it owes
+    /// 
+    /// Owed per token: the principal behind all liquidity at the current
+    /// price, LP fees not yet collected, and unclaimed creator and protocol
+    /// fees. Deposits, withdrawals and swaps each round per position or per
+    /// step, so the principal may exceed the balance by one unit per
+    /// initialized tick. Walks every initialized tick, hence opt-in via
+    /// `invariant_checks`.
+    fn check_solvency(env: &Env, state: &PoolState) -> Result<(), PoolError> {
+        let (principal0, principal1, ticks) = total_principal(env, state);
+        let owed0 = i128_to_u128_safe(principal0)
+            .saturating_add(state.lp_fees_owed_0)
+            .saturating_add(state.creator_fees_0)
+            .saturating_add(state.protocol_fees_0);
+        let owed1 = i128_to_u128_safe(principal1)
+            .saturating_add(state.lp_fees_owed_1)
+            .saturating_add(state.creator_fees_1)
+            .saturating_add(state.protocol_fees_1);
+        
+        let pool_addr = env.current_contract_address();
+        let balance0 = i128_to_u128_safe(token::Client::new(env, &state.token0).balance(&pool_addr));
+        let balance1 = i128_to_u128_safe(token::Client::new(env, &state.token1).balance(&pool_addr));
+        let slack = ticks as u128;
+        if balance0.saturating_add(slack) < owed0 || balance1.saturating_add(slack) < owed1 {
+            return Err(PoolError::InvariantViolated);
+        }
+        Ok(())
+    }
+    
     /// Safe version - returns 0 if factory call fails 
     fn get_active_creator_fee_bps_safe(env: &Env, config: &PoolConfig) -> i128 {
         let pool_addr = env.current_contract_address();
@@ -769,6 +2860,181 @@ impl BelugaPool {
             _ => 0, // Factory call failed or returned false - no creator fee
         }
     }
+    
+    /// Report the pool's token balances to the factory if TVL reporting is on
+    fn report_reserves(env: &Env) {
+        let state = read_pool_state(env);
+        if !state.tvl_reporting {
+            return;
+        }
+        let pool_addr = env.current_contract_address();
+        let reserve0 = token::Client::new(env, &state.token0).balance(&pool_addr);
+        let reserve1 = token::Client::new(env, &state.token1).balance(&pool_addr);
+        Self::try_report_reserves(env, &read_pool_config(env), reserve0, reserve1);
+    }
+    
+    /// Best-effort `report_reserves` call: failures are ignored
+    fn try_report_reserves(env: &Env, config: &PoolConfig, reserve0: i128, reserve1: i128) {
+        let _ = env.try_invoke_contract::<(), soroban_sdk::Error>(
+            &config.factory,
+            &Symbol::new(env, "report_reserves"),
+            vec![
+                env,
+                env.current_contract_address().into_val(env),
+                reserve0.into_val(env),
+                reserve1.into_val(env),
+            ],
+        );
+    }
+}
+
+/// Entrypoints taking more than seven arguments besides `env`
+/// 
+/// `#[contractimpl]` also generates a `BelugaPoolArgs` builder for every
+/// entrypoint without copying the function's attributes, so a
+/// `too_many_arguments` allow on the function does not reach it. These
+/// entrypoints get their own block, with the allow on the module.
+#[allow(clippy::too_many_arguments)]
+mod wide_entrypoints {
+    use super::*;
+
+    #[contractimpl]
+    impl BelugaPool {
+        // ========================================================
+        // INITIALIZATION
+        // ========================================================
+        
+        /// Initialize pool
+        /// 
+        /// # Arguments
+        /// * `factory` - Factory contract address
+        /// * `router` - Router contract address (for authorized swaps)
+        /// * `creator` - Pool creator address
+        /// * `token_a` - First token
+        /// * `token_b` - Second token
+        /// * `fee_bps` - Trading fee in basis points
+        /// * `creator_fee_bps` - Creator's share of fees
+        /// * `sqrt_price_x64` - Initial sqrt price
+        /// * `current_tick` - Initial tick
+        /// * `tick_spacing` - Tick spacing for this fee tier
+        pub fn initialize(
+            env: Env,
+            factory: Address,
+            router: Address,      // NEW: router parameter
+            creator: Address,
+            token_a: Address,
+            token_b: Address,
+            fee_bps: u32,
+            creator_fee_bps: u32,
+            sqrt_price_x64: u128,
+            current_tick: i32,
+            tick_spacing: i32,
+        ) {
+            // Factory must authorize (proves caller is factory contract)
+            factory.require_auth();
+            
+            if is_initialized(&env) {
+                panic!("{}", ErrorMsg::ALREADY_INITIALIZED);
+            }
+            
+            if fee_bps == 0 || fee_bps > MAX_FEE_BPS {
+                panic!("{}", ErrorMsg::INVALID_FEE);
+            }
+            
+            if !(MIN_CREATOR_FEE_BPS..=MAX_CREATOR_FEE_BPS).contains(&creator_fee_bps) {
+                panic!("{}", ErrorMsg::INVALID_CREATOR_FEE);
+            }
+            
+            if tick_spacing <= 0 {
+                panic!("{}", ErrorMsg::INVALID_TICK_SPACING);
+            }
+            
+            let (token0, token1, _) = sort_tokens(&token_a, &token_b);
+            
+            let config = PoolConfig {
+                factory,
+                router,           // NEW: store router
+                creator: creator.clone(),
+                token_a,
+                token_b,
+                fee_bps,
+                creator_fee_bps,
+                protocol_fee_bps: 0,
+                protocol_fee_recipient: None,
+                dust_threshold_0: dust_threshold(&env, &token0),
+                dust_threshold_1: dust_threshold(&env, &token1),
+                max_price_impact_bps: 0,
+                creator_fee_cap_bps: 0,
+            };
+            
+            write_pool_config(&env, &config);
+            init_pool_state(&env, sqrt_price_x64, current_tick, tick_spacing, token0, token1, creator);
+            set_initialized(&env);
+            twap::initialize_twap(&env);
+            
+            emit_initialized(&env, fee_bps, creator_fee_bps, tick_spacing);
+            emit_pool_init(&env, sqrt_price_x64, current_tick, tick_spacing);
+        }
+        
+        // ========================================================
+        // SWAP FUNCTION
+        // ========================================================
+        
+        /// Execute a swap paid from `payer`'s allowance to `spender`
+        /// 
+        /// The pool pulls the input straight from `payer` with `transfer_from`
+        /// (spending the allowance `payer` granted `spender`), refunds unspent
+        /// input to `payer` and pays the output to `recipient`. Lets the router
+        /// settle a user's swap without taking custody of either token.
+        pub fn swap_from(
+            env: Env,
+            spender: Address,
+            payer: Address,
+            recipient: Address,
+            token_in: Address,
+            amount_in: i128,
+            amount_out_min: i128,
+            sqrt_price_limit_x64: Option<u128>,
+            max_tick_deviation: u32,
+        ) -> Result<SwapResult, PoolError> {
+            spender.require_auth();
+            Self::do_swap(
+                env,
+                &payer,
+                Some(&spender),
+                &recipient,
+                token_in,
+                amount_in,
+                amount_out_min,
+                sqrt_price_limit_x64,
+                max_tick_deviation,
+            )
+        }
+        
+        // ========================================================
+        // LIQUIDITY FUNCTIONS
+        // ========================================================
+        
+        /// Remove liquidity from a position, paying the proceeds to `recipient`
+        /// 
+        /// Fails with `DeadlineExpired` once the ledger timestamp passes
+        /// `deadline_ts` (0 means no deadline).
+        pub fn remove_liquidity_to(
+            env: Env,
+            owner: Address,
+            lower_tick: i32,
+            upper_tick: i32,
+            liquidity: i128,
+            amount0_min: i128,
+            amount1_min: i128,
+            recipient: Address,
+            deadline_ts: u64,
+        ) -> Result<(i128, i128), PoolError> {
+            owner.require_auth();
+            check_deadline(&env, deadline_ts)?;
+            Self::do_remove_liquidity(env, &owner, &recipient, lower_tick, upper_tick, liquidity, amount0_min, amount1_min)
+        }
+    }
 }
 
 // ============================================================
@@ -785,7 +3051,7 @@ fn get_fee_growth_inside_local(
 ) -> (u128, u128) {
     belugaswap_tick::get_fee_growth_inside(
         env,
-        |e, t| read_tick_info(e, t),
+        read_tick_info,
         lower_tick,
         upper_tick,
         current_tick,
@@ -794,6 +3060,204 @@ fn get_fee_growth_inside_local(
     )
 }
 
+/// Engine view of the pool for a swap in one direction, with the dust
+/// thresholds of the input and output tokens
+fn swap_state(pool: &PoolState, config: &PoolConfig, zero_for_one: bool) -> SwapState {
+    let (min_amount_in, min_amount_out, lifetime_fees_in, lifetime_creator_fees_in) = if zero_for_one {
+        (config.dust_threshold_0, config.dust_threshold_1, pool.lifetime_fees_0, pool.lifetime_creator_fees_0)
+    } else {
+        (config.dust_threshold_1, config.dust_threshold_0, pool.lifetime_fees_1, pool.lifetime_creator_fees_1)
+    };
+    SwapState {
+        sqrt_price_x64: pool.sqrt_price_x64,
+        current_tick: pool.current_tick,
+        liquidity: pool.liquidity,
+        tick_spacing: pool.tick_spacing,
+        fee_growth_global_0: pool.fee_growth_global_0,
+        fee_growth_global_1: pool.fee_growth_global_1,
+        max_iterations: pool.max_swap_iterations,
+        min_amount_in,
+        min_amount_out,
+        creator_fee_cap_bps: config.creator_fee_cap_bps,
+        lifetime_fees_in,
+        lifetime_creator_fees_in,
+    }
+}
+
+/// Whether a swap's price impact breaks the pool's limit (0 = no limit)
+fn exceeds_price_impact(config: &PoolConfig, price_impact_bps: i128) -> bool {
+    config.max_price_impact_bps > 0 && price_impact_bps > config.max_price_impact_bps as i128
+}
+
+/// Whether a swap's price limit lies strictly beyond the current price in
+/// the swap direction (below it when selling token0); `None` is no limit
+fn is_valid_price_limit(pool: &PoolState, zero_for_one: bool, sqrt_price_limit_x64: Option<u128>) -> bool {
+    match sqrt_price_limit_x64 {
+        None => true,
+        Some(limit) if zero_for_one => limit < pool.sqrt_price_x64,
+        Some(limit) => limit > pool.sqrt_price_x64,
+    }
+}
+
+/// `sqrt_price_x64` squared and decimal-adjusted, as a reduced rational
+/// 
+/// Terms too large for i128 are divided down together, keeping ~38
+/// significant digits. Prices above `i128::MAX` saturate to
+/// `(i128::MAX, 1)`.
+fn price_ratio(env: &Env, sqrt_price_x64: u128, decimals0: u32, decimals1: u32) -> (i128, i128) {
+    // sqrt_price / 2^64 with common factors of two cancelled
+    let shift = sqrt_price_x64.trailing_zeros().min(64);
+    let root = U256::from_u128(env, sqrt_price_x64 >> shift);
+    let (mut num, mut den) = fit_i128(env, root.mul(&root), U256::from_u128(env, 1).shl(2 * (64 - shift)));
+
+    // 10^38 is the largest power of ten that fits in i128
+    let scale = U256::from_u128(env, 10).pow(decimals0.abs_diff(decimals1).min(38));
+    if decimals0 > decimals1 {
+        num = num.mul(&scale);
+    } else {
+        den = den.mul(&scale);
+    }
+    let (num, den) = fit_i128(env, num, den);
+
+    // An exact denominator is a power of two times a power of ten, so twos
+    // and fives are the only factors the terms can share
+    let (mut num, mut den) = (num.to_u128().unwrap(), den.to_u128().unwrap());
+    let twos = num.trailing_zeros().min(den.trailing_zeros());
+    num >>= twos;
+    den >>= twos;
+    while num % 5 == 0 && den % 5 == 0 {
+        num /= 5;
+        den /= 5;
+    }
+    (num as i128, den as i128)
+}
+
+/// Token amounts behind all liquidity at the current price, rounded down,
+/// and the number of initialized ticks
+/// 
+/// Sums every segment between consecutive initialized ticks, carrying the
+/// liquidity net up from the lowest tick.
+fn total_principal(env: &Env, state: &PoolState) -> (i128, i128, u32) {
+    let ticks = match initialized_tick_range(env, state) {
+        Some((lowest, highest)) => initialized_ticks_between(env, lowest - 1, highest, state.tick_spacing),
+        None => Vec::new(env),
+    };
+    let (mut amount0, mut amount1, mut liquidity) = (0i128, 0i128, 0i128);
+    for i in 1..ticks.len() {
+        let lower = ticks.get_unchecked(i - 1);
+        let upper = ticks.get_unchecked(i);
+        liquidity = liquidity.saturating_add(read_tick_info(env, lower).liquidity_net);
+        let (segment0, segment1) = get_amounts_for_liquidity(
+            env,
+            liquidity,
+            get_sqrt_ratio_at_tick(lower),
+            get_sqrt_ratio_at_tick(upper),
+            state.sqrt_price_x64,
+        );
+        amount0 = amount0.saturating_add(segment0);
+        amount1 = amount1.saturating_add(segment1);
+    }
+    (amount0, amount1, ticks.len())
+}
+
+/// Divide both terms of a ratio by the same factor until each fits in i128
+/// 
+/// A ratio above `i128::MAX` would round its denominator to 0; it saturates
+/// to `(i128::MAX, 1)` instead.
+fn fit_i128(env: &Env, num: U256, den: U256) -> (U256, U256) {
+    let max = U256::from_u128(env, i128::MAX as u128);
+    let larger = if num > den { &num } else { &den };
+    if *larger <= max {
+        return (num, den);
+    }
+    let one = U256::from_u128(env, 1);
+    let divisor = larger.div(&max).add(&one);
+    let den = den.div(&divisor);
+    if den == U256::from_u128(env, 0) {
+        return (max, one);
+    }
+    (num.div(&divisor), den)
+}
+
+/// Dust threshold for a token: one unit at `DUST_REFERENCE_DECIMALS`
+/// precision, or 1 for tokens with that many decimals or fewer
+/// 
+/// Tokens that don't report `decimals` are treated as 7-decimal assets.
+fn dust_threshold(env: &Env, token: &Address) -> i128 {
+    let decimals = match env.try_invoke_contract::<u32, soroban_sdk::Error>(
+        token,
+        &Symbol::new(env, "decimals"),
+        vec![env],
+    ) {
+        Ok(Ok(decimals)) => decimals,
+        _ => DUST_REFERENCE_DECIMALS,
+    };
+    // 10^39 would overflow i128
+    10i128.pow(decimals.saturating_sub(DUST_REFERENCE_DECIMALS).min(38))
+}
+
+/// Lowest and highest initialized tick, `None` before the first deposit
+/// 
+/// Deposits and withdrawals keep the stored range current. A pool upgraded
+/// from a build that did not store it gets the range from one walk over the
+/// whole bitmap, until its next deposit stores it.
+fn initialized_tick_range(env: &Env, state: &PoolState) -> Option<(i32, i32)> {
+    read_tick_range(env).or_else(|| {
+        if !state.min_liquidity_locked {
+            return None;
+        }
+        let ticks = initialized_ticks_between(env, MIN_TICK - 1, MAX_TICK, state.tick_spacing);
+        ticks.first().zip(ticks.last())
+    })
+}
+
+/// Initialized ticks in (from, to], ascending, read from the tick bitmap
+fn initialized_ticks_between(env: &Env, from: i32, to: i32, tick_spacing: i32) -> Vec<i32> {
+    let mut ticks = Vec::new(env);
+    let mut tick = from;
+    while tick < to {
+        let (next, initialized) = belugaswap_tick::next_initialized_tick_within_one_word(
+            env,
+            read_tick_word,
+            tick,
+            tick_spacing,
+            false,
+        );
+        if next > to {
+            break;
+        }
+        if initialized {
+            ticks.push_back(next);
+        }
+        tick = next;
+    }
+    ticks
+}
+
+/// Aligned range and amounts of a prospective deposit
+struct DepositQuote {
+    lower_aligned: i32,
+    upper_aligned: i32,
+    /// Total liquidity added, including any first-deposit lock
+    liquidity: i128,
+    locked_liquidity: i128,
+    amount0: i128,
+    amount1: i128,
+}
+
+/// Deadlines are ledger timestamps (seconds); 0 means no deadline
+fn check_deadline(env: &Env, deadline_ts: u64) -> Result<(), PoolError> {
+    if deadline_ts != 0 && env.ledger().timestamp() > deadline_ts {
+        return Err(PoolError::DeadlineExpired);
+    }
+    Ok(())
+}
+
+/// No liquidity and nothing left to collect
+fn is_position_empty(pos: &Position) -> bool {
+    pos.liquidity == 0 && pos.tokens_owed_0 == 0 && pos.tokens_owed_1 == 0
+}
+
 /// Safe conversion from u128 to i128
 #[inline]
 fn safe_u128_to_i128(value: u128) -> i128 {
@@ -1,10 +1,11 @@
 // SPDX-License-Identifier: MIT
 // Pool Storage
 
-use soroban_sdk::{contracttype, Address, Env};
-use belugaswap_tick::TickInfo;
-use belugaswap_position::Position;
-use crate::types::{PoolConfig, PoolState, TWAPObservation};
+use soroban_sdk::{contracttype, Address, Env, FromVal, Map, Symbol, Val, Vec};
+use belugaswap_math::{DEFAULT_SWAP_ITERATIONS, DEFAULT_TWAP_CARDINALITY};
+use belugaswap_tick::{TickBitmapWord, TickInfo};
+use belugaswap_position::{Position, PositionToken};
+use crate::types::{PendingCreatorFee, PoolConfig, PoolState, PositionKey, TWAPObservation, TWAPObservationV1};
 
 // ============================================================
 // STORAGE KEYS
@@ -14,12 +15,20 @@ use crate::types::{PoolConfig, PoolState, TWAPObservation};
 pub enum DataKey {
     PoolState,
     PoolConfig,
+    PendingCreatorFee,
     Initialized,
     Tick(i32),
+    TickWord(i32),
+    TickRange,
     Position(Address, i32, i32),
+    OwnerPositions(Address),
+    PositionToken(u64),
+    NextPositionId,
     TWAPObservation(u32),
     TWAPNewestIndex,
     TWAPInitialized,
+    TWAPCardinality,
+    TWAPCardinalityNext,
 }
 
 // ============================================================
@@ -61,6 +70,19 @@ pub fn read_pool_config(env: &Env) -> PoolConfig {
         .unwrap_or_else(|| panic!("pool not initialized"))
 }
 
+pub fn read_pending_creator_fee(env: &Env) -> Option<PendingCreatorFee> {
+    env.storage().persistent().get(&DataKey::PendingCreatorFee)
+}
+
+pub fn write_pending_creator_fee(env: &Env, pending: &PendingCreatorFee) {
+    env.storage().persistent().set(&DataKey::PendingCreatorFee, pending);
+    extend_persistent_ttl(env, &DataKey::PendingCreatorFee);
+}
+
+pub fn remove_pending_creator_fee(env: &Env) {
+    env.storage().persistent().remove(&DataKey::PendingCreatorFee);
+}
+
 // ============================================================
 // POOL STATE STORAGE
 // ============================================================
@@ -84,6 +106,7 @@ pub fn init_pool_state(
     tick_spacing: i32,
     token0: Address,
     token1: Address,
+    creator: Address,
 ) {
     let state = PoolState {
         sqrt_price_x64,
@@ -96,6 +119,22 @@ pub fn init_pool_state(
         fee_growth_global_1: 0,
         creator_fees_0: 0,
         creator_fees_1: 0,
+        protocol_fees_0: 0,
+        protocol_fees_1: 0,
+        lifetime_fees_0: 0,
+        lifetime_fees_1: 0,
+        lifetime_creator_fees_0: 0,
+        lifetime_creator_fees_1: 0,
+        volume_token0: 0,
+        volume_token1: 0,
+        min_liquidity_locked: false,
+        paused: false,
+        max_swap_iterations: DEFAULT_SWAP_ITERATIONS,
+        tvl_reporting: false,
+        lp_fees_owed_0: 0,
+        lp_fees_owed_1: 0,
+        invariant_checks: false,
+        creator_fee_recipient: creator,
     };
 
     write_pool_state(env, &state);
@@ -118,6 +157,33 @@ pub fn read_tick_info(env: &Env, tick: i32) -> TickInfo {
         .unwrap_or_default()
 }
 
+pub fn remove_tick_info(env: &Env, tick: i32) {
+    env.storage().persistent().remove(&DataKey::Tick(tick));
+}
+
+pub fn write_tick_word(env: &Env, word_pos: i32, word: &TickBitmapWord) {
+    let key = DataKey::TickWord(word_pos);
+    env.storage().persistent().set(&key, word);
+    extend_persistent_ttl(env, &key);
+}
+
+pub fn read_tick_word(env: &Env, word_pos: i32) -> TickBitmapWord {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TickWord(word_pos))
+        .unwrap_or_default()
+}
+
+/// Lowest and highest initialized tick, once a deposit has stored them
+pub fn read_tick_range(env: &Env) -> Option<(i32, i32)> {
+    env.storage().persistent().get(&DataKey::TickRange)
+}
+
+pub fn write_tick_range(env: &Env, lowest: i32, highest: i32) {
+    env.storage().persistent().set(&DataKey::TickRange, &(lowest, highest));
+    extend_persistent_ttl(env, &DataKey::TickRange);
+}
+
 // ============================================================
 // POSITION STORAGE
 // ============================================================
@@ -128,6 +194,10 @@ pub fn write_position(env: &Env, owner: &Address, lower: i32, upper: i32, pos: &
     extend_persistent_ttl(env, &key);
 }
 
+pub fn remove_position(env: &Env, owner: &Address, lower: i32, upper: i32) {
+    env.storage().persistent().remove(&DataKey::Position(owner.clone(), lower, upper));
+}
+
 pub fn read_position(env: &Env, owner: &Address, lower: i32, upper: i32) -> Position {
     env.storage()
         .persistent()
@@ -136,25 +206,115 @@ pub fn read_position(env: &Env, owner: &Address, lower: i32, upper: i32) -> Posi
 }
 
 // ============================================================
-// TWAP STORAGE (Reserved for future use)
+// POSITION TOKEN STORAGE
+// ============================================================
+
+pub fn read_position_token(env: &Env, id: u64) -> Option<PositionToken> {
+    env.storage().persistent().get(&DataKey::PositionToken(id))
+}
+
+pub fn write_position_token(env: &Env, id: u64, token: &PositionToken) {
+    let key = DataKey::PositionToken(id);
+    env.storage().persistent().set(&key, token);
+    extend_persistent_ttl(env, &key);
+}
+
+pub fn remove_position_token(env: &Env, id: u64) {
+    env.storage().persistent().remove(&DataKey::PositionToken(id));
+}
+
+/// Allocate the next position id (ids start at 1)
+pub fn next_position_id(env: &Env) -> u64 {
+    let id = env
+        .storage()
+        .persistent()
+        .get(&DataKey::NextPositionId)
+        .unwrap_or(1u64);
+    env.storage().persistent().set(&DataKey::NextPositionId, &(id + 1));
+    extend_persistent_ttl(env, &DataKey::NextPositionId);
+    id
+}
+
+// ============================================================
+// OWNER POSITION INDEX
+// ============================================================
+
+pub fn read_owner_positions(env: &Env, owner: &Address) -> Vec<PositionKey> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OwnerPositions(owner.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn write_owner_positions(env: &Env, owner: &Address, keys: &Vec<PositionKey>) {
+    let key = DataKey::OwnerPositions(owner.clone());
+    if keys.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, keys);
+        extend_persistent_ttl(env, &key);
+    }
+}
+
+/// Record a range for `owner` (no-op if already indexed)
+pub fn add_owner_position(env: &Env, owner: &Address, lower: i32, upper: i32) {
+    let mut keys = read_owner_positions(env, owner);
+    let entry = PositionKey { lower_tick: lower, upper_tick: upper };
+    if keys.contains(&entry) {
+        return;
+    }
+    keys.push_back(entry);
+    write_owner_positions(env, owner, &keys);
+}
+
+/// Drop a range from `owner`'s index
+pub fn remove_owner_position(env: &Env, owner: &Address, lower: i32, upper: i32) {
+    let mut keys = read_owner_positions(env, owner);
+    let entry = PositionKey { lower_tick: lower, upper_tick: upper };
+    if let Some(index) = keys.first_index_of(&entry) {
+        keys.remove(index);
+        write_owner_positions(env, owner, &keys);
+    }
+}
+
+// ============================================================
+// TWAP STORAGE
 // ============================================================
 
-#[allow(dead_code)]
 pub fn write_twap_observation(env: &Env, index: u32, obs: &TWAPObservation) {
     let key = DataKey::TWAPObservation(index);
     env.storage().persistent().set(&key, obs);
     extend_persistent_ttl(env, &key);
 }
 
-#[allow(dead_code)]
-pub fn read_twap_observation(env: &Env, index: u32) -> TWAPObservation {
+pub fn has_twap_observation(env: &Env, index: u32) -> bool {
     env.storage()
         .persistent()
-        .get(&DataKey::TWAPObservation(index))
-        .unwrap_or_default()
+        .has(&DataKey::TWAPObservation(index))
+}
+
+/// Observations written before `seconds_per_liquidity_x64` existed
+/// are decoded with the old layout and zero-filled
+pub fn read_twap_observation(env: &Env, index: u32) -> TWAPObservation {
+    let raw: Option<Val> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::TWAPObservation(index));
+
+    let Some(val) = raw else {
+        return TWAPObservation::default();
+    };
+
+    // Struct values are stored as field maps; the legacy layout lacks the
+    // seconds-per-liquidity key
+    let fields = Map::<Symbol, Val>::from_val(env, &val);
+    if fields.contains_key(Symbol::new(env, "seconds_per_liquidity_x64")) {
+        TWAPObservation::from_val(env, &val)
+    } else {
+        TWAPObservationV1::from_val(env, &val).into()
+    }
 }
 
-#[allow(dead_code)]
 pub fn set_twap_newest_index(env: &Env, index: u32) {
     env.storage()
         .persistent()
@@ -162,7 +322,6 @@ pub fn set_twap_newest_index(env: &Env, index: u32) {
     extend_persistent_ttl(env, &DataKey::TWAPNewestIndex);
 }
 
-#[allow(dead_code)]
 pub fn get_twap_newest_index(env: &Env) -> u32 {
     env.storage()
         .persistent()
@@ -170,12 +329,38 @@ pub fn get_twap_newest_index(env: &Env) -> u32 {
         .unwrap_or(0)
 }
 
-#[allow(dead_code)]
+pub fn set_twap_cardinality(env: &Env, cardinality: u32) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::TWAPCardinality, &cardinality);
+    extend_persistent_ttl(env, &DataKey::TWAPCardinality);
+}
+
+pub fn get_twap_cardinality(env: &Env) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TWAPCardinality)
+        .unwrap_or(DEFAULT_TWAP_CARDINALITY)
+}
+
+pub fn set_twap_cardinality_next(env: &Env, cardinality: u32) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::TWAPCardinalityNext, &cardinality);
+    extend_persistent_ttl(env, &DataKey::TWAPCardinalityNext);
+}
+
+pub fn get_twap_cardinality_next(env: &Env) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TWAPCardinalityNext)
+        .unwrap_or_else(|| get_twap_cardinality(env))
+}
+
 pub fn is_twap_initialized(env: &Env) -> bool {
     env.storage().persistent().has(&DataKey::TWAPInitialized)
 }
 
-#[allow(dead_code)]
 pub fn set_twap_initialized(env: &Env) {
     env.storage()
         .persistent()
@@ -0,0 +1,248 @@
+// SPDX-License-Identifier: MIT
+// Pool TWAP Oracle
+//
+// Ring buffer of tick/liquidity cumulatives, written at most once per ledger
+// timestamp before the pool state changes (Uniswap V3 oracle style). The ring
+// starts at DEFAULT_TWAP_CARDINALITY slots and can be grown on demand.
+
+use soroban_sdk::{Env, Vec};
+use belugaswap_math::{DEFAULT_TWAP_CARDINALITY, MAX_TWAP_OBSERVATIONS};
+use crate::storage::*;
+use crate::types::TWAPObservation;
+
+// ============================================================
+// WRITE
+// ============================================================
+
+/// Seed the oracle with a zero observation at the current timestamp
+pub fn initialize_twap(env: &Env) {
+    let obs = TWAPObservation {
+        timestamp: env.ledger().timestamp(),
+        tick_cumulative: 0,
+        liquidity_cumulative: 0,
+        seconds_per_liquidity_x64: 0,
+    };
+    write_twap_observation(env, 0, &obs);
+    set_twap_newest_index(env, 0);
+    set_twap_cardinality(env, DEFAULT_TWAP_CARDINALITY);
+    set_twap_cardinality_next(env, DEFAULT_TWAP_CARDINALITY);
+    set_twap_initialized(env);
+}
+
+/// Request a larger observation ring
+///
+/// The new size takes effect once the newest observation reaches the end of
+/// the current ring, so stored observations stay in chronological order.
+/// Returns the pending cardinality after the call.
+pub fn increase_observation_cardinality(env: &Env, new_cardinality: u32) -> u32 {
+    if !is_twap_initialized(env) {
+        initialize_twap(env);
+    }
+
+    let current_next = get_twap_cardinality_next(env);
+    let target = new_cardinality.min(MAX_TWAP_OBSERVATIONS);
+    if target <= current_next {
+        return current_next;
+    }
+
+    set_twap_cardinality_next(env, target);
+    target
+}
+
+/// Record the tick and liquidity that were in effect since the last observation
+///
+/// Must be called BEFORE the pool's tick or liquidity changes. At most one
+/// observation is written per timestamp.
+pub fn update_twap(env: &Env, tick: i32, liquidity: i128) {
+    if !is_twap_initialized(env) {
+        initialize_twap(env);
+        return;
+    }
+
+    let newest_index = get_twap_newest_index(env);
+    let newest = read_twap_observation(env, newest_index);
+    let now = env.ledger().timestamp();
+
+    if now <= newest.timestamp {
+        return;
+    }
+
+    // Grow only at the end of the ring so ordering is preserved
+    let mut cardinality = get_twap_cardinality(env);
+    let cardinality_next = get_twap_cardinality_next(env);
+    if cardinality_next > cardinality && newest_index == cardinality - 1 {
+        cardinality = cardinality_next;
+        set_twap_cardinality(env, cardinality);
+    }
+
+    let obs = transform(&newest, now, tick, liquidity);
+    let next_index = (newest_index + 1) % cardinality;
+    write_twap_observation(env, next_index, &obs);
+    set_twap_newest_index(env, next_index);
+}
+
+// ============================================================
+// READ
+// ============================================================
+
+/// Tick cumulative at each `seconds_ago` lookback point
+///
+/// Lookbacks past the oldest observation saturate to the oldest cumulative.
+pub fn observe(env: &Env, seconds_ago: &Vec<u32>, tick: i32, liquidity: i128) -> Vec<i64> {
+    let mut result = Vec::new(env);
+    if !is_twap_initialized(env) {
+        for _ in 0..seconds_ago.len() {
+            result.push_back(0);
+        }
+        return result;
+    }
+
+    let now = env.ledger().timestamp();
+    for i in 0..seconds_ago.len() {
+        let target = now.saturating_sub(seconds_ago.get(i).unwrap() as u64);
+        let obs = observe_single(env, target, tick, liquidity);
+        result.push_back(obs.tick_cumulative as i64);
+    }
+    result
+}
+
+/// Seconds-per-liquidity cumulative (Q64.64) at each `seconds_ago` lookback point
+pub fn observe_liquidity(env: &Env, seconds_ago: &Vec<u32>, tick: i32, liquidity: i128) -> Vec<u128> {
+    let mut result = Vec::new(env);
+    if !is_twap_initialized(env) {
+        for _ in 0..seconds_ago.len() {
+            result.push_back(0);
+        }
+        return result;
+    }
+
+    let now = env.ledger().timestamp();
+    for i in 0..seconds_ago.len() {
+        let target = now.saturating_sub(seconds_ago.get(i).unwrap() as u64);
+        let obs = observe_single(env, target, tick, liquidity);
+        result.push_back(obs.seconds_per_liquidity_x64);
+    }
+    result
+}
+
+/// Arithmetic mean tick over the last `window` seconds
+///
+/// Uses whatever history exists when the oracle is younger than `window`, and
+/// returns the current tick when there is none.
+pub fn average_tick(env: &Env, window: u32, tick: i32, liquidity: i128) -> i32 {
+    if !is_twap_initialized(env) || window == 0 {
+        return tick;
+    }
+
+    let now = env.ledger().timestamp();
+    let start = observe_single(env, now.saturating_sub(window as u64), tick, liquidity);
+    let elapsed = now.saturating_sub(start.timestamp);
+    if elapsed == 0 {
+        return tick;
+    }
+
+    let end = transform(&read_twap_observation(env, get_twap_newest_index(env)), now, tick, liquidity);
+    let delta = end.tick_cumulative.wrapping_sub(start.tick_cumulative);
+    // Round towards negative infinity, as in Uniswap V3's OracleLibrary
+    delta.div_euclid(elapsed as i128) as i32
+}
+
+/// Observation (real or interpolated) at `target` timestamp
+fn observe_single(env: &Env, target: u64, tick: i32, liquidity: i128) -> TWAPObservation {
+    let newest_index = get_twap_newest_index(env);
+    let newest = read_twap_observation(env, newest_index);
+
+    // At or after the newest observation: extrapolate with the current tick
+    if target >= newest.timestamp {
+        return transform(&newest, target, tick, liquidity);
+    }
+
+    let (oldest_index, count) = oldest_and_count(env, newest_index);
+    let oldest = read_twap_observation(env, oldest_index);
+
+    if target <= oldest.timestamp {
+        return oldest;
+    }
+
+    // Binary search over logical positions [0, count) for the surrounding pair
+    let cardinality = get_twap_cardinality(env);
+    let physical = |pos: u32| (oldest_index + pos) % cardinality;
+    let mut lo: u32 = 0;
+    let mut hi: u32 = count - 1;
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if read_twap_observation(env, physical(mid)).timestamp <= target {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+
+    let before = read_twap_observation(env, physical(lo));
+    let after = read_twap_observation(env, physical(hi));
+    interpolate(&before, &after, target)
+}
+
+// ============================================================
+// HELPERS
+// ============================================================
+
+/// Oldest physical index and number of stored observations
+fn oldest_and_count(env: &Env, newest_index: u32) -> (u32, u32) {
+    let cardinality = get_twap_cardinality(env);
+    let next = (newest_index + 1) % cardinality;
+    if has_twap_observation(env, next) {
+        // Buffer has wrapped: the slot after the newest is the oldest
+        (next, cardinality)
+    } else {
+        (0, newest_index + 1)
+    }
+}
+
+/// Advance an observation to `timestamp` assuming `tick`/`liquidity` held
+fn transform(last: &TWAPObservation, timestamp: u64, tick: i32, liquidity: i128) -> TWAPObservation {
+    let delta = timestamp.saturating_sub(last.timestamp);
+    let liquidity_u = if liquidity > 0 { liquidity as u128 } else { 0 };
+    // Zero liquidity is treated as 1, as in Uniswap V3
+    let seconds_per_liquidity = ((delta as u128) << 64) / liquidity_u.max(1);
+    TWAPObservation {
+        timestamp,
+        tick_cumulative: last
+            .tick_cumulative
+            .wrapping_add((tick as i128).wrapping_mul(delta as i128)),
+        liquidity_cumulative: last
+            .liquidity_cumulative
+            .wrapping_add(liquidity_u.wrapping_mul(delta as u128)),
+        seconds_per_liquidity_x64: last
+            .seconds_per_liquidity_x64
+            .wrapping_add(seconds_per_liquidity),
+    }
+}
+
+/// Linear interpolation between two observations at `target`
+fn interpolate(before: &TWAPObservation, after: &TWAPObservation, target: u64) -> TWAPObservation {
+    let span = after.timestamp.saturating_sub(before.timestamp);
+    if span == 0 {
+        return before.clone();
+    }
+    let elapsed = target.saturating_sub(before.timestamp);
+
+    let tick_delta = after.tick_cumulative.wrapping_sub(before.tick_cumulative);
+    let liq_delta = after.liquidity_cumulative.wrapping_sub(before.liquidity_cumulative);
+    let spl_delta = after
+        .seconds_per_liquidity_x64
+        .wrapping_sub(before.seconds_per_liquidity_x64);
+
+    TWAPObservation {
+        timestamp: target,
+        tick_cumulative: before
+            .tick_cumulative
+            .wrapping_add(tick_delta / span as i128 * elapsed as i128),
+        liquidity_cumulative: before
+            .liquidity_cumulative
+            .wrapping_add(liq_delta / span as u128 * elapsed as u128),
+        seconds_per_liquidity_x64: before
+            .seconds_per_liquidity_x64
+            .wrapping_add(spl_delta / span as u128 * elapsed as u128),
+    }
+}
@@ -27,6 +27,18 @@ pub struct PoolConfig {
     pub fee_bps: u32,
     /// Creator's share of trading fees in basis points
     pub creator_fee_bps: u32,
+    /// Protocol's share of the LP fee in basis points (0 = switch off)
+    pub protocol_fee_bps: u32,
+    /// Where collected protocol fees go (None = the collecting admin)
+    pub protocol_fee_recipient: Option<Address>,
+    /// Smallest token0 amount a swap takes in or pays out
+    pub dust_threshold_0: i128,
+    /// Smallest token1 amount a swap takes in or pays out
+    pub dust_threshold_1: i128,
+    /// Largest price impact a single swap may have, in bps (0 = no limit)
+    pub max_price_impact_bps: u32,
+    /// Largest share of lifetime pool fees the creator may take, in bps (0 = no cap)
+    pub creator_fee_cap_bps: u32,
 }
 
 // ============================================================
@@ -46,6 +58,61 @@ pub struct PoolState {
     pub fee_growth_global_1: u128,
     pub creator_fees_0: u128,
     pub creator_fees_1: u128,
+    pub protocol_fees_0: u128,
+    pub protocol_fees_1: u128,
+    /// Cumulative token0 swap fees charged (creator, protocol and LP), saturating
+    pub lifetime_fees_0: u128,
+    /// Cumulative token1 swap fees charged (creator, protocol and LP), saturating
+    pub lifetime_fees_1: u128,
+    /// Cumulative token0 creator fees accrued, claimed or not, saturating
+    pub lifetime_creator_fees_0: u128,
+    /// Cumulative token1 creator fees accrued, claimed or not, saturating
+    pub lifetime_creator_fees_1: u128,
+    /// Cumulative token0 swapped through the pool (in and out), saturating
+    pub volume_token0: u128,
+    /// Cumulative token1 swapped through the pool (in and out), saturating
+    pub volume_token1: u128,
+    /// Set once the first deposit has locked MINIMUM_LOCKED_LIQUIDITY
+    pub min_liquidity_locked: bool,
+    /// Swaps and deposits are disabled while set; withdrawals still work
+    pub paused: bool,
+    /// Swap loop iteration budget (1..=MAX_SWAP_ITERATIONS)
+    pub max_swap_iterations: u32,
+    /// Report reserves to the factory's TVL after liquidity changes
+    pub tvl_reporting: bool,
+    /// token0 LP fees credited by swaps and not yet collected
+    pub lp_fees_owed_0: u128,
+    /// token1 LP fees credited by swaps and not yet collected
+    pub lp_fees_owed_1: u128,
+    /// Check after every swap that balances cover everything owed
+    pub invariant_checks: bool,
+    /// Where `claim_creator_fees` pays out; starts as the creator
+    pub creator_fee_recipient: Address,
+}
+
+// ============================================================
+// PENDING CREATOR FEE
+// ============================================================
+
+/// Creator fee increase waiting out its timelock
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PendingCreatorFee {
+    pub creator_fee_bps: u32,
+    /// First ledger at which `apply_creator_fee` succeeds
+    pub effective_ledger: u32,
+}
+
+// ============================================================
+// POSITION KEY
+// ============================================================
+
+/// Tick range of one of an owner's positions
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PositionKey {
+    pub lower_tick: i32,
+    pub upper_tick: i32,
 }
 
 // ============================================================
@@ -53,39 +120,74 @@ pub struct PoolState {
 // ============================================================
 
 #[contracttype]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct CreatorFeesInfo {
     pub fees_token0: u128,
     pub fees_token1: u128,
 }
 
-impl Default for CreatorFeesInfo {
-    fn default() -> Self {
-        Self {
-            fees_token0: 0,
-            fees_token1: 0,
-        }
-    }
+// ============================================================
+// PROTOCOL FEES INFO
+// ============================================================
+
+#[contracttype]
+#[derive(Clone, Debug, Default)]
+pub struct ProtocolFeesInfo {
+    pub fees_token0: u128,
+    pub fees_token1: u128,
 }
 
 // ============================================================
-// TWAP TYPES (Reserved for future use)
+// QUOTE TO PRICE
 // ============================================================
 
+/// Result of `quote_to_price`
 #[contracttype]
 #[derive(Clone, Debug)]
+pub struct QuoteResult {
+    /// Input consumed before the target (or until the input ran out)
+    pub amount_in_used: i128,
+    pub amount_out: i128,
+    /// Price and tick the swap would end at
+    pub sqrt_price_x64: u128,
+    pub current_tick: i32,
+    /// Whether the input was enough to move the price all the way to the target
+    pub reached_target: bool,
+    /// Tick boundaries crossed on the way
+    pub steps: u32,
+}
+
+// ============================================================
+// TWAP TYPES
+// ============================================================
+
+#[contracttype]
+#[derive(Clone, Debug, Default)]
 pub struct TWAPObservation {
     pub timestamp: u64,
     pub tick_cumulative: i128,
     pub liquidity_cumulative: u128,
+    /// Cumulative `seconds / liquidity` in Q64.64 (in-range liquidity).
+    /// Named to fit the 30-character contracttype field limit.
+    pub seconds_per_liquidity_x64: u128,
 }
 
-impl Default for TWAPObservation {
-    fn default() -> Self {
+/// Observation layout before `seconds_per_liquidity_x64` existed
+#[contracttype]
+#[derive(Clone, Debug, Default)]
+pub struct TWAPObservationV1 {
+    pub timestamp: u64,
+    pub tick_cumulative: i128,
+    pub liquidity_cumulative: u128,
+}
+
+impl From<TWAPObservationV1> for TWAPObservation {
+    fn from(old: TWAPObservationV1) -> Self {
         Self {
-            timestamp: 0,
-            tick_cumulative: 0,
-            liquidity_cumulative: 0,
+            timestamp: old.timestamp,
+            tick_cumulative: old.tick_cumulative,
+            liquidity_cumulative: old.liquidity_cumulative,
+            seconds_per_liquidity_x64: 0,
         }
     }
-}
\ No newline at end of file
+}
@@ -1,4 +1,7 @@
-use soroban_sdk::{testutils::Address as _, Address, Env};
+#![allow(dead_code)]
+
+use soroban_sdk::{contract, contractimpl, symbol_short, testutils::Address as _, Address, Env, Vec};
+use beluga_factory::{BelugaFactory, BelugaFactoryClient, CreatePoolParams};
 use belugaswap_pool::{BelugaPool, BelugaPoolClient};
 
 // Test constants
@@ -69,6 +72,133 @@ pub fn setup_custom_pool(
     (client, creator, factory, router, token_a, token_b)
 }
 
+// Factory stub: creator fee active, nothing locked, configurable admin.
+#[contract]
+pub struct MockFactory;
+
+#[contractimpl]
+impl MockFactory {
+    pub fn set_admin(env: Env, admin: Address) {
+        env.storage().instance().set(&symbol_short!("admin"), &admin);
+    }
+    pub fn get_admin(env: Env) -> Address {
+        env.storage().instance().get(&symbol_short!("admin")).unwrap()
+    }
+    pub fn is_creator_fee_active(_env: Env, _pool: Address, _creator: Address) -> bool {
+        true
+    }
+    pub fn is_liquidity_locked(
+        _env: Env,
+        _pool: Address,
+        _creator: Address,
+        _lower: i32,
+        _upper: i32,
+    ) -> bool {
+        false
+    }
+}
+
+/// Pool behind a MockFactory at price 1.0 with tick spacing 60, without
+/// liquidity; returns (pool, factory admin, creator)
+pub fn deploy_admin_pool(
+    env: &Env,
+    fee_bps: u32,
+    creator_fee_bps: u32,
+) -> (BelugaPoolClient<'_>, Address, Address) {
+    let admin = Address::generate(env);
+    let factory = env.register(MockFactory, ());
+    MockFactoryClient::new(env, &factory).set_admin(&admin);
+
+    let creator = Address::generate(env);
+    let token_a = create_token(env, &creator);
+    let token_b = create_token(env, &creator);
+
+    let client = BelugaPoolClient::new(env, &env.register(BelugaPool, ()));
+    client.initialize(
+        &factory,
+        &Address::generate(env),
+        &creator,
+        &token_a,
+        &token_b,
+        &fee_bps,
+        &creator_fee_bps,
+        &DEFAULT_SQRT_PRICE_X64,
+        &DEFAULT_TICK,
+        &DEFAULT_TICK_SPACING,
+    );
+
+    (client, admin, creator)
+}
+
+/// `deploy_admin_pool` with one LP position over [-600, 600] funded with
+/// `amount` of each token; returns (pool, factory admin, LP, creator)
+pub fn setup_admin_pool(
+    env: &Env,
+    fee_bps: u32,
+    creator_fee_bps: u32,
+    amount: i128,
+) -> (BelugaPoolClient<'_>, Address, Address, Address) {
+    let (client, admin, creator) = deploy_admin_pool(env, fee_bps, creator_fee_bps);
+
+    let state = client.get_pool_state();
+    let lp = Address::generate(env);
+    mint_tokens(env, &state.token0, &lp, amount);
+    mint_tokens(env, &state.token1, &lp, amount);
+    client.add_liquidity(&lp, &-600, &600, &amount, &amount, &0, &0);
+
+    (client, admin, lp, creator)
+}
+
+/// Pool contract build the real factory deploys, from `make pool-fixture`
+pub const POOL_WASM: &[u8] = include_bytes!("../../factory/tests/fixtures/belugaswap_pool.wasm");
+
+/// Real factory deploying `POOL_WASM`, with a router set; returns
+/// (factory, factory admin)
+pub fn setup_factory(env: &Env) -> (BelugaFactoryClient<'_>, Address) {
+    // Deploying and running the pool WASM exceeds the default test budget
+    env.cost_estimate().budget().reset_unlimited();
+
+    let admin = Address::generate(env);
+    let factory = BelugaFactoryClient::new(env, &env.register(BelugaFactory, ()));
+    factory.initialize(&admin, &env.deployer().upload_contract_wasm(POOL_WASM));
+    factory.set_router(&Address::generate(env));
+    (factory, admin)
+}
+
+/// Pool made by `factory.create_pool` at price 1.0, with the creator's
+/// position over [-600, 600] locked for `lock_duration` ledgers
+/// (0 = permanently); returns (pool, creator)
+pub fn create_factory_pool<'a>(
+    env: &'a Env,
+    factory: &BelugaFactoryClient,
+    lock_duration: u32,
+) -> (BelugaPoolClient<'a>, Address) {
+    let creator = Address::generate(env);
+    let token_a = create_token(env, &creator);
+    let token_b = create_token(env, &creator);
+    mint_tokens(env, &token_a, &creator, 100_000_000);
+    mint_tokens(env, &token_b, &creator, 100_000_000);
+
+    let params = CreatePoolParams {
+        token_a,
+        token_b,
+        fee_bps: DEFAULT_FEE_BPS,
+        creator_fee_bps: DEFAULT_CREATOR_FEE_BPS,
+        initial_sqrt_price_x64: DEFAULT_SQRT_PRICE_X64,
+        initial_tick: None,
+        reference_sqrt_price_x64: None,
+        max_init_deviation_bps: 0,
+        amount0_desired: 10_000_000,
+        amount1_desired: 10_000_000,
+        lower_tick: -600,
+        upper_tick: 600,
+        lock_duration,
+        extra_ranges: Vec::new(env),
+    };
+    let pool = factory.create_pool(&creator, &params);
+    (BelugaPoolClient::new(env, &pool), creator)
+}
+
 /// Create a test token
 pub fn create_token(env: &Env, admin: &Address) -> Address {
     let token_id = env.register_stellar_asset_contract_v2(admin.clone());
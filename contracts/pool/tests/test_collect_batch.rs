@@ -0,0 +1,81 @@
+mod common;
+
+use belugaswap_pool::{BelugaPoolClient, PoolError};
+use soroban_sdk::{testutils::Address as _, token::TokenClient, vec, Address, Env, Vec};
+
+const RANGES: [(i32, i32); 3] = [(-600, 600), (-1200, 0), (0, 1200)];
+
+/// Default pool with one LP holding every range in `RANGES`, after swaps in
+/// both directions so each range has earned fees
+fn setup(env: &Env) -> (BelugaPoolClient<'_>, Address) {
+    let (client, _, _, _, token_a, token_b) = common::setup_pool(env);
+
+    let amt: i128 = 10_000_000_000;
+    let lp = Address::generate(env);
+    common::mint_tokens(env, &token_a, &lp, amt * 3);
+    common::mint_tokens(env, &token_b, &lp, amt * 3);
+    for (lower, upper) in RANGES {
+        client.add_liquidity(&lp, &lower, &upper, &amt, &amt, &0, &0);
+    }
+
+    let state = client.get_pool_state();
+    let swapper = Address::generate(env);
+    common::mint_tokens(env, &state.token0, &swapper, 100_000_000_000);
+    common::mint_tokens(env, &state.token1, &swapper, 100_000_000_000);
+    client.swap(&swapper, &state.token0, &3_000_000_000, &0, &None, &0);
+    client.swap(&swapper, &state.token1, &6_000_000_000, &0, &None, &0);
+    (client, lp)
+}
+
+fn balances(env: &Env, client: &BelugaPoolClient, owner: &Address) -> (i128, i128) {
+    let state = client.get_pool_state();
+    (
+        TokenClient::new(env, &state.token0).balance(owner),
+        TokenClient::new(env, &state.token1).balance(owner),
+    )
+}
+
+#[test]
+fn test_collect_batch_matches_individual_collects() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (batched, batched_lp) = setup(&env);
+    let (single, single_lp) = setup(&env);
+
+    let mut expected = (0u128, 0u128);
+    for (lower, upper) in RANGES {
+        let (fees0, fees1) = single.collect_fees(&single_lp, &lower, &upper);
+        assert!(fees0 > 0 || fees1 > 0, "range [{}, {}] earned nothing", lower, upper);
+        expected = (expected.0 + fees0, expected.1 + fees1);
+    }
+
+    let before = balances(&env, &batched, &batched_lp);
+    let ranges: Vec<(i32, i32)> = Vec::from_array(&env, RANGES);
+    let collected = batched.collect_batch(&batched_lp, &ranges);
+    assert_eq!(collected, expected);
+
+    let after = balances(&env, &batched, &batched_lp);
+    assert_eq!(((after.0 - before.0) as u128, (after.1 - before.1) as u128), collected);
+
+    // Everything was settled: a second batch pays nothing
+    assert_eq!(batched.collect_batch(&batched_lp, &ranges), (0, 0));
+}
+
+#[test]
+fn test_collect_batch_skips_empty_and_rejects_inverted_ranges() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, lp) = setup(&env);
+    let owed = client.get_position(&lp, &-600, &600);
+
+    // No position at [-1800, -1200]; unaligned ticks snap like collect_fees
+    let ranges = vec![&env, (-1800, -1200), (-599, 599)];
+    let collected = client.collect_batch(&lp, &ranges);
+    assert_eq!(collected, (owed.fees_owed_0, owed.fees_owed_1));
+    assert_eq!(client.get_owner_positions(&lp).len(), RANGES.len() as u32);
+
+    let result = client.try_collect_batch(&lp, &vec![&env, (-600, 600), (600, -600)]);
+    assert_eq!(result, Err(Ok(PoolError::InvalidTickRange)));
+}
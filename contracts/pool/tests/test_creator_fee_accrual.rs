@@ -1,6 +1,6 @@
 mod common;
 
-use belugaswap_pool::{BelugaPool, BelugaPoolClient};
+use belugaswap_pool::{BelugaPool, BelugaPoolClient, PoolError};
 use soroban_sdk::{contract, contractimpl, testutils::Address as _, token::TokenClient, Address, Env};
 
 // Minimal factory stub: creator fee is gated on the factory reporting it active.
@@ -63,13 +63,13 @@ fn test_creator_fee_accrues_and_is_claimable() {
     let swapper = Address::generate(&env);
     let amt_in: i128 = 10_000_000_000;
     common::mint_tokens(&env, &t0, &swapper, amt_in);
-    let res = client.swap(&swapper, &t0, &amt_in, &0, &0);
+    let res = client.swap(&swapper, &t0, &amt_in, &0, &None, &0);
     std::println!("[PoC] swap in={} out={}", res.amount_in, res.amount_out);
 
     let cf = client.get_creator_fees();
     std::println!("[PoC] get_creator_fees -> token0={} token1={}", cf.fees_token0, cf.fees_token1);
 
-    let claimed = client.claim_creator_fees();
+    let claimed = client.claim_creator_fees(&0, &0);
     let creator_bal = TokenClient::new(&env, &t0).balance(&creator);
     std::println!("[PoC] claim -> ({}, {}); creator t0 balance = {}", claimed.0, claimed.1, creator_bal);
 
@@ -77,3 +77,111 @@ fn test_creator_fee_accrues_and_is_claimable() {
     assert_eq!(claimed.0, cf.fees_token0, "claim must pay out the recorded creator fee");
     assert_eq!(creator_bal as u128, cf.fees_token0, "creator must actually receive the tokens");
 }
+
+/// Pool behind the stub with creator fees accrued in token0;
+/// returns (client, creator, token0, accrued)
+fn setup_accrued(env: &Env) -> (BelugaPoolClient<'_>, Address, Address, u128) {
+    env.mock_all_auths();
+
+    let factory = env.register(MockFactory, ());
+    let router = Address::generate(env);
+    let creator = Address::generate(env);
+    let token_a = common::create_token(env, &creator);
+    let token_b = common::create_token(env, &creator);
+
+    let pool_id = env.register(BelugaPool, ());
+    let client = BelugaPoolClient::new(env, &pool_id);
+    client.initialize(
+        &factory, &router, &creator, &token_a, &token_b,
+        &30, &1000,
+        &common::DEFAULT_SQRT_PRICE_X64,
+        &0, &60,
+    );
+
+    let lp = Address::generate(env);
+    let liq_amt: i128 = 100_000_000_000;
+    common::mint_tokens(env, &token_a, &lp, liq_amt);
+    common::mint_tokens(env, &token_b, &lp, liq_amt);
+    client.add_liquidity(&lp, &-600, &600, &liq_amt, &liq_amt, &0, &0);
+
+    let t0 = client.get_pool_state().token0;
+    let swapper = Address::generate(env);
+    common::mint_tokens(env, &t0, &swapper, 10_000_000_000);
+    client.swap(&swapper, &t0, &10_000_000_000, &0, &None, &0);
+
+    let accrued = client.get_creator_fees().fees_token0;
+    assert!(accrued > 0);
+    (client, creator, t0, accrued)
+}
+
+#[test]
+fn test_claim_creator_fees_full_amount_meets_minimum() {
+    let env = Env::default();
+    let (client, creator, t0, accrued) = setup_accrued(&env);
+
+    let claimed = client.claim_creator_fees(&accrued, &0);
+    assert_eq!(claimed, (accrued, 0));
+    assert_eq!(TokenClient::new(&env, &t0).balance(&creator) as u128, accrued);
+    assert_eq!(client.get_creator_fees().fees_token0, 0);
+}
+
+#[test]
+fn test_claim_creator_fees_reverts_when_pool_short() {
+    let env = Env::default();
+    let (client, creator, t0, accrued) = setup_accrued(&env);
+
+    // Drain the pool's token0 down to half of the accrued creator fee
+    let token0 = TokenClient::new(&env, &t0);
+    let leave = (accrued / 2) as i128;
+    let sink = Address::generate(&env);
+    token0.transfer(&client.address, &sink, &(token0.balance(&client.address) - leave));
+
+    assert_eq!(
+        client.try_claim_creator_fees(&accrued, &0),
+        Err(Ok(PoolError::InsufficientOutput))
+    );
+    assert_eq!(token0.balance(&creator), 0);
+    assert_eq!(client.get_creator_fees().fees_token0, accrued);
+
+    // Accepting the capped amount leaves the shortfall claimable
+    let (paid0, _) = client.claim_creator_fees(&0, &0);
+    assert_eq!(paid0, leave as u128);
+    assert_eq!(client.get_creator_fees().fees_token0, accrued - paid0);
+}
+
+#[test]
+fn test_claim_creator_fees_pays_recipient() {
+    let env = Env::default();
+    let (client, creator, t0, accrued) = setup_accrued(&env);
+    assert_eq!(client.get_pool_state().creator_fee_recipient, creator);
+
+    let treasury = Address::generate(&env);
+    client.set_creator_fee_recipient(&creator, &treasury);
+    assert_eq!(client.get_pool_state().creator_fee_recipient, treasury);
+
+    // The creator authorizes the claim, the treasury gets the tokens
+    let claimed = client.claim_creator_fees(&0, &0);
+    assert_eq!(claimed, (accrued, 0));
+    assert_eq!(env.auths()[0].0, creator);
+    let token0 = TokenClient::new(&env, &t0);
+    assert_eq!(token0.balance(&treasury) as u128, accrued);
+    assert_eq!(token0.balance(&creator), 0);
+}
+
+#[test]
+fn test_set_creator_fee_recipient_creator_only() {
+    let env = Env::default();
+    let (client, creator, _, _) = setup_accrued(&env);
+
+    let treasury = Address::generate(&env);
+    assert_eq!(
+        client.try_set_creator_fee_recipient(&treasury, &treasury),
+        Err(Ok(PoolError::Unauthorized))
+    );
+    assert_eq!(client.get_pool_state().creator_fee_recipient, creator);
+
+    // Pointing it back at the creator undoes the change
+    client.set_creator_fee_recipient(&creator, &treasury);
+    client.set_creator_fee_recipient(&creator, &creator);
+    assert_eq!(client.get_pool_state().creator_fee_recipient, creator);
+}
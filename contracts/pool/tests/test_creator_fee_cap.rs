@@ -0,0 +1,110 @@
+mod common;
+
+use belugaswap_pool::{BelugaPoolClient, PoolError};
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+const FEE_BPS: u32 = 30;
+const CREATOR_FEE_BPS: u32 = 1000; // 10% of the total fee
+const CAP_BPS: u32 = 500; // creator keeps at most 5% of lifetime fees
+const LIQUIDITY_AMOUNT: i128 = 100_000_000_000;
+const WASH_AMOUNT: i128 = 1_000_000_000;
+
+/// Pool behind a MockFactory with one LP position over [-600, 600];
+/// returns (pool, factory admin, LP)
+fn setup(env: &Env) -> (BelugaPoolClient<'_>, Address, Address) {
+    let (client, admin, lp, _) = common::setup_admin_pool(env, FEE_BPS, CREATOR_FEE_BPS, LIQUIDITY_AMOUNT);
+    (client, admin, lp)
+}
+
+/// Round-trip the pair `rounds` times, like a creator wash-trading for fees
+fn wash_trade(env: &Env, client: &BelugaPoolClient, rounds: u32) {
+    let state = client.get_pool_state();
+    let trader = Address::generate(env);
+    common::mint_tokens(env, &state.token0, &trader, WASH_AMOUNT * rounds as i128);
+    common::mint_tokens(env, &state.token1, &trader, WASH_AMOUNT * 2 * rounds as i128);
+    for _ in 0..rounds {
+        let res = client.swap(&trader, &state.token0, &WASH_AMOUNT, &0, &None, &0);
+        client.swap(&trader, &state.token1, &res.amount_out, &0, &None, &0);
+    }
+}
+
+#[test]
+fn test_creator_fee_cap_redirects_excess_to_lps() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (capped, admin, capped_lp) = setup(&env);
+    let (uncapped, _, uncapped_lp) = setup(&env);
+    capped.set_creator_fee_cap(&admin, &CAP_BPS);
+    assert_eq!(capped.get_pool_config().creator_fee_cap_bps, CAP_BPS);
+
+    wash_trade(&env, &capped, 10);
+    wash_trade(&env, &uncapped, 10);
+
+    let (c, u) = (capped.get_pool_state(), uncapped.get_pool_state());
+    // The split never changes the total fee, so both pools charged the same
+    assert_eq!((c.lifetime_fees_0, c.lifetime_fees_1), (u.lifetime_fees_0, u.lifetime_fees_1));
+    assert!(u.lifetime_fees_0 > 0 && u.lifetime_fees_1 > 0);
+
+    // Uncapped, the creator takes its full 10%
+    assert_eq!(u.lifetime_creator_fees_0, u.creator_fees_0);
+    assert!(u.creator_fees_0 * 10_000 >= u.lifetime_fees_0 * (CREATOR_FEE_BPS as u128 - 10));
+
+    // Capped, the creator's lifetime share stops at 5% in each token
+    for (fees, creator_fees) in [(c.lifetime_fees_0, c.creator_fees_0), (c.lifetime_fees_1, c.creator_fees_1)] {
+        assert!(creator_fees * 10_000 <= fees * CAP_BPS as u128);
+        assert!(creator_fees * 10_000 >= fees * (CAP_BPS as u128 - 10));
+    }
+
+    // Everything the cap withheld from the creator went to the LP
+    let withheld_0 = (u.creator_fees_0 - c.creator_fees_0) as i128;
+    let withheld_1 = (u.creator_fees_1 - c.creator_fees_1) as i128;
+    let cp = capped.get_position(&capped_lp, &-600, &600);
+    let up = uncapped.get_position(&uncapped_lp, &-600, &600);
+    assert!((cp.fees_owed_0 as i128 - up.fees_owed_0 as i128 - withheld_0).abs() <= 2);
+    assert!((cp.fees_owed_1 as i128 - up.fees_owed_1 as i128 - withheld_1).abs() <= 2);
+}
+
+#[test]
+fn test_creator_over_cap_earns_nothing_until_fees_catch_up() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _) = setup(&env);
+    wash_trade(&env, &client, 5);
+    let before = client.get_pool_state();
+    assert!(before.creator_fees_0 > 0);
+
+    // The creator already holds ~10%, so halving the cap freezes its cut
+    // until lifetime fees double
+    client.set_creator_fee_cap(&admin, &CAP_BPS);
+    wash_trade(&env, &client, 3);
+    let after = client.get_pool_state();
+    assert!(after.lifetime_fees_0 > before.lifetime_fees_0);
+    assert_eq!(after.creator_fees_0, before.creator_fees_0);
+    assert_eq!(after.creator_fees_1, before.creator_fees_1);
+
+    // Lifting the cap restores the full creator fee
+    client.set_creator_fee_cap(&admin, &0);
+    wash_trade(&env, &client, 1);
+    assert!(client.get_pool_state().creator_fees_0 > after.creator_fees_0);
+}
+
+#[test]
+fn test_set_creator_fee_cap_requires_factory_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, lp) = setup(&env);
+    assert_eq!(client.get_pool_config().creator_fee_cap_bps, 0);
+
+    assert_eq!(
+        client.try_set_creator_fee_cap(&lp, &CAP_BPS),
+        Err(Ok(PoolError::NotFactoryAdmin))
+    );
+    assert_eq!(
+        client.try_set_creator_fee_cap(&admin, &10_001),
+        Err(Ok(PoolError::InvalidBps))
+    );
+    assert_eq!(client.get_pool_config().creator_fee_cap_bps, 0);
+}
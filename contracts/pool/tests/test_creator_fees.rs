@@ -1,6 +1,8 @@
 mod common;
 
-use soroban_sdk::{testutils::Address as _, Address, Env};
+use belugaswap_math::constants::CREATOR_FEE_TIMELOCK_LEDGERS;
+use belugaswap_pool::PoolError;
+use soroban_sdk::{testutils::{Address as _, Ledger}, Address, Env};
 
 #[test]
 fn test_creator_fee_configuration() {
@@ -42,12 +44,89 @@ fn test_claim_creator_fees_no_fees() {
     let (client, _creator, _factory, _router, _token_a, _token_b) = common::setup_pool(&env);
     
     // Claiming when no fees exist should return zero
-    // Note: claim_creator_fees() now takes no arguments - it uses require_auth internally
-    let result = client.claim_creator_fees();
+    // Note: claim_creator_fees() takes only the minimums - it uses require_auth internally
+    let result = client.claim_creator_fees(&0, &0);
     assert_eq!(result.0, 0);
     assert_eq!(result.1, 0);
 }
 
 // Note: The unauthorized test is removed because claim_creator_fees no longer takes
 // a caller argument - it uses config.creator.require_auth() internally.
-// Authorization is handled via mock_all_auths() in tests.
\ No newline at end of file
+// Authorization is handled via mock_all_auths() in tests.
+
+// ============================================================
+// CREATOR FEE UPDATES
+// ============================================================
+
+#[test]
+fn test_creator_fee_decrease_applies_immediately() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, creator, _factory, _router, _token_a, _token_b) = common::setup_pool(&env);
+    
+    let effective = client.propose_creator_fee(&creator, &50);
+    assert_eq!(effective, env.ledger().sequence());
+    assert_eq!(client.get_pool_config().creator_fee_bps, 50);
+    assert_eq!(client.get_pending_creator_fee(), None);
+}
+
+#[test]
+fn test_creator_fee_increase_is_timelocked() {
+    let env = Env::default();
+    env.mock_all_auths();
+    // Keep the contract instances alive across the timelock
+    env.ledger().with_mut(|li| {
+        li.min_persistent_entry_ttl = CREATOR_FEE_TIMELOCK_LEDGERS * 2;
+        li.max_entry_ttl = CREATOR_FEE_TIMELOCK_LEDGERS * 4;
+    });
+    
+    let (client, creator, _factory, _router, _token_a, _token_b) = common::setup_pool(&env);
+    let start = env.ledger().sequence();
+    
+    let effective = client.propose_creator_fee(&creator, &500);
+    assert_eq!(effective, start + CREATOR_FEE_TIMELOCK_LEDGERS);
+    assert_eq!(client.get_pool_config().creator_fee_bps, common::DEFAULT_CREATOR_FEE_BPS);
+    assert_eq!(client.get_pending_creator_fee().unwrap().creator_fee_bps, 500);
+    
+    // Too early
+    env.ledger().set_sequence_number(effective - 1);
+    assert_eq!(client.try_apply_creator_fee(&creator), Err(Ok(PoolError::CreatorFeeTimelocked)));
+    
+    env.ledger().set_sequence_number(effective);
+    assert_eq!(client.apply_creator_fee(&creator), 500);
+    assert_eq!(client.get_pool_config().creator_fee_bps, 500);
+    assert_eq!(client.get_pending_creator_fee(), None);
+    
+    // Nothing left to apply
+    assert_eq!(client.try_apply_creator_fee(&creator), Err(Ok(PoolError::NoPendingCreatorFee)));
+}
+
+#[test]
+fn test_creator_fee_decrease_cancels_pending_increase() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, creator, _factory, _router, _token_a, _token_b) = common::setup_pool(&env);
+    
+    client.propose_creator_fee(&creator, &500);
+    client.propose_creator_fee(&creator, &80);
+    
+    assert_eq!(client.get_pool_config().creator_fee_bps, 80);
+    assert_eq!(client.get_pending_creator_fee(), None);
+}
+
+#[test]
+fn test_propose_creator_fee_rejects_invalid() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, creator, _factory, _router, _token_a, _token_b) = common::setup_pool(&env);
+    
+    assert_eq!(client.try_propose_creator_fee(&creator, &0), Err(Ok(PoolError::InvalidCreatorFee)));
+    assert_eq!(client.try_propose_creator_fee(&creator, &1001), Err(Ok(PoolError::InvalidCreatorFee)));
+    
+    let stranger = Address::generate(&env);
+    assert_eq!(client.try_propose_creator_fee(&stranger, &50), Err(Ok(PoolError::Unauthorized)));
+    assert_eq!(client.get_pool_config().creator_fee_bps, common::DEFAULT_CREATOR_FEE_BPS);
+}
@@ -0,0 +1,134 @@
+mod common;
+
+use belugaswap_pool::{BelugaPool, BelugaPoolClient, PoolError};
+use soroban_sdk::{contract, contractimpl, testutils::Address as _, Address, Env, Symbol};
+
+// Plain token reporting 18 decimals, like a bridged EVM asset
+#[contract]
+pub struct WeiToken;
+
+#[contractimpl]
+impl WeiToken {
+    pub fn decimals(_env: Env) -> u32 {
+        18
+    }
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        let balance = Self::balance(env.clone(), to.clone());
+        env.storage().persistent().set(&to, &(balance + amount));
+    }
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage().persistent().get(&id).unwrap_or(0)
+    }
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+        let from_balance = Self::balance(env.clone(), from.clone());
+        if from_balance < amount {
+            panic!("insufficient balance");
+        }
+        env.storage().persistent().set(&from, &(from_balance - amount));
+        let to_balance = Self::balance(env.clone(), to.clone());
+        env.storage().persistent().set(&to, &(to_balance + amount));
+    }
+}
+
+/// One unit at 7-decimal precision, in 18-decimal units
+const WEI_DUST: i128 = 100_000_000_000;
+const LIQUIDITY_AMOUNT: i128 = 1_000_000_000_000_000;
+
+/// Pool pairing the 18-decimal token with a 7-decimal token, seeded over
+/// [-600, 600]; returns (pool, wei token, other token)
+fn setup(env: &Env) -> (BelugaPoolClient<'_>, WeiTokenClient<'_>, Address) {
+    let creator = Address::generate(env);
+    let wei = WeiTokenClient::new(env, &env.register(WeiToken, ()));
+    let other = common::create_token(env, &creator);
+
+    let pool_id = env.register(BelugaPool, ());
+    let client = BelugaPoolClient::new(env, &pool_id);
+    client.initialize(
+        &Address::generate(env),
+        &Address::generate(env),
+        &creator,
+        &wei.address,
+        &other,
+        &common::DEFAULT_FEE_BPS,
+        &common::DEFAULT_CREATOR_FEE_BPS,
+        &common::DEFAULT_SQRT_PRICE_X64,
+        &common::DEFAULT_TICK,
+        &common::DEFAULT_TICK_SPACING,
+    );
+
+    let lp = Address::generate(env);
+    wei.mint(&lp, &LIQUIDITY_AMOUNT);
+    common::mint_tokens(env, &other, &lp, LIQUIDITY_AMOUNT);
+    client.add_liquidity(&lp, &-600, &600, &LIQUIDITY_AMOUNT, &LIQUIDITY_AMOUNT, &0, &0);
+
+    (client, wei, other)
+}
+
+#[test]
+fn test_dust_thresholds_follow_token_decimals() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, wei, _) = setup(&env);
+    let config = client.get_pool_config();
+    let (wei_dust, other_dust) = if client.get_pool_state().token0 == wei.address {
+        (config.dust_threshold_0, config.dust_threshold_1)
+    } else {
+        (config.dust_threshold_1, config.dust_threshold_0)
+    };
+    assert_eq!(wei_dust, WEI_DUST);
+    assert_eq!(other_dust, 1);
+
+    // 7-decimal tokens keep the 1-unit threshold
+    let (default_pool, _, _, _, _, _) = common::setup_pool(&env);
+    let config = default_pool.get_pool_config();
+    assert_eq!((config.dust_threshold_0, config.dust_threshold_1), (1, 1));
+}
+
+#[test]
+fn test_swap_input_below_dust_threshold_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, wei, _) = setup(&env);
+    let swapper = Address::generate(&env);
+    wei.mint(&swapper, &(WEI_DUST * 10));
+
+    let preview = client.preview_swap(&wei.address, &(WEI_DUST - 1), &0, &None);
+    assert!(!preview.is_valid);
+    assert_eq!(preview.error_message, Some(Symbol::new(&env, "AMT_LOW")));
+    assert_eq!(
+        client.try_swap(&swapper, &wei.address, &(WEI_DUST - 1), &0, &None, &0).err(),
+        Some(Ok(PoolError::SwapAmountTooSmall))
+    );
+    assert_eq!(wei.balance(&swapper), WEI_DUST * 10);
+
+    let res = client.swap(&swapper, &wei.address, &(WEI_DUST * 10), &0, &None, &0);
+    assert_eq!(res.amount_in, WEI_DUST * 10);
+    assert!(res.amount_out > 0);
+}
+
+#[test]
+fn test_swap_output_below_dust_threshold_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, wei, other) = setup(&env);
+    let swapper = Address::generate(&env);
+    common::mint_tokens(&env, &other, &swapper, WEI_DUST * 10);
+
+    // Well above the 7-decimal input threshold, but pays out under WEI_DUST
+    let small = WEI_DUST / 10;
+    let preview = client.preview_swap(&other, &small, &0, &None);
+    assert!(!preview.is_valid);
+    assert_eq!(preview.error_message, Some(Symbol::new(&env, "OUT_DUST")));
+    assert_eq!(
+        client.try_swap(&swapper, &other, &small, &0, &None, &0).err(),
+        Some(Ok(PoolError::OutputDust))
+    );
+
+    let res = client.swap(&swapper, &other, &(WEI_DUST * 10), &0, &None, &0);
+    assert!(res.amount_out >= WEI_DUST);
+    assert_eq!(wei.balance(&swapper), res.amount_out);
+}
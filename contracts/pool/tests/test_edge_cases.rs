@@ -1,9 +1,9 @@
 mod common;
 
+use belugaswap_pool::PoolError;
 use soroban_sdk::{testutils::Address as _, Address, Env};
 
 #[test]
-#[should_panic(expected = "tick out of range")]
 fn test_edge_tick_boundaries() {
     let env = Env::default();
     env.mock_all_auths();
@@ -11,16 +11,17 @@ fn test_edge_tick_boundaries() {
     let (client, _creator, _factory, _router, _token_a, _token_b) = common::setup_pool(&env);
     let lp = Address::generate(&env);
     
-    // Try to add liquidity at extreme tick boundaries
-    client.add_liquidity(
+    // Extreme tick boundaries widen past MIN_TICK/MAX_TICK at spacing 60
+    let result = client.try_add_liquidity(
         &lp,
-        &-887272, // Near min tick
-        &887272,  // Near max tick
+        &-887272, // Min tick
+        &887272,  // Max tick
         &1_000_000,
         &1_000_000,
         &0,
         &0,
     );
+    assert_eq!(result, Err(Ok(PoolError::InvalidTickRange)));
 }
 
 #[test]
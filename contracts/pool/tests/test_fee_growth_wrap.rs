@@ -0,0 +1,97 @@
+// Fee growth accumulators are Q64.64 values that wrap modulo 2^128 (Uniswap
+// V3 semantics). A pool whose fee_growth_global runs past u128::MAX must pay
+// positions exactly what an identical pool starting from zero pays.
+
+mod common;
+
+use belugaswap_pool::BelugaPoolClient;
+use belugaswap_pool::types::PoolState;
+use soroban_sdk::{contracttype, testutils::Address as _, Address, Env};
+
+/// Mirror of the pool's storage key for its state
+#[contracttype]
+enum PoolKey {
+    PoolState,
+}
+
+/// Fee growth the wrapping pool starts from: a few swaps push it past u128::MAX
+const NEAR_MAX: u128 = u128::MAX - (1u128 << 40);
+
+const RANGES: [(i32, i32); 3] = [(-600, 600), (-1200, -600), (600, 1200)];
+
+/// Default pool with both fee growth accumulators set to `fee_growth` before
+/// any liquidity is added, then one LP per range in `RANGES`
+fn setup(env: &Env, fee_growth: u128) -> (BelugaPoolClient<'_>, [Address; 3]) {
+    let (client, _, _, _, token_a, token_b) = common::setup_pool(env);
+
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        let mut state: PoolState = storage.get(&PoolKey::PoolState).unwrap();
+        state.fee_growth_global_0 = fee_growth;
+        state.fee_growth_global_1 = fee_growth;
+        storage.set(&PoolKey::PoolState, &state);
+    });
+
+    let amt: i128 = 10_000_000_000;
+    let lps = [Address::generate(env), Address::generate(env), Address::generate(env)];
+    for (lp, (lower, upper)) in lps.iter().zip(RANGES) {
+        common::mint_tokens(env, &token_a, lp, amt);
+        common::mint_tokens(env, &token_b, lp, amt);
+        client.add_liquidity(lp, &lower, &upper, &amt, &amt, &0, &0);
+    }
+    (client, lps)
+}
+
+/// Swap down through -600 and back up through 600, crossing initialized
+/// ticks in both directions
+fn run_swaps(env: &Env, client: &BelugaPoolClient) {
+    let state = client.get_pool_state();
+    let swapper = Address::generate(env);
+    common::mint_tokens(env, &state.token0, &swapper, 100_000_000_000);
+    common::mint_tokens(env, &state.token1, &swapper, 100_000_000_000);
+
+    client.swap(&swapper, &state.token0, &15_000_000_000, &0, &None, &0);
+    assert!(client.get_pool_state().current_tick < -600);
+    client.swap(&swapper, &state.token1, &30_000_000_000, &0, &None, &0);
+    assert!(client.get_pool_state().current_tick > 600);
+    client.swap(&swapper, &state.token0, &1_000_000_000, &0, &None, &0);
+}
+
+#[test]
+fn test_fee_growth_global_wraps_during_swaps() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup(&env, NEAR_MAX);
+    run_swaps(&env, &client);
+
+    // Both accumulators ran past u128::MAX
+    let state = client.get_pool_state();
+    assert!(state.fee_growth_global_0 < NEAR_MAX, "token0 growth did not wrap");
+    assert!(state.fee_growth_global_1 < NEAR_MAX, "token1 growth did not wrap");
+}
+
+#[test]
+fn test_positions_accrue_same_fees_across_wrap() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (wrapped, wrapped_lps) = setup(&env, NEAR_MAX);
+    let (control, control_lps) = setup(&env, 0);
+    run_swaps(&env, &wrapped);
+    run_swaps(&env, &control);
+
+    for (i, (lower, upper)) in RANGES.into_iter().enumerate() {
+        let w = wrapped.get_position(&wrapped_lps[i], &lower, &upper);
+        let c = control.get_position(&control_lps[i], &lower, &upper);
+        assert!(c.fees_owed_0 > 0 && c.fees_owed_1 > 0, "range {} earned nothing", i);
+        assert_eq!((w.fees_owed_0, w.fees_owed_1), (c.fees_owed_0, c.fees_owed_1), "range {}", i);
+    }
+
+    // Collected amounts match too, not just the views
+    for (i, (lower, upper)) in RANGES.into_iter().enumerate() {
+        let w = wrapped.collect_fees(&wrapped_lps[i], &lower, &upper);
+        let c = control.collect_fees(&control_lps[i], &lower, &upper);
+        assert_eq!(w, c, "range {}", i);
+    }
+}
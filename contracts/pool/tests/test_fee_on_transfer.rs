@@ -0,0 +1,133 @@
+mod common;
+
+use belugaswap_pool::{BelugaPool, BelugaPoolClient};
+use soroban_sdk::{contract, contractimpl, testutils::Address as _, Address, Env};
+
+// Token that burns 1% of every transfer
+#[contract]
+pub struct SkimToken;
+
+#[contractimpl]
+impl SkimToken {
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        let balance = Self::balance(env.clone(), to.clone());
+        env.storage().persistent().set(&to, &(balance + amount));
+    }
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage().persistent().get(&id).unwrap_or(0)
+    }
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+        let from_balance = Self::balance(env.clone(), from.clone());
+        if from_balance < amount {
+            panic!("insufficient balance");
+        }
+        env.storage().persistent().set(&from, &(from_balance - amount));
+        let to_balance = Self::balance(env.clone(), to.clone());
+        env.storage().persistent().set(&to, &(to_balance + amount - amount / 100));
+    }
+}
+
+const LIQUIDITY_AMOUNT: i128 = 100_000_000_000;
+
+/// Pool pairing a skim token with a regular token, seeded over [-600, 600]
+fn setup(env: &Env) -> (BelugaPoolClient<'_>, SkimTokenClient<'_>) {
+    let creator = Address::generate(env);
+    let skim = SkimTokenClient::new(env, &env.register(SkimToken, ()));
+    let other = common::create_token(env, &creator);
+
+    let pool_id = env.register(BelugaPool, ());
+    let client = BelugaPoolClient::new(env, &pool_id);
+    client.initialize(
+        &Address::generate(env),
+        &Address::generate(env),
+        &creator,
+        &skim.address,
+        &other,
+        &common::DEFAULT_FEE_BPS,
+        &common::DEFAULT_CREATOR_FEE_BPS,
+        &common::DEFAULT_SQRT_PRICE_X64,
+        &common::DEFAULT_TICK,
+        &common::DEFAULT_TICK_SPACING,
+    );
+
+    let lp = Address::generate(env);
+    skim.mint(&lp, &LIQUIDITY_AMOUNT);
+    common::mint_tokens(env, &other, &lp, LIQUIDITY_AMOUNT);
+    client.add_liquidity(&lp, &-600, &600, &LIQUIDITY_AMOUNT, &LIQUIDITY_AMOUNT, &0, &0);
+
+    (client, skim)
+}
+
+#[test]
+fn test_swap_uses_amount_actually_received() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, skim) = setup(&env);
+    let balance_before = skim.balance(&client.address);
+
+    let swapper = Address::generate(&env);
+    let amount: i128 = 1_000_000_000;
+    skim.mint(&swapper, &amount);
+    let res = client.swap(&swapper, &skim.address, &amount, &0, &None, &0);
+
+    // Only what arrived after the 1% skim is swapped
+    assert_eq!(res.amount_in, amount - amount / 100);
+    assert_eq!(res.amount_in_remaining, 0);
+
+    // Pool holds at least everything the engine accounted for
+    let balance_after = skim.balance(&client.address);
+    assert!(balance_after >= balance_before + res.amount_in);
+}
+
+#[test]
+fn test_swap_refunds_unspent_received_input() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, skim) = setup(&env);
+    let balance_before = skim.balance(&client.address);
+    let zero_for_one = client.get_pool_state().token0 == skim.address;
+    let limit_tick = if zero_for_one { -60 } else { 60 };
+
+    let swapper = Address::generate(&env);
+    let amount: i128 = 50_000_000_000;
+    skim.mint(&swapper, &amount);
+    let limit = belugaswap_math::get_sqrt_ratio_at_tick(limit_tick);
+    let res = client.swap(&swapper, &skim.address, &amount, &0, &Some(limit), &0);
+
+    assert!(res.amount_in_remaining > 0);
+    assert_eq!(res.amount_in + res.amount_in_remaining, amount - amount / 100);
+
+    // The refund is itself skimmed on the way out
+    let refunded = res.amount_in_remaining - res.amount_in_remaining / 100;
+    assert_eq!(skim.balance(&swapper), refunded);
+    assert_eq!(skim.balance(&client.address), balance_before + res.amount_in);
+}
+
+#[test]
+fn test_single_sided_deposit_uses_balances_left_after_the_swap() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, skim) = setup(&env);
+    let state = client.get_pool_state();
+    let other = if state.token0 == skim.address { state.token1 } else { state.token0 };
+    let amount: i128 = 1_000_000_000;
+
+    // Skim token in: the balancing swap costs the owner more than the pool
+    // swaps. Skim token out: the owner gets less than the pool pays. Either
+    // way the deposit must fit what the owner holds afterwards.
+    for token_in in [skim.address.clone(), other.clone()] {
+        let owner = Address::generate(&env);
+        if token_in == skim.address {
+            skim.mint(&owner, &amount);
+        } else {
+            common::mint_tokens(&env, &other, &owner, amount);
+        }
+        let (liquidity, _, _) = client.add_liquidity_single_sided(&owner, &-600, &600, &token_in, &amount, &0);
+        assert!(liquidity > 0);
+        assert_eq!(client.get_position(&owner, &-600, &600).liquidity, liquidity);
+    }
+}
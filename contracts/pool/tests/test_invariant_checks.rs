@@ -0,0 +1,175 @@
+mod common;
+
+use belugaswap_pool::types::PoolState;
+use belugaswap_pool::{BelugaPool, BelugaPoolClient, PoolError};
+use soroban_sdk::{contracttype, testutils::Address as _, Address, Env};
+
+/// Mirror of the pool's storage key for its state
+#[contracttype]
+enum PoolKey {
+    PoolState,
+}
+
+const RANGES: [(i32, i32); 3] = [(-600, 600), (-1200, 0), (0, 1200)];
+const AMOUNT: i128 = 10_000_000_000;
+
+/// Pool behind a MockFactory with one LP holding every range in `RANGES`;
+/// returns (pool, factory admin, LP, swapper)
+fn setup(env: &Env) -> (BelugaPoolClient<'_>, Address, Address, Address) {
+    let (client, admin, lp, _) = common::setup_admin_pool(
+        env,
+        common::DEFAULT_FEE_BPS,
+        common::DEFAULT_CREATOR_FEE_BPS,
+        AMOUNT,
+    );
+    client.set_protocol_fee(&admin, &2500);
+
+    // setup_admin_pool opened RANGES[0]
+    let state = client.get_pool_state();
+    common::mint_tokens(env, &state.token0, &lp, AMOUNT * 2);
+    common::mint_tokens(env, &state.token1, &lp, AMOUNT * 2);
+    for (lower, upper) in &RANGES[1..] {
+        client.add_liquidity(&lp, lower, upper, &AMOUNT, &AMOUNT, &0, &0);
+    }
+
+    let swapper = Address::generate(env);
+    common::mint_tokens(env, &state.token0, &swapper, 1_000_000_000_000);
+    common::mint_tokens(env, &state.token1, &swapper, 1_000_000_000_000);
+    (client, admin, lp, swapper)
+}
+
+/// Swap `amount` in the given direction, stopping at `limit_tick` if given
+fn swap(
+    client: &BelugaPoolClient,
+    swapper: &Address,
+    zero_for_one: bool,
+    amount: i128,
+    limit_tick: Option<i32>,
+) -> Result<(), PoolError> {
+    let state = client.get_pool_state();
+    let token_in = if zero_for_one { state.token0 } else { state.token1 };
+    let limit = limit_tick.map(belugaswap_math::get_sqrt_ratio_at_tick);
+    match client.try_swap(swapper, &token_in, &amount, &0, &limit, &0) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(err.unwrap()),
+    }
+}
+
+#[test]
+fn test_invariant_holds_through_swaps_and_collects() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, lp, swapper) = setup(&env);
+    client.set_invariant_checks(&admin, &true);
+    assert!(client.get_pool_state().invariant_checks);
+
+    // Back and forth across every initialized tick, collecting in between
+    for round in 0..4 {
+        swap(&client, &swapper, true, AMOUNT * 4, Some(-1100)).unwrap();
+        assert_eq!(client.get_pool_state().current_tick, -1100);
+        swap(&client, &swapper, false, AMOUNT * 4, Some(1100)).unwrap();
+        assert_eq!(client.get_pool_state().current_tick, 1100);
+        let (lower, upper) = RANGES[round % RANGES.len()];
+        client.collect_fees(&lp, &lower, &upper);
+    }
+
+    // What is still owed to LPs shrinks as they collect
+    let owed = client.get_pool_state().lp_fees_owed_0;
+    assert!(owed > 0);
+    client.collect_fees(&lp, &-1200, &0);
+    assert!(client.get_pool_state().lp_fees_owed_0 < owed);
+    client.claim_creator_fees(&0, &0);
+    swap(&client, &swapper, true, 1_000_000_000, None).unwrap();
+}
+
+#[test]
+fn test_invariant_catches_corrupted_fee_state() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, swapper) = setup(&env);
+    swap(&client, &swapper, true, 1_000_000_000, None).unwrap();
+
+    // Claim more creator fees than the pool could ever pay
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        let mut state: PoolState = storage.get(&PoolKey::PoolState).unwrap();
+        state.creator_fees_1 += AMOUNT as u128;
+        storage.set(&PoolKey::PoolState, &state);
+    });
+
+    // Unchecked, the pool keeps trading on the broken books
+    swap(&client, &swapper, true, 1_000_000_000, None).unwrap();
+
+    client.set_invariant_checks(&admin, &true);
+    let before = client.get_pool_state();
+    assert_eq!(swap(&client, &swapper, true, 1_000_000_000, None), Err(PoolError::InvariantViolated));
+    assert_eq!(swap(&client, &swapper, false, 1_000_000_000, None), Err(PoolError::InvariantViolated));
+    assert_eq!(client.get_pool_state().sqrt_price_x64, before.sqrt_price_x64);
+
+    client.set_invariant_checks(&admin, &false);
+    swap(&client, &swapper, false, 1_000_000_000, None).unwrap();
+}
+
+#[test]
+fn test_set_invariant_checks_requires_factory_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, lp, _) = setup(&env);
+    assert_eq!(
+        client.try_set_invariant_checks(&lp, &true),
+        Err(Ok(PoolError::NotFactoryAdmin))
+    );
+    assert!(!client.get_pool_state().invariant_checks);
+}
+
+#[test]
+fn test_invariant_check_walks_only_the_active_tick_range() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    // Tick spacing 1: walking the whole bitmap would read thousands of words
+    let admin = Address::generate(&env);
+    let factory = env.register(common::MockFactory, ());
+    common::MockFactoryClient::new(&env, &factory).set_admin(&admin);
+    let creator = Address::generate(&env);
+    let client = BelugaPoolClient::new(&env, &env.register(BelugaPool, ()));
+    client.initialize(
+        &factory,
+        &Address::generate(&env),
+        &creator,
+        &common::create_token(&env, &creator),
+        &common::create_token(&env, &creator),
+        &1,
+        &common::DEFAULT_CREATOR_FEE_BPS,
+        &common::DEFAULT_SQRT_PRICE_X64,
+        &common::DEFAULT_TICK,
+        &1,
+    );
+
+    let state = client.get_pool_state();
+    let (lp, swapper) = (Address::generate(&env), Address::generate(&env));
+    for user in [&lp, &swapper] {
+        common::mint_tokens(&env, &state.token0, user, AMOUNT * 10);
+        common::mint_tokens(&env, &state.token1, user, AMOUNT * 10);
+    }
+    for (lower, upper) in [(-10, 10), (-30, -10), (10, 30)] {
+        client.add_liquidity(&lp, &lower, &upper, &AMOUNT, &AMOUNT, &0, &0);
+    }
+    client.set_invariant_checks(&admin, &true);
+
+    // Within the default budget, crossing ticks both ways
+    env.cost_estimate().budget().reset_default();
+    swap(&client, &swapper, true, AMOUNT * 4, Some(-20)).unwrap();
+    assert_eq!(client.get_pool_state().current_tick, -20);
+    swap(&client, &swapper, false, AMOUNT * 8, Some(20)).unwrap();
+    assert_eq!(client.get_pool_state().current_tick, 20);
+
+    // Closing the outer ranges narrows the walk again
+    client.close_position(&lp, &10, &30);
+    client.close_position(&lp, &-30, &-10);
+    env.cost_estimate().budget().reset_default();
+    swap(&client, &swapper, true, 1_000_000, None).unwrap();
+}
@@ -1,13 +1,15 @@
 mod common;
 
-use soroban_sdk::{testutils::Address as _, Address, Env};
+use belugaswap_math::constants::{MAX_TICK, MINIMUM_LOCKED_LIQUIDITY, MIN_TICK};
+use belugaswap_pool::{types::PositionKey, PoolError};
+use belugaswap_tick::TickInfo;
+use soroban_sdk::{contracttype, testutils::Address as _, token::TokenClient, vec, Address, Env};
 
 // ============================================================
 // ADD LIQUIDITY VALIDATION TESTS
 // ============================================================
 
 #[test]
-#[should_panic(expected = "invalid tick range")]
 fn test_add_liquidity_invalid_tick_range_equal() {
     let env = Env::default();
     env.mock_all_auths();
@@ -16,7 +18,7 @@ fn test_add_liquidity_invalid_tick_range_equal() {
     let lp = Address::generate(&env);
     
     // Lower tick == Upper tick (invalid)
-    client.add_liquidity(
+    let result = client.try_add_liquidity(
         &lp,
         &-600,
         &-600, // Same as lower
@@ -25,10 +27,10 @@ fn test_add_liquidity_invalid_tick_range_equal() {
         &0,
         &0,
     );
+    assert_eq!(result, Err(Ok(PoolError::InvalidTickRange)));
 }
 
 #[test]
-#[should_panic(expected = "invalid tick range")]
 fn test_add_liquidity_invalid_tick_range_inverted() {
     let env = Env::default();
     env.mock_all_auths();
@@ -37,7 +39,7 @@ fn test_add_liquidity_invalid_tick_range_inverted() {
     let lp = Address::generate(&env);
     
     // Lower tick > Upper tick (invalid)
-    client.add_liquidity(
+    let result = client.try_add_liquidity(
         &lp,
         &600,
         &-600, // Inverted
@@ -46,10 +48,10 @@ fn test_add_liquidity_invalid_tick_range_inverted() {
         &0,
         &0,
     );
+    assert_eq!(result, Err(Ok(PoolError::InvalidTickRange)));
 }
 
 #[test]
-#[should_panic(expected = "liquidity amount too low")]
 fn test_add_liquidity_zero_amounts() {
     let env = Env::default();
     env.mock_all_auths();
@@ -58,7 +60,7 @@ fn test_add_liquidity_zero_amounts() {
     let lp = Address::generate(&env);
     
     // Both amounts zero
-    client.add_liquidity(
+    let result = client.try_add_liquidity(
         &lp,
         &-600,
         &600,
@@ -67,10 +69,10 @@ fn test_add_liquidity_zero_amounts() {
         &0,
         &0,
     );
+    assert_eq!(result, Err(Ok(PoolError::LiquidityTooLow)));
 }
 
 #[test]
-#[should_panic(expected = "liquidity amount too low")]
 fn test_add_liquidity_below_minimum() {
     let env = Env::default();
     env.mock_all_auths();
@@ -79,7 +81,7 @@ fn test_add_liquidity_below_minimum() {
     let lp = Address::generate(&env);
     
     // Amounts too small (below MIN_LIQUIDITY threshold)
-    client.add_liquidity(
+    let result = client.try_add_liquidity(
         &lp,
         &-600,
         &600,
@@ -88,6 +90,54 @@ fn test_add_liquidity_below_minimum() {
         &0,
         &0,
     );
+    assert_eq!(result, Err(Ok(PoolError::LiquidityTooLow)));
+}
+
+#[test]
+fn test_add_liquidity_widens_unaligned_range() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    let lp = Address::generate(&env);
+    let amt: i128 = 10_000_000_000;
+    common::mint_tokens(&env, &token_a, &lp, amt);
+    common::mint_tokens(&env, &token_b, &lp, amt);
+    
+    // Spacing 60: lower rounds down, upper rounds up (601 -> 660, not 600)
+    let (liquidity, _, _) = client.add_liquidity(&lp, &-601, &601, &amt, &amt, &0, &0);
+    assert_eq!(client.get_position(&lp, &-660, &660).liquidity, liquidity);
+    assert_eq!(client.get_position(&lp, &-660, &600).liquidity, 0);
+    
+    // Later calls with the same unaligned ticks find the same position
+    client.collect_fees(&lp, &-601, &601);
+    client.remove_liquidity_bps(&lp, &-601, &601, &5_000);
+    client.remove_liquidity(&lp, &-601, &601, &(liquidity - liquidity / 2), &0, &0);
+    assert_eq!(client.get_position(&lp, &-660, &660).liquidity, 0);
+}
+
+#[test]
+fn test_add_liquidity_out_of_bounds_ticks() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    let lp = Address::generate(&env);
+    let amt: i128 = 10_000_000_000;
+    common::mint_tokens(&env, &token_a, &lp, amt);
+    common::mint_tokens(&env, &token_b, &lp, amt);
+    
+    // Far out of range, and in range but widened past the bound by spacing 60
+    for (lower, upper) in [(i32::MIN, 600), (-600, i32::MAX), (i32::MIN, i32::MAX), (MIN_TICK, 600), (-600, MAX_TICK)] {
+        let result = client.try_add_liquidity(&lp, &lower, &upper, &amt, &amt, &0, &0);
+        assert_eq!(result, Err(Ok(PoolError::InvalidTickRange)), "[{}, {}]", lower, upper);
+        assert_eq!(client.simulate_add_liquidity(&lower, &upper, &amt, &amt), (0, 0, 0));
+    }
+    
+    // The widest aligned range inside the bounds passes the tick checks
+    let (lower, upper) = (MIN_TICK - MIN_TICK % 60, MAX_TICK - MAX_TICK % 60);
+    let result = client.try_add_liquidity(&lp, &lower, &upper, &amt, &amt, &0, &0);
+    assert_ne!(result, Err(Ok(PoolError::InvalidTickRange)));
 }
 
 // ============================================================
@@ -95,7 +145,6 @@ fn test_add_liquidity_below_minimum() {
 // ============================================================
 
 #[test]
-#[should_panic(expected = "insufficient liquidity")]
 fn test_remove_liquidity_nonexistent_position() {
     let env = Env::default();
     env.mock_all_auths();
@@ -105,7 +154,7 @@ fn test_remove_liquidity_nonexistent_position() {
     
     // Try to remove liquidity from non-existent position
     // Signature: remove_liquidity(owner, lower_tick, upper_tick, liquidity, amount0_min, amount1_min)
-    client.remove_liquidity(
+    let result = client.try_remove_liquidity(
         &lp,
         &-600,
         &600,
@@ -113,10 +162,10 @@ fn test_remove_liquidity_nonexistent_position() {
         &0,         // amount0_min
         &0,         // amount1_min
     );
+    assert_eq!(result, Err(Ok(PoolError::InsufficientLiquidity)));
 }
 
 #[test]
-#[should_panic(expected = "liquidity amount must be positive")]
 fn test_remove_liquidity_zero_amount() {
     let env = Env::default();
     env.mock_all_auths();
@@ -125,7 +174,7 @@ fn test_remove_liquidity_zero_amount() {
     let lp = Address::generate(&env);
     
     // Try to remove zero liquidity
-    client.remove_liquidity(
+    let result = client.try_remove_liquidity(
         &lp,
         &-600,
         &600,
@@ -133,6 +182,47 @@ fn test_remove_liquidity_zero_amount() {
         &0, // amount0_min
         &0, // amount1_min
     );
+    assert_eq!(result, Err(Ok(PoolError::InvalidLiquidityAmount)));
+}
+
+#[test]
+fn test_remove_liquidity_beyond_tick_gross_fails_cleanly() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    let lp = Address::generate(&env);
+    let amt: i128 = 10_000_000_000;
+    common::mint_tokens(&env, &token_a, &lp, amt);
+    common::mint_tokens(&env, &token_b, &lp, amt);
+    client.add_liquidity(&lp, &-600, &600, &amt, &amt, &0, &0);
+    let liquidity = client.get_position(&lp, &-600, &600).liquidity;
+    
+    // Leave the lower tick holding less than the position it backs
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        let mut info: TickInfo = storage.get(&PoolKey::Tick(-600)).unwrap();
+        info.liquidity_gross = liquidity / 2;
+        storage.set(&PoolKey::Tick(-600), &info);
+    });
+    
+    let tick_state = |tick: i32| {
+        let info = client.get_tick_info(&tick);
+        (info.liquidity_gross, info.liquidity_net, info.initialized)
+    };
+    let ticks_before = (tick_state(-600), tick_state(600));
+    let pool_liquidity = client.get_pool_state().liquidity;
+    
+    // The position holds enough, the tick does not: no saturation to zero
+    let result = client.try_remove_liquidity(&lp, &-600, &600, &liquidity, &0, &0);
+    assert_eq!(result, Err(Ok(PoolError::InsufficientLiquidity)));
+    assert_eq!((tick_state(-600), tick_state(600)), ticks_before);
+    assert_eq!(client.get_position(&lp, &-600, &600).liquidity, liquidity);
+    assert_eq!(client.get_pool_state().liquidity, pool_liquidity);
+    
+    // Within the tick's gross still works
+    client.remove_liquidity(&lp, &-600, &600, &(liquidity / 2), &0, &0);
+    assert_eq!(client.get_tick_info(&-600).liquidity_gross, 0);
 }
 
 // ============================================================
@@ -154,6 +244,83 @@ fn test_collect_fees_no_position() {
     assert_eq!(result.1, 0);
 }
 
+#[test]
+fn test_collect_amounts_partial_leaves_remainder() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    let state = client.get_pool_state();
+    
+    let lp = Address::generate(&env);
+    let amt: i128 = 100_000_000_000;
+    common::mint_tokens(&env, &token_a, &lp, amt);
+    common::mint_tokens(&env, &token_b, &lp, amt);
+    client.add_liquidity(&lp, &-600, &600, &amt, &amt, &0, &0);
+    
+    // Generate token0 fees
+    let swapper = Address::generate(&env);
+    let swap_amt: i128 = 10_000_000_000;
+    common::mint_tokens(&env, &state.token0, &swapper, swap_amt);
+    client.swap(&swapper, &state.token0, &swap_amt, &0, &None, &0);
+    
+    // First call takes only part of the token0 fees
+    let (first0, first1) = client.collect_amounts(&lp, &-600, &600, &1_000, &0);
+    assert_eq!(first0, 1_000);
+    assert_eq!(first1, 0);
+    
+    let pos = client.get_position(&lp, &-600, &600);
+    assert!(pos.fees_owed_0 > 0);
+    
+    // Second call drains the remainder
+    let (second0, _) = client.collect_fees(&lp, &-600, &600);
+    assert_eq!(second0, pos.fees_owed_0);
+    
+    let pos = client.get_position(&lp, &-600, &600);
+    assert_eq!(pos.fees_owed_0, 0);
+    assert_eq!(pos.fees_owed_1, 0);
+}
+
+#[test]
+fn test_lifetime_fees_accumulate_across_collects() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    let state = client.get_pool_state();
+    
+    let lp = Address::generate(&env);
+    let amt: i128 = 100_000_000_000;
+    common::mint_tokens(&env, &token_a, &lp, amt);
+    common::mint_tokens(&env, &token_b, &lp, amt);
+    client.add_liquidity(&lp, &-600, &600, &amt, &amt, &0, &0);
+    
+    let swapper = Address::generate(&env);
+    common::mint_tokens(&env, &state.token0, &swapper, amt);
+    common::mint_tokens(&env, &state.token1, &swapper, amt);
+    
+    client.swap(&swapper, &state.token0, &5_000_000_000, &0, &None, &0);
+    assert_eq!(client.get_position_lifetime_fees(&lp, &-600, &600), (client.get_position(&lp, &-600, &600).fees_owed_0, 0));
+    let first = client.collect_fees(&lp, &-600, &600);
+    assert!(first.0 > 0);
+    
+    client.swap(&swapper, &state.token1, &5_000_000_000, &0, &None, &0);
+    let second = client.collect_fees(&lp, &-600, &600);
+    assert!(second.1 > 0);
+    
+    // tokens_owed resets on every collect; the lifetime totals keep both
+    let pos = client.get_position(&lp, &-600, &600);
+    assert_eq!((pos.fees_owed_0, pos.fees_owed_1), (0, 0));
+    let lifetime = client.get_position_lifetime_fees(&lp, &-600, &600);
+    assert_eq!(lifetime, (first.0 + second.0, first.1 + second.1));
+    
+    // Partial withdrawals settle fees into the totals as well
+    client.swap(&swapper, &state.token0, &5_000_000_000, &0, &None, &0);
+    let pending = client.get_position(&lp, &-600, &600).fees_owed_0;
+    client.remove_liquidity(&lp, &-600, &600, &(pos.liquidity / 2), &0, &0);
+    assert_eq!(client.get_position_lifetime_fees(&lp, &-600, &600), (lifetime.0 + pending, lifetime.1));
+}
+
 // ============================================================
 // POSITION QUERY TESTS
 // ============================================================
@@ -176,8 +343,577 @@ fn test_get_position_various_ranges() {
     assert_eq!(pos3.liquidity, 0);
 }
 
+#[test]
+fn test_position_cost_basis_is_liquidity_weighted() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    let amt: i128 = 100_000_000_000;
+    let seeder = Address::generate(&env);
+    common::mint_tokens(&env, &token_a, &seeder, amt);
+    common::mint_tokens(&env, &token_b, &seeder, amt);
+    client.add_liquidity(&seeder, &-1800, &1800, &amt, &amt, &0, &0);
+    
+    let lp = Address::generate(&env);
+    common::mint_tokens(&env, &token_a, &lp, amt * 2);
+    common::mint_tokens(&env, &token_b, &lp, amt * 2);
+    let p1 = client.get_pool_state().sqrt_price_x64;
+    let (l1, _, _) = client.add_liquidity(&lp, &-600, &600, &amt, &amt, &0, &0);
+    assert_eq!(client.get_position(&lp, &-600, &600).cost_basis_sqrt_price_x64, p1);
+    
+    // Move the price down inside the range, then add again
+    let state = client.get_pool_state();
+    let swapper = Address::generate(&env);
+    common::mint_tokens(&env, &state.token0, &swapper, 20_000_000_000);
+    client.swap(&swapper, &state.token0, &20_000_000_000, &0, &None, &0);
+    let p2 = client.get_pool_state().sqrt_price_x64;
+    assert!(p2 < p1);
+    let (l2, _, _) = client.add_liquidity(&lp, &-600, &600, &(amt / 2), &(amt / 2), &0, &0);
+    
+    let total = (l1 + l2) as u128;
+    let expected = p1 * l1 as u128 / total + p2 * l2 as u128 / total;
+    let position = client.get_position(&lp, &-600, &600);
+    assert_eq!(position.cost_basis_sqrt_price_x64, expected);
+    assert!(expected > p2 && expected < p1);
+    
+    // Removing liquidity leaves the basis alone
+    client.remove_liquidity(&lp, &-600, &600, &(position.liquidity / 2), &0, &0);
+    assert_eq!(client.get_position(&lp, &-600, &600).cost_basis_sqrt_price_x64, expected);
+}
+
 // Note: Full liquidity tests with actual token transfers require:
 // 1. Token minting setup
 // 2. Allowance/approval handling
 // 3. Integration test environment
-// These tests focus on validation logic
\ No newline at end of file
+// These tests focus on validation logic
+// ============================================================
+// OWNER POSITION INDEX TESTS
+// ============================================================
+
+#[test]
+fn test_owner_positions_enumerated_and_pruned() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    
+    let lp = Address::generate(&env);
+    let amt: i128 = 10_000_000_000;
+    common::mint_tokens(&env, &token_a, &lp, amt * 3);
+    common::mint_tokens(&env, &token_b, &lp, amt * 3);
+    
+    assert_eq!(client.get_owner_positions(&lp).len(), 0);
+    
+    let (liquidity, _, _) = client.add_liquidity(&lp, &-600, &600, &amt, &amt, &0, &0);
+    client.add_liquidity(&lp, &-1200, &1200, &amt, &amt, &0, &0);
+    // Topping up an existing range must not duplicate it
+    client.add_liquidity(&lp, &-600, &600, &amt, &amt, &0, &0);
+    
+    let positions = client.get_owner_positions(&lp);
+    assert_eq!(positions.len(), 2);
+    assert!(positions.contains(&PositionKey { lower_tick: -600, upper_tick: 600 }));
+    assert!(positions.contains(&PositionKey { lower_tick: -1200, upper_tick: 1200 }));
+    
+    // Partial removal keeps the range listed
+    client.remove_liquidity(&lp, &-600, &600, &liquidity, &0, &0);
+    assert_eq!(client.get_owner_positions(&lp).len(), 2);
+    
+    // Removing the rest drops it
+    let remaining = client.get_position(&lp, &-600, &600).liquidity;
+    client.remove_liquidity(&lp, &-600, &600, &remaining, &0, &0);
+    
+    let positions = client.get_owner_positions(&lp);
+    assert_eq!(positions.len(), 1);
+    assert_eq!(positions.get(0).unwrap(), PositionKey { lower_tick: -1200, upper_tick: 1200 });
+}
+
+#[test]
+fn test_owner_position_kept_until_fees_collected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    let state = client.get_pool_state();
+    
+    let lp = Address::generate(&env);
+    let amt: i128 = 100_000_000_000;
+    common::mint_tokens(&env, &token_a, &lp, amt);
+    common::mint_tokens(&env, &token_b, &lp, amt);
+    let (liquidity, _, _) = client.add_liquidity(&lp, &-600, &600, &amt, &amt, &0, &0);
+    
+    // Generate token0 fees
+    let swapper = Address::generate(&env);
+    let swap_amt: i128 = 10_000_000_000;
+    common::mint_tokens(&env, &state.token0, &swapper, swap_amt);
+    client.swap(&swapper, &state.token0, &swap_amt, &0, &None, &0);
+    
+    // Liquidity gone but fees still owed: range stays discoverable
+    client.remove_liquidity(&lp, &-600, &600, &liquidity, &0, &0);
+    assert_eq!(client.get_owner_positions(&lp).len(), 1);
+    
+    client.collect_fees(&lp, &-600, &600);
+    assert_eq!(client.get_owner_positions(&lp).len(), 0);
+}
+
+// ============================================================
+// MINIMUM LIQUIDITY LOCK TESTS
+// ============================================================
+
+#[test]
+fn test_first_deposit_locks_minimum_liquidity() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    let lp = Address::generate(&env);
+    let amt: i128 = 100_000_000_000;
+    common::mint_tokens(&env, &token_a, &lp, amt);
+    common::mint_tokens(&env, &token_b, &lp, amt);
+    
+    let (liquidity, _, _) = client.add_liquidity(&lp, &-600, &600, &amt, &amt, &0, &0);
+    
+    let locked = client.get_position(&client.address, &-600, &600).liquidity;
+    assert_eq!(locked, MINIMUM_LOCKED_LIQUIDITY);
+    assert_eq!(client.get_position(&lp, &-600, &600).liquidity, liquidity);
+    assert_eq!(client.get_pool_state().liquidity, liquidity + MINIMUM_LOCKED_LIQUIDITY);
+    
+    // Full withdrawal by the LP leaves the locked slice in the pool
+    client.remove_liquidity(&lp, &-600, &600, &liquidity, &0, &0);
+    assert_eq!(client.get_pool_state().liquidity, MINIMUM_LOCKED_LIQUIDITY);
+}
+
+#[test]
+fn test_locked_liquidity_cannot_be_removed() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    let lp = Address::generate(&env);
+    let amt: i128 = 100_000_000_000;
+    common::mint_tokens(&env, &token_a, &lp, amt);
+    common::mint_tokens(&env, &token_b, &lp, amt);
+    client.add_liquidity(&lp, &-600, &600, &amt, &amt, &0, &0);
+    
+    let result = client.try_remove_liquidity(
+        &client.address,
+        &-600,
+        &600,
+        &MINIMUM_LOCKED_LIQUIDITY,
+        &0,
+        &0,
+    );
+    assert_eq!(result, Err(Ok(PoolError::PositionLocked)));
+}
+
+#[test]
+fn test_second_deposit_is_not_charged_lock() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    let amt: i128 = 100_000_000_000;
+    
+    let first = Address::generate(&env);
+    common::mint_tokens(&env, &token_a, &first, amt);
+    common::mint_tokens(&env, &token_b, &first, amt);
+    client.add_liquidity(&first, &-600, &600, &amt, &amt, &0, &0);
+    
+    let second = Address::generate(&env);
+    common::mint_tokens(&env, &token_a, &second, amt);
+    common::mint_tokens(&env, &token_b, &second, amt);
+    let before = client.get_pool_state().liquidity;
+    let (liquidity, _, _) = client.add_liquidity(&second, &-600, &600, &amt, &amt, &0, &0);
+    
+    assert_eq!(client.get_pool_state().liquidity, before + liquidity);
+    assert_eq!(client.get_position(&client.address, &-600, &600).liquidity, MINIMUM_LOCKED_LIQUIDITY);
+}
+
+// ============================================================
+// PERCENTAGE REMOVAL TESTS
+// ============================================================
+
+#[test]
+fn test_remove_liquidity_bps_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    let lp = Address::generate(&env);
+    let amt: i128 = 100_000_000_000;
+    common::mint_tokens(&env, &token_a, &lp, amt);
+    common::mint_tokens(&env, &token_b, &lp, amt);
+    let (liquidity, _, _) = client.add_liquidity(&lp, &-600, &600, &amt, &amt, &0, &0);
+    
+    // 25% of the original, then 25% of what is left
+    let (amount0, amount1) = client.remove_liquidity_bps(&lp, &-600, &600, &2500);
+    assert!(amount0 > 0 && amount1 > 0);
+    let after_first = client.get_position(&lp, &-600, &600).liquidity;
+    assert_eq!(after_first, liquidity - liquidity * 2500 / 10_000);
+    
+    client.remove_liquidity_bps(&lp, &-600, &600, &2500);
+    let after_second = client.get_position(&lp, &-600, &600).liquidity;
+    assert_eq!(after_second, after_first - after_first * 2500 / 10_000);
+    
+    // 56.25% of the original remains, up to rounding
+    let expected = liquidity * 5625 / 10_000;
+    assert!((after_second - expected).abs() <= 1, "remaining {} vs {}", after_second, expected);
+}
+
+#[test]
+fn test_remove_liquidity_bps_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    let lp = Address::generate(&env);
+    
+    assert_eq!(
+        client.try_remove_liquidity_bps(&lp, &-600, &600, &2500),
+        Err(Ok(PoolError::PositionNotFound))
+    );
+    
+    let amt: i128 = 100_000_000_000;
+    common::mint_tokens(&env, &token_a, &lp, amt);
+    common::mint_tokens(&env, &token_b, &lp, amt);
+    client.add_liquidity(&lp, &-600, &600, &amt, &amt, &0, &0);
+    
+    assert_eq!(client.try_remove_liquidity_bps(&lp, &-600, &600, &0), Err(Ok(PoolError::InvalidBps)));
+    assert_eq!(client.try_remove_liquidity_bps(&lp, &-600, &600, &10_001), Err(Ok(PoolError::InvalidBps)));
+    
+    // 100% empties the position
+    client.remove_liquidity_bps(&lp, &-600, &600, &10_000);
+    assert_eq!(client.get_position(&lp, &-600, &600).liquidity, 0);
+}
+
+// ============================================================
+// SIMULATION TESTS
+// ============================================================
+
+/// Simulate, then deposit for real and compare with what left the wallet
+fn assert_simulation_matches(
+    env: &Env,
+    client: &belugaswap_pool::BelugaPoolClient,
+    lower: i32,
+    upper: i32,
+    amount0: i128,
+    amount1: i128,
+) {
+    let state = client.get_pool_state();
+    let lp = Address::generate(env);
+    common::mint_tokens(env, &state.token0, &lp, amount0);
+    common::mint_tokens(env, &state.token1, &lp, amount1);
+    
+    let simulated = client.simulate_add_liquidity(&lower, &upper, &amount0, &amount1);
+    let after = client.get_pool_state();
+    assert_eq!(
+        (after.liquidity, after.min_liquidity_locked),
+        (state.liquidity, state.min_liquidity_locked),
+        "simulation must not touch state"
+    );
+    
+    let actual = client.add_liquidity(&lp, &lower, &upper, &amount0, &amount1, &0, &0);
+    assert_eq!(simulated, actual);
+    
+    let pulled0 = amount0 - TokenClient::new(env, &state.token0).balance(&lp);
+    let pulled1 = amount1 - TokenClient::new(env, &state.token1).balance(&lp);
+    assert_eq!((simulated.1, simulated.2), (pulled0, pulled1));
+}
+
+#[test]
+fn test_simulate_add_liquidity_matches_deposit() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, _token_a, _token_b) = common::setup_pool(&env);
+    
+    // First deposit carries the minimum-liquidity lock
+    assert_simulation_matches(&env, &client, -600, 600, 10_000_000_000, 10_000_000_000);
+    // Unaligned ticks snap to the spacing, uneven amounts leave one side unused
+    assert_simulation_matches(&env, &client, -650, 610, 3_000_000_000, 7_000_000_000);
+    // Single-sided ranges away from the price
+    assert_simulation_matches(&env, &client, 1200, 2400, 5_000_000_000, 5_000_000_000);
+    assert_simulation_matches(&env, &client, -2400, -1200, 5_000_000_000, 5_000_000_000);
+    
+    // Move the price, then simulate again at the new one
+    let token0 = client.get_pool_state().token0;
+    let swapper = Address::generate(&env);
+    common::mint_tokens(&env, &token0, &swapper, 1_000_000_000);
+    client.swap(&swapper, &token0, &1_000_000_000, &0, &None, &0);
+    assert_simulation_matches(&env, &client, -600, 600, 4_000_000_000, 4_000_000_000);
+}
+
+#[test]
+fn test_simulate_add_liquidity_failures_return_zero() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, _token_a, _token_b) = common::setup_pool(&env);
+    
+    // Inverted range, even though it would snap to [0, 60]
+    assert_eq!(client.simulate_add_liquidity(&50, &10, &1_000_000, &1_000_000), (0, 0, 0));
+    // Too small to cover the first-deposit lock
+    assert_eq!(client.simulate_add_liquidity(&-600, &600, &10, &10), (0, 0, 0));
+}
+
+// ============================================================
+// CLOSE POSITION TESTS
+// ============================================================
+
+/// Mirror of the pool's storage keys for the entries a close removes
+#[contracttype]
+enum PoolKey {
+    Tick(i32),
+    Position(Address, i32, i32),
+}
+
+#[test]
+fn test_close_position_returns_principal_plus_fees() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    let amt: i128 = 10_000_000_000;
+    
+    // A first LP takes the minimum-liquidity lock on the shared range
+    let base = Address::generate(&env);
+    common::mint_tokens(&env, &token_a, &base, amt);
+    common::mint_tokens(&env, &token_b, &base, amt);
+    client.add_liquidity(&base, &-600, &600, &amt, &amt, &0, &0);
+    
+    // The closed position sits on ticks nobody else references
+    let lp = Address::generate(&env);
+    common::mint_tokens(&env, &token_a, &lp, amt);
+    common::mint_tokens(&env, &token_b, &lp, amt);
+    client.add_liquidity(&lp, &-1200, &1200, &amt, &amt, &0, &0);
+    
+    let state = client.get_pool_state();
+    let swapper = Address::generate(&env);
+    common::mint_tokens(&env, &state.token0, &swapper, 1_000_000_000);
+    client.swap(&swapper, &state.token0, &1_000_000_000, &0, &None, &0);
+    
+    let info = client.get_position(&lp, &-1200, &1200);
+    assert!(info.fees_owed_0 > 0);
+    let token0 = TokenClient::new(&env, &state.token0);
+    let token1 = TokenClient::new(&env, &state.token1);
+    let (before0, before1) = (token0.balance(&lp), token1.balance(&lp));
+    
+    let (total0, total1) = client.close_position(&lp, &-1200, &1200);
+    
+    let expected0 = info.amount0 + info.fees_owed_0 as i128;
+    let expected1 = info.amount1 + info.fees_owed_1 as i128;
+    assert!((total0 - expected0).abs() <= 1, "token0 {} vs {}", total0, expected0);
+    assert!((total1 - expected1).abs() <= 1, "token1 {} vs {}", total1, expected1);
+    assert_eq!(token0.balance(&lp) - before0, total0);
+    assert_eq!(token1.balance(&lp) - before1, total1);
+    
+    assert_eq!(client.get_position(&lp, &-1200, &1200).liquidity, 0);
+    assert_eq!(client.get_owner_positions(&lp).len(), 0);
+    
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        assert!(!storage.has(&PoolKey::Position(lp.clone(), -1200, 1200)));
+        assert!(!storage.has(&PoolKey::Tick(-1200)));
+        assert!(!storage.has(&PoolKey::Tick(1200)));
+        // Ticks still backing another position stay
+        assert!(storage.has(&PoolKey::Tick(-600)));
+        assert!(storage.has(&PoolKey::Tick(600)));
+    });
+    
+    // Closing again finds nothing
+    let result = client.try_close_position(&lp, &-1200, &1200);
+    assert_eq!(result.err(), Some(Ok(PoolError::PositionNotFound)));
+}
+
+#[test]
+fn test_close_position_after_reopen_earns_only_new_fees() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    let amt: i128 = 10_000_000_000;
+    
+    let base = Address::generate(&env);
+    common::mint_tokens(&env, &token_a, &base, amt);
+    common::mint_tokens(&env, &token_b, &base, amt);
+    client.add_liquidity(&base, &-600, &600, &amt, &amt, &0, &0);
+    
+    let lp = Address::generate(&env);
+    common::mint_tokens(&env, &token_a, &lp, amt * 2);
+    common::mint_tokens(&env, &token_b, &lp, amt * 2);
+    client.add_liquidity(&lp, &-1200, &1200, &amt, &amt, &0, &0);
+    
+    let token0 = client.get_pool_state().token0;
+    let swapper = Address::generate(&env);
+    common::mint_tokens(&env, &token0, &swapper, 1_000_000_000);
+    client.swap(&swapper, &token0, &1_000_000_000, &0, &None, &0);
+    client.close_position(&lp, &-1200, &1200);
+    
+    // Re-initialized ticks start from fresh fee growth outside
+    client.add_liquidity(&lp, &-1200, &1200, &amt, &amt, &0, &0);
+    let info = client.get_position(&lp, &-1200, &1200);
+    assert_eq!((info.fees_owed_0, info.fees_owed_1), (0, 0));
+}
+
+#[test]
+fn test_close_position_rejects_locked_liquidity() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    let lp = Address::generate(&env);
+    let amt: i128 = 10_000_000_000;
+    common::mint_tokens(&env, &token_a, &lp, amt);
+    common::mint_tokens(&env, &token_b, &lp, amt);
+    client.add_liquidity(&lp, &-600, &600, &amt, &amt, &0, &0);
+    
+    let result = client.try_close_position(&client.address, &-600, &600);
+    assert_eq!(result.err(), Some(Ok(PoolError::PositionLocked)));
+}
+
+// ============================================================
+// FACTORY MINT TESTS
+// ============================================================
+
+#[test]
+fn test_mint_refunds_unused_prefunded_tokens() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, creator, _factory, _router, _token_a, _token_b) = common::setup_pool(&env);
+    let state = client.get_pool_state();
+    let (token0, token1) = (TokenClient::new(&env, &state.token0), TokenClient::new(&env, &state.token1));
+    
+    // Desired amounts plus an extra token0 surplus already sitting in the pool
+    let amt: i128 = 1_000_000_000;
+    let surplus: i128 = 500_000_000;
+    common::mint_tokens(&env, &state.token0, &client.address, amt + surplus);
+    common::mint_tokens(&env, &state.token1, &client.address, amt);
+    
+    let (_, used0, used1) = client.simulate_add_liquidity(&-600, &1200, &amt, &amt);
+    assert!(used1 < amt, "range should leave some token1 unused");
+    client.mint(&creator, &-600, &1200, &amt, &amt);
+    
+    assert_eq!(client.get_reserves(), (used0, used1));
+    assert_eq!(token0.balance(&creator), amt + surplus - used0);
+    assert_eq!(token1.balance(&creator), amt - used1);
+}
+
+#[test]
+fn test_later_mint_refunds_only_its_own_tokens() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    let lp = Address::generate(&env);
+    let amt: i128 = 1_000_000_000;
+    common::mint_tokens(&env, &token_a, &lp, amt);
+    common::mint_tokens(&env, &token_b, &lp, amt);
+    client.add_liquidity(&lp, &-600, &600, &amt, &amt, &0, &0);
+    let (reserve0, reserve1) = client.get_reserves();
+    
+    let state = client.get_pool_state();
+    common::mint_tokens(&env, &state.token0, &client.address, amt);
+    common::mint_tokens(&env, &state.token1, &client.address, amt);
+    let (_, used0, used1) = client.simulate_add_liquidity(&-600, &1200, &amt, &amt);
+    client.mint(&creator, &-600, &1200, &amt, &amt);
+    
+    // Other LPs' reserves stay in the pool
+    assert_eq!(client.get_reserves(), (reserve0 + used0, reserve1 + used1));
+    assert_eq!(TokenClient::new(&env, &state.token0).balance(&creator), amt - used0);
+    assert_eq!(TokenClient::new(&env, &state.token1).balance(&creator), amt - used1);
+}
+
+#[test]
+fn test_mint_ranges_seeds_every_range_from_one_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, creator, _factory, _router, _token_a, _token_b) = common::setup_pool(&env);
+    let state = client.get_pool_state();
+    let (token0, token1) = (TokenClient::new(&env, &state.token0), TokenClient::new(&env, &state.token1));
+    
+    // Primary range plus a wider one, funded by a single transfer of the sums
+    let amt: i128 = 1_000_000_000;
+    let (_, used0_a, used1_a) = client.simulate_add_liquidity(&-600, &600, &amt, &amt);
+    common::mint_tokens(&env, &state.token0, &client.address, amt * 2);
+    common::mint_tokens(&env, &state.token1, &client.address, amt * 2);
+    let liquidities = client.mint_ranges(&creator, &vec![&env, (-600, 600, amt, amt), (-1200, 1800, amt, amt)]);
+    assert_eq!(liquidities.len(), 2);
+    
+    let primary = client.get_position(&creator, &-600, &600);
+    let extra = client.get_position(&creator, &-1200, &1800);
+    assert_eq!(primary.liquidity, liquidities.get(0).unwrap());
+    assert_eq!(extra.liquidity, liquidities.get(1).unwrap());
+    assert!(extra.liquidity > 0);
+    
+    // One refund of everything the two positions did not use
+    let (reserve0, reserve1) = client.get_reserves();
+    assert!(reserve0 > used0_a && reserve1 > used1_a);
+    assert_eq!(token0.balance(&creator), amt * 2 - reserve0);
+    assert_eq!(token1.balance(&creator), amt * 2 - reserve1);
+    
+    // The positions must be backed by what was transferred
+    let (client, creator, _, _, _, _) = common::setup_pool(&env);
+    let state = client.get_pool_state();
+    common::mint_tokens(&env, &state.token0, &client.address, amt);
+    common::mint_tokens(&env, &state.token1, &client.address, amt);
+    let result = client.try_mint_ranges(&creator, &vec![&env, (-600, 600, amt, amt), (-1200, 1800, amt, amt)]);
+    assert_eq!(result.err(), Some(Ok(PoolError::InsufficientLiquidity)));
+}
+
+// ============================================================
+// BPS SLIPPAGE TESTS
+// ============================================================
+
+/// Pool seeded over [-600, 600], then pushed off 1.0 by a token0 sale
+fn setup_moved_pool(env: &Env) -> (belugaswap_pool::BelugaPoolClient<'_>, Address) {
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(env);
+    let amt: i128 = 10_000_000_000;
+    let seeder = Address::generate(env);
+    common::mint_tokens(env, &token_a, &seeder, amt * 2);
+    common::mint_tokens(env, &token_b, &seeder, amt * 2);
+    client.add_liquidity(&seeder, &-600, &600, &amt, &amt, &0, &0);
+    client.swap(&seeder, &client.get_pool_state().token0, &(amt / 10), &0, &None, &0);
+    
+    let lp = Address::generate(env);
+    common::mint_tokens(env, &token_a, &lp, amt);
+    common::mint_tokens(env, &token_b, &lp, amt);
+    (client, lp)
+}
+
+#[test]
+fn test_slippage_bps_matches_explicit_minimums() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let desired: i128 = 1_000_000_000;
+    // The moved price takes well under the desired token0, so tight
+    // tolerances fail and loose ones pass, the same both ways
+    for slippage_bps in [0u32, 100, 5_000, 10_000] {
+        let (explicit, lp_a) = setup_moved_pool(&env);
+        let (bps, lp_b) = setup_moved_pool(&env);
+        let min = desired * (10_000 - slippage_bps as i128) / 10_000;
+        
+        let by_mins = explicit.try_add_liquidity(&lp_a, &-600, &600, &desired, &desired, &min, &min);
+        let by_bps = bps.try_add_liquidity_with_slippage(&lp_b, &-600, &600, &desired, &desired, &slippage_bps);
+        assert_eq!(by_mins, by_bps, "slippage {} bps", slippage_bps);
+    }
+    
+    // Sanity: the tight end is rejected, the loose end deposits
+    let (client, lp) = setup_moved_pool(&env);
+    let result = client.try_add_liquidity_with_slippage(&lp, &-600, &600, &desired, &desired, &100);
+    assert_eq!(result.err(), Some(Ok(PoolError::SlippageExceeded)));
+    let (liquidity, _, _) = client.add_liquidity_with_slippage(&lp, &-600, &600, &desired, &desired, &10_000);
+    assert!(liquidity > 0);
+}
+
+#[test]
+fn test_slippage_bps_above_10000_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, lp) = setup_moved_pool(&env);
+    let result = client.try_add_liquidity_with_slippage(&lp, &-600, &600, &1_000_000, &1_000_000, &10_001);
+    assert_eq!(result.err(), Some(Ok(PoolError::InvalidBps)));
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use belugaswap_pool::BelugaPoolClient;
+use soroban_sdk::{testutils::{Address as _, Ledger}, Address, Env};
+
+/// Shortest lock `create_pool` accepts
+const LOCK_LEDGERS: u32 = 120_960;
+
+/// Pool created through a real factory, its creator's position over
+/// [-600, 600] locked for LOCK_LEDGERS (or permanently)
+fn setup(env: &Env, is_permanent: bool) -> (BelugaPoolClient<'_>, Address) {
+    env.mock_all_auths();
+    // Keep the contract instances alive past the lock
+    env.ledger().with_mut(|li| {
+        li.min_persistent_entry_ttl = LOCK_LEDGERS * 2;
+        li.max_entry_ttl = LOCK_LEDGERS * 4;
+    });
+
+    let (factory, _) = common::setup_factory(env);
+    common::create_factory_pool(env, &factory, if is_permanent { 0 } else { LOCK_LEDGERS })
+}
+
+#[test]
+fn test_lock_remaining_counts_down_with_ledger() {
+    let env = Env::default();
+    let (pool, creator) = setup(&env, false);
+
+    assert_eq!(pool.get_lock_remaining(&creator, &-600, &600), Some(LOCK_LEDGERS));
+
+    env.ledger().with_mut(|li| li.sequence_number += 400);
+    assert_eq!(pool.get_lock_remaining(&creator, &-600, &600), Some(LOCK_LEDGERS - 400));
+
+    env.ledger().with_mut(|li| li.sequence_number += LOCK_LEDGERS);
+    assert_eq!(pool.get_lock_remaining(&creator, &-600, &600), Some(0));
+}
+
+#[test]
+fn test_lock_remaining_none_without_matching_lock() {
+    let env = Env::default();
+    let (pool, creator) = setup(&env, true);
+
+    assert_eq!(pool.get_lock_remaining(&creator, &-600, &600), Some(u32::MAX));
+    assert_eq!(pool.get_lock_remaining(&creator, &-1200, &600), None);
+    assert_eq!(pool.get_lock_remaining(&Address::generate(&env), &-600, &600), None);
+}
+
+#[test]
+fn test_lock_remaining_none_when_factory_call_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    // Factory here is a plain account, so the forwarded call fails
+    let (pool, creator, _, _, _, _) = common::setup_pool(&env);
+    assert_eq!(pool.get_lock_remaining(&creator, &-600, &600), None);
+}
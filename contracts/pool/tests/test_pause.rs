@@ -0,0 +1,76 @@
+mod common;
+
+use belugaswap_pool::{BelugaPoolClient, PoolError};
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+const LIQUIDITY_AMOUNT: i128 = 100_000_000_000;
+
+/// Pool behind a MockFactory with one LP position over [-600, 600];
+/// returns (pool, factory admin, LP, position liquidity)
+fn setup(env: &Env) -> (BelugaPoolClient<'_>, Address, Address, i128) {
+    let (client, admin, lp, _) = common::setup_admin_pool(
+        env,
+        common::DEFAULT_FEE_BPS,
+        common::DEFAULT_CREATOR_FEE_BPS,
+        LIQUIDITY_AMOUNT,
+    );
+    let liquidity = client.get_position(&lp, &-600, &600).liquidity;
+    (client, admin, lp, liquidity)
+}
+
+#[test]
+fn test_paused_pool_rejects_swaps_but_allows_exit() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, lp, liquidity) = setup(&env);
+    let token0 = client.get_pool_state().token0;
+
+    // Accrue some fees first
+    let swapper = Address::generate(&env);
+    common::mint_tokens(&env, &token0, &swapper, 2_000_000_000);
+    client.swap(&swapper, &token0, &1_000_000_000, &0, &None, &0);
+
+    client.set_paused(&admin, &true);
+    assert!(client.get_pool_state().paused);
+
+    let result = client.try_swap(&swapper, &token0, &1_000_000_000, &0, &None, &0);
+    assert_eq!(result.err(), Some(Ok(PoolError::PoolPaused)));
+
+    // LPs can still withdraw and collect
+    let (amount0, amount1) = client.remove_liquidity(&lp, &-600, &600, &liquidity, &0, &0);
+    assert!(amount0 > 0 && amount1 > 0);
+    let (fees0, _) = client.collect_fees(&lp, &-600, &600);
+    assert!(fees0 > 0);
+
+    // Resuming re-enables trading
+    client.set_paused(&admin, &false);
+    client.swap(&swapper, &token0, &1_000_000_000, &0, &None, &0);
+}
+
+#[test]
+fn test_paused_pool_rejects_deposits() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, lp, _) = setup(&env);
+    client.set_paused(&admin, &true);
+
+    let result = client.try_add_liquidity(&lp, &-600, &600, &1_000_000, &1_000_000, &0, &0);
+    assert_eq!(result, Err(Ok(PoolError::PoolPaused)));
+
+    let result = client.try_mint(&lp, &-600, &600, &1_000_000, &1_000_000);
+    assert_eq!(result, Err(Ok(PoolError::PoolPaused)));
+}
+
+#[test]
+fn test_set_paused_rejects_non_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, lp, _) = setup(&env);
+
+    let result = client.try_set_paused(&lp, &true);
+    assert_eq!(result, Err(Ok(PoolError::NotFactoryAdmin)));
+    assert!(!client.get_pool_state().paused);
+}
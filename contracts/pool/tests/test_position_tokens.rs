@@ -0,0 +1,180 @@
+mod common;
+
+use belugaswap_pool::{BelugaPoolClient, PoolError};
+use soroban_sdk::{testutils::Address as _, token::TokenClient, Address, Env};
+
+const AMOUNT: i128 = 100_000_000_000;
+
+/// Pool with a range LP (so the minimum lock is already taken) and a minted
+/// position token for `alice`; returns (client, token0, alice, position_id)
+fn setup(env: &Env) -> (BelugaPoolClient<'_>, Address, Address, u64) {
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(env);
+    
+    let lp = Address::generate(env);
+    common::mint_tokens(env, &token_a, &lp, AMOUNT);
+    common::mint_tokens(env, &token_b, &lp, AMOUNT);
+    client.add_liquidity(&lp, &-600, &600, &AMOUNT, &AMOUNT, &0, &0);
+    
+    let alice = Address::generate(env);
+    common::mint_tokens(env, &token_a, &alice, AMOUNT);
+    common::mint_tokens(env, &token_b, &alice, AMOUNT);
+    let id = client.mint_position(&alice, &-600, &600, &AMOUNT, &AMOUNT, &0, &0);
+    
+    let token0 = client.get_pool_state().token0;
+    (client, token0, alice, id)
+}
+
+/// Swap token0 in to accrue fees for positions over the current price
+fn generate_fees(env: &Env, client: &BelugaPoolClient, token0: &Address) {
+    let swapper = Address::generate(env);
+    common::mint_tokens(env, token0, &swapper, 1_000_000_000);
+    client.swap(&swapper, token0, &1_000_000_000, &0, &None, &0);
+}
+
+#[test]
+fn test_mint_position_is_separate_from_range_position() {
+    let env = Env::default();
+    let (client, _, alice, id) = setup(&env);
+    
+    let token = client.get_position_token(&id).unwrap();
+    assert_eq!(token.owner, alice);
+    assert_eq!((token.lower_tick, token.upper_tick), (-600, 600));
+    assert!(token.position.liquidity > 0);
+    
+    // Not visible through the range-keyed views
+    assert_eq!(client.get_position(&alice, &-600, &600).liquidity, 0);
+    assert!(client.get_owner_positions(&alice).is_empty());
+    
+    // Ids are sequential
+    let token0 = client.get_pool_state().token0;
+    let token1 = client.get_pool_state().token1;
+    common::mint_tokens(&env, &token0, &alice, AMOUNT);
+    common::mint_tokens(&env, &token1, &alice, AMOUNT);
+    assert_eq!(client.mint_position(&alice, &-600, &600, &AMOUNT, &AMOUNT, &0, &0), id + 1);
+}
+
+#[test]
+fn test_transferred_position_keeps_fees_for_new_owner() {
+    let env = Env::default();
+    let (client, token0, alice, id) = setup(&env);
+    generate_fees(&env, &client, &token0);
+    
+    let before = client.get_position_by_id(&id);
+    assert!(before.fees_owed_0 > 0);
+    
+    let bob = Address::generate(&env);
+    client.transfer_position(&alice, &bob, &id);
+    assert_eq!(client.get_position_token(&id).unwrap().owner, bob);
+    
+    // Old owner is locked out
+    assert_eq!(
+        client.try_collect_position(&alice, &id, &u128::MAX, &u128::MAX),
+        Err(Ok(PoolError::NotPositionOwner))
+    );
+    assert_eq!(
+        client.try_remove_position_liquidity(&alice, &id, &before.liquidity, &0, &0),
+        Err(Ok(PoolError::NotPositionOwner))
+    );
+    assert_eq!(
+        client.try_transfer_position(&alice, &alice, &id),
+        Err(Ok(PoolError::NotPositionOwner))
+    );
+    
+    // New owner collects the fees accrued before the transfer
+    let (fees0, _) = client.collect_position(&bob, &id, &u128::MAX, &u128::MAX);
+    assert_eq!(fees0, before.fees_owed_0);
+    assert_eq!(TokenClient::new(&env, &token0).balance(&bob) as u128, fees0);
+    
+    // ... and withdraws the liquidity
+    let (amount0, amount1) = client.remove_position_liquidity(&bob, &id, &before.liquidity, &0, &0);
+    assert!(amount0 > 0 && amount1 > 0);
+    assert_eq!(client.get_position_by_id(&id).liquidity, 0);
+}
+
+#[test]
+fn test_position_token_errors() {
+    let env = Env::default();
+    let (client, _, alice, id) = setup(&env);
+    let bob = Address::generate(&env);
+    
+    assert_eq!(
+        client.try_transfer_position(&alice, &bob, &(id + 100)),
+        Err(Ok(PoolError::PositionNotFound))
+    );
+    
+    let liquidity = client.get_position_by_id(&id).liquidity;
+    assert_eq!(
+        client.try_remove_position_liquidity(&alice, &id, &(liquidity + 1), &0, &0),
+        Err(Ok(PoolError::InsufficientLiquidity))
+    );
+    assert_eq!(
+        client.try_remove_position_liquidity(&alice, &id, &0, &0, &0),
+        Err(Ok(PoolError::InvalidLiquidityAmount))
+    );
+}
+
+#[test]
+fn test_merge_same_range_positions() {
+    let env = Env::default();
+    let (client, token0, alice, id_a) = setup(&env);
+    let token1 = client.get_pool_state().token1;
+    
+    // First position earns alone, then both earn together
+    generate_fees(&env, &client, &token0);
+    common::mint_tokens(&env, &token0, &alice, AMOUNT);
+    common::mint_tokens(&env, &token1, &alice, AMOUNT);
+    let id_b = client.mint_position(&alice, &-600, &600, &AMOUNT, &AMOUNT, &0, &0);
+    generate_fees(&env, &client, &token0);
+    
+    let a = client.get_position_by_id(&id_a);
+    let b = client.get_position_by_id(&id_b);
+    assert!(a.fees_owed_0 > b.fees_owed_0 && b.fees_owed_0 > 0);
+    
+    let merged = client.merge_positions(&alice, &id_a, &id_b);
+    assert_eq!(merged, a.liquidity + b.liquidity);
+    assert!(client.get_position_token(&id_b).is_none());
+    
+    let info = client.get_position_by_id(&id_a);
+    assert_eq!(info.liquidity, merged);
+    assert_eq!(info.fees_owed_0, a.fees_owed_0 + b.fees_owed_0);
+    
+    // Fees of both halves come out of the merged position
+    let balance_before = TokenClient::new(&env, &token0).balance(&alice);
+    let (fees0, _) = client.collect_position(&alice, &id_a, &u128::MAX, &u128::MAX);
+    assert_eq!(fees0, a.fees_owed_0 + b.fees_owed_0);
+    assert_eq!(TokenClient::new(&env, &token0).balance(&alice) - balance_before, fees0 as i128);
+    
+    // The merged liquidity is fully withdrawable
+    client.remove_position_liquidity(&alice, &id_a, &merged, &0, &0);
+    assert_eq!(client.get_position_by_id(&id_a).liquidity, 0);
+}
+
+#[test]
+fn test_merge_rejects_mismatched_positions() {
+    let env = Env::default();
+    let (client, token0, alice, id_a) = setup(&env);
+    let token1 = client.get_pool_state().token1;
+    
+    common::mint_tokens(&env, &token0, &alice, 2 * AMOUNT);
+    common::mint_tokens(&env, &token1, &alice, 2 * AMOUNT);
+    let other_range = client.mint_position(&alice, &-1200, &1200, &AMOUNT, &AMOUNT, &0, &0);
+    let same_range = client.mint_position(&alice, &-600, &600, &AMOUNT, &AMOUNT, &0, &0);
+    
+    assert_eq!(
+        client.try_merge_positions(&alice, &id_a, &other_range),
+        Err(Ok(PoolError::PositionMismatch))
+    );
+    assert_eq!(
+        client.try_merge_positions(&alice, &id_a, &id_a),
+        Err(Ok(PoolError::PositionMismatch))
+    );
+    
+    let bob = Address::generate(&env);
+    client.transfer_position(&alice, &bob, &same_range);
+    assert_eq!(
+        client.try_merge_positions(&alice, &id_a, &same_range),
+        Err(Ok(PoolError::NotPositionOwner))
+    );
+}
@@ -0,0 +1,105 @@
+use belugaswap_math::{get_sqrt_ratio_at_tick, constants::{MAX_TICK, MIN_TICK}};
+use belugaswap_pool::{BelugaPool, BelugaPoolClient};
+use soroban_sdk::{contract, contractimpl, symbol_short, testutils::Address as _, Address, Env};
+
+// Token stub that only reports its decimals
+#[contract]
+pub struct DecimalsToken;
+
+#[contractimpl]
+impl DecimalsToken {
+    pub fn set_decimals(env: Env, decimals: u32) {
+        env.storage().instance().set(&symbol_short!("decimals"), &decimals);
+    }
+    pub fn decimals(env: Env) -> u32 {
+        env.storage().instance().get(&symbol_short!("decimals")).unwrap()
+    }
+}
+
+const Q64: u128 = 1 << 64;
+
+/// Pool at `sqrt_price_x64` whose token0 reports `decimals0` and token1
+/// `decimals1`
+fn setup(env: &Env, sqrt_price_x64: u128, decimals0: u32, decimals1: u32) -> BelugaPoolClient<'_> {
+    let token_a = DecimalsTokenClient::new(env, &env.register(DecimalsToken, ()));
+    let token_b = DecimalsTokenClient::new(env, &env.register(DecimalsToken, ()));
+    let (token0, token1) = if token_a.address < token_b.address {
+        (token_a, token_b)
+    } else {
+        (token_b, token_a)
+    };
+    token0.set_decimals(&decimals0);
+    token1.set_decimals(&decimals1);
+
+    let client = BelugaPoolClient::new(env, &env.register(BelugaPool, ()));
+    client.initialize(
+        &Address::generate(env), &Address::generate(env), &Address::generate(env),
+        &token0.address, &token1.address,
+        &30, &100,
+        &sqrt_price_x64,
+        &0, &60,
+    );
+    client
+}
+
+#[test]
+fn test_price_scales_by_decimal_difference() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    // Raw price 1: one wei of an 18-decimal token0 buys one unit of a
+    // 6-decimal token1, so a whole token0 is worth 10^12 token1
+    assert_eq!(setup(&env, Q64, 18, 6).get_price(), (1_000_000_000_000, 1));
+    assert_eq!(setup(&env, Q64, 6, 18).get_price(), (1, 1_000_000_000_000));
+    assert_eq!(setup(&env, Q64, 7, 7).get_price(), (1, 1));
+}
+
+#[test]
+fn test_price_is_reduced_rational() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    // sqrt price 1.5: raw price 9/4, then 10^-2 for 6 vs 8 decimals
+    let pool = setup(&env, 3 << 63, 6, 8);
+    assert_eq!(pool.get_sqrt_price(), 3 << 63);
+    assert_eq!(pool.get_price(), (9, 400));
+
+    // The other way round the fours and the hundred share factors
+    assert_eq!(setup(&env, 3 << 63, 8, 6).get_price(), (225, 1));
+}
+
+#[test]
+fn test_price_without_exact_form_stays_close() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    // An odd sqrt price has no exact i128 form, but stays within rounding
+    let sqrt_price_x64 = Q64 + 12_345;
+    let (num, den) = setup(&env, sqrt_price_x64, 9, 7).get_price();
+    assert!(num > 0 && den > 0);
+
+    let expected = (sqrt_price_x64 as f64 / Q64 as f64).powi(2) * 100.0;
+    let actual = num as f64 / den as f64;
+    assert!((actual / expected - 1.0).abs() < 1e-12, "{} vs {}", actual, expected);
+}
+
+#[test]
+fn test_price_at_sqrt_ratio_bounds_keeps_positive_denominator() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    // Near the top the price outgrows i128 many times over; dividing it down
+    // must not round the denominator away
+    let max_sqrt = get_sqrt_ratio_at_tick(MAX_TICK);
+    for (decimals0, decimals1) in [(7, 7), (18, 0), (38, 0)] {
+        let (num, den) = setup(&env, max_sqrt, decimals0, decimals1).get_price();
+        assert!(num > 0 && den >= 1, "({}, {}) at {} vs {} decimals", num, den, decimals0, decimals1);
+    }
+
+    // Near the bottom the price rounds towards zero, over a positive denominator
+    let min_sqrt = get_sqrt_ratio_at_tick(MIN_TICK);
+    for (decimals0, decimals1) in [(7, 7), (0, 18), (0, 38)] {
+        let (num, den) = setup(&env, min_sqrt, decimals0, decimals1).get_price();
+        assert!(num >= 0 && den >= 1, "({}, {}) at {} vs {} decimals", num, den, decimals0, decimals1);
+    }
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use belugaswap_pool::{BelugaPoolClient, PoolError};
+use soroban_sdk::{symbol_short, testutils::Address as _, token::TokenClient, Address, Env};
+
+const THIN_LIQUIDITY: i128 = 10_000_000;
+const MAX_IMPACT_BPS: u32 = 100;
+
+/// Thin pool behind a MockFactory with a small position over [-600, 600];
+/// returns (pool, admin, swapper)
+fn setup(env: &Env) -> (BelugaPoolClient<'_>, Address, Address) {
+    let (client, admin, _, _) = common::setup_admin_pool(
+        env,
+        common::DEFAULT_FEE_BPS,
+        common::DEFAULT_CREATOR_FEE_BPS,
+        THIN_LIQUIDITY,
+    );
+    (client, admin, Address::generate(env))
+}
+
+#[test]
+fn test_huge_swap_into_thin_pool_reverts() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, swapper) = setup(&env);
+    client.set_max_price_impact(&admin, &MAX_IMPACT_BPS);
+    assert_eq!(client.get_pool_config().max_price_impact_bps, MAX_IMPACT_BPS);
+
+    let state = client.get_pool_state();
+    let huge = THIN_LIQUIDITY;
+    common::mint_tokens(&env, &state.token0, &swapper, huge);
+
+    let preview = client.preview_swap(&state.token0, &huge, &0, &None);
+    assert!(!preview.is_valid);
+    assert_eq!(preview.error_message, Some(symbol_short!("IMPACT_HI")));
+
+    let result = client.try_swap(&swapper, &state.token0, &huge, &0, &None, &0);
+    assert_eq!(result.err(), Some(Ok(PoolError::PriceImpactTooHigh)));
+    assert_eq!(client.get_pool_state().sqrt_price_x64, state.sqrt_price_x64);
+    assert_eq!(TokenClient::new(&env, &state.token0).balance(&swapper), huge);
+
+    // Lifting the limit lets the same swap through
+    client.set_max_price_impact(&admin, &0);
+    let res = client.swap(&swapper, &state.token0, &huge, &0, &None, &0);
+    assert!(res.amount_out > 0);
+}
+
+#[test]
+fn test_small_swap_under_limit_passes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, swapper) = setup(&env);
+    client.set_max_price_impact(&admin, &MAX_IMPACT_BPS);
+
+    let state = client.get_pool_state();
+    let small: i128 = 10_000;
+    common::mint_tokens(&env, &state.token0, &swapper, small);
+
+    let preview = client.preview_swap(&state.token0, &small, &0, &None);
+    assert!(preview.is_valid);
+    assert!(preview.price_impact_bps <= MAX_IMPACT_BPS as i128);
+
+    let res = client.swap(&swapper, &state.token0, &small, &0, &None, &0);
+    assert_eq!(res.amount_out, preview.amount_out_expected);
+}
+
+#[test]
+fn test_set_max_price_impact_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, swapper) = setup(&env);
+
+    let result = client.try_set_max_price_impact(&admin, &10_001);
+    assert_eq!(result.err(), Some(Ok(PoolError::InvalidBps)));
+    let result = client.try_set_max_price_impact(&swapper, &MAX_IMPACT_BPS);
+    assert_eq!(result.err(), Some(Ok(PoolError::NotFactoryAdmin)));
+    assert_eq!(client.get_pool_config().max_price_impact_bps, 0);
+}
@@ -0,0 +1,322 @@
+mod common;
+
+use belugaswap_pool::{BelugaPoolClient, PoolError};
+use soroban_sdk::{
+    testutils::{Address as _, Events},
+    token::TokenClient, Address, Env, IntoVal, Symbol,
+};
+
+const FEE_BPS: u32 = 30;
+const CREATOR_FEE_BPS: u32 = 1000; // 10% of the total fee
+const LIQUIDITY_AMOUNT: i128 = 100_000_000_000;
+
+/// Pool behind a MockFactory with one LP position over [-600, 600];
+/// returns (pool, factory admin, LP, creator)
+fn setup(env: &Env) -> (BelugaPoolClient<'_>, Address, Address, Address) {
+    common::setup_admin_pool(env, FEE_BPS, CREATOR_FEE_BPS, LIQUIDITY_AMOUNT)
+}
+
+/// Swap token0 -> token1, returning (token0, amount_in_used)
+fn swap_token0(env: &Env, client: &BelugaPoolClient, amount: i128) -> (Address, i128) {
+    let token0 = client.get_pool_state().token0;
+    let swapper = Address::generate(env);
+    common::mint_tokens(env, &token0, &swapper, amount);
+    let res = client.swap(&swapper, &token0, &amount, &0, &None, &0);
+    (token0, res.amount_in)
+}
+
+#[test]
+fn test_protocol_fee_off_by_default() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, _) = setup(&env);
+    assert_eq!(client.get_pool_config().protocol_fee_bps, 0);
+
+    swap_token0(&env, &client, 1_000_000_000);
+
+    let fees = client.get_protocol_fees();
+    assert_eq!(fees.fees_token0, 0);
+    assert_eq!(fees.fees_token1, 0);
+}
+
+#[test]
+fn test_three_way_fee_split_sums_to_total_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, lp, _) = setup(&env);
+    client.set_protocol_fee(&admin, &2500); // 25% of the LP share
+
+    let amount: i128 = 1_000_000_000;
+    let (_, amount_in_used) = swap_token0(&env, &client, amount);
+    assert_eq!(amount_in_used, amount, "swap should stay inside the position range");
+
+    // Single step consuming the whole input: fee = input - floor(input * 9970 / 10000)
+    let total_fee = amount - amount * (10_000 - FEE_BPS as i128) / 10_000;
+    let creator_expected = total_fee * CREATOR_FEE_BPS as i128 / 10_000;
+    let protocol_expected = (total_fee - creator_expected) * 2500 / 10_000;
+    let lp_expected = total_fee - creator_expected - protocol_expected;
+
+    let creator_fee = client.get_creator_fees().fees_token0 as i128;
+    let protocol_fee = client.get_protocol_fees().fees_token0 as i128;
+    let lp_fee = client.get_position(&lp, &-600, &600).fees_owed_0 as i128;
+
+    assert_eq!(creator_fee, creator_expected);
+    assert_eq!(protocol_fee, protocol_expected);
+    // LP fees go through Q64 fee growth, which can round down by one unit
+    assert!(lp_fee <= lp_expected && lp_fee >= lp_expected - 1, "lp fee {}", lp_fee);
+    assert!(total_fee - (creator_fee + protocol_fee + lp_fee) <= 1);
+}
+
+#[test]
+fn test_swap_event_reports_fee_breakdown() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, _) = setup(&env);
+    client.set_protocol_fee(&admin, &2500);
+
+    let amount: i128 = 1_000_000_000;
+    swap_token0(&env, &client, amount);
+
+    let (contract, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(contract, client.address);
+    let topic: Symbol = topics.get(0).unwrap().into_val(&env);
+    assert_eq!(topic, Symbol::new(&env, "Swap"));
+
+    let (amount_in, _amount_out, zero_for_one, sqrt_price_x64, current_tick, lp_fee, creator_fee, protocol_fee): (
+        i128, i128, bool, u128, i32, i128, i128, i128,
+    ) = data.into_val(&env);
+
+    let state = client.get_pool_state();
+    assert_eq!(amount_in, amount);
+    assert!(zero_for_one);
+    assert_eq!(sqrt_price_x64, state.sqrt_price_x64);
+    assert_eq!(current_tick, state.current_tick);
+
+    let total_fee = amount - amount * (10_000 - FEE_BPS as i128) / 10_000;
+    assert_eq!(lp_fee + creator_fee + protocol_fee, total_fee);
+    assert_eq!(creator_fee, client.get_creator_fees().fees_token0 as i128);
+    assert_eq!(protocol_fee, client.get_protocol_fees().fees_token0 as i128);
+}
+
+#[test]
+fn test_creator_fee_accrued_event_matches_state_delta() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, creator) = setup(&env);
+    swap_token0(&env, &client, 1_000_000_000);
+    let before = client.get_creator_fees().fees_token0;
+    let (token0, _) = swap_token0(&env, &client, 500_000_000);
+
+    // Published just before the Swap event of the same swap
+    let events = env.events().all();
+    let swap_fees = last_swap_fees(&env);
+    let after = client.get_creator_fees().fees_token0;
+    let (contract, topics, data) = events.get(events.len() - 2).unwrap();
+    assert_eq!(contract, client.address);
+    let topic: Symbol = topics.get(0).unwrap().into_val(&env);
+    assert_eq!(topic, Symbol::new(&env, "CreatorFeeAccrued"));
+    let indexed: Address = topics.get(1).unwrap().into_val(&env);
+    assert_eq!(indexed, creator);
+
+    let (token, amount, unclaimed): (Address, u128, u128) = data.into_val(&env);
+    assert_eq!(token, token0);
+    assert!(amount > 0);
+    assert_eq!(amount, after - before);
+    assert_eq!(unclaimed, after);
+    assert_eq!(swap_fees.1, amount as i128);
+}
+
+/// `(lp_fee, creator_fee, protocol_fee)` from the last Swap event
+fn last_swap_fees(env: &Env) -> (i128, i128, i128) {
+    let (_, _, data) = env.events().all().last().unwrap();
+    let (_, _, _, _, _, lp_fee, creator_fee, protocol_fee): (
+        i128, i128, bool, u128, i32, i128, i128, i128,
+    ) = data.into_val(env);
+    (lp_fee, creator_fee, protocol_fee)
+}
+
+#[test]
+fn test_estimate_fees_matches_swap() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, _) = setup(&env);
+    client.set_protocol_fee(&admin, &2500);
+    let token0 = client.get_pool_state().token0;
+
+    // Inside the position, then past its lower tick where only part of the
+    // input is consumed
+    for amount in [1_000_000_000, 1_000_000_007, 200_000_000_000] {
+        let estimate = client.estimate_fees(&token0, &amount);
+        assert!(estimate.0 > 0 && estimate.1 > 0 && estimate.2 > 0);
+
+        swap_token0(&env, &client, amount);
+        assert_eq!(estimate, last_swap_fees(&env), "amount {}", amount);
+    }
+}
+
+#[test]
+fn test_estimate_fees_unknown_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, _) = setup(&env);
+    assert_eq!(client.estimate_fees(&Address::generate(&env), &1_000_000_000), (0, 0, 0));
+}
+
+#[test]
+fn test_protocol_fee_reduces_only_lp_share() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (off_pool, _, off_lp, _) = setup(&env);
+    let (on_pool, admin, on_lp, _) = setup(&env);
+    on_pool.set_protocol_fee(&admin, &1000);
+
+    swap_token0(&env, &off_pool, 1_000_000_000);
+    swap_token0(&env, &on_pool, 1_000_000_000);
+
+    // Creator fee is unaffected by the protocol switch
+    assert_eq!(
+        off_pool.get_creator_fees().fees_token0,
+        on_pool.get_creator_fees().fees_token0,
+    );
+
+    let off_lp_fee = off_pool.get_position(&off_lp, &-600, &600).fees_owed_0;
+    let on_lp_fee = on_pool.get_position(&on_lp, &-600, &600).fees_owed_0;
+    let protocol_fee = on_pool.get_protocol_fees().fees_token0;
+
+    assert!(protocol_fee > 0);
+    assert!(on_lp_fee < off_lp_fee);
+    assert!(off_lp_fee.abs_diff(on_lp_fee + protocol_fee) <= 1);
+}
+
+#[test]
+fn test_collect_protocol_pays_admin_and_resets() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, _) = setup(&env);
+    client.set_protocol_fee(&admin, &2500);
+
+    let (token0, _) = swap_token0(&env, &client, 1_000_000_000);
+    let accrued = client.get_protocol_fees().fees_token0;
+    assert!(accrued > 0);
+
+    let (collected0, collected1) = client.collect_protocol(&admin);
+    assert_eq!(collected0, accrued);
+    assert_eq!(collected1, 0);
+    assert_eq!(TokenClient::new(&env, &token0).balance(&admin) as u128, accrued);
+
+    let after = client.get_protocol_fees();
+    assert_eq!(after.fees_token0, 0);
+    assert_eq!(after.fees_token1, 0);
+}
+
+#[test]
+fn test_collect_protocol_rejects_non_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, creator) = setup(&env);
+    client.set_protocol_fee(&admin, &2500);
+    swap_token0(&env, &client, 1_000_000_000);
+
+    assert_eq!(client.try_collect_protocol(&creator), Err(Ok(PoolError::NotFactoryAdmin)));
+}
+
+#[test]
+fn test_partial_protocol_collection_pays_recipient() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, lp, _) = setup(&env);
+    client.set_protocol_fee(&admin, &2500);
+    let recipient = Address::generate(&env);
+    client.set_protocol_fee_recipient(&admin, &Some(recipient.clone()));
+    assert_eq!(client.get_pool_config().protocol_fee_recipient, Some(recipient.clone()));
+
+    let (token0, _) = swap_token0(&env, &client, 1_000_000_000);
+    let token = TokenClient::new(&env, &token0);
+    let accrued = client.get_protocol_fees().fees_token0;
+    assert!(accrued > 1);
+
+    // Partial withdrawal: the recipient is paid, the remainder stays claimable
+    let half = accrued / 2;
+    assert_eq!(client.collect_protocol_amounts(&admin, &half, &0), (half, 0));
+    assert_eq!(token.balance(&recipient) as u128, half);
+    assert_eq!(token.balance(&admin), 0);
+    assert_eq!(client.get_protocol_fees().fees_token0, accrued - half);
+
+    // Requests above what is left are capped
+    assert_eq!(client.collect_protocol_amounts(&admin, &u128::MAX, &5), (accrued - half, 0));
+    assert_eq!(token.balance(&recipient) as u128, accrued);
+    assert_eq!(client.get_protocol_fees().fees_token0, 0);
+
+    // Clearing the recipient pays the collecting admin again
+    swap_token0(&env, &client, 1_000_000_000);
+    client.set_protocol_fee_recipient(&admin, &None);
+    let (collected0, _) = client.collect_protocol(&admin);
+    assert!(collected0 > 0);
+    assert_eq!(token.balance(&admin) as u128, collected0);
+
+    assert_eq!(
+        client.try_set_protocol_fee_recipient(&lp, &Some(lp.clone())),
+        Err(Ok(PoolError::NotFactoryAdmin))
+    );
+}
+
+#[test]
+fn test_set_protocol_fee_above_max_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, _) = setup(&env);
+    assert_eq!(client.try_set_protocol_fee(&admin, &2501), Err(Ok(PoolError::InvalidProtocolFee)));
+}
+
+#[test]
+fn test_set_protocol_fee_on_live_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, lp, _) = setup(&env);
+    swap_token0(&env, &client, 1_000_000_000);
+
+    // The factory admin toggles the switch after creation
+    client.set_protocol_fee(&admin, &2500);
+    assert_eq!(client.get_pool_config().protocol_fee_bps, 2500);
+    swap_token0(&env, &client, 1_000_000_000);
+    assert!(client.get_protocol_fees().fees_token0 > 0);
+
+    assert_eq!(client.try_set_protocol_fee(&lp, &0), Err(Ok(PoolError::NotFactoryAdmin)));
+
+    // The factory itself may set it as well
+    let factory = client.get_pool_config().factory;
+    client.set_protocol_fee(&factory, &0);
+    assert_eq!(client.get_pool_config().protocol_fee_bps, 0);
+}
+
+#[test]
+fn test_fee_config_tracks_admin_updates() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, creator) = setup(&env);
+    assert_eq!(client.get_fee_config(), (FEE_BPS, CREATOR_FEE_BPS, 0));
+
+    client.set_protocol_fee(&admin, &1_000);
+    // A decrease applies at once
+    client.propose_creator_fee(&creator, &500);
+    assert_eq!(client.get_fee_config(), (FEE_BPS, 500, 1_000));
+
+    let config = client.get_pool_config();
+    assert_eq!(
+        client.get_fee_config(),
+        (config.fee_bps, config.creator_fee_bps, config.protocol_fee_bps)
+    );
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use belugaswap_pool::{BelugaPoolClient, PoolError};
+use soroban_sdk::{
+    testutils::{Address as _, Events},
+    Address, Env, IntoVal, Symbol,
+};
+
+/// Pool at tick 0 with background liquidity over [-6000, 6000]
+fn setup(env: &Env) -> (BelugaPoolClient<'_>, Address, Address) {
+    env.mock_all_auths();
+
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(env);
+    let lp = Address::generate(env);
+    let amount: i128 = 100_000_000_000;
+    common::mint_tokens(env, &token_a, &lp, amount);
+    common::mint_tokens(env, &token_b, &lp, amount);
+    client.add_liquidity(&lp, &-6000, &6000, &amount, &amount, &0, &0);
+
+    let state = client.get_pool_state();
+    (client, state.token0, state.token1)
+}
+
+#[test]
+fn test_range_order_below_price_converts_token1_to_token0() {
+    let env = Env::default();
+    let (client, token0, token1) = setup(&env);
+
+    let maker = Address::generate(&env);
+    let amount: i128 = 1_000_000_000;
+    common::mint_tokens(&env, &token1, &maker, amount);
+    let (liquidity, amount0, amount1) = client.add_range_order(&maker, &-1200, &-600, &amount, &false);
+
+    assert!(liquidity > 0);
+    assert_eq!(amount0, 0);
+    assert!(amount1 > 0 && amount1 <= amount);
+
+    // Event names the tick where the order is fully filled
+    let (_, topics, data) = env.events().all().last().unwrap();
+    let topic: Symbol = topics.get(0).unwrap().into_val(&env);
+    assert_eq!(topic, Symbol::new(&env, "RangeOrder"));
+    let (lower, upper, token_is_zero, target_tick): (i32, i32, bool, i32) = data.into_val(&env);
+    assert_eq!((lower, upper, token_is_zero, target_tick), (-1200, -600, false, -1200));
+
+    let before = client.get_position(&maker, &-1200, &-600);
+    assert_eq!(before.amount0, 0);
+    assert!(before.amount1 > 0);
+
+    // Sell token0 until the price is below the whole range
+    let swapper = Address::generate(&env);
+    let swap_in: i128 = 40_000_000_000;
+    common::mint_tokens(&env, &token0, &swapper, swap_in);
+    client.swap(&swapper, &token0, &swap_in, &0, &None, &0);
+    assert!(client.get_pool_state().current_tick < -1200);
+
+    let after = client.get_position(&maker, &-1200, &-600);
+    assert_eq!(after.liquidity, liquidity);
+    assert_eq!(after.amount1, 0);
+    assert!(after.amount0 > 0);
+}
+
+#[test]
+fn test_range_order_above_price_holds_token0() {
+    let env = Env::default();
+    let (client, token0, _) = setup(&env);
+
+    let maker = Address::generate(&env);
+    common::mint_tokens(&env, &token0, &maker, 1_000_000_000);
+    let (_, amount0, amount1) = client.add_range_order(&maker, &600, &1200, &1_000_000_000, &true);
+
+    assert!(amount0 > 0);
+    assert_eq!(amount1, 0);
+}
+
+#[test]
+fn test_range_order_must_be_one_sided() {
+    let env = Env::default();
+    let (client, token0, token1) = setup(&env);
+
+    let maker = Address::generate(&env);
+    common::mint_tokens(&env, &token0, &maker, 1_000_000_000);
+    common::mint_tokens(&env, &token1, &maker, 1_000_000_000);
+
+    // Straddles the price
+    assert_eq!(
+        client.try_add_range_order(&maker, &-600, &600, &1_000_000, &true),
+        Err(Ok(PoolError::InvalidRangeOrder))
+    );
+    // Wrong side for the token
+    assert_eq!(
+        client.try_add_range_order(&maker, &-1200, &-600, &1_000_000, &true),
+        Err(Ok(PoolError::InvalidRangeOrder))
+    );
+    assert_eq!(
+        client.try_add_range_order(&maker, &600, &1200, &1_000_000, &false),
+        Err(Ok(PoolError::InvalidRangeOrder))
+    );
+    // Lower bound on the current tick is still in range
+    assert_eq!(
+        client.try_add_range_order(&maker, &0, &600, &1_000_000, &true),
+        Err(Ok(PoolError::InvalidRangeOrder))
+    );
+}
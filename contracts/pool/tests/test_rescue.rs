@@ -0,0 +1,75 @@
+mod common;
+
+use belugaswap_pool::{BelugaPoolClient, PoolError};
+use soroban_sdk::{testutils::Address as _, token::TokenClient, Address, Env};
+
+const LIQUIDITY_AMOUNT: i128 = 10_000_000_000;
+
+/// Pool behind a MockFactory with one LP position over [-600, 600];
+/// returns (pool, factory admin, creator)
+fn setup(env: &Env) -> (BelugaPoolClient<'_>, Address, Address) {
+    let (client, admin, _, creator) = common::setup_admin_pool(
+        env,
+        common::DEFAULT_FEE_BPS,
+        common::DEFAULT_CREATOR_FEE_BPS,
+        LIQUIDITY_AMOUNT,
+    );
+    (client, admin, creator)
+}
+
+#[test]
+fn test_rescue_foreign_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, creator) = setup(&env);
+    let (reserve0, reserve1) = client.get_reserves();
+
+    // A third token sent to the pool by mistake
+    let stray = common::create_token(&env, &creator);
+    common::mint_tokens(&env, &stray, &client.address, 5_000_000);
+
+    let to = Address::generate(&env);
+    assert_eq!(client.rescue_token(&admin, &stray, &to), 5_000_000);
+
+    let stray_client = TokenClient::new(&env, &stray);
+    assert_eq!(stray_client.balance(&to), 5_000_000);
+    assert_eq!(stray_client.balance(&client.address), 0);
+    assert_eq!(client.get_reserves(), (reserve0, reserve1));
+
+    // Nothing left: a second rescue moves zero
+    assert_eq!(client.rescue_token(&admin, &stray, &to), 0);
+}
+
+#[test]
+fn test_rescue_rejects_pool_tokens() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _) = setup(&env);
+    let (token0, token1) = client.get_tokens();
+    let reserves = client.get_reserves();
+    let to = Address::generate(&env);
+
+    let result = client.try_rescue_token(&admin, &token0, &to);
+    assert_eq!(result.err(), Some(Ok(PoolError::ReserveToken)));
+    let result = client.try_rescue_token(&admin, &token1, &to);
+    assert_eq!(result.err(), Some(Ok(PoolError::ReserveToken)));
+
+    assert_eq!(client.get_reserves(), reserves);
+    assert_eq!(TokenClient::new(&env, &token0).balance(&to), 0);
+}
+
+#[test]
+fn test_rescue_rejects_non_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, creator) = setup(&env);
+    let stray = common::create_token(&env, &creator);
+    common::mint_tokens(&env, &stray, &client.address, 5_000_000);
+
+    let result = client.try_rescue_token(&creator, &stray, &creator);
+    assert_eq!(result.err(), Some(Ok(PoolError::NotFactoryAdmin)));
+    assert_eq!(TokenClient::new(&env, &stray).balance(&client.address), 5_000_000);
+}
@@ -0,0 +1,98 @@
+mod common;
+
+use belugaswap_math::get_sqrt_ratio_at_tick;
+use belugaswap_pool::BelugaPoolClient;
+use soroban_sdk::{testutils::{Address as _, Ledger}, Address, Env};
+
+const START_TS: u64 = 1_700_000_000;
+
+/// Default pool at tick 0 with positions over [-600, 600] and [600, 1200],
+/// clock at START_TS
+fn setup(env: &Env) -> BelugaPoolClient<'_> {
+    env.mock_all_auths();
+    env.ledger().set_timestamp(START_TS);
+
+    let (client, _, _, _, token_a, token_b) = common::setup_pool(env);
+    let lp = Address::generate(env);
+    let amt: i128 = 10_000_000_000;
+    common::mint_tokens(env, &token_a, &lp, amt * 2);
+    common::mint_tokens(env, &token_b, &lp, amt * 2);
+    client.add_liquidity(&lp, &-600, &600, &amt, &amt, &0, &0);
+    client.add_liquidity(&lp, &600, &1200, &amt, &amt, &0, &0);
+    client
+}
+
+/// Swap until the price reaches `tick`
+fn swap_to_tick(env: &Env, client: &BelugaPoolClient, tick: i32) {
+    let state = client.get_pool_state();
+    let token_in = if tick < state.current_tick { state.token0 } else { state.token1 };
+    let swapper = Address::generate(env);
+    common::mint_tokens(env, &token_in, &swapper, 100_000_000_000);
+    client.swap(&swapper, &token_in, &100_000_000_000, &0, &Some(get_sqrt_ratio_at_tick(tick)), &0);
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger().set_timestamp(env.ledger().timestamp() + seconds);
+}
+
+#[test]
+fn test_seconds_inside_accrues_only_in_range() {
+    let env = Env::default();
+    let client = setup(&env);
+
+    advance(&env, 100);
+    assert_eq!(client.get_seconds_inside(&-600, &600), 100);
+    assert_eq!(client.get_seconds_inside(&600, &1200), 0);
+
+    // Up through 600 into the upper range
+    swap_to_tick(&env, &client, 900);
+    assert!(client.get_pool_state().current_tick >= 600);
+    advance(&env, 50);
+    assert_eq!(client.get_seconds_inside(&-600, &600), 100);
+    assert_eq!(client.get_seconds_inside(&600, &1200), 50);
+
+    // Back down into the lower range
+    swap_to_tick(&env, &client, 300);
+    assert!(client.get_pool_state().current_tick < 600);
+    advance(&env, 30);
+    assert_eq!(client.get_seconds_inside(&-600, &600), 130);
+    assert_eq!(client.get_seconds_inside(&600, &1200), 50);
+
+    // The outer range was in range throughout
+    assert_eq!(client.get_seconds_inside(&-600, &1200), 180);
+}
+
+#[test]
+fn test_seconds_inside_starts_when_range_is_initialized() {
+    let env = Env::default();
+    let client = setup(&env);
+
+    advance(&env, 1_000);
+    let state = client.get_pool_state();
+    let lp = Address::generate(&env);
+    common::mint_tokens(&env, &state.token0, &lp, 1_000_000_000);
+    common::mint_tokens(&env, &state.token1, &lp, 1_000_000_000);
+    client.add_liquidity(&lp, &-1200, &-600, &1_000_000_000, &1_000_000_000, &0, &0);
+
+    // -600 was initialized earlier, so only differences between readings
+    // are meaningful
+    let start = client.get_seconds_inside(&-1200, &-600);
+
+    // Above the range: nothing accrues until the price enters
+    advance(&env, 20);
+    assert_eq!(client.get_seconds_inside(&-1200, &-600), start);
+
+    swap_to_tick(&env, &client, -900);
+    advance(&env, 15);
+    assert_eq!(client.get_seconds_inside(&-1200, &-600).wrapping_sub(start), 15);
+}
+
+#[test]
+fn test_seconds_inside_uninitialized_range_is_zero() {
+    let env = Env::default();
+    let client = setup(&env);
+
+    advance(&env, 100);
+    assert_eq!(client.get_seconds_inside(&-1800, &1800), 0);
+    assert_eq!(client.get_seconds_inside(&600, &-600), 0);
+}
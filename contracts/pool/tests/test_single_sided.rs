@@ -0,0 +1,103 @@
+mod common;
+
+use belugaswap_pool::{BelugaPoolClient, PoolError};
+use soroban_sdk::{testutils::Address as _, token::TokenClient, Address, Env};
+
+const DEPOSIT: i128 = 1_000_000_000;
+
+/// Default pool with deep liquidity over [-600, 600]
+fn setup(env: &Env) -> BelugaPoolClient<'_> {
+    env.mock_all_auths();
+
+    let (client, _, _, _, token_a, token_b) = common::setup_pool(env);
+    let lp = Address::generate(env);
+    let amt: i128 = 100_000_000_000;
+    common::mint_tokens(env, &token_a, &lp, amt);
+    common::mint_tokens(env, &token_b, &lp, amt);
+    client.add_liquidity(&lp, &-600, &600, &amt, &amt, &0, &0);
+    client
+}
+
+/// Deposit `DEPOSIT` of `token_in` alone and return the owner's leftover
+/// (token0, token1) balances
+fn deposit(env: &Env, client: &BelugaPoolClient, token_in: &Address, lower: i32, upper: i32) -> (i128, i128) {
+    let state = client.get_pool_state();
+    let owner = Address::generate(env);
+    common::mint_tokens(env, token_in, &owner, DEPOSIT);
+
+    let (liquidity, amount0, amount1) =
+        client.add_liquidity_single_sided(&owner, &lower, &upper, token_in, &DEPOSIT, &0);
+    assert!(liquidity > 0);
+    assert_eq!(client.get_position(&owner, &lower, &upper).liquidity, liquidity);
+    assert!(amount0 > 0 || amount1 > 0);
+
+    (
+        TokenClient::new(env, &state.token0).balance(&owner),
+        TokenClient::new(env, &state.token1).balance(&owner),
+    )
+}
+
+#[test]
+fn test_single_sided_token0_uses_nearly_everything() {
+    let env = Env::default();
+    let client = setup(&env);
+    let token0 = client.get_pool_state().token0;
+
+    let (left0, left1) = deposit(&env, &client, &token0, -1200, 1200);
+    assert!(left0 + left1 <= DEPOSIT / 10_000, "leftover ({}, {})", left0, left1);
+}
+
+#[test]
+fn test_single_sided_token1_uses_nearly_everything() {
+    let env = Env::default();
+    let client = setup(&env);
+    let token1 = client.get_pool_state().token1;
+
+    let (left0, left1) = deposit(&env, &client, &token1, -1200, 1200);
+    assert!(left0 + left1 <= DEPOSIT / 10_000, "leftover ({}, {})", left0, left1);
+}
+
+#[test]
+fn test_single_sided_skewed_range() {
+    let env = Env::default();
+    let client = setup(&env);
+    let token0 = client.get_pool_state().token0;
+
+    // Mostly above the price, so most of the token0 is kept
+    let (left0, left1) = deposit(&env, &client, &token0, -120, 1800);
+    assert!(left0 + left1 <= DEPOSIT / 10_000, "leftover ({}, {})", left0, left1);
+}
+
+#[test]
+fn test_single_sided_range_on_input_side_skips_swap() {
+    let env = Env::default();
+    let client = setup(&env);
+    let state = client.get_pool_state();
+
+    // Entirely above the price: only token0 is needed
+    let (left0, left1) = deposit(&env, &client, &state.token0, 600, 1200);
+    assert!(left0 <= 1);
+    assert_eq!(left1, 0);
+    assert_eq!(client.get_pool_state().sqrt_price_x64, state.sqrt_price_x64);
+}
+
+#[test]
+fn test_single_sided_rejects_bad_input() {
+    let env = Env::default();
+    let client = setup(&env);
+    let owner = Address::generate(&env);
+    let token0 = client.get_pool_state().token0;
+
+    assert_eq!(
+        client.try_add_liquidity_single_sided(&owner, &-600, &600, &Address::generate(&env), &DEPOSIT, &0),
+        Err(Ok(PoolError::InvalidToken))
+    );
+    assert_eq!(
+        client.try_add_liquidity_single_sided(&owner, &600, &-600, &token0, &DEPOSIT, &0),
+        Err(Ok(PoolError::InvalidTickRange))
+    );
+    assert_eq!(
+        client.try_add_liquidity_single_sided(&owner, &-600, &600, &token0, &0, &0),
+        Err(Ok(PoolError::InvalidLiquidityAmount))
+    );
+}
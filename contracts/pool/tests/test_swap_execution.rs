@@ -1,9 +1,9 @@
 mod common;
 
-use soroban_sdk::{testutils::Address as _, Address, Env};
+use belugaswap_pool::PoolError;
+use soroban_sdk::{testutils::{Address as _, Events}, Address, Env, IntoVal, Symbol, Vec};
 
 #[test]
-#[should_panic(expected = "invalid token")]
 fn test_swap_invalid_token() {
     let env = Env::default();
     env.mock_all_auths();
@@ -14,18 +14,19 @@ fn test_swap_invalid_token() {
     // Create invalid token
     let invalid_token = common::create_token(&env, &creator);
     
-    client.swap(
+    let result = client.try_swap(
         &sender,
         &invalid_token,
         &1_000_000,
         &0,
+        &None,
         &0,
     );
+    assert_eq!(result.err(), Some(Ok(PoolError::InvalidToken)));
 }
 
 #[test]
-#[should_panic(expected = "no liquidity available")]
-fn test_swap_slippage_exceeded() {
+fn test_swap_no_liquidity() {
     let env = Env::default();
     env.mock_all_auths();
     
@@ -35,16 +36,430 @@ fn test_swap_slippage_exceeded() {
     
     // Try to swap with no liquidity but high slippage requirement
     // This will fail with no liquidity available
-    client.swap(
+    let result = client.try_swap(
         &sender,
         &state.token0,
         &1_000_000,
         &1_000_000, // Expecting at least same amount out (impossible)
+        &None,
         &0,
     );
+    assert_eq!(result.err(), Some(Ok(PoolError::NoLiquidity)));
 }
 
 // Note: Full swap tests with liquidity require:
 // 1. add_liquidity to be tested first
 // 2. Token minting and approvals
-// 3. These are integration tests that would go in a separate file
\ No newline at end of file
+// 3. These are integration tests that would go in a separate file
+/// Seed the default pool with two overlapping ranges around the current price.
+fn seed_liquidity(env: &Env, client: &belugaswap_pool::BelugaPoolClient, token_a: &Address, token_b: &Address) {
+    let lp = Address::generate(env);
+    let amt: i128 = 100_000_000_000;
+    common::mint_tokens(env, token_a, &lp, amt * 2);
+    common::mint_tokens(env, token_b, &lp, amt * 2);
+    client.add_liquidity(&lp, &-600, &600, &amt, &amt, &0, &0);
+    client.add_liquidity(&lp, &-1800, &1800, &amt, &amt, &0, &0);
+}
+
+fn assert_tick_matches_price(tick: i32, sqrt_price_x64: u128) {
+    let lower = belugaswap_math::get_sqrt_ratio_at_tick(tick);
+    let upper = belugaswap_math::get_sqrt_ratio_at_tick(tick + 1);
+    assert!(
+        lower <= sqrt_price_x64 && sqrt_price_x64 < upper,
+        "tick {} does not bracket sqrt price {}",
+        tick,
+        sqrt_price_x64
+    );
+}
+
+#[test]
+fn test_swap_final_tick_across_ticks() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, _, token_a, token_b) = common::setup_pool(&env);
+    seed_liquidity(&env, &client, &token_a, &token_b);
+    let state = client.get_pool_state();
+
+    // Large enough to move the price through the inner range boundary.
+    let swapper = Address::generate(&env);
+    let amt_in: i128 = 200_000_000_000;
+    common::mint_tokens(&env, &state.token0, &swapper, amt_in);
+    let res = client.swap(&swapper, &state.token0, &amt_in, &0, &None, &0);
+
+    let after = client.get_pool_state();
+    assert!(res.current_tick < -600);
+    assert_eq!(res.current_tick, after.current_tick);
+    assert_tick_matches_price(after.current_tick, after.sqrt_price_x64);
+}
+
+#[test]
+fn test_swap_final_tick_within_range() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, _, token_a, token_b) = common::setup_pool(&env);
+    seed_liquidity(&env, &client, &token_a, &token_b);
+    let state = client.get_pool_state();
+
+    // Stops inside the inner range, away from any initialized tick.
+    let swapper = Address::generate(&env);
+    let amt_in: i128 = 5_000_000_000;
+    common::mint_tokens(&env, &state.token1, &swapper, amt_in);
+    let res = client.swap(&swapper, &state.token1, &amt_in, &0, &None, &0);
+
+    let after = client.get_pool_state();
+    assert!(res.current_tick > 0 && res.current_tick < 600);
+    assert_eq!(res.current_tick, after.current_tick);
+    assert_tick_matches_price(after.current_tick, after.sqrt_price_x64);
+}
+
+#[test]
+fn test_swap_slippage_exceeded() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, _, token_a, token_b) = common::setup_pool(&env);
+    seed_liquidity(&env, &client, &token_a, &token_b);
+    let state = client.get_pool_state();
+
+    let swapper = Address::generate(&env);
+    let amt_in: i128 = 1_000_000_000;
+    common::mint_tokens(&env, &state.token0, &swapper, amt_in);
+
+    // Fee alone makes a 1:1 output impossible
+    let result = client.try_swap(&swapper, &state.token0, &amt_in, &amt_in, &None, &0);
+    assert_eq!(result.err(), Some(Ok(PoolError::SlippageExceeded)));
+
+    // Nothing moved
+    let after = client.get_pool_state();
+    assert_eq!(after.sqrt_price_x64, state.sqrt_price_x64);
+}
+
+#[test]
+fn test_remove_liquidity_slippage_exceeded() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, _, token_a, token_b) = common::setup_pool(&env);
+    let lp = Address::generate(&env);
+    let amt: i128 = 100_000_000_000;
+    common::mint_tokens(&env, &token_a, &lp, amt);
+    common::mint_tokens(&env, &token_b, &lp, amt);
+    let (liquidity, amount0, amount1) = client.add_liquidity(&lp, &-600, &600, &amt, &amt, &0, &0);
+
+    let result = client.try_remove_liquidity(&lp, &-600, &600, &liquidity, &(amount0 + 1), &amount1);
+    assert_eq!(result, Err(Ok(PoolError::SlippageExceeded)));
+}
+
+#[test]
+fn test_swap_price_limit_reports_remaining_input() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, _, token_a, token_b) = common::setup_pool(&env);
+    seed_liquidity(&env, &client, &token_a, &token_b);
+    let state = client.get_pool_state();
+
+    // Tight limit: price may only fall to tick -60
+    let limit = belugaswap_math::get_sqrt_ratio_at_tick(-60);
+    let swapper = Address::generate(&env);
+    let amt_in: i128 = 100_000_000_000;
+    common::mint_tokens(&env, &state.token0, &swapper, amt_in);
+    let res = client.swap(&swapper, &state.token0, &amt_in, &0, &Some(limit), &0);
+
+    assert!(res.amount_in_remaining > 0, "limit should leave input unspent");
+    assert_eq!(res.amount_in + res.amount_in_remaining, amt_in);
+    assert!(res.sqrt_price_x64 >= limit);
+
+    // The unspent part is refunded to the swapper
+    let balance = soroban_sdk::token::TokenClient::new(&env, &state.token0).balance(&swapper);
+    assert_eq!(balance, res.amount_in_remaining);
+}
+
+#[test]
+fn test_swap_full_fill_has_no_remaining_input() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, _, token_a, token_b) = common::setup_pool(&env);
+    seed_liquidity(&env, &client, &token_a, &token_b);
+    let state = client.get_pool_state();
+
+    let swapper = Address::generate(&env);
+    let amt_in: i128 = 1_000_000_000;
+    common::mint_tokens(&env, &state.token0, &swapper, amt_in);
+    let res = client.swap(&swapper, &state.token0, &amt_in, &0, &None, &0);
+
+    assert_eq!(res.amount_in, amt_in);
+    assert_eq!(res.amount_in_remaining, 0);
+}
+
+#[test]
+fn test_price_limit_near_minimum_is_a_real_limit() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, _, token_a, token_b) = common::setup_pool(&env);
+    seed_liquidity(&env, &client, &token_a, &token_b);
+    let state = client.get_pool_state();
+
+    let amt_in: i128 = 100_000_000_000;
+    let unlimited = client.preview_swap(&state.token0, &amt_in, &0, &None);
+    assert!(unlimited.is_valid);
+    assert_eq!(unlimited.amount_in_used, amt_in);
+
+    // A limit at the minimum price is never reached
+    let min_price = Some(belugaswap_math::constants::MIN_SQRT_PRICE);
+    let near_min = client.preview_swap(&state.token0, &amt_in, &0, &min_price);
+    assert_eq!(near_min.amount_in_used, unlimited.amount_in_used);
+
+    // A tight limit stops the swap early
+    let tight = Some(belugaswap_math::get_sqrt_ratio_at_tick(-300));
+    let limited = client.preview_swap(&state.token0, &amt_in, &0, &tight);
+    assert!(limited.is_valid);
+    assert!(limited.amount_in_used < unlimited.amount_in_used);
+
+    let swapper = Address::generate(&env);
+    common::mint_tokens(&env, &state.token0, &swapper, amt_in);
+    let res = client.swap(&swapper, &state.token0, &amt_in, &0, &min_price, &0);
+    assert_eq!(res.amount_in, amt_in);
+    assert_eq!(res.amount_in_remaining, 0);
+    assert_eq!(res.amount_out, unlimited.amount_out_expected);
+}
+
+#[test]
+fn test_zero_price_limit_is_not_no_limit() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, _, token_a, token_b) = common::setup_pool(&env);
+    seed_liquidity(&env, &client, &token_a, &token_b);
+    let state = client.get_pool_state();
+
+    let swapper = Address::generate(&env);
+    let amt_in: i128 = 1_000_000_000;
+    common::mint_tokens(&env, &state.token1, &swapper, amt_in);
+
+    // Selling token1 raises the price, so a limit of 0 is already passed
+    assert!(!client.preview_swap(&state.token1, &amt_in, &0, &Some(0)).is_valid);
+    assert!(client.try_swap(&swapper, &state.token1, &amt_in, &0, &Some(0), &0).is_err());
+
+    let res = client.swap(&swapper, &state.token1, &amt_in, &0, &None, &0);
+    assert_eq!(res.amount_in, amt_in);
+    assert!(res.amount_out > 0);
+}
+
+#[test]
+fn test_wrong_side_price_limit_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, _, token_a, token_b) = common::setup_pool(&env);
+    seed_liquidity(&env, &client, &token_a, &token_b);
+    let state = client.get_pool_state();
+
+    let swapper = Address::generate(&env);
+    let amt_in: i128 = 1_000_000_000;
+    common::mint_tokens(&env, &state.token0, &swapper, amt_in);
+    common::mint_tokens(&env, &state.token1, &swapper, amt_in);
+
+    // Selling token0 lowers the price: a limit above or at it is already passed
+    let above = state.sqrt_price_x64 + (1 << 60);
+    for (token_in, limit) in [
+        (&state.token0, above),
+        (&state.token0, state.sqrt_price_x64),
+        (&state.token1, state.sqrt_price_x64 - (1 << 60)),
+        (&state.token1, state.sqrt_price_x64),
+    ] {
+        let preview = client.preview_swap(token_in, &amt_in, &0, &Some(limit));
+        assert!(!preview.is_valid);
+        assert_eq!(preview.error_message, Some(Symbol::new(&env, "BAD_LIMIT")));
+        let result = client.try_swap(&swapper, token_in, &amt_in, &0, &Some(limit), &0);
+        assert_eq!(result.err(), Some(Ok(PoolError::InvalidPriceLimit)));
+    }
+
+    // Nothing moved, and a limit on the right side still swaps
+    assert_eq!(client.get_pool_state().sqrt_price_x64, state.sqrt_price_x64);
+    let res = client.swap(&swapper, &state.token1, &amt_in, &0, &Some(above), &0);
+    assert!(res.amount_out > 0);
+}
+
+/// Three overlapping positions with asymmetric `liquidity_net`:
+/// tick -600 nets `l1 - l2`, tick -1200 nets `+l2`. Returns (l1, l2, l3).
+fn seed_asymmetric_liquidity(
+    env: &Env,
+    client: &belugaswap_pool::BelugaPoolClient,
+    token_a: &Address,
+    token_b: &Address,
+) -> (i128, i128, i128) {
+    let lp = Address::generate(env);
+    common::mint_tokens(env, token_a, &lp, 1_000_000_000_000);
+    common::mint_tokens(env, token_b, &lp, 1_000_000_000_000);
+    let (l1, _, _) = client.add_liquidity(&lp, &-600, &600, &50_000_000_000, &50_000_000_000, &0, &0);
+    let (l2, _, _) = client.add_liquidity(&lp, &-1200, &-600, &80_000_000_000, &80_000_000_000, &0, &0);
+    let (l3, _, _) = client.add_liquidity(&lp, &-1800, &1800, &100_000_000_000, &100_000_000_000, &0, &0);
+    // The first deposit also carries the pool-owned locked liquidity
+    (l1 + belugaswap_math::constants::MINIMUM_LOCKED_LIQUIDITY, l2, l3)
+}
+
+fn swap_to_tick(env: &Env, client: &belugaswap_pool::BelugaPoolClient, zero_for_one: bool, limit_tick: i32) {
+    let state = client.get_pool_state();
+    let token_in = if zero_for_one { state.token0 } else { state.token1 };
+    let swapper = Address::generate(env);
+    let amt_in: i128 = 500_000_000_000;
+    common::mint_tokens(env, &token_in, &swapper, amt_in);
+    let limit = belugaswap_math::get_sqrt_ratio_at_tick(limit_tick);
+    client.swap(&swapper, &token_in, &amt_in, &0, &Some(limit), &0);
+}
+
+#[test]
+fn test_crossing_asymmetric_liquidity_net_both_ways() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, _, token_a, token_b) = common::setup_pool(&env);
+    let (l1, l2, l3) = seed_asymmetric_liquidity(&env, &client, &token_a, &token_b);
+    assert_eq!(client.get_pool_state().liquidity, l1 + l3);
+
+    // Down through -600 (l1 leaves, l2 enters) and -1200 (l2 leaves)
+    swap_to_tick(&env, &client, true, -1500);
+    let state = client.get_pool_state();
+    assert!(state.current_tick < -1200);
+    assert_eq!(state.liquidity, l3);
+
+    // Back up through both ticks
+    swap_to_tick(&env, &client, false, 300);
+    let state = client.get_pool_state();
+    assert!(state.current_tick >= 0);
+    assert_eq!(state.liquidity, l1 + l3);
+
+    // And down again, stopping between the two ticks
+    swap_to_tick(&env, &client, true, -900);
+    assert_eq!(client.get_pool_state().liquidity, l2 + l3);
+}
+
+#[test]
+fn test_crossing_when_price_limit_lands_on_tick() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, _, token_a, token_b) = common::setup_pool(&env);
+    let (l1, l2, l3) = seed_asymmetric_liquidity(&env, &client, &token_a, &token_b);
+
+    swap_to_tick(&env, &client, true, -900);
+    assert_eq!(client.get_pool_state().liquidity, l2 + l3);
+
+    // Rising exactly onto tick -600 crosses it
+    swap_to_tick(&env, &client, false, -600);
+    let state = client.get_pool_state();
+    assert_eq!(state.sqrt_price_x64, belugaswap_math::get_sqrt_ratio_at_tick(-600));
+    assert_eq!(state.current_tick, -600);
+    assert_eq!(state.liquidity, l1 + l3);
+
+    // Falling back from exactly on the tick crosses it again
+    swap_to_tick(&env, &client, true, -900);
+    assert_eq!(client.get_pool_state().liquidity, l2 + l3);
+
+    // Falling exactly onto tick -1200 leaves the pool just below it
+    swap_to_tick(&env, &client, true, -1200);
+    let state = client.get_pool_state();
+    assert_eq!(state.current_tick, -1201);
+    assert_eq!(state.liquidity, l3);
+
+    // Rising from exactly on the tick picks l2 back up
+    swap_to_tick(&env, &client, false, -900);
+    assert_eq!(client.get_pool_state().liquidity, l2 + l3);
+}
+
+#[test]
+fn test_crossing_from_just_above_initialized_tick() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, _, token_a, token_b) = common::setup_pool(&env);
+    let (l1, l2, l3) = seed_asymmetric_liquidity(&env, &client, &token_a, &token_b);
+
+    // Stop inside the spacing bucket that starts at tick -600
+    swap_to_tick(&env, &client, true, -590);
+    let state = client.get_pool_state();
+    assert_eq!(state.current_tick, -590);
+    assert_eq!(state.liquidity, l1 + l3);
+
+    // The next leftward swap must still cross -600
+    swap_to_tick(&env, &client, true, -900);
+    assert_eq!(client.get_pool_state().liquidity, l2 + l3);
+}
+
+#[test]
+fn test_swap_spans_liquidity_gap() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, _, token_a, token_b) = common::setup_pool(&env);
+    let lp = Address::generate(&env);
+    common::mint_tokens(&env, &token_a, &lp, 1_000_000_000_000);
+    common::mint_tokens(&env, &token_b, &lp, 1_000_000_000_000);
+    let (l_mid, _, _) = client.add_liquidity(&lp, &-600, &600, &10_000_000_000, &10_000_000_000, &0, &0);
+    // Empty gaps [600, 1200] and [-1200, -600] on either side
+    let (l_up, _, _) = client.add_liquidity(&lp, &1200, &1800, &50_000_000_000, &50_000_000_000, &0, &0);
+    let (l_down, _, _) = client.add_liquidity(&lp, &-1800, &-1200, &50_000_000_000, &50_000_000_000, &0, &0);
+    assert_eq!(l_mid, client.get_position(&lp, &-600, &600).liquidity);
+
+    // Up through the gap into the upper range
+    swap_to_tick(&env, &client, false, 1500);
+    let state = client.get_pool_state();
+    assert_eq!(state.current_tick, 1500);
+    assert_eq!(state.liquidity, l_up);
+    assert_tick_matches_price(state.current_tick, state.sqrt_price_x64);
+
+    // Down across both gaps into the lower range
+    swap_to_tick(&env, &client, true, -1500);
+    let state = client.get_pool_state();
+    assert_eq!(state.current_tick, -1500);
+    assert_eq!(state.liquidity, l_down);
+    assert_tick_matches_price(state.current_tick, state.sqrt_price_x64);
+}
+
+/// `(tick, liquidity)` of every TickCrossed event from the last invocation
+fn crossed_ticks(env: &Env, client: &belugaswap_pool::BelugaPoolClient) -> Vec<(i32, i128)> {
+    let mut crossed = Vec::new(env);
+    for (contract, topics, data) in env.events().all().iter() {
+        let topic: Symbol = topics.get(0).unwrap().into_val(env);
+        if contract == client.address && topic == Symbol::new(env, "TickCrossed") {
+            crossed.push_back(data.into_val(env));
+        }
+    }
+    crossed
+}
+
+#[test]
+fn test_swap_emits_one_cross_event_per_tick() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, _, token_a, token_b) = common::setup_pool(&env);
+    let lp = Address::generate(&env);
+    common::mint_tokens(&env, &token_a, &lp, 1_000_000_000_000);
+    common::mint_tokens(&env, &token_b, &lp, 1_000_000_000_000);
+    client.add_liquidity(&lp, &-600, &600, &10_000_000_000, &10_000_000_000, &0, &0);
+    let l_mid = client.get_pool_state().liquidity;
+    let (l_up, _, _) = client.add_liquidity(&lp, &1200, &1800, &50_000_000_000, &50_000_000_000, &0, &0);
+    let (l_down, _, _) = client.add_liquidity(&lp, &-1800, &-1200, &50_000_000_000, &50_000_000_000, &0, &0);
+
+    // Down out of the middle range, then across the gap into the lower one
+    swap_to_tick(&env, &client, true, -1500);
+    assert_eq!(crossed_ticks(&env, &client), soroban_sdk::vec![&env, (-600, 0), (-1200, l_down)]);
+
+    // Back up through all four boundaries between -1500 and 1500
+    swap_to_tick(&env, &client, false, 1500);
+    assert_eq!(
+        crossed_ticks(&env, &client),
+        soroban_sdk::vec![&env, (-1200, 0), (-600, l_mid), (600, 0), (1200, l_up)]
+    );
+
+    // A swap that stays inside one range crosses nothing
+    swap_to_tick(&env, &client, false, 1600);
+    assert_eq!(crossed_ticks(&env, &client).len(), 0);
+    assert_eq!(client.get_pool_state().liquidity, l_up);
+}
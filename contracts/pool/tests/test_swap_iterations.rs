@@ -0,0 +1,100 @@
+mod common;
+
+use belugaswap_math::constants::{DEFAULT_SWAP_ITERATIONS, MAX_SWAP_ITERATIONS};
+use belugaswap_pool::{BelugaPoolClient, PoolError};
+use soroban_sdk::{testutils::Address as _, token::TokenClient, Address, Env};
+
+const POSITION_AMOUNT: i128 = 1_000_000_000;
+const POSITIONS: i32 = 10;
+
+/// Pool behind a MockFactory with a position over [-60, 60] and token1-only
+/// bands [-60(k+1), -60k] below it, so selling token0 crosses an initialized
+/// tick every 60 ticks
+fn setup(env: &Env) -> (BelugaPoolClient<'_>, Address, Address) {
+    let (client, admin, _) =
+        common::deploy_admin_pool(env, common::DEFAULT_FEE_BPS, common::DEFAULT_CREATOR_FEE_BPS);
+
+    let state = client.get_pool_state();
+    let lp = Address::generate(env);
+    common::mint_tokens(env, &state.token0, &lp, POSITION_AMOUNT * POSITIONS as i128);
+    common::mint_tokens(env, &state.token1, &lp, POSITION_AMOUNT * POSITIONS as i128);
+    client.add_liquidity(&lp, &-60, &60, &POSITION_AMOUNT, &POSITION_AMOUNT, &0, &0);
+    for k in 1..POSITIONS {
+        client.add_liquidity(&lp, &(-60 * (k + 1)), &(-60 * k), &0, &POSITION_AMOUNT, &0, &0);
+    }
+
+    let swapper = Address::generate(env);
+    (client, admin, swapper)
+}
+
+#[test]
+fn test_swap_over_iteration_budget_reverts() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, swapper) = setup(&env);
+    assert_eq!(client.get_pool_state().max_swap_iterations, DEFAULT_SWAP_ITERATIONS);
+    client.set_max_swap_iterations(&admin, &3);
+
+    let state = client.get_pool_state();
+    let amount: i128 = 6_000_000_000;
+    common::mint_tokens(&env, &state.token0, &swapper, amount);
+
+    // The preview stops at the budget and reports a partial fill
+    let preview = client.preview_swap(&state.token0, &amount, &0, &None);
+    assert!(
+        preview.amount_in_used > 0 && preview.amount_in_used < amount,
+        "preview {}",
+        preview.amount_in_used
+    );
+
+    // Execution fails loudly instead of under-filling
+    let result = client.try_swap(&swapper, &state.token0, &amount, &0, &None, &0);
+    assert_eq!(result.err(), Some(Ok(PoolError::IterationLimit)));
+
+    let after = client.get_pool_state();
+    assert_eq!(after.sqrt_price_x64, state.sqrt_price_x64);
+    assert_eq!(after.liquidity, state.liquidity);
+    assert_eq!(TokenClient::new(&env, &state.token0).balance(&swapper), amount);
+
+    // With the default budget the same swap fills completely
+    client.set_max_swap_iterations(&admin, &DEFAULT_SWAP_ITERATIONS);
+    let res = client.swap(&swapper, &state.token0, &amount, &0, &None, &0);
+    assert_eq!(res.amount_in, amount);
+    assert_eq!(res.amount_in_remaining, 0);
+    assert!(res.current_tick < -60 * 5, "tick {}", res.current_tick);
+}
+
+#[test]
+fn test_swap_within_budget_is_unaffected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, swapper) = setup(&env);
+    client.set_max_swap_iterations(&admin, &3);
+
+    // Stays inside the innermost range: a single step
+    let token0 = client.get_pool_state().token0;
+    common::mint_tokens(&env, &token0, &swapper, 10_000_000);
+    let res = client.swap(&swapper, &token0, &10_000_000, &0, &None, &0);
+    assert_eq!(res.amount_in, 10_000_000);
+    assert!(res.current_tick > -60);
+}
+
+#[test]
+fn test_set_max_swap_iterations_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, swapper) = setup(&env);
+
+    let result = client.try_set_max_swap_iterations(&admin, &0);
+    assert_eq!(result.err(), Some(Ok(PoolError::InvalidSwapIterations)));
+    let result = client.try_set_max_swap_iterations(&admin, &(MAX_SWAP_ITERATIONS + 1));
+    assert_eq!(result.err(), Some(Ok(PoolError::InvalidSwapIterations)));
+    let result = client.try_set_max_swap_iterations(&swapper, &64);
+    assert_eq!(result.err(), Some(Ok(PoolError::NotFactoryAdmin)));
+
+    client.set_max_swap_iterations(&admin, &MAX_SWAP_ITERATIONS);
+    assert_eq!(client.get_pool_state().max_swap_iterations, MAX_SWAP_ITERATIONS);
+}
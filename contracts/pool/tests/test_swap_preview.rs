@@ -1,6 +1,7 @@
 mod common;
 
-use soroban_sdk::Env;
+use belugaswap_math::{constants::{MAX_DEPTH_AMOUNT_IN, MAX_DEPTH_TICKS}, get_sqrt_ratio_at_tick};
+use soroban_sdk::{testutils::Address as _, Address, Env, Symbol, Vec};
 
 #[test]
 fn test_pool_initialized_state() {
@@ -15,4 +16,212 @@ fn test_pool_initialized_state() {
     // Get state to verify
     let state = client.get_pool_state();
     assert_eq!(state.liquidity, 0); // No liquidity yet
-}
\ No newline at end of file
+}
+
+/// Seed a full-width-ish position of `amount` of each token around price 1.0
+fn seed_liquidity(env: &Env, client: &belugaswap_pool::BelugaPoolClient, token_a: &Address, token_b: &Address, amount: i128) {
+    let lp = Address::generate(env);
+    common::mint_tokens(env, token_a, &lp, amount);
+    common::mint_tokens(env, token_b, &lp, amount);
+    client.add_liquidity(&lp, &-6000, &6000, &amount, &amount, &0, &0);
+}
+
+#[test]
+fn test_preview_price_impact_excludes_fee_on_deep_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    seed_liquidity(&env, &client, &token_a, &token_b, 1_000_000_000_000_000);
+    let state = client.get_pool_state();
+    
+    // A tiny trade against deep liquidity: the 30 bps fee must not show up as impact
+    for token_in in [state.token0, state.token1] {
+        let preview = client.preview_swap(&token_in, &1_000_000, &0, &None);
+        assert!(preview.is_valid);
+        assert!(preview.price_impact_bps <= 1, "impact {} bps", preview.price_impact_bps);
+        assert_eq!(preview.fee_paid, 1_000_000 * common::DEFAULT_FEE_BPS as i128 / 10_000);
+    }
+}
+
+#[test]
+fn test_preview_price_impact_grows_with_trade_size() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    seed_liquidity(&env, &client, &token_a, &token_b, 100_000_000_000);
+    let token0 = client.get_pool_state().token0;
+    
+    let small = client.preview_swap(&token0, &10_000_000, &0, &None);
+    let large = client.preview_swap(&token0, &10_000_000_000, &0, &None);
+    
+    assert!(small.price_impact_bps <= 1);
+    assert!(large.price_impact_bps > small.price_impact_bps);
+    // Impact is measured in output units against the fee-adjusted spot output
+    let spot_out = 10_000_000_000i128 * (10_000 - common::DEFAULT_FEE_BPS as i128) / 10_000;
+    let expected_bps = (spot_out - large.amount_out_expected) * 10_000 / spot_out;
+    assert!((large.price_impact_bps - expected_bps).abs() <= 1);
+}
+
+#[test]
+fn test_quote_to_price_stops_one_tick_away() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    seed_liquidity(&env, &client, &token_a, &token_b, 100_000_000_000);
+    let state = client.get_pool_state();
+    
+    // Plenty of input: the quote must stop exactly at the next tick down
+    let target = get_sqrt_ratio_at_tick(state.current_tick - 1);
+    let amount_in_max: i128 = 10_000_000_000;
+    let quote = client.quote_to_price(&amount_in_max, &true, &target);
+    
+    assert!(quote.reached_target);
+    assert_eq!(quote.sqrt_price_x64, target);
+    assert_eq!(quote.current_tick, state.current_tick - 1);
+    assert!(quote.amount_in_used > 0 && quote.amount_in_used < amount_in_max);
+    assert!(quote.amount_out > 0);
+    
+    // Executing the quoted input lands on the same price and output
+    let swapper = Address::generate(&env);
+    common::mint_tokens(&env, &state.token0, &swapper, quote.amount_in_used);
+    let result = client.swap(&swapper, &state.token0, &quote.amount_in_used, &0, &Some(target), &0);
+    assert_eq!(result.amount_out, quote.amount_out);
+    assert_eq!(client.get_pool_state().sqrt_price_x64, target);
+}
+
+#[test]
+fn test_quote_to_price_runs_out_of_input() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    seed_liquidity(&env, &client, &token_a, &token_b, 100_000_000_000);
+    let state = client.get_pool_state();
+    
+    // One tick up needs far more than this
+    let target = get_sqrt_ratio_at_tick(state.current_tick + 1);
+    let quote = client.quote_to_price(&1_000, &false, &target);
+    
+    assert!(!quote.reached_target);
+    assert_eq!(quote.amount_in_used, 1_000);
+    assert!(quote.sqrt_price_x64 > state.sqrt_price_x64 && quote.sqrt_price_x64 < target);
+    
+    // A target behind the current price quotes nothing
+    let behind = client.quote_to_price(&1_000_000, &false, &get_sqrt_ratio_at_tick(state.current_tick - 1));
+    assert_eq!(behind.amount_in_used, 0);
+    assert_eq!(behind.amount_out, 0);
+    assert!(!behind.reached_target);
+}
+
+#[test]
+fn test_preview_skips_to_liquidity_above_price() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _, _, _, token_a, token_b) = common::setup_pool(&env);
+    
+    // Only a range just above the current price: no active liquidity
+    let lp = Address::generate(&env);
+    common::mint_tokens(&env, &token_a, &lp, 1_000_000_000);
+    common::mint_tokens(&env, &token_b, &lp, 1_000_000_000);
+    client.add_liquidity(&lp, &60, &120, &1_000_000_000, &0, &0, &0);
+    let state = client.get_pool_state();
+    assert_eq!(state.liquidity, 0);
+    
+    // One-for-zero moves the price up into the range
+    let up = client.preview_swap(&state.token1, &1_000_000, &0, &None);
+    assert!(up.is_valid, "{:?}", up.error_message);
+    assert_eq!(up.amount_in_used, 1_000_000);
+    assert!(up.amount_out_expected > 0);
+    
+    // Zero-for-one has nothing to reach below the price
+    let down = client.preview_swap(&state.token0, &1_000_000, &0, &None);
+    assert!(!down.is_valid);
+    assert_eq!(down.error_message, Some(Symbol::new(&env, "NO_LIQ")));
+    
+    // Execution agrees with the preview
+    let swapper = Address::generate(&env);
+    common::mint_tokens(&env, &state.token1, &swapper, 1_000_000);
+    let res = client.swap(&swapper, &state.token1, &1_000_000, &0, &None, &0);
+    assert_eq!(res.amount_out, up.amount_out_expected);
+    assert!(res.current_tick >= 60);
+}
+
+#[test]
+fn test_quotes_count_tick_crossings() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    // Deep pool: one wide range, nothing initialized near the price
+    let (deep, _, _, _, token_a, token_b) = common::setup_pool(&env);
+    seed_liquidity(&env, &deep, &token_a, &token_b, 100_000_000_000);
+    
+    // Thin pool: nested ranges with a boundary every 60 ticks
+    let (thin, _, _, _, token_a, token_b) = common::setup_pool(&env);
+    let lp = Address::generate(&env);
+    common::mint_tokens(&env, &token_a, &lp, 10_000_000_000);
+    common::mint_tokens(&env, &token_b, &lp, 10_000_000_000);
+    for k in 1..=10 {
+        thin.add_liquidity(&lp, &(-60 * k), &(60 * k), &1_000_000_000, &1_000_000_000, &0, &0);
+    }
+    
+    // Down to tick -300 crosses -60, -120, -180, -240 and lands on -300
+    let target = get_sqrt_ratio_at_tick(-300);
+    let thin_quote = thin.quote_to_price(&10_000_000_000, &true, &target);
+    let deep_quote = deep.quote_to_price(&10_000_000_000, &true, &target);
+    assert!(thin_quote.reached_target && deep_quote.reached_target);
+    assert_eq!(thin_quote.steps, 5);
+    assert_eq!(deep_quote.steps, 0);
+    
+    // Previews report the same count
+    let thin_token0 = thin.get_pool_state().token0;
+    let preview = thin.preview_swap(&thin_token0, &thin_quote.amount_in_used, &0, &None);
+    assert!(preview.is_valid);
+    assert!(preview.steps >= 4);
+    let deep_token0 = deep.get_pool_state().token0;
+    assert_eq!(deep.preview_swap(&deep_token0, &thin_quote.amount_in_used, &0, &None).steps, 0);
+}
+
+#[test]
+fn test_amounts_to_reach_ticks_follow_liquidity_layout() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    // Nested ranges: liquidity steps down every 60 ticks away from the price
+    let (client, _, _, _, token_a, token_b) = common::setup_pool(&env);
+    let lp = Address::generate(&env);
+    common::mint_tokens(&env, &token_a, &lp, 10_000_000_000);
+    common::mint_tokens(&env, &token_b, &lp, 10_000_000_000);
+    for k in 1..=10 {
+        client.add_liquidity(&lp, &(-60 * k), &(60 * k), &1_000_000_000, &1_000_000_000, &0, &0);
+    }
+    
+    let ticks = Vec::from_array(&env, [-60, -120, -180, -240, -300, -360, -420, -480, -540, -600]);
+    let amounts = client.amounts_to_reach_ticks(&ticks, &true);
+    assert_eq!(amounts.len(), ticks.len());
+    
+    // Cumulative input grows with every tick, but each 60-tick band is
+    // thinner than the last, so costs less to cross
+    let mut previous = (0, i128::MAX);
+    for (tick, amount) in ticks.iter().zip(amounts.iter()) {
+        let band = amount - previous.0;
+        assert!(band > 0 && band < previous.1, "tick {}: band {}", tick, band);
+        previous = (amount, band);
+        
+        // Same as a dry run to that tick
+        let quote = client.quote_to_price(&MAX_DEPTH_AMOUNT_IN, &true, &get_sqrt_ratio_at_tick(tick));
+        assert!(quote.reached_target);
+        assert_eq!(amount, quote.amount_in_used);
+    }
+    
+    // Behind the price is free; past the last range only what it absorbs
+    let edges = client.amounts_to_reach_ticks(&Vec::from_array(&env, [60, 0, -1200]), &true);
+    assert_eq!(edges, Vec::from_array(&env, [0, 0, amounts.last().unwrap()]));
+    
+    // Long lists are cut off
+    let many = Vec::from_array(&env, [-60; 40]);
+    assert_eq!(client.amounts_to_reach_ticks(&many, &true).len(), MAX_DEPTH_TICKS);
+}
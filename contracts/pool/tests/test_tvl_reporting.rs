@@ -0,0 +1,120 @@
+mod common;
+
+use beluga_factory::BelugaFactoryClient;
+use belugaswap_pool::{BelugaPool, BelugaPoolClient, PoolError};
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+const AMOUNT: i128 = 1_000_000_000;
+
+/// Real factory with two pools created through it
+fn setup(env: &Env) -> (BelugaFactoryClient<'_>, Address, [BelugaPoolClient<'_>; 2]) {
+    env.mock_all_auths();
+
+    let (factory, admin) = common::setup_factory(env);
+    let pools = [0, 1].map(|_| common::create_factory_pool(env, &factory, 0).0);
+    (factory, admin, pools)
+}
+
+fn add(env: &Env, pool: &BelugaPoolClient, lp: &Address) -> i128 {
+    let state = pool.get_pool_state();
+    common::mint_tokens(env, &state.token0, lp, AMOUNT);
+    common::mint_tokens(env, &state.token1, lp, AMOUNT);
+    pool.add_liquidity(lp, &-600, &600, &AMOUNT, &AMOUNT, &0, &0).0
+}
+
+fn reserves(pool: &BelugaPoolClient) -> (u128, u128) {
+    let (r0, r1) = pool.get_reserves();
+    (r0 as u128, r1 as u128)
+}
+
+/// Factory TVL of the pool's token0 and token1
+fn tvl(factory: &BelugaFactoryClient, pool: &BelugaPoolClient) -> (u128, u128) {
+    let state = pool.get_pool_state();
+    (factory.get_tvl(&state.token0), factory.get_tvl(&state.token1))
+}
+
+#[test]
+fn test_tvl_tracks_liquidity_across_pools() {
+    let env = Env::default();
+    let (factory, admin, pools) = setup(&env);
+    for pool in &pools {
+        pool.set_tvl_reporting(&admin, &true);
+    }
+
+    // Both pools hold their creator's seed liquidity. Each has its own pair,
+    // so every token's TVL is that pool's balance of it
+    let check = || {
+        for pool in &pools {
+            assert_eq!(tvl(&factory, pool), reserves(pool));
+        }
+    };
+    check();
+
+    let lp = Address::generate(&env);
+    let liquidity = add(&env, &pools[0], &lp);
+    check();
+
+    add(&env, &pools[1], &lp);
+    add(&env, &pools[1], &Address::generate(&env));
+    check();
+
+    pools[0].remove_liquidity(&lp, &-600, &600, &(liquidity / 2), &0, &0);
+    check();
+
+    pools[1].close_position(&lp, &-600, &600);
+    check();
+}
+
+#[test]
+fn test_tvl_reporting_toggle_adds_and_drops_pool() {
+    let env = Env::default();
+    let (factory, admin, pools) = setup(&env);
+
+    // Off by default: deposits are not reported
+    add(&env, &pools[0], &Address::generate(&env));
+    assert_eq!(tvl(&factory, &pools[0]), (0, 0));
+
+    // Enabling reports the balances already in the pool
+    pools[0].set_tvl_reporting(&admin, &true);
+    assert_eq!(tvl(&factory, &pools[0]), reserves(&pools[0]));
+
+    pools[0].set_tvl_reporting(&admin, &false);
+    assert_eq!(tvl(&factory, &pools[0]), (0, 0));
+}
+
+#[test]
+fn test_tvl_reporting_is_best_effort() {
+    let env = Env::default();
+    let (factory, admin, _) = setup(&env);
+
+    // A pool the factory does not know about: reports fail, deposits do not
+    let creator = Address::generate(&env);
+    let pool = BelugaPoolClient::new(&env, &env.register(BelugaPool, ()));
+    pool.initialize(
+        &factory.address,
+        &Address::generate(&env),
+        &creator,
+        &common::create_token(&env, &creator),
+        &common::create_token(&env, &creator),
+        &common::DEFAULT_FEE_BPS,
+        &common::DEFAULT_CREATOR_FEE_BPS,
+        &common::DEFAULT_SQRT_PRICE_X64,
+        &common::DEFAULT_TICK,
+        &common::DEFAULT_TICK_SPACING,
+    );
+    pool.set_tvl_reporting(&admin, &true);
+    add(&env, &pool, &Address::generate(&env));
+    assert_eq!(tvl(&factory, &pool), (0, 0));
+}
+
+#[test]
+fn test_set_tvl_reporting_requires_factory_admin() {
+    let env = Env::default();
+    let (_, _, pools) = setup(&env);
+
+    assert_eq!(
+        pools[0].try_set_tvl_reporting(&Address::generate(&env), &true),
+        Err(Ok(PoolError::NotFactoryAdmin))
+    );
+    assert!(!pools[0].get_pool_state().tvl_reporting);
+}
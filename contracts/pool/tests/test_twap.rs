@@ -0,0 +1,317 @@
+mod common;
+
+use soroban_sdk::{contracttype, testutils::{Address as _, Ledger}, vec, Address, Env};
+
+const START_TS: u64 = 1_700_000_000;
+
+/// Default pool with liquidity around price 1.0, clock at START_TS
+fn setup(env: &Env) -> (belugaswap_pool::BelugaPoolClient<'_>, Address, Address) {
+    env.mock_all_auths();
+    env.ledger().set_timestamp(START_TS);
+    
+    let (client, _, _, _, token_a, token_b) = common::setup_pool(env);
+    
+    let lp = Address::generate(env);
+    let amt: i128 = 100_000_000_000;
+    common::mint_tokens(env, &token_a, &lp, amt);
+    common::mint_tokens(env, &token_b, &lp, amt);
+    client.add_liquidity(&lp, &-600, &600, &amt, &amt, &0, &0);
+    
+    let state = client.get_pool_state();
+    (client, state.token0, state.token1)
+}
+
+fn swap_at(env: &Env, client: &belugaswap_pool::BelugaPoolClient, token_in: &Address, amount: i128, ts: u64) -> i32 {
+    env.ledger().set_timestamp(ts);
+    let swapper = Address::generate(env);
+    common::mint_tokens(env, token_in, &swapper, amount);
+    client.swap(&swapper, token_in, &amount, &0, &None, &0).current_tick
+}
+
+#[test]
+fn test_observe_fresh_pool_is_zero_at_start() {
+    let env = Env::default();
+    let (client, _, _) = setup(&env);
+    
+    let cumulatives = client.observe(&vec![&env, 0u32]);
+    assert_eq!(cumulatives, vec![&env, 0i64]);
+}
+
+#[test]
+fn test_observe_extrapolates_current_tick() {
+    let env = Env::default();
+    let (client, token0, _) = setup(&env);
+    
+    let tick = swap_at(&env, &client, &token0, 5_000_000_000, START_TS + 10);
+    assert!(tick < 0);
+    
+    env.ledger().set_timestamp(START_TS + 110);
+    let cumulatives = client.observe(&vec![&env, 0u32, 100]);
+    
+    // The swapped-to tick has been in effect for the last 100 seconds
+    let mean_tick = (cumulatives.get(0).unwrap() - cumulatives.get(1).unwrap()) / 100;
+    assert_eq!(mean_tick, tick as i64);
+}
+
+#[test]
+fn test_observe_arithmetic_mean_over_window() {
+    let env = Env::default();
+    let (client, token0, token1) = setup(&env);
+    
+    // tick 0 for [0, 100), tick_a for [100, 200), tick_b for [200, 400)
+    let tick_a = swap_at(&env, &client, &token0, 5_000_000_000, START_TS + 100);
+    let tick_b = swap_at(&env, &client, &token1, 8_000_000_000, START_TS + 200);
+    assert!(tick_a < 0 && tick_b > 0);
+    
+    env.ledger().set_timestamp(START_TS + 400);
+    let cumulatives = client.observe(&vec![&env, 0u32, 300, 400]);
+    let now = cumulatives.get(0).unwrap();
+    
+    let expected_total = tick_a as i64 * 100 + tick_b as i64 * 200;
+    assert_eq!(now - cumulatives.get(2).unwrap(), expected_total);
+    
+    // Window starting at +100 covers only the last two ticks
+    assert_eq!((now - cumulatives.get(1).unwrap()) / 300, expected_total / 300);
+}
+
+#[test]
+fn test_observe_interpolates_between_observations() {
+    let env = Env::default();
+    let (client, token0, _) = setup(&env);
+    
+    let tick_a = swap_at(&env, &client, &token0, 5_000_000_000, START_TS + 100);
+    swap_at(&env, &client, &token0, 1_000_000_000, START_TS + 200);
+    
+    env.ledger().set_timestamp(START_TS + 200);
+    // 50 seconds ago lies halfway between the +100 and +200 observations
+    let cumulatives = client.observe(&vec![&env, 100u32, 50]);
+    
+    assert_eq!(cumulatives.get(1).unwrap() - cumulatives.get(0).unwrap(), tick_a as i64 * 50);
+}
+
+#[test]
+fn test_observe_saturates_past_oldest() {
+    let env = Env::default();
+    let (client, token0, _) = setup(&env);
+    
+    swap_at(&env, &client, &token0, 5_000_000_000, START_TS + 100);
+    env.ledger().set_timestamp(START_TS + 200);
+    
+    // Anything older than the seed observation returns its cumulative
+    let cumulatives = client.observe(&vec![&env, 200u32, 10_000, u32::MAX]);
+    assert_eq!(cumulatives, vec![&env, 0i64, 0, 0]);
+}
+
+#[test]
+fn test_twap_tick_averages_over_stored_history() {
+    let env = Env::default();
+    let (client, token0, _) = setup(&env);
+    
+    // No history yet: the current tick
+    assert_eq!(client.get_twap_tick(&3600), 0);
+    
+    // tick 0 for [0, 100), tick_a for [100, 400)
+    let tick_a = swap_at(&env, &client, &token0, 5_000_000_000, START_TS + 100);
+    env.ledger().set_timestamp(START_TS + 400);
+    assert_eq!(client.get_twap_tick(&300), tick_a);
+    
+    // A day-long window covers only the 400 seconds the oracle holds
+    let expected = (tick_a as i64 * 300).div_euclid(400) as i32;
+    assert_eq!(client.get_twap_tick(&86_400), expected);
+}
+
+/// Swap back and forth every `interval` seconds; returns the tick in effect
+/// after each swap
+fn swap_many(env: &Env, client: &belugaswap_pool::BelugaPoolClient, token0: &Address, token1: &Address, count: u64, interval: u64) -> std::vec::Vec<i32> {
+    let mut ticks = std::vec::Vec::new();
+    for i in 1..=count {
+        let token_in = if i % 2 == 1 { token0 } else { token1 };
+        ticks.push(swap_at(env, client, token_in, 1_000_000_000, START_TS + i * interval));
+    }
+    ticks
+}
+
+#[test]
+fn test_default_cardinality_overwrites_history() {
+    let env = Env::default();
+    let (client, token0, token1) = setup(&env);
+    
+    swap_many(&env, &client, &token0, &token1, 15, 10);
+    
+    // Only the last 10 observations (from +60) survive, so +10 saturates
+    // to the same value as +60
+    let now = START_TS + 150;
+    env.ledger().set_timestamp(now);
+    let cumulatives = client.observe(&vec![&env, (now - START_TS - 10) as u32, (now - START_TS - 60) as u32]);
+    assert_eq!(cumulatives.get(0).unwrap(), cumulatives.get(1).unwrap());
+}
+
+#[test]
+fn test_increased_cardinality_keeps_older_observations() {
+    let env = Env::default();
+    let (client, token0, token1) = setup(&env);
+    
+    assert_eq!(client.increase_observation_cardinality(&50), 50);
+    let ticks = swap_many(&env, &client, &token0, &token1, 15, 10);
+    
+    let now = START_TS + 150;
+    env.ledger().set_timestamp(now);
+    let cumulatives = client.observe(&vec![&env, 0u32, (now - START_TS - 10) as u32]);
+    
+    // Between +10 and +150 each swap's tick held for 10 seconds (the tick set
+    // at +150 has not accrued yet)
+    let expected: i64 = ticks[..14].iter().map(|t| *t as i64 * 10).sum();
+    assert_eq!(cumulatives.get(0).unwrap() - cumulatives.get(1).unwrap(), expected);
+}
+
+#[test]
+fn test_cardinality_growth_mid_ring_preserves_order() {
+    let env = Env::default();
+    let (client, token0, token1) = setup(&env);
+    
+    // Wrap the default ring, then grow: the new size applies at the ring's end
+    let mut ticks = swap_many(&env, &client, &token0, &token1, 13, 10);
+    client.increase_observation_cardinality(&40);
+    for i in 14..=30u64 {
+        let token_in = if i % 2 == 1 { &token0 } else { &token1 };
+        ticks.push(swap_at(&env, &client, token_in, 1_000_000_000, START_TS + i * 10));
+    }
+    
+    let now = START_TS + 300;
+    env.ledger().set_timestamp(now);
+    let window: u64 = 200;
+    let cumulatives = client.observe(&vec![&env, 0u32, window as u32]);
+    
+    // Ticks set at +100 .. +290 each held for 10 seconds within the window
+    let expected: i64 = ticks[9..29].iter().map(|t| *t as i64 * 10).sum();
+    assert_eq!(cumulatives.get(0).unwrap() - cumulatives.get(1).unwrap(), expected);
+}
+
+#[test]
+fn test_increase_cardinality_never_shrinks() {
+    let env = Env::default();
+    let (client, _, _) = setup(&env);
+    
+    assert_eq!(client.increase_observation_cardinality(&30), 30);
+    assert_eq!(client.increase_observation_cardinality(&5), 30);
+    assert_eq!(client.increase_observation_cardinality(&u32::MAX), belugaswap_math::MAX_TWAP_OBSERVATIONS);
+}
+
+#[test]
+fn test_observe_liquidity_monotonic_with_constant_liquidity() {
+    let env = Env::default();
+    let (client, _, _) = setup(&env);
+    let liquidity = client.get_pool_state().liquidity as u128;
+    
+    env.ledger().set_timestamp(START_TS + 300);
+    let cumulatives = client.observe_liquidity(&vec![&env, 300u32, 200, 100, 0]);
+    
+    let step = (100u128 << 64) / liquidity;
+    for i in 1..cumulatives.len() {
+        let prev = cumulatives.get(i - 1).unwrap();
+        let next = cumulatives.get(i).unwrap();
+        assert!(next > prev);
+        assert_eq!(next - prev, step);
+    }
+}
+
+#[test]
+fn test_observe_liquidity_tracks_liquidity_changes() {
+    let env = Env::default();
+    let (client, token0, _) = setup(&env);
+    let liquidity = client.get_pool_state().liquidity as u128;
+    
+    // A swap at +100 records the first 100 seconds at the seeded liquidity
+    swap_at(&env, &client, &token0, 1_000_000, START_TS + 100);
+    env.ledger().set_timestamp(START_TS + 100);
+    let cumulatives = client.observe_liquidity(&vec![&env, 100u32, 0]);
+    
+    assert_eq!(cumulatives.get(1).unwrap() - cumulatives.get(0).unwrap(), (100u128 << 64) / liquidity);
+}
+
+/// Storage layout of an observation before seconds-per-liquidity existed
+#[contracttype]
+enum LegacyKey {
+    TWAPObservation(u32),
+}
+
+#[contracttype]
+struct LegacyObservation {
+    timestamp: u64,
+    tick_cumulative: i128,
+    liquidity_cumulative: u128,
+}
+
+#[test]
+fn test_legacy_observation_zero_fills_seconds_per_liquidity() {
+    let env = Env::default();
+    let (client, _, _) = setup(&env);
+    let liquidity = client.get_pool_state().liquidity as u128;
+    
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(
+            &LegacyKey::TWAPObservation(0),
+            &LegacyObservation {
+                timestamp: START_TS,
+                tick_cumulative: 0,
+                liquidity_cumulative: 0,
+            },
+        );
+    });
+    
+    env.ledger().set_timestamp(START_TS + 50);
+    let cumulatives = client.observe_liquidity(&vec![&env, 50u32, 0]);
+    assert_eq!(cumulatives.get(0).unwrap(), 0);
+    assert_eq!(cumulatives.get(1).unwrap(), (50u128 << 64) / liquidity);
+    assert_eq!(client.observe(&vec![&env, 50u32]), vec![&env, 0i64]);
+}
+
+fn guarded_swap(
+    env: &Env,
+    client: &belugaswap_pool::BelugaPoolClient,
+    token_in: &Address,
+    max_tick_deviation: u32,
+) -> Result<(), belugaswap_pool::PoolError> {
+    let swapper = Address::generate(env);
+    common::mint_tokens(env, token_in, &swapper, 1_000_000);
+    match client.try_swap(&swapper, token_in, &1_000_000, &0, &None, &max_tick_deviation) {
+        Ok(_) => Ok(()),
+        Err(Ok(err)) => Err(err),
+        Err(Err(_)) => panic!("unexpected host error"),
+    }
+}
+
+#[test]
+fn test_swap_deviation_guard_rejects_manipulated_spot() {
+    let env = Env::default();
+    let (client, token0, token1) = setup(&env);
+    
+    // Price sat at tick 0 for a while, then gets pushed down in one ledger
+    let tick = swap_at(&env, &client, &token0, 30_000_000_000, START_TS + 600);
+    assert!(tick < -100);
+    
+    // Spot is far from the 5 minute TWAP, which is still ~0
+    assert_eq!(
+        guarded_swap(&env, &client, &token1, 100),
+        Err(belugaswap_pool::PoolError::PriceDeviation)
+    );
+    // A loose enough bound, or no guard, still lets the swap through
+    assert_eq!(guarded_swap(&env, &client, &token1, tick.unsigned_abs() + 1), Ok(()));
+    assert_eq!(guarded_swap(&env, &client, &token1, 0), Ok(()));
+}
+
+#[test]
+fn test_swap_deviation_guard_passes_once_twap_catches_up() {
+    let env = Env::default();
+    let (client, token0, token1) = setup(&env);
+    
+    swap_at(&env, &client, &token0, 30_000_000_000, START_TS + 600);
+    assert_eq!(
+        guarded_swap(&env, &client, &token1, 1),
+        Err(belugaswap_pool::PoolError::PriceDeviation)
+    );
+    
+    // After a full window at the new price the TWAP agrees with spot
+    env.ledger().set_timestamp(START_TS + 600 + 300);
+    assert_eq!(guarded_swap(&env, &client, &token1, 1), Ok(()));
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use belugaswap_pool::types::PoolState;
+use belugaswap_pool::{BelugaPoolClient, PoolError};
+use soroban_sdk::{
+    contracttype, symbol_short, testutils::Address as _, vec,
+    Address, Env, IntoVal, Map, Symbol, Val,
+};
+
+/// Stand-in for the next pool release: a build of the SDK's storage test
+/// contract, whose `put`/`get`/`del` are functions the current pool lacks
+const UPGRADED_WASM: &[u8] = include_bytes!("fixtures/contract_data.wasm");
+
+/// Mirror of the pool's storage keys read by these tests
+#[contracttype]
+enum PoolKey {
+    PoolState,
+    Position(Address, i32, i32),
+}
+
+const LIQUIDITY_AMOUNT: i128 = 100_000_000_000;
+
+/// Pool behind a MockFactory with one LP position over [-600, 600] and one
+/// swap through it; returns (pool, factory admin, LP)
+fn setup(env: &Env) -> (BelugaPoolClient<'_>, Address, Address) {
+    let (client, admin, lp, _) = common::setup_admin_pool(
+        env,
+        common::DEFAULT_FEE_BPS,
+        common::DEFAULT_CREATOR_FEE_BPS,
+        LIQUIDITY_AMOUNT,
+    );
+
+    let token0 = client.get_pool_state().token0;
+    let trader = Address::generate(env);
+    common::mint_tokens(env, &token0, &trader, 1_000_000_000);
+    client.swap(&trader, &token0, &1_000_000_000, &0, &None, &0);
+
+    (client, admin, lp)
+}
+
+/// Price, tick, liquidity, fee growth and creator fees of a pool state
+fn summary(state: &PoolState) -> (u128, i32, i128, u128, u128) {
+    (
+        state.sqrt_price_x64,
+        state.current_tick,
+        state.liquidity,
+        state.fee_growth_global_0,
+        state.creator_fees_0,
+    )
+}
+
+/// Pool state summary and the LP's position liquidity, read straight from
+/// the pool's storage so they work whatever code the pool runs
+fn stored_state(env: &Env, pool: &Address, lp: &Address) -> ((u128, i32, i128, u128, u128), i128) {
+    env.as_contract(pool, || {
+        let storage = env.storage().persistent();
+        let state: PoolState = storage.get(&PoolKey::PoolState).unwrap();
+        let position: Map<Symbol, Val> = storage.get(&PoolKey::Position(lp.clone(), -600, 600)).unwrap();
+        let liquidity: i128 = position.get(symbol_short!("liquidity")).unwrap().into_val(env);
+        (summary(&state), liquidity)
+    })
+}
+
+#[test]
+fn test_upgrade_keeps_storage_and_runs_new_code() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, lp) = setup(&env);
+    let state = client.get_pool_state();
+    let reserves = client.get_reserves();
+    let position = client.get_position(&lp, &-600, &600);
+    assert!(position.liquidity > 0 && position.fees_owed_0 > 0);
+    assert_eq!(stored_state(&env, &client.address, &lp), (summary(&state), position.liquidity));
+
+    let hash = env.deployer().upload_contract_wasm(UPGRADED_WASM);
+    client.upgrade(&admin, &hash);
+
+    // The new code answers calls the old pool didn't have
+    let key = symbol_short!("key");
+    let get = |env: &Env| -> Option<Symbol> {
+        env.invoke_contract(&client.address, &symbol_short!("get"), vec![env, key.into_val(env)])
+    };
+    assert_eq!(get(&env), None);
+    let _: () = env.invoke_contract(
+        &client.address,
+        &symbol_short!("put"),
+        vec![&env, key.into_val(&env), symbol_short!("val").into_val(&env)],
+    );
+    assert_eq!(get(&env), Some(symbol_short!("val")));
+
+    // Same address, same storage, same balances
+    assert_eq!(stored_state(&env, &client.address, &lp), (summary(&state), position.liquidity));
+    let token0 = soroban_sdk::token::TokenClient::new(&env, &state.token0);
+    let token1 = soroban_sdk::token::TokenClient::new(&env, &state.token1);
+    assert_eq!((token0.balance(&client.address), token1.balance(&client.address)), reserves);
+}
+
+#[test]
+fn test_upgrade_requires_factory_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, lp) = setup(&env);
+    let hash = env.deployer().upload_contract_wasm(UPGRADED_WASM);
+    assert_eq!(client.try_upgrade(&lp, &hash), Err(Ok(PoolError::NotFactoryAdmin)));
+
+    // Still the pool
+    assert!(client.get_pool_state().liquidity > 0);
+}
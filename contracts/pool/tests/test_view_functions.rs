@@ -1,6 +1,6 @@
 mod common;
 
-use soroban_sdk::{testutils::Address as _, Address, Env};
+use soroban_sdk::{testutils::Address as _, token::TokenClient, Address, Env};
 
 #[test]
 fn test_get_pool_state() {
@@ -49,6 +49,155 @@ fn test_get_position_empty() {
     assert_eq!(position.amount1, 0);
 }
 
+#[test]
+fn test_get_reserves_tracks_swaps() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    let (token0, token1) = client.get_tokens();
+    let state = client.get_pool_state();
+    assert_eq!((token0.clone(), token1.clone()), (state.token0, state.token1));
+    assert_eq!(client.get_reserves(), (0, 0));
+    
+    let lp = Address::generate(&env);
+    common::mint_tokens(&env, &token_a, &lp, 10_000_000_000);
+    common::mint_tokens(&env, &token_b, &lp, 10_000_000_000);
+    let (_, amount0, amount1) = client.add_liquidity(&lp, &-600, &600, &10_000_000_000, &10_000_000_000, &0, &0);
+    assert_eq!(client.get_reserves(), (amount0, amount1));
+    
+    let swapper = Address::generate(&env);
+    common::mint_tokens(&env, &token0, &swapper, 100_000_000);
+    let result = client.swap(&swapper, &token0, &100_000_000, &0, &None, &0);
+    
+    let (reserve0, reserve1) = client.get_reserves();
+    assert_eq!(reserve0, amount0 + result.amount_in);
+    assert_eq!(reserve1, amount1 - result.amount_out);
+    assert_eq!(reserve0, TokenClient::new(&env, &token0).balance(&client.address));
+    assert_eq!(reserve1, TokenClient::new(&env, &token1).balance(&client.address));
+}
+
+#[test]
+fn test_get_volume_sums_swaps_in_both_directions() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    let (token0, token1) = client.get_tokens();
+    assert_eq!(client.get_volume(), (0, 0));
+    
+    let lp = Address::generate(&env);
+    common::mint_tokens(&env, &token_a, &lp, 10_000_000_000);
+    common::mint_tokens(&env, &token_b, &lp, 10_000_000_000);
+    client.add_liquidity(&lp, &-600, &600, &10_000_000_000, &10_000_000_000, &0, &0);
+    assert_eq!(client.get_volume(), (0, 0), "liquidity changes are not volume");
+    
+    let swapper = Address::generate(&env);
+    common::mint_tokens(&env, &token0, &swapper, 1_000_000_000);
+    common::mint_tokens(&env, &token1, &swapper, 1_000_000_000);
+    
+    let (mut expected0, mut expected1) = (0u128, 0u128);
+    for (token_in, amount) in [
+        (&token0, 100_000_000i128),
+        (&token1, 250_000_000),
+        (&token0, 40_000_000),
+        (&token1, 5_000_000),
+    ] {
+        let result = client.swap(&swapper, token_in, &amount, &0, &None, &0);
+        if *token_in == token0 {
+            expected0 += result.amount_in as u128;
+            expected1 += result.amount_out as u128;
+        } else {
+            expected1 += result.amount_in as u128;
+            expected0 += result.amount_out as u128;
+        }
+    }
+    
+    assert_eq!(client.get_volume(), (expected0, expected1));
+    let state = client.get_pool_state();
+    assert_eq!((state.volume_token0, state.volume_token1), (expected0, expected1));
+}
+
+#[test]
+fn test_get_fee_growth_global_grows_in_token_paid_in() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    let (token0, token1) = client.get_tokens();
+    assert_eq!(client.get_fee_growth_global(), (0, 0));
+    
+    let lp = Address::generate(&env);
+    common::mint_tokens(&env, &token_a, &lp, 10_000_000_000);
+    common::mint_tokens(&env, &token_b, &lp, 10_000_000_000);
+    client.add_liquidity(&lp, &-600, &600, &10_000_000_000, &10_000_000_000, &0, &0);
+    
+    let swapper = Address::generate(&env);
+    common::mint_tokens(&env, &token0, &swapper, 1_000_000_000);
+    common::mint_tokens(&env, &token1, &swapper, 1_000_000_000);
+    
+    // token0 in: only the token0 accumulator moves
+    client.swap(&swapper, &token0, &100_000_000, &0, &None, &0);
+    let (growth0, growth1) = client.get_fee_growth_global();
+    assert!(growth0 > 0);
+    assert_eq!(growth1, 0);
+    
+    // token1 in: only the token1 accumulator moves
+    client.swap(&swapper, &token1, &100_000_000, &0, &None, &0);
+    let (after0, after1) = client.get_fee_growth_global();
+    assert_eq!(after0, growth0);
+    assert!(after1 > 0);
+    
+    let state = client.get_pool_state();
+    assert_eq!((state.fee_growth_global_0, state.fee_growth_global_1), (after0, after1));
+}
+
+#[test]
+fn test_get_liquidity_distribution_buckets_active_liquidity() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    assert_eq!(client.get_pool_state().current_tick, 0);
+    
+    let lp = Address::generate(&env);
+    common::mint_tokens(&env, &token_a, &lp, 100_000_000_000);
+    common::mint_tokens(&env, &token_b, &lp, 100_000_000_000);
+    let amount = 1_000_000_000;
+    client.add_liquidity(&lp, &-600, &600, &amount, &amount, &0, &0);
+    // Includes the liquidity locked by the first deposit
+    let around = client.get_pool_state().liquidity;
+    let (above, _, _) = client.add_liquidity(&lp, &0, &1200, &amount, &amount, &0, &0);
+    let (below, _, _) = client.add_liquidity(&lp, &-1800, &-600, &amount, &amount, &0, &0);
+    assert_eq!(client.get_pool_state().liquidity, around + above);
+    
+    let histogram = client.get_liquidity_distribution(&-2400, &1800, &7);
+    assert_eq!(histogram.len(), 7);
+    assert_eq!(histogram.get(0).unwrap(), 0);
+    assert_eq!(histogram.get(1).unwrap(), below);
+    assert_eq!(histogram.get(2).unwrap(), below);
+    assert_eq!(histogram.get(3).unwrap(), around);
+    assert_eq!(histogram.get(4).unwrap(), around + above);
+    assert_eq!(histogram.get(5).unwrap(), above);
+    assert_eq!(histogram.get(6).unwrap(), 0);
+    
+    // A bucket straddling a boundary reports the tick-weighted average
+    let whole = client.get_liquidity_distribution(&-600, &600, &1);
+    assert_eq!(whole.len(), 1);
+    assert_eq!(whole.get(0).unwrap(), around + above / 2);
+    
+    // Same histogram when the range starts above the current tick
+    let upper = client.get_liquidity_distribution(&600, &1800, &2);
+    assert_eq!(upper.get(0).unwrap(), above);
+    assert_eq!(upper.get(1).unwrap(), 0);
+    
+    // Bucket count is clamped, and empty ranges return nothing
+    assert_eq!(client.get_liquidity_distribution(&-600, &600, &0).len(), 1);
+    assert_eq!(client.get_liquidity_distribution(&-100_000, &100_000, &10_000).len(), 200);
+    assert_eq!(client.get_liquidity_distribution(&0, &10, &50).len(), 10);
+    assert_eq!(client.get_liquidity_distribution(&600, &600, &4).len(), 0);
+}
+
 #[test]
 fn test_is_initialized() {
     let env = Env::default();
@@ -57,4 +206,198 @@ fn test_is_initialized() {
     let (client, _creator, _factory, _router, _token_a, _token_b) = common::setup_pool(&env);
     
     assert!(client.is_initialized());
-}
\ No newline at end of file
+}
+#[test]
+fn test_quote_position_amounts_matches_liquidity_math() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, _token_a, _token_b) = common::setup_pool(&env);
+    let liquidity: i128 = 1_000_000_000;
+    let lower = belugaswap_math::get_sqrt_ratio_at_tick(-600);
+    let upper = belugaswap_math::get_sqrt_ratio_at_tick(600);
+    
+    // Below, inside and above the range, independent of the pool's own price
+    for tick in [-1200, -600, -300, 0, 300, 600, 1200] {
+        let sqrt_price = belugaswap_math::get_sqrt_ratio_at_tick(tick);
+        let expected = belugaswap_math::get_amounts_for_liquidity(&env, liquidity, lower, upper, sqrt_price);
+        assert_eq!(client.quote_position_amounts(&liquidity, &-600, &600, &sqrt_price), expected);
+    }
+    
+    let below = client.quote_position_amounts(&liquidity, &-600, &600, &belugaswap_math::get_sqrt_ratio_at_tick(-1200));
+    assert!(below.0 > 0 && below.1 == 0);
+    let above = client.quote_position_amounts(&liquidity, &-600, &600, &belugaswap_math::get_sqrt_ratio_at_tick(1200));
+    assert!(above.0 == 0 && above.1 > 0);
+    
+    // Degenerate ranges
+    assert_eq!(client.quote_position_amounts(&liquidity, &600, &-600, &common::DEFAULT_SQRT_PRICE_X64), (0, 0));
+    assert_eq!(client.quote_position_amounts(&0, &-600, &600, &common::DEFAULT_SQRT_PRICE_X64), (0, 0));
+}
+
+#[test]
+fn test_quote_position_amounts_at_pool_price_matches_position() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    let lp = Address::generate(&env);
+    let amt: i128 = 100_000_000_000;
+    common::mint_tokens(&env, &token_a, &lp, amt);
+    common::mint_tokens(&env, &token_b, &lp, amt);
+    client.add_liquidity(&lp, &-600, &600, &amt, &amt, &0, &0);
+    
+    let position = client.get_position(&lp, &-600, &600);
+    let quoted = client.quote_position_amounts(
+        &position.liquidity,
+        &-600,
+        &600,
+        &client.get_pool_state().sqrt_price_x64,
+    );
+    assert_eq!(quoted, (position.amount0, position.amount1));
+}
+
+#[test]
+fn test_position_value_at_price_adds_constant_fees_to_principal() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    let lp = Address::generate(&env);
+    let amt: i128 = 100_000_000_000;
+    common::mint_tokens(&env, &token_a, &lp, amt);
+    common::mint_tokens(&env, &token_b, &lp, amt);
+    client.add_liquidity(&lp, &-600, &600, &amt, &amt, &0, &0);
+    
+    // Earn fees in both tokens
+    let state = client.get_pool_state();
+    let swapper = Address::generate(&env);
+    common::mint_tokens(&env, &state.token0, &swapper, 1_000_000_000);
+    common::mint_tokens(&env, &state.token1, &swapper, 1_000_000_000);
+    client.swap(&swapper, &state.token0, &1_000_000_000, &0, &None, &0);
+    client.swap(&swapper, &state.token1, &1_000_000_000, &0, &None, &0);
+    
+    let position = client.get_position(&lp, &-600, &600);
+    let fees = (position.fees_owed_0 as i128, position.fees_owed_1 as i128);
+    assert!(fees.0 > 0 && fees.1 > 0);
+    
+    // At the pool price it is get_position's amounts plus fees
+    let now = client.position_value_at_price(&lp, &-600, &600, &client.get_pool_state().sqrt_price_x64);
+    assert_eq!(now, (position.amount0 + fees.0, position.amount1 + fees.1));
+    
+    // Below, inside and above the range: principal follows the liquidity
+    // curve, the fee component never moves
+    let mut previous = (i128::MAX, i128::MIN);
+    for tick in [-1200, 0, 1200] {
+        let sqrt_price = belugaswap_math::get_sqrt_ratio_at_tick(tick);
+        let (value0, value1) = client.position_value_at_price(&lp, &-600, &600, &sqrt_price);
+        let principal = client.quote_position_amounts(&position.liquidity, &-600, &600, &sqrt_price);
+        assert_eq!((value0 - principal.0, value1 - principal.1), fees, "tick {}", tick);
+        
+        // A rising price trades token0 for token1
+        assert!(principal.0 < previous.0 && principal.1 > previous.1, "tick {}", tick);
+        previous = principal;
+    }
+    assert_eq!(previous.0, 0);
+    
+    // Nothing owned, nothing worth
+    let stranger = Address::generate(&env);
+    assert_eq!(client.position_value_at_price(&stranger, &-600, &600, &common::DEFAULT_SQRT_PRICE_X64), (0, 0));
+}
+
+#[test]
+fn test_next_initialized_tick_finds_neighbors() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    assert_eq!(client.next_initialized_tick(&0, &true), None);
+    assert_eq!(client.next_initialized_tick(&0, &false), None);
+    
+    // Scattered ticks, some several bitmap words apart
+    let lp = Address::generate(&env);
+    let amt: i128 = 10_000_000_000;
+    for (lower, upper) in [(-19980, -600), (600, 1200), (30000, 60000)] {
+        common::mint_tokens(&env, &token_a, &lp, amt);
+        common::mint_tokens(&env, &token_b, &lp, amt);
+        client.add_liquidity(&lp, &lower, &upper, &amt, &amt, &0, &0);
+    }
+    
+    // Upward search is strictly above the start
+    assert_eq!(client.next_initialized_tick(&0, &false), Some(600));
+    assert_eq!(client.next_initialized_tick(&599, &false), Some(600));
+    assert_eq!(client.next_initialized_tick(&600, &false), Some(1200));
+    assert_eq!(client.next_initialized_tick(&1200, &false), Some(30000));
+    assert_eq!(client.next_initialized_tick(&60000, &false), None);
+    
+    // Downward search includes the start
+    assert_eq!(client.next_initialized_tick(&0, &true), Some(-600));
+    assert_eq!(client.next_initialized_tick(&-600, &true), Some(-600));
+    assert_eq!(client.next_initialized_tick(&-601, &true), Some(-19980));
+    assert_eq!(client.next_initialized_tick(&60000, &true), Some(60000));
+    assert_eq!(client.next_initialized_tick(&-19981, &true), None);
+}
+
+#[test]
+fn test_tick_info_reports_initialization() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    assert!(!client.get_tick_info(&-600).initialized);
+    
+    let lp = Address::generate(&env);
+    common::mint_tokens(&env, &token_a, &lp, 20_000_000_000);
+    common::mint_tokens(&env, &token_b, &lp, 20_000_000_000);
+    client.add_liquidity(&lp, &-600, &600, &10_000_000_000, &10_000_000_000, &0, &0);
+    
+    // The second LP's range touches -1200 fresh and shares 600
+    let other = Address::generate(&env);
+    common::mint_tokens(&env, &token_a, &other, 10_000_000_000);
+    common::mint_tokens(&env, &token_b, &other, 10_000_000_000);
+    client.add_liquidity(&other, &-1200, &600, &10_000_000_000, &10_000_000_000, &0, &0);
+    let fresh = client.get_tick_info(&-1200);
+    assert!(fresh.initialized);
+    assert!(fresh.liquidity_gross > 0);
+    
+    // Removed back to zero: uninitialized, while the shared tick stays live
+    let liquidity = client.get_position(&other, &-1200, &600).liquidity;
+    client.remove_liquidity(&other, &-1200, &600, &liquidity, &0, &0);
+    let removed = client.get_tick_info(&-1200);
+    assert!(!removed.initialized);
+    assert_eq!(removed.liquidity_gross, 0);
+    assert!(client.get_tick_info(&600).initialized);
+}
+
+#[test]
+fn test_is_position_in_range_follows_current_tick() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (client, _creator, _factory, _router, token_a, token_b) = common::setup_pool(&env);
+    assert_eq!(client.get_pool_state().current_tick, 0);
+    
+    // Price inside, below and above the range; lower inclusive, upper exclusive
+    assert!(client.is_position_in_range(&-600, &600));
+    assert!(client.is_position_in_range(&0, &600));
+    assert!(!client.is_position_in_range(&-600, &0));
+    assert!(!client.is_position_in_range(&60, &600));
+    assert!(!client.is_position_in_range(&-1200, &-600));
+    
+    // Unaligned ticks snap outward first: [1, 59] covers [0, 60)
+    assert!(client.is_position_in_range(&1, &59));
+    
+    // Selling token0 moves the price below the range
+    let lp = Address::generate(&env);
+    common::mint_tokens(&env, &token_a, &lp, 10_000_000_000);
+    common::mint_tokens(&env, &token_b, &lp, 10_000_000_000);
+    client.add_liquidity(&lp, &-1200, &600, &10_000_000_000, &10_000_000_000, &0, &0);
+    let token0 = client.get_pool_state().token0;
+    let swapper = Address::generate(&env);
+    common::mint_tokens(&env, &token0, &swapper, 7_000_000_000);
+    client.swap(&swapper, &token0, &7_000_000_000, &0, &None, &0);
+    
+    let tick = client.get_pool_state().current_tick;
+    assert!(tick < -600, "tick {}", tick);
+    assert!(!client.is_position_in_range(&-600, &600));
+    assert!(client.is_position_in_range(&-1200, &-600));
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use belugaswap_pool::{BelugaPoolClient, PoolError};
+use soroban_sdk::{testutils::{Address as _, Ledger}, token::TokenClient, Address, Env};
+
+const AMOUNT: i128 = 10_000_000_000;
+const NOW: u64 = 1_000_000;
+
+/// Default pool with a position over [-600, 600] that has earned fees from
+/// swaps both ways; returns (pool, lp, token0, token1)
+fn setup(env: &Env) -> (BelugaPoolClient<'_>, Address, TokenClient<'_>, TokenClient<'_>) {
+    env.ledger().with_mut(|li| li.timestamp = NOW);
+    let (client, _, _, _, token_a, token_b) = common::setup_pool(env);
+
+    let lp = Address::generate(env);
+    common::mint_tokens(env, &token_a, &lp, AMOUNT);
+    common::mint_tokens(env, &token_b, &lp, AMOUNT);
+    client.add_liquidity(&lp, &-600, &600, &AMOUNT, &AMOUNT, &0, &0);
+
+    let state = client.get_pool_state();
+    let swapper = Address::generate(env);
+    common::mint_tokens(env, &state.token0, &swapper, AMOUNT);
+    common::mint_tokens(env, &state.token1, &swapper, AMOUNT);
+    client.swap(&swapper, &state.token0, &1_000_000_000, &0, &None, &0);
+    client.swap(&swapper, &state.token1, &1_000_000_000, &0, &None, &0);
+
+    let token0 = TokenClient::new(env, &state.token0);
+    let token1 = TokenClient::new(env, &state.token1);
+    (client, lp, token0, token1)
+}
+
+#[test]
+fn test_remove_liquidity_to_pays_recipient() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, lp, token0, token1) = setup(&env);
+    let recipient = Address::generate(&env);
+    let lp_before = (token0.balance(&lp), token1.balance(&lp));
+    let liquidity = client.get_position(&lp, &-600, &600).liquidity;
+
+    let (amount0, amount1) =
+        client.remove_liquidity_to(&lp, &-600, &600, &(liquidity / 2), &0, &0, &recipient, &(NOW + 60));
+    assert!(amount0 > 0 && amount1 > 0);
+    assert_eq!((token0.balance(&recipient), token1.balance(&recipient)), (amount0, amount1));
+    assert_eq!((token0.balance(&lp), token1.balance(&lp)), lp_before);
+    assert_eq!(client.get_position(&lp, &-600, &600).liquidity, liquidity - liquidity / 2);
+}
+
+#[test]
+fn test_collect_amounts_to_pays_recipient() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, lp, token0, token1) = setup(&env);
+    let recipient = Address::generate(&env);
+    let owed = client.get_position(&lp, &-600, &600);
+    assert!(owed.fees_owed_0 > 0 && owed.fees_owed_1 > 0);
+
+    // No deadline
+    let (fees0, fees1) = client.collect_amounts_to(&lp, &-600, &600, &u128::MAX, &u128::MAX, &recipient, &0);
+    assert_eq!((fees0, fees1), (owed.fees_owed_0, owed.fees_owed_1));
+    assert_eq!(token0.balance(&recipient) as u128, fees0);
+    assert_eq!(token1.balance(&recipient) as u128, fees1);
+    assert_eq!(client.collect_fees(&lp, &-600, &600), (0, 0));
+}
+
+#[test]
+fn test_withdrawals_to_recipient_enforce_deadline() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, lp, token0, _) = setup(&env);
+    let recipient = Address::generate(&env);
+    let before = client.get_position(&lp, &-600, &600);
+
+    let result = client.try_remove_liquidity_to(&lp, &-600, &600, &before.liquidity, &0, &0, &recipient, &(NOW - 1));
+    assert_eq!(result, Err(Ok(PoolError::DeadlineExpired)));
+    let result = client.try_collect_amounts_to(&lp, &-600, &600, &u128::MAX, &u128::MAX, &recipient, &(NOW - 1));
+    assert_eq!(result, Err(Ok(PoolError::DeadlineExpired)));
+
+    let after = client.get_position(&lp, &-600, &600);
+    assert_eq!(after.liquidity, before.liquidity);
+    assert_eq!((after.fees_owed_0, after.fees_owed_1), (before.fees_owed_0, before.fees_owed_1));
+    assert_eq!(token0.balance(&recipient), 0);
+
+    // A deadline equal to the ledger timestamp is still valid
+    assert!(client.try_remove_liquidity_to(&lp, &-600, &600, &before.liquidity, &0, &0, &recipient, &NOW).is_ok());
+}
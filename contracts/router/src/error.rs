@@ -17,6 +17,7 @@ pub enum RouterError {
     InsufficientOutput = 13,
     SlippageExceeded = 14,
     DeadlineExpired = 15,
+    InvalidSlippage = 16,
     
     // Pool errors
     PoolNotFound = 20,
@@ -26,11 +27,16 @@ pub enum RouterError {
     // Quote errors
     QuoteFailed = 30,
     InvalidAmount = 31,
+    InvalidTwapWindow = 32,
     
     // Split errors
     EmptySplits = 40,
     SplitAmountMismatch = 41,
+    InvalidLegMinimums = 42,
     
     // Authorization
     Unauthorized = 50,
+    
+    // Multicall
+    EmptyCalls = 60,
 }
\ No newline at end of file
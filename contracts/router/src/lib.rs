@@ -17,7 +17,8 @@
 //! 4. Ready to swap!
 
 use soroban_sdk::{
-    contract, contractimpl, vec, Address, Env, IntoVal, Symbol, Vec, token,
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contract, contractimpl, vec, xdr::ToXdr, Address, Bytes, Env, IntoVal, Map, Symbol, Vec, token,
 };
 
 mod error;
@@ -26,6 +27,10 @@ mod storage;
 mod types;
 
 pub use error::RouterError;
+use belugaswap_math::{
+    constants::BPS_DENOMINATOR, get_sqrt_ratio_at_tick, mul_bps, mul_div, sort_tokens, u128_to_i128_saturating,
+    ONE_X64,
+};
 use events::*;
 use storage::*;
 pub use types::*;
@@ -43,8 +48,13 @@ const DEFAULT_FEE_TIERS: [u32; 3] = [5, 30, 100];
 /// Minimum output threshold (dust protection)
 const MIN_OUTPUT: i128 = 1;
 
-/// Approval expiry buffer (minimal - just for the current tx)
-const APPROVAL_LEDGER_BUFFER: u32 = 100;
+/// Number of chunks the split quote hands out (bounds pool previews to
+/// chunks × pools)
+const MAX_SPLIT_CHUNKS: i128 = 10;
+
+/// Quotes within this many bps of the best output count as equal, and the
+/// one crossing the fewest ticks wins
+const NEAR_EQUAL_OUTPUT_BPS: u32 = 1;
 
 // ============================================================
 // CONTRACT
@@ -98,20 +108,28 @@ impl BelugaRouter {
         params: ExactInputParams,
     ) -> Result<SwapResult, RouterError> {
         sender.require_auth();
-        
+        Self::do_swap_exact_input(env, sender, params)
+    }
+    
+    fn do_swap_exact_input(
+        env: Env,
+        sender: Address,
+        params: ExactInputParams,
+    ) -> Result<SwapResult, RouterError> {
         if !is_initialized(&env) {
             return Err(RouterError::NotInitialized);
         }
         
-        // Check deadline
-        if env.ledger().sequence() > params.deadline {
-            return Err(RouterError::DeadlineExpired);
-        }
+        Self::check_deadline(&env, params.deadline)?;
         
         if params.amount_in <= 0 {
             return Err(RouterError::InvalidAmount);
         }
         
+        if params.max_slippage_bps as u128 >= BPS_DENOMINATOR {
+            return Err(RouterError::InvalidSlippage);
+        }
+        
         let config = read_config(&env);
         
         // Get best quote
@@ -123,7 +141,7 @@ impl BelugaRouter {
         
         let best_quote = Self::find_best_pool(
             &env,
-            &config.factory,
+            &mut QuoteCache::new(&env, &config.factory),
             &params.token_in,
             &params.token_out,
             params.amount_in,
@@ -135,6 +153,15 @@ impl BelugaRouter {
             return Err(RouterError::SlippageExceeded);
         }
         
+        // Stop the pool once the price has moved past the caller's tolerance
+        let sqrt_price_limit = Self::slippage_sqrt_price_limit(
+            &env,
+            &best_quote.pool,
+            &params.token_in,
+            &params.token_out,
+            params.max_slippage_bps,
+        );
+        
         // Execute swap - pull from user
         let result = Self::execute_single_swap(
             &env,
@@ -144,6 +171,7 @@ impl BelugaRouter {
             &params.token_out,
             params.amount_in,
             params.amount_out_min,
+            sqrt_price_limit,
             &best_quote.pool,
             best_quote.fee_bps,
         )?;
@@ -161,105 +189,89 @@ impl BelugaRouter {
         Ok(result)
     }
     
-    /// Split swap across multiple fee tiers for better execution on large orders
+    /// Swap for an exact output with automatic best fee selection
     /// 
-    /// Splits the input across pools to minimize price impact
-    pub fn swap_split(
+    /// Picks the pool needing the least input, pulls `amount_in_max` from the
+    /// sender and refunds whatever the swap did not use
+    pub fn swap_exact_output(
         env: Env,
         sender: Address,
-        token_in: Address,
-        token_out: Address,
-        amount_in: i128,
-        amount_out_min: i128,
-        splits: Vec<SplitQuote>,
-        recipient: Address,
-        deadline: u32,
+        params: ExactOutputParams,
     ) -> Result<SwapResult, RouterError> {
         sender.require_auth();
-        
+        Self::do_swap_exact_output(env, sender, params)
+    }
+    
+    fn do_swap_exact_output(
+        env: Env,
+        sender: Address,
+        params: ExactOutputParams,
+    ) -> Result<SwapResult, RouterError> {
         if !is_initialized(&env) {
             return Err(RouterError::NotInitialized);
         }
         
-        if env.ledger().sequence() > deadline {
-            return Err(RouterError::DeadlineExpired);
-        }
+        Self::check_deadline(&env, params.deadline)?;
         
-        if splits.is_empty() {
-            return Err(RouterError::EmptySplits);
+        if params.amount_out <= 0 || params.amount_in_max <= 0 {
+            return Err(RouterError::InvalidAmount);
         }
         
-        // Validate total split amounts
-        let mut total_split_in: i128 = 0;
-        let mut valid_splits: u32 = 0;
+        let config = read_config(&env);
         
-        for i in 0..splits.len() {
-            let split = splits.get(i).unwrap();
-            if split.amount_in > 0 {
-                total_split_in = total_split_in.saturating_add(split.amount_in);
-                valid_splits += 1;
-            }
-        }
+        let fee_tiers = if params.fee_tiers.is_empty() {
+            Self::default_fee_tiers(&env)
+        } else {
+            params.fee_tiers.clone()
+        };
         
-        if total_split_in != amount_in {
-            return Err(RouterError::SplitAmountMismatch);
-        }
+        let (pool, fee_bps, amount_in) = Self::find_best_pool_exact_output(
+            &env,
+            &config.factory,
+            &params.token_in,
+            &params.token_out,
+            params.amount_out,
+            &fee_tiers,
+        )?;
         
-        if valid_splits == 0 {
-            return Err(RouterError::EmptySplits);
+        // Slippage check
+        if amount_in > params.amount_in_max {
+            return Err(RouterError::SlippageExceeded);
         }
         
-        // Execute each split - all pull from user (same token)
-        let mut total_out: i128 = 0;
-        let mut pools_used = Vec::new(&env);
-        let mut fee_tiers_used = Vec::new(&env);
+        let router_addr = env.current_contract_address();
+        let token_in_client = token::Client::new(&env, &params.token_in);
+        token_in_client.transfer(&sender, &router_addr, &params.amount_in_max);
         
-        for i in 0..splits.len() {
-            let split = splits.get(i).unwrap();
-            
-            // Skip zero-amount splits
-            if split.amount_in <= 0 {
-                continue;
-            }
-            
-            let result = Self::execute_single_swap(
-                &env,
-                &sender,
-                &recipient,
-                &token_in,
-                &token_out,
-                split.amount_in,
-                MIN_OUTPUT, 
-                &split.pool,
-                split.fee_bps,
-            )?;
-            
-            total_out = total_out.saturating_add(result.amount_out);
-            pools_used.push_back(split.pool.clone());
-            fee_tiers_used.push_back(split.fee_bps);
-        }
+        let result = Self::execute_pool_swap(
+            &env,
+            &params.recipient,
+            &params.token_in,
+            &params.token_out,
+            amount_in,
+            params.amount_out,
+            None, // no price limit
+            &pool,
+            fee_bps,
+        )?;
         
-        // Final slippage check on total output
-        if total_out < amount_out_min {
-            return Err(RouterError::SlippageExceeded);
+        // Refund unused input
+        let refund = params.amount_in_max.saturating_sub(result.amount_in);
+        if refund > 0 {
+            token_in_client.transfer(&router_addr, &sender, &refund);
         }
         
-        emit_split_swap(
+        emit_swap(
             &env,
             &sender,
-            &token_in,
-            &token_out,
-            amount_in,
-            total_out,
-            valid_splits,
+            &params.token_in,
+            &params.token_out,
+            result.amount_in,
+            result.amount_out,
+            &result.pools_used,
         );
         
-        Ok(SwapResult {
-            amount_in,
-            amount_out: total_out,
-            pools_used,
-            fee_tiers_used,
-        })
+        Ok(result)
     }
     
     /// Multi-hop swap (A -> B -> C -> ...)
@@ -269,14 +281,19 @@ impl BelugaRouter {
         params: MultihopExactInputParams,
     ) -> Result<SwapResult, RouterError> {
         sender.require_auth();
-        
+        Self::do_swap_multihop(env, sender, params)
+    }
+    
+    fn do_swap_multihop(
+        env: Env,
+        sender: Address,
+        params: MultihopExactInputParams,
+    ) -> Result<SwapResult, RouterError> {
         if !is_initialized(&env) {
             return Err(RouterError::NotInitialized);
         }
         
-        if env.ledger().sequence() > params.deadline {
-            return Err(RouterError::DeadlineExpired);
-        }
+        Self::check_deadline(&env, params.deadline)?;
         
         if params.path.is_empty() {
             return Err(RouterError::InvalidPath);
@@ -292,6 +309,12 @@ impl BelugaRouter {
         
         let config = read_config(&env);
         let router_addr = env.current_contract_address();
+        // Pool lookups only: quotes go stale once a hop has executed
+        let mut cache = QuoteCache::new(&env, &config.factory);
+        
+        if cache.first_invalid_hop(&params.token_in, &params.path).is_some() {
+            return Err(RouterError::InvalidPath);
+        }
         
         let mut current_token = params.token_in.clone();
         let mut current_amount = params.amount_in;
@@ -305,13 +328,8 @@ impl BelugaRouter {
             let fee_bps = hop.fee_bps;
             
             // Get pool for this hop
-            let pool = Self::get_pool_address(
-                &env,
-                &config.factory,
-                &current_token,
-                &next_token,
-                fee_bps,
-            ).ok_or(RouterError::PoolNotFound)?;
+            let pool = cache.pool(&current_token, &next_token, fee_bps)
+                .ok_or(RouterError::PoolNotFound)?;
             
             // Determine recipient for this hop
             let is_final_hop = i == params.path.len() - 1;
@@ -340,6 +358,7 @@ impl BelugaRouter {
                     &next_token,
                     current_amount,
                     min_out,
+                    None, // no price limit
                     &pool,
                     fee_bps,
                 )?
@@ -381,6 +400,175 @@ impl BelugaRouter {
         })
     }
     
+    /// Multi-hop swap for an exact final output (A -> B -> C -> ...)
+    /// 
+    /// Sizes each hop backwards from the requested output, then executes the
+    /// hops forward feeding each hop's full output into the next
+    pub fn swap_multihop_exact_output(
+        env: Env,
+        sender: Address,
+        params: MultihopExactOutputParams,
+    ) -> Result<SwapResult, RouterError> {
+        sender.require_auth();
+        Self::do_swap_multihop_exact_output(env, sender, params)
+    }
+    
+    fn do_swap_multihop_exact_output(
+        env: Env,
+        sender: Address,
+        params: MultihopExactOutputParams,
+    ) -> Result<SwapResult, RouterError> {
+        if !is_initialized(&env) {
+            return Err(RouterError::NotInitialized);
+        }
+        
+        Self::check_deadline(&env, params.deadline)?;
+        
+        if params.path.is_empty() {
+            return Err(RouterError::InvalidPath);
+        }
+        
+        if params.path.len() > MAX_HOPS {
+            return Err(RouterError::PathTooLong);
+        }
+        
+        if params.amount_out <= 0 || params.amount_in_max <= 0 {
+            return Err(RouterError::InvalidAmount);
+        }
+        
+        let config = read_config(&env);
+        let router_addr = env.current_contract_address();
+        
+        let (pools, amounts_in) = Self::get_reverse_path_amounts(
+            &env,
+            &config.factory,
+            &params.token_in,
+            params.amount_out,
+            &params.path,
+        )?;
+        
+        let amount_in = amounts_in.get(0).unwrap();
+        if amount_in > params.amount_in_max {
+            return Err(RouterError::SlippageExceeded);
+        }
+        
+        let token_in_client = token::Client::new(&env, &params.token_in);
+        token_in_client.transfer(&sender, &router_addr, &params.amount_in_max);
+        
+        let mut current_token = params.token_in.clone();
+        let mut current_amount = amount_in;
+        let mut fee_tiers_used = Vec::new(&env);
+        
+        for i in 0..params.path.len() {
+            let hop = params.path.get(i).unwrap();
+            let is_final_hop = i == params.path.len() - 1;
+            
+            let hop_recipient = if is_final_hop {
+                params.recipient.clone()
+            } else {
+                router_addr.clone()
+            };
+            
+            // Each hop must cover what the next hop (or the caller) needs
+            let min_out = if is_final_hop {
+                params.amount_out
+            } else {
+                amounts_in.get(i + 1).unwrap()
+            };
+            
+            let result = Self::execute_swap_from_router(
+                &env,
+                &hop_recipient,
+                &current_token,
+                &hop.token,
+                current_amount,
+                min_out,
+                &pools.get(i).unwrap(),
+                hop.fee_bps,
+            )?;
+            
+            fee_tiers_used.push_back(hop.fee_bps);
+            
+            current_token = hop.token;
+            current_amount = result.amount_out;
+        }
+        
+        // Refund unused input
+        let refund = params.amount_in_max.saturating_sub(amount_in);
+        if refund > 0 {
+            token_in_client.transfer(&router_addr, &sender, &refund);
+        }
+        
+        emit_multihop_swap(
+            &env,
+            &sender,
+            &params.token_in,
+            &current_token,
+            amount_in,
+            current_amount,
+            params.path.len(),
+        );
+        
+        Ok(SwapResult {
+            amount_in,
+            amount_out: current_amount,
+            pools_used: pools,
+            fee_tiers_used,
+        })
+    }
+    
+    /// Run several router swaps atomically, in order
+    /// 
+    /// `ExactInputChained` and `MultihopChained` spend the previous call's
+    /// output, so a swap can feed the next one (send the first to `sender`,
+    /// which the next pulls from).
+    /// Returns each call's `SwapResult` as XDR. Any failing call reverts the
+    /// whole batch.
+    pub fn multicall(
+        env: Env,
+        sender: Address,
+        calls: Vec<RouterCall>,
+    ) -> Result<Vec<Bytes>, RouterError> {
+        sender.require_auth();
+        
+        if calls.is_empty() {
+            return Err(RouterError::EmptyCalls);
+        }
+        
+        let mut results = Vec::new(&env);
+        let mut last_amount_out: Option<i128> = None;
+        
+        for call in calls.iter() {
+            let result = match call {
+                RouterCall::ExactInput(params) => {
+                    Self::do_swap_exact_input(env.clone(), sender.clone(), params)?
+                }
+                RouterCall::ExactOutput(params) => {
+                    Self::do_swap_exact_output(env.clone(), sender.clone(), params)?
+                }
+                RouterCall::Multihop(params) => {
+                    Self::do_swap_multihop(env.clone(), sender.clone(), params)?
+                }
+                RouterCall::MultihopExactOutput(params) => {
+                    Self::do_swap_multihop_exact_output(env.clone(), sender.clone(), params)?
+                }
+                RouterCall::ExactInputChained(mut params) => {
+                    params.amount_in = last_amount_out.ok_or(RouterError::InvalidAmount)?;
+                    Self::do_swap_exact_input(env.clone(), sender.clone(), params)?
+                }
+                RouterCall::MultihopChained(mut params) => {
+                    params.amount_in = last_amount_out.ok_or(RouterError::InvalidAmount)?;
+                    Self::do_swap_multihop(env.clone(), sender.clone(), params)?
+                }
+            };
+            
+            last_amount_out = Some(result.amount_out);
+            results.push_back(result.to_xdr(&env));
+        }
+        
+        Ok(results)
+    }
+    
     // ========================================================
     // QUOTE FUNCTIONS 
     // ========================================================
@@ -405,7 +593,7 @@ impl BelugaRouter {
             fee_tiers
         };
         
-        Self::find_best_pool(&env, &config.factory, &token_in, &token_out, amount_in, &tiers)
+        Self::find_best_pool(&env, &mut QuoteCache::new(&env, &config.factory), &token_in, &token_out, amount_in, &tiers)
     }
     
     /// Get quotes from all available pools
@@ -428,10 +616,14 @@ impl BelugaRouter {
             fee_tiers
         };
         
-        Self::get_quotes_for_tiers(&env, &config.factory, &token_in, &token_out, amount_in, &tiers)
+        Self::get_quotes_for_tiers(&env, &mut QuoteCache::new(&env, &config.factory), &token_in, &token_out, amount_in, &tiers)
     }
     
     /// Get optimal split quote for large orders
+    /// 
+    /// Hands out `amount_in` in chunks, each to the pool whose marginal output
+    /// for that chunk is highest given what it already holds. Falls back to
+    /// the single best pool when splitting does not beat it.
     pub fn get_split_quote(
         env: Env,
         token_in: Address,
@@ -451,22 +643,106 @@ impl BelugaRouter {
             fee_tiers
         };
         
-        let best_quote = Self::find_best_pool(&env, &config.factory, &token_in, &token_out, amount_in, &tiers)?;
-        
-        // Simple implementation: single pool for now
-        // TODO: Implement proper split logic based on liquidity depth
-        let split = SplitQuote {
-            pool: best_quote.pool.clone(),
-            fee_bps: best_quote.fee_bps,
-            amount_in,
-            amount_out: best_quote.amount_out,
-        };
+        // Shared by the tier quotes and every chunk of the split search
+        let mut cache = QuoteCache::new(&env, &config.factory);
+        let best_quote = Self::find_best_pool(&env, &mut cache, &token_in, &token_out, amount_in, &tiers)?;
         
-        Ok(AggregatedQuote {
+        let single = AggregatedQuote {
             total_amount_in: amount_in,
             total_amount_out: best_quote.amount_out,
-            splits: vec![&env, split],
+            splits: vec![
+                &env,
+                SplitQuote {
+                    pool: best_quote.pool.clone(),
+                    fee_bps: best_quote.fee_bps,
+                    amount_in,
+                    amount_out: best_quote.amount_out,
+                },
+            ],
             is_split_recommended: false,
+        };
+        
+        if best_quote.all_quotes.len() < 2 {
+            return Ok(single);
+        }
+        
+        match Self::allocate_split(&env, &mut cache, &token_in, &token_out, amount_in, &best_quote.all_quotes) {
+            Some(split) if split.total_amount_out > single.total_amount_out => Ok(split),
+            _ => Ok(single),
+        }
+    }
+    
+    /// Manipulation-resistant quote from each pool's TWAP
+    /// 
+    /// Prices `amount_in` at the mean tick over the last `window_secs` (from
+    /// the pool's `get_twap_tick`) instead of the current price, so a swap
+    /// earlier in the same ledger cannot move it. The TWAP output is net of
+    /// the fee but ignores price impact; each quote also carries the spot
+    /// output for comparison. The best quote is the highest TWAP output.
+    pub fn get_twap_quote(
+        env: Env,
+        token_in: Address,
+        token_out: Address,
+        amount_in: i128,
+        window_secs: u32,
+        fee_tiers: Vec<u32>,
+    ) -> Result<TwapQuote, RouterError> {
+        if !is_initialized(&env) {
+            return Err(RouterError::NotInitialized);
+        }
+        
+        if amount_in <= 0 {
+            return Err(RouterError::InvalidAmount);
+        }
+        
+        if window_secs == 0 {
+            return Err(RouterError::InvalidTwapWindow);
+        }
+        
+        let config = read_config(&env);
+        
+        let tiers = if fee_tiers.is_empty() {
+            Self::default_fee_tiers(&env)
+        } else {
+            fee_tiers
+        };
+        
+        let mut cache = QuoteCache::new(&env, &config.factory);
+        let mut quotes: Vec<PoolTwapQuote> = Vec::new(&env);
+        for fee_bps in tiers.iter() {
+            let Some(pool) = cache.pool(&token_in, &token_out, fee_bps) else {
+                continue;
+            };
+            let spot_amount_out = cache
+                .quote(&token_in, &token_out, fee_bps, amount_in)
+                .map(|q| q.amount_out)
+                .unwrap_or(0);
+            quotes.push_back(Self::twap_quote_pool(
+                &env,
+                pool,
+                &token_in,
+                &token_out,
+                amount_in,
+                fee_bps,
+                window_secs,
+                spot_amount_out,
+            ));
+        }
+        
+        let mut best: Option<PoolTwapQuote> = None;
+        for q in quotes.iter() {
+            if best.as_ref().is_none_or(|b| q.twap_amount_out > b.twap_amount_out) {
+                best = Some(q);
+            }
+        }
+        let best = best.ok_or(RouterError::NoPoolsFound)?;
+        
+        Ok(TwapQuote {
+            pool: best.pool,
+            fee_bps: best.fee_bps,
+            twap_amount_out: best.twap_amount_out,
+            spot_amount_out: best.spot_amount_out,
+            all_quotes: quotes,
         })
     }
     
@@ -490,6 +766,11 @@ impl BelugaRouter {
         }
         
         let config = read_config(&env);
+        let mut cache = QuoteCache::new(&env, &config.factory);
+        
+        if cache.first_invalid_hop(&token_in, &path).is_some() {
+            return Err(RouterError::InvalidPath);
+        }
         
         let mut current_token = token_in;
         let mut current_amount = amount_in;
@@ -497,14 +778,7 @@ impl BelugaRouter {
         for i in 0..path.len() {
             let hop = path.get(i).unwrap();
             
-            let quote = Self::get_single_quote(
-                &env,
-                &config.factory,
-                &current_token,
-                &hop.token,
-                current_amount,
-                hop.fee_bps,
-            )?;
+            let quote = cache.quote(&current_token, &hop.token, hop.fee_bps, current_amount)?;
             
             current_token = hop.token;
             current_amount = quote.amount_out;
@@ -513,6 +787,25 @@ impl BelugaRouter {
         Ok(current_amount)
     }
     
+    /// Index of the first hop in `path` that can't be traded
+    /// 
+    /// A hop is invalid when its token repeats the token it swaps from or
+    /// the factory has no pool for the pair at its fee tier. `swap_multihop`
+    /// and `quote_multihop` fail with `InvalidPath` for such paths; this
+    /// names the hop. Returns `None` for a tradeable path.
+    pub fn find_invalid_hop(
+        env: Env,
+        token_in: Address,
+        path: Vec<Hop>,
+    ) -> Result<Option<u32>, RouterError> {
+        if !is_initialized(&env) {
+            return Err(RouterError::NotInitialized);
+        }
+        
+        let config = read_config(&env);
+        Ok(QuoteCache::new(&env, &config.factory).first_invalid_hop(&token_in, &path))
+    }
+    
     // ========================================================
     // VIEW FUNCTIONS
     // ========================================================
@@ -542,6 +835,14 @@ impl BelugaRouter {
     // INTERNAL HELPERS
     // ========================================================
     
+    /// Deadlines are ledger timestamps (seconds); 0 means no deadline
+    fn check_deadline(env: &Env, deadline: u64) -> Result<(), RouterError> {
+        if deadline != 0 && env.ledger().timestamp() > deadline {
+            return Err(RouterError::DeadlineExpired);
+        }
+        Ok(())
+    }
+    
     fn default_fee_tiers(env: &Env) -> Vec<u32> {
         vec![env, DEFAULT_FEE_TIERS[0], DEFAULT_FEE_TIERS[1], DEFAULT_FEE_TIERS[2]]
     }
@@ -570,8 +871,8 @@ impl BelugaRouter {
         pool: &Address,
         token_in: &Address,
         amount_in: i128,
-        sqrt_price_limit: u128,
-    ) -> Option<(i128, i128)> {
+        sqrt_price_limit: Option<u128>,
+    ) -> Option<(i128, i128, u32)> {
         let result: PreviewResultRaw = env.invoke_contract(
             pool,
             &Symbol::new(env, "preview_swap"),
@@ -585,34 +886,182 @@ impl BelugaRouter {
         );
         
         if result.is_valid {
-            Some((result.amount_out, result.price_impact_bps))
+            Some((result.amount_out_expected, result.price_impact_bps, result.steps))
         } else {
             None
         }
     }
     
-    fn find_best_pool(
+    /// Preview the input a pool needs for an exact output
+    fn preview_pool_swap_exact_output(
+        env: &Env,
+        pool: &Address,
+        token_in: &Address,
+        amount_out: i128,
+    ) -> Option<i128> {
+        let result: PreviewResultRaw = env.invoke_contract(
+            pool,
+            &Symbol::new(env, "preview_swap_exact_output"),
+            vec![
+                env,
+                token_in.clone().into_val(env),
+                amount_out.into_val(env),
+                0i128.into_val(env), // no input cap, checked by the caller
+                None::<u128>.into_val(env), // no price limit
+            ],
+        );
+        
+        if result.is_valid {
+            Some(result.amount_in_used)
+        } else {
+            None
+        }
+    }
+    
+    /// Sqrt price limit allowing the pool price to move at most
+    /// `max_slippage_bps` against the swapper; `None` (no limit) when unset
+    /// 
+    /// The price moves by the ratio (10000 ± bps) / 10000, so the sqrt price
+    /// moves by the square root of it.
+    fn slippage_sqrt_price_limit(
+        env: &Env,
+        pool: &Address,
+        token_in: &Address,
+        token_out: &Address,
+        max_slippage_bps: u32,
+    ) -> Option<u128> {
+        if max_slippage_bps == 0 {
+            return None;
+        }
+        
+        let (_, _, swapped) = sort_tokens(token_in, token_out);
+        let zero_for_one = !swapped;
+        let sqrt_price: u128 = env.invoke_contract(
+            pool,
+            &Symbol::new(env, "get_sqrt_price"),
+            vec![env],
+        );
+        
+        // Selling token0 pushes the price down, selling token1 pushes it up
+        let ratio_bps = if zero_for_one {
+            BPS_DENOMINATOR - max_slippage_bps as u128
+        } else {
+            BPS_DENOMINATOR + max_slippage_bps as u128
+        };
+        
+        // ratio < 2, so ratio in Q64 shifted to Q126 still fits in u128 and
+        // its square root is the sqrt factor in Q63
+        let ratio_x64 = (ratio_bps << 64) / BPS_DENOMINATOR;
+        let sqrt_factor_x63 = (ratio_x64 << 62).isqrt();
+        
+        Some(mul_div(env, sqrt_price, sqrt_factor_x63, 1u128 << 63).max(1))
+    }
+    
+    /// Walk a path backwards, sizing each hop's input from the output the
+    /// following hop needs
+    /// 
+    /// Returns `(pools, amounts_in)` in forward path order
+    fn get_reverse_path_amounts(
+        env: &Env,
+        factory: &Address,
+        token_in: &Address,
+        amount_out: i128,
+        path: &Vec<Hop>,
+    ) -> Result<(Vec<Address>, Vec<i128>), RouterError> {
+        let mut pools = Vec::new(env);
+        let mut amounts_in = Vec::new(env);
+        let mut amount_needed = amount_out;
+        
+        for i in (0..path.len()).rev() {
+            let hop = path.get(i).unwrap();
+            let hop_token_in = if i == 0 {
+                token_in.clone()
+            } else {
+                path.get(i - 1).unwrap().token
+            };
+            
+            let pool = Self::get_pool_address(env, factory, &hop_token_in, &hop.token, hop.fee_bps)
+                .ok_or(RouterError::PoolNotFound)?;
+            
+            amount_needed = Self::preview_pool_swap_exact_output(env, &pool, &hop_token_in, amount_needed)
+                .ok_or(RouterError::QuoteFailed)?;
+            
+            pools.push_front(pool);
+            amounts_in.push_front(amount_needed);
+        }
+        
+        Ok((pools, amounts_in))
+    }
+    
+    /// Find the pool needing the least input for an exact output
+    /// 
+    /// Returns `(pool, fee_bps, amount_in)`
+    fn find_best_pool_exact_output(
         env: &Env,
         factory: &Address,
         token_in: &Address,
         token_out: &Address,
+        amount_out: i128,
+        fee_tiers: &Vec<u32>,
+    ) -> Result<(Address, u32, i128), RouterError> {
+        let mut best: Option<(Address, u32, i128)> = None;
+        
+        for i in 0..fee_tiers.len() {
+            let fee_bps = fee_tiers.get(i).unwrap();
+            
+            let Some(pool) = Self::get_pool_address(env, factory, token_in, token_out, fee_bps) else {
+                continue;
+            };
+            
+            if let Some(amount_in) = Self::preview_pool_swap_exact_output(env, &pool, token_in, amount_out) {
+                let is_better = match &best {
+                    Some((_, _, best_in)) => amount_in < *best_in,
+                    None => true,
+                };
+                if is_better {
+                    best = Some((pool, fee_bps, amount_in));
+                }
+            }
+        }
+        
+        best.ok_or(RouterError::NoPoolsFound)
+    }
+    
+    fn find_best_pool(
+        env: &Env,
+        cache: &mut QuoteCache,
+        token_in: &Address,
+        token_out: &Address,
         amount_in: i128,
         fee_tiers: &Vec<u32>,
     ) -> Result<BestQuote, RouterError> {
-        let quotes = Self::get_quotes_for_tiers(env, factory, token_in, token_out, amount_in, fee_tiers)?;
+        let quotes = Self::get_quotes_for_tiers(env, cache, token_in, token_out, amount_in, fee_tiers)?;
         
         if quotes.is_empty() {
             return Err(RouterError::NoPoolsFound);
         }
         
         // Find best quote by output amount
-        let mut best_idx: u32 = 0;
         let mut best_out: i128 = 0;
+        for q in quotes.iter() {
+            best_out = best_out.max(q.amount_out);
+        }
         
+        // Among near-equal outputs, prefer the fewest tick crossings
+        let tolerance = mul_bps(env, best_out, NEAR_EQUAL_OUTPUT_BPS);
+        let mut best_idx: u32 = 0;
+        let mut best_key: Option<(u32, i128)> = None;
         for i in 0..quotes.len() {
             let q = quotes.get(i).unwrap();
-            if q.amount_out > best_out {
-                best_out = q.amount_out;
+            if q.amount_out < best_out - tolerance {
+                continue;
+            }
+            let is_better = match best_key {
+                Some((steps, out)) => q.steps < steps || (q.steps == steps && q.amount_out > out),
+                None => true,
+            };
+            if is_better {
+                best_key = Some((q.steps, q.amount_out));
                 best_idx = i;
             }
         }
@@ -628,9 +1077,86 @@ impl BelugaRouter {
         })
     }
     
+    /// Greedy marginal-output allocation of `amount_in` across `pools`
+    /// 
+    /// Returns None when a chunk cannot be quoted on any pool.
+    fn allocate_split(
+        env: &Env,
+        cache: &mut QuoteCache,
+        token_in: &Address,
+        token_out: &Address,
+        amount_in: i128,
+        pools: &Vec<PoolQuote>,
+    ) -> Option<AggregatedQuote> {
+        let chunk = amount_in / MAX_SPLIT_CHUNKS;
+        if chunk <= 0 {
+            return None;
+        }
+        
+        let mut allocated: Vec<i128> = Vec::new(env);
+        let mut outputs: Vec<i128> = Vec::new(env);
+        for _ in 0..pools.len() {
+            allocated.push_back(0);
+            outputs.push_back(0);
+        }
+        
+        let mut remaining = amount_in;
+        while remaining > 0 {
+            // Last chunk absorbs the rounding remainder
+            let size = if remaining < chunk * 2 { remaining } else { chunk };
+            
+            // Only the last winner's quote is new each round; the others
+            // come from the cache
+            let mut best: Option<(u32, i128, i128)> = None; // (index, new output, marginal)
+            for i in 0..pools.len() {
+                let fee_bps = pools.get(i).unwrap().fee_bps;
+                let current_in = allocated.get(i).unwrap();
+                let current_out = outputs.get(i).unwrap();
+                
+                if let Ok(quote) = cache.quote(token_in, token_out, fee_bps, current_in + size) {
+                    let out = quote.amount_out;
+                    let marginal = out - current_out;
+                    if best.is_none_or(|(_, _, m)| marginal > m) {
+                        best = Some((i, out, marginal));
+                    }
+                }
+            }
+            
+            let (index, out, _) = best?;
+            allocated.set(index, allocated.get(index).unwrap() + size);
+            outputs.set(index, out);
+            remaining -= size;
+        }
+        
+        let mut splits = Vec::new(env);
+        let mut total_out: i128 = 0;
+        for i in 0..pools.len() {
+            let amount = allocated.get(i).unwrap();
+            if amount == 0 {
+                continue;
+            }
+            let quote = pools.get(i).unwrap();
+            let out = outputs.get(i).unwrap();
+            total_out = total_out.saturating_add(out);
+            splits.push_back(SplitQuote {
+                pool: quote.pool,
+                fee_bps: quote.fee_bps,
+                amount_in: amount,
+                amount_out: out,
+            });
+        }
+        
+        Some(AggregatedQuote {
+            total_amount_in: amount_in,
+            total_amount_out: total_out,
+            is_split_recommended: splits.len() > 1,
+            splits,
+        })
+    }
+    
     fn get_quotes_for_tiers(
         env: &Env,
-        factory: &Address,
+        cache: &mut QuoteCache,
         token_in: &Address,
         token_out: &Address,
         amount_in: i128,
@@ -641,7 +1167,7 @@ impl BelugaRouter {
         for i in 0..fee_tiers.len() {
             let fee_bps = fee_tiers.get(i).unwrap();
             
-            if let Ok(quote) = Self::get_single_quote(env, factory, token_in, token_out, amount_in, fee_bps) {
+            if let Ok(quote) = cache.quote(token_in, token_out, fee_bps, amount_in) {
                 quotes.push_back(quote);
             }
         }
@@ -649,18 +1175,14 @@ impl BelugaRouter {
         Ok(quotes)
     }
     
-    fn get_single_quote(
+    fn quote_pool(
         env: &Env,
-        factory: &Address,
+        pool: Address,
         token_in: &Address,
-        token_out: &Address,
         amount_in: i128,
         fee_bps: u32,
     ) -> Result<PoolQuote, RouterError> {
-        let pool = Self::get_pool_address(env, factory, token_in, token_out, fee_bps)
-            .ok_or(RouterError::PoolNotFound)?;
-        
-        let (amount_out, price_impact) = Self::preview_pool_swap(env, &pool, token_in, amount_in, 0)
+        let (amount_out, price_impact, steps) = Self::preview_pool_swap(env, &pool, token_in, amount_in, None)
             .ok_or(RouterError::QuoteFailed)?;
         
         Ok(PoolQuote {
@@ -668,10 +1190,60 @@ impl BelugaRouter {
             fee_bps,
             amount_out,
             price_impact_bps: price_impact,
+            steps,
         })
     }
     
+    /// Price `amount_in` at a pool's mean tick over `window_secs`
+    #[allow(clippy::too_many_arguments)]
+    fn twap_quote_pool(
+        env: &Env,
+        pool: Address,
+        token_in: &Address,
+        token_out: &Address,
+        amount_in: i128,
+        fee_bps: u32,
+        window_secs: u32,
+        spot_amount_out: i128,
+    ) -> PoolTwapQuote {
+        // Averaged over the history the oracle holds, which may be shorter
+        // than the window
+        let twap_tick: i32 = env.invoke_contract(
+            &pool,
+            &Symbol::new(env, "get_twap_tick"),
+            vec![env, window_secs.into_val(env)],
+        );
+        
+        let (_, _, swapped) = sort_tokens(token_in, token_out);
+        let zero_for_one = !swapped;
+        
+        // Input after the fee, converted at price = sqrt_price^2
+        let net_in = mul_bps(env, amount_in, 10_000 - fee_bps) as u128;
+        let sqrt_price = get_sqrt_ratio_at_tick(twap_tick);
+        let out = if zero_for_one {
+            mul_div(env, mul_div(env, net_in, sqrt_price, ONE_X64), sqrt_price, ONE_X64)
+        } else {
+            mul_div(env, mul_div(env, net_in, ONE_X64, sqrt_price), ONE_X64, sqrt_price)
+        };
+        
+        PoolTwapQuote {
+            pool,
+            fee_bps,
+            twap_tick,
+            twap_amount_out: u128_to_i128_saturating(out),
+            spot_amount_out,
+        }
+    }
+    
     /// Execute swap pulling tokens from an external source (user)
+    /// 
+    /// When the source has approved the router for at least `amount_in`, the
+    /// pool pulls the input straight from the source through that allowance
+    /// (`swap_from`) and pays `recipient` directly, so neither token passes
+    /// through the router. Without the allowance the input is transferred to
+    /// the router first. Either way, input left over when the pool stops at
+    /// `sqrt_price_limit` goes back to the source.
+    #[allow(clippy::too_many_arguments)]
     fn execute_single_swap(
         env: &Env,
         token_source: &Address,
@@ -680,28 +1252,135 @@ impl BelugaRouter {
         token_out: &Address,
         amount_in: i128,
         amount_out_min: i128,
+        sqrt_price_limit: Option<u128>,
         pool: &Address,
         fee_bps: u32,
     ) -> Result<SwapResult, RouterError> {
         let router_addr = env.current_contract_address();
+        let token_in_client = token::Client::new(env, token_in);
+        
+        if token_in_client.allowance(token_source, &router_addr) >= amount_in {
+            return Self::execute_pool_swap_from(
+                env,
+                token_source,
+                recipient,
+                token_in,
+                amount_in,
+                amount_out_min,
+                sqrt_price_limit,
+                pool,
+                fee_bps,
+            );
+        }
         
         // Transfer token_in from source (user) to router
-        token::Client::new(env, token_in).transfer(token_source, &router_addr, &amount_in);
+        token_in_client.transfer(token_source, &router_addr, &amount_in);
         
         // Execute the actual swap via pool
-        Self::execute_pool_swap(
+        let result = Self::execute_pool_swap(
             env,
             recipient,
             token_in,
             token_out,
             amount_in,
             amount_out_min,
+            sqrt_price_limit,
             pool,
             fee_bps,
-        )
+        )?;
+        
+        let unspent = amount_in.saturating_sub(result.amount_in);
+        if unspent > 0 {
+            token_in_client.transfer(&router_addr, token_source, &unspent);
+        }
+        
+        Ok(result)
+    }
+    
+    /// Pool swap paid from `payer`'s allowance to the router
+    /// 
+    /// The pool spends the allowance with `transfer_from`, refunds unspent
+    /// input to `payer` and sends the output to `recipient`.
+    #[allow(clippy::too_many_arguments)]
+    fn execute_pool_swap_from(
+        env: &Env,
+        payer: &Address,
+        recipient: &Address,
+        token_in: &Address,
+        amount_in: i128,
+        amount_out_min: i128,
+        sqrt_price_limit: Option<u128>,
+        pool: &Address,
+        fee_bps: u32,
+    ) -> Result<SwapResult, RouterError> {
+        let router_addr = env.current_contract_address();
+        
+        // transfer_from is a sub-invocation of the pool and needs the
+        // router's explicit auth as the spender
+        env.authorize_as_current_contract(vec![
+            env,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: token_in.clone(),
+                    fn_name: Symbol::new(env, "transfer_from"),
+                    args: vec![
+                        env,
+                        router_addr.clone().into_val(env),
+                        payer.clone().into_val(env),
+                        pool.clone().into_val(env),
+                        amount_in.into_val(env),
+                    ],
+                },
+                sub_invocations: vec![env],
+            }),
+        ]);
+        
+        let swap_result: PoolSwapResult = env.invoke_contract(
+            pool,
+            &Symbol::new(env, "swap_from"),
+            vec![
+                env,
+                router_addr.into_val(env),
+                payer.clone().into_val(env),
+                recipient.clone().into_val(env),
+                token_in.clone().into_val(env),
+                amount_in.into_val(env),
+                amount_out_min.into_val(env),
+                sqrt_price_limit.into_val(env),
+                0u32.into_val(env), // no TWAP deviation guard
+            ],
+        );
+        
+        Ok(SwapResult {
+            amount_in: swap_result.amount_in,
+            amount_out: swap_result.amount_out,
+            pools_used: vec![env, pool.clone()],
+            fee_tiers_used: vec![env, fee_bps],
+        })
+    }
+    
+    /// Share of `amount_out_min` a split leg must return: its quoted output
+    /// scaled by `amount_out_min / total_quoted_out`
+    fn proportional_leg_min(
+        env: &Env,
+        quoted_out: i128,
+        amount_out_min: i128,
+        total_quoted_out: i128,
+    ) -> i128 {
+        if quoted_out <= 0 || amount_out_min <= 0 || total_quoted_out <= 0 {
+            return MIN_OUTPUT;
+        }
+        let share = mul_div(
+            env,
+            quoted_out as u128,
+            amount_out_min as u128,
+            total_quoted_out as u128,
+        );
+        (share.min(i128::MAX as u128) as i128).max(MIN_OUTPUT)
     }
     
     /// Execute swap using tokens already in router
+    #[allow(clippy::too_many_arguments)]
     fn execute_swap_from_router(
         env: &Env,
         recipient: &Address,
@@ -719,12 +1398,14 @@ impl BelugaRouter {
             token_out,
             amount_in,
             amount_out_min,
+            None, // no price limit
             pool,
             fee_bps,
         )
     }
     
     /// Common pool swap execution logic
+    #[allow(clippy::too_many_arguments)]
     fn execute_pool_swap(
         env: &Env,
         recipient: &Address,
@@ -732,19 +1413,30 @@ impl BelugaRouter {
         token_out: &Address,
         amount_in: i128,
         amount_out_min: i128,
+        sqrt_price_limit: Option<u128>,
         pool: &Address,
         fee_bps: u32,
     ) -> Result<SwapResult, RouterError> {
         let router_addr = env.current_contract_address();
-        let current_ledger = env.ledger().sequence();
         
-        // Minimal approval - just enough for this transaction
-        token::Client::new(env, token_in).approve(
-            &router_addr,
-            pool,
-            &amount_in,
-            &(current_ledger + APPROVAL_LEDGER_BUFFER),
-        );
+        // The pool pulls token_in from the router with a plain transfer, which is
+        // a sub-invocation of the pool and needs the router's explicit auth
+        env.authorize_as_current_contract(vec![
+            env,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: token_in.clone(),
+                    fn_name: Symbol::new(env, "transfer"),
+                    args: vec![
+                        env,
+                        router_addr.clone().into_val(env),
+                        pool.clone().into_val(env),
+                        amount_in.into_val(env),
+                    ],
+                },
+                sub_invocations: vec![env],
+            }),
+        ]);
         
         // Call pool swap - router is the sender
         let swap_result: PoolSwapResult = env.invoke_contract(
@@ -756,7 +1448,8 @@ impl BelugaRouter {
                 token_in.clone().into_val(env),
                 amount_in.into_val(env),
                 amount_out_min.into_val(env),
-                0u128.into_val(env), // no price limit
+                sqrt_price_limit.into_val(env),
+                0u32.into_val(env), // no TWAP deviation guard
             ],
         );
         
@@ -778,20 +1471,230 @@ impl BelugaRouter {
     }
 }
 
+/// Entrypoints taking more than seven arguments besides `env`
+/// 
+/// `#[contractimpl]` also generates a `BelugaRouterArgs` builder for every
+/// entrypoint without copying the function's attributes, so a
+/// `too_many_arguments` allow on the function does not reach it. These
+/// entrypoints get their own block, with the allow on the module.
+#[allow(clippy::too_many_arguments)]
+mod wide_entrypoints {
+    use super::*;
+
+    #[contractimpl]
+    impl BelugaRouter {
+        // ========================================================
+        // SWAP FUNCTIONS 
+        // ========================================================
+        
+        /// Split swap across multiple fee tiers for better execution on large orders
+        /// 
+        /// Splits the input across pools to minimize price impact. `per_leg_min_out`
+        /// bounds each leg's output (one entry per split); when empty, each leg must
+        /// return its quoted share of `amount_out_min`.
+        pub fn swap_split(
+            env: Env,
+            sender: Address,
+            token_in: Address,
+            token_out: Address,
+            amount_in: i128,
+            amount_out_min: i128,
+            splits: Vec<SplitQuote>,
+            per_leg_min_out: Vec<i128>,
+            recipient: Address,
+            deadline: u64,
+        ) -> Result<SwapResult, RouterError> {
+            sender.require_auth();
+            
+            if !is_initialized(&env) {
+                return Err(RouterError::NotInitialized);
+            }
+            
+            Self::check_deadline(&env, deadline)?;
+            
+            if splits.is_empty() {
+                return Err(RouterError::EmptySplits);
+            }
+            
+            if !per_leg_min_out.is_empty() && per_leg_min_out.len() != splits.len() {
+                return Err(RouterError::InvalidLegMinimums);
+            }
+            
+            // Validate total split amounts
+            let mut total_split_in: i128 = 0;
+            let mut total_quoted_out: i128 = 0;
+            let mut valid_splits: u32 = 0;
+            
+            for i in 0..splits.len() {
+                let split = splits.get(i).unwrap();
+                if split.amount_in > 0 {
+                    total_split_in = total_split_in.saturating_add(split.amount_in);
+                    total_quoted_out = total_quoted_out.saturating_add(split.amount_out.max(0));
+                    valid_splits += 1;
+                }
+            }
+            
+            if total_split_in != amount_in {
+                return Err(RouterError::SplitAmountMismatch);
+            }
+            
+            if valid_splits == 0 {
+                return Err(RouterError::EmptySplits);
+            }
+            
+            // Execute each split - all pull from user (same token)
+            let mut total_out: i128 = 0;
+            let mut pools_used = Vec::new(&env);
+            let mut fee_tiers_used = Vec::new(&env);
+            
+            for i in 0..splits.len() {
+                let split = splits.get(i).unwrap();
+                
+                // Skip zero-amount splits
+                if split.amount_in <= 0 {
+                    continue;
+                }
+                
+                let leg_min = if per_leg_min_out.is_empty() {
+                    Self::proportional_leg_min(&env, split.amount_out, amount_out_min, total_quoted_out)
+                } else {
+                    per_leg_min_out.get(i).unwrap()
+                };
+                
+                let result = Self::execute_single_swap(
+                    &env,
+                    &sender,
+                    &recipient,
+                    &token_in,
+                    &token_out,
+                    split.amount_in,
+                    MIN_OUTPUT, 
+                    None, // no price limit
+                    &split.pool,
+                    split.fee_bps,
+                )?;
+                
+                // A pool moved since the quote cannot skew a single leg
+                if result.amount_out < leg_min {
+                    return Err(RouterError::SlippageExceeded);
+                }
+                
+                total_out = total_out.saturating_add(result.amount_out);
+                pools_used.push_back(split.pool.clone());
+                fee_tiers_used.push_back(split.fee_bps);
+            }
+            
+            // Final slippage check on total output
+            if total_out < amount_out_min {
+                return Err(RouterError::SlippageExceeded);
+            }
+            
+            emit_split_swap(
+                &env,
+                &sender,
+                &token_in,
+                &token_out,
+                amount_in,
+                total_out,
+                valid_splits,
+            );
+            
+            Ok(SwapResult {
+                amount_in,
+                amount_out: total_out,
+                pools_used,
+                fee_tiers_used,
+            })
+        }
+    }
+}
+
+// ============================================================
+// QUOTE CACHE
+// ============================================================
+
+/// Pool lookups and quotes memoized for the length of one router call
+/// 
+/// Quotes are keyed by `(token_in, token_out, fee_bps, amount_in)` and only
+/// stay valid while no swap has run, so a cache must not outlive the quoting
+/// phase of the call that created it.
+struct QuoteCache {
+    env: Env,
+    factory: Address,
+    pools: Map<(Address, Address, u32), Option<Address>>,
+    quotes: Map<(Address, Address, u32, i128), Option<PoolQuote>>,
+}
+
+impl QuoteCache {
+    fn new(env: &Env, factory: &Address) -> Self {
+        Self {
+            env: env.clone(),
+            factory: factory.clone(),
+            pools: Map::new(env),
+            quotes: Map::new(env),
+        }
+    }
+    
+    /// Pool for a pair and fee tier (`get_pool_address` on the factory)
+    fn pool(&mut self, token_in: &Address, token_out: &Address, fee_bps: u32) -> Option<Address> {
+        let key = (token_in.clone(), token_out.clone(), fee_bps);
+        if let Some(pool) = self.pools.get(key.clone()) {
+            return pool;
+        }
+        let pool = BelugaRouter::get_pool_address(&self.env, &self.factory, token_in, token_out, fee_bps);
+        self.pools.set(key, pool.clone());
+        pool
+    }
+    
+    /// Index of the first hop whose tokens don't form a pool pair, walking
+    /// the path from `token_in`
+    fn first_invalid_hop(&mut self, token_in: &Address, path: &Vec<Hop>) -> Option<u32> {
+        let mut current_token = token_in.clone();
+        for i in 0..path.len() {
+            let hop = path.get(i).unwrap();
+            if hop.token == current_token || self.pool(&current_token, &hop.token, hop.fee_bps).is_none() {
+                return Some(i);
+            }
+            current_token = hop.token;
+        }
+        None
+    }
+    
+    /// Exact-input quote on the pool for a pair and fee tier
+    fn quote(
+        &mut self,
+        token_in: &Address,
+        token_out: &Address,
+        fee_bps: u32,
+        amount_in: i128,
+    ) -> Result<PoolQuote, RouterError> {
+        let pool = self.pool(token_in, token_out, fee_bps).ok_or(RouterError::PoolNotFound)?;
+        
+        let key = (token_in.clone(), token_out.clone(), fee_bps, amount_in);
+        if let Some(quote) = self.quotes.get(key.clone()) {
+            return quote.ok_or(RouterError::QuoteFailed);
+        }
+        let quote = BelugaRouter::quote_pool(&self.env, pool, token_in, amount_in, fee_bps).ok();
+        self.quotes.set(key, quote.clone());
+        quote.ok_or(RouterError::QuoteFailed)
+    }
+}
+
 // ============================================================
 // HELPER TYPES FOR CROSS-CONTRACT CALLS
 // ============================================================
 
-/// Raw preview result from pool
+/// Raw preview result from pool (mirrors the pool's `PreviewResult` fields)
 #[soroban_sdk::contracttype]
 #[derive(Clone, Debug)]
 struct PreviewResultRaw {
-    pub is_valid: bool,
-    pub amount_in: i128,
-    pub amount_out: i128,
-    pub fee_amount: i128,
+    pub amount_in_used: i128,
+    pub amount_out_expected: i128,
+    pub fee_paid: i128,
     pub price_impact_bps: i128,
-    pub error_code: Symbol,
+    pub is_valid: bool,
+    pub error_message: Option<Symbol>,
+    pub steps: u32,
 }
 
 /// Raw swap result from pool
@@ -802,4 +1705,5 @@ struct PoolSwapResult {
     pub amount_out: i128,
     pub current_tick: i32,
     pub sqrt_price_x64: u128,
+    pub amount_in_remaining: i128,
 }
\ No newline at end of file
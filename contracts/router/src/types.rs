@@ -31,12 +31,14 @@ pub struct ExactInputParams {
     pub amount_in: i128,
     /// Minimum amount of token_out (slippage protection)
     pub amount_out_min: i128,
+    /// Maximum price move in bps before the pool stops the swap (0 = no limit)
+    pub max_slippage_bps: u32,
     /// Fee tiers to try (e.g., [5, 30, 100] for 0.05%, 0.3%, 1%)
     pub fee_tiers: Vec<u32>,
     /// Recipient address
     pub recipient: Address,
-    /// Deadline (ledger sequence)
-    pub deadline: u32,
+    /// Deadline (ledger timestamp in seconds, 0 = none)
+    pub deadline: u64,
 }
 
 /// Parameters for exact output swap
@@ -55,8 +57,8 @@ pub struct ExactOutputParams {
     pub fee_tiers: Vec<u32>,
     /// Recipient address
     pub recipient: Address,
-    /// Deadline (ledger sequence)
-    pub deadline: u32,
+    /// Deadline (ledger timestamp in seconds, 0 = none)
+    pub deadline: u64,
 }
 
 /// Single hop in a multi-hop path
@@ -84,8 +86,41 @@ pub struct MultihopExactInputParams {
     pub amount_out_min: i128,
     /// Recipient address
     pub recipient: Address,
-    /// Deadline (ledger sequence)
-    pub deadline: u32,
+    /// Deadline (ledger timestamp in seconds, 0 = none)
+    pub deadline: u64,
+}
+
+/// Parameters for multi-hop exact output swap
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MultihopExactOutputParams {
+    /// Token to start with
+    pub token_in: Address,
+    /// Exact amount of the final token desired
+    pub amount_out: i128,
+    /// Path of hops (intermediate tokens + fees)
+    /// Final element's token is the output token
+    pub path: Vec<Hop>,
+    /// Maximum amount of token_in willing to spend
+    pub amount_in_max: i128,
+    /// Recipient address
+    pub recipient: Address,
+    /// Deadline (ledger timestamp in seconds, 0 = none)
+    pub deadline: u64,
+}
+
+/// One router operation in a `multicall` batch
+#[contracttype]
+#[derive(Clone, Debug)]
+pub enum RouterCall {
+    ExactInput(ExactInputParams),
+    ExactOutput(ExactOutputParams),
+    Multihop(MultihopExactInputParams),
+    MultihopExactOutput(MultihopExactOutputParams),
+    /// `ExactInput` spending the previous call's output; `amount_in` is ignored
+    ExactInputChained(ExactInputParams),
+    /// `Multihop` spending the previous call's output; `amount_in` is ignored
+    MultihopChained(MultihopExactInputParams),
 }
 
 // ============================================================
@@ -104,6 +139,8 @@ pub struct PoolQuote {
     pub amount_out: i128,
     /// Price impact in bps
     pub price_impact_bps: i128,
+    /// Tick boundaries the swap would cross (execution cost)
+    pub steps: u32,
 }
 
 /// Best quote result aggregating multiple pools
@@ -122,6 +159,38 @@ pub struct BestQuote {
     pub all_quotes: Vec<PoolQuote>,
 }
 
+/// Spot and TWAP quote from a single pool
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PoolTwapQuote {
+    /// Pool address
+    pub pool: Address,
+    /// Fee tier in bps
+    pub fee_bps: u32,
+    /// Mean tick over the window
+    pub twap_tick: i32,
+    /// Output at the TWAP price, net of the fee, ignoring price impact
+    pub twap_amount_out: i128,
+    /// Output `preview_swap` gives against the current state (0 if it fails)
+    pub spot_amount_out: i128,
+}
+
+/// Best TWAP quote across fee tiers
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct TwapQuote {
+    /// Pool with the highest TWAP output
+    pub pool: Address,
+    /// Fee tier of that pool
+    pub fee_bps: u32,
+    /// Its output at the TWAP price
+    pub twap_amount_out: i128,
+    /// Its output at the spot state, for comparison
+    pub spot_amount_out: i128,
+    /// All pool quotes for comparison
+    pub all_quotes: Vec<PoolTwapQuote>,
+}
+
 /// Split quote for splitting across multiple pools
 #[contracttype]
 #[derive(Clone, Debug)]
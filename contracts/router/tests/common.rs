@@ -0,0 +1,117 @@
+#![allow(dead_code)]
+
+use belugaswap_pool::{BelugaPool, BelugaPoolClient};
+use belugaswap_router::{BelugaRouter, BelugaRouterClient};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, testutils::Address as _, token::StellarAssetClient,
+    Address, Env,
+};
+
+// Test constants
+pub const DEFAULT_FEE_BPS: u32 = 30; // 0.30%
+pub const DEFAULT_TICK_SPACING: i32 = 60;
+pub const DEFAULT_SQRT_PRICE_X64: u128 = 1u128 << 64; // Price = 1.0
+pub const DEFAULT_LIQUIDITY_AMOUNT: i128 = 100_000_000_000;
+
+#[contracttype]
+enum MockKey {
+    Pool(Address, Address, u32),
+    Disabled(Address),
+}
+
+/// Minimal factory stub: a pool registry plus the hooks pools call back into.
+#[contract]
+pub struct MockFactory;
+
+#[contractimpl]
+impl MockFactory {
+    pub fn register_pool(env: Env, token_a: Address, token_b: Address, fee_bps: u32, pool: Address) {
+        let storage = env.storage().instance();
+        storage.set(&MockKey::Pool(token_a.clone(), token_b.clone(), fee_bps), &pool);
+        storage.set(&MockKey::Pool(token_b, token_a, fee_bps), &pool);
+    }
+
+    /// Hide a pool from lookups, like the real factory's `set_pool_enabled`
+    pub fn set_pool_enabled(env: Env, pool: Address, enabled: bool) {
+        env.storage().instance().set(&MockKey::Disabled(pool), &!enabled);
+    }
+
+    pub fn get_pool_address(env: Env, token_a: Address, token_b: Address, fee_bps: u32) -> Option<Address> {
+        let storage = env.storage().instance();
+        let pool: Option<Address> = storage.get(&MockKey::Pool(token_a, token_b, fee_bps));
+        pool.filter(|p| !storage.get(&MockKey::Disabled(p.clone())).unwrap_or(false))
+    }
+
+    pub fn is_creator_fee_active(_env: Env, _pool: Address, _creator: Address) -> bool {
+        false
+    }
+
+    pub fn is_liquidity_locked(
+        _env: Env,
+        _pool: Address,
+        _creator: Address,
+        _lower: i32,
+        _upper: i32,
+    ) -> bool {
+        false
+    }
+}
+
+/// Setup router wired to a mock factory
+pub fn setup_router(env: &Env) -> (BelugaRouterClient<'_>, MockFactoryClient<'_>) {
+    let admin = Address::generate(env);
+    let factory_id = env.register(MockFactory, ());
+    let router_id = env.register(BelugaRouter, ());
+    
+    let router = BelugaRouterClient::new(env, &router_id);
+    router.initialize(&factory_id, &admin);
+    
+    (router, MockFactoryClient::new(env, &factory_id))
+}
+
+/// Create a pool at price 1.0, register it and seed it with liquidity
+pub fn create_pool(
+    env: &Env,
+    factory: &MockFactoryClient,
+    router: &Address,
+    token_a: &Address,
+    token_b: &Address,
+    fee_bps: u32,
+    liquidity_amount: i128,
+) -> BelugaPoolClient<'static> {
+    let creator = Address::generate(env);
+    let pool_id = env.register(BelugaPool, ());
+    let pool = BelugaPoolClient::new(env, &pool_id);
+    
+    pool.initialize(
+        &factory.address,
+        router,
+        &creator,
+        token_a,
+        token_b,
+        &fee_bps,
+        &100,
+        &DEFAULT_SQRT_PRICE_X64,
+        &0,
+        &DEFAULT_TICK_SPACING,
+    );
+    factory.register_pool(token_a, token_b, &fee_bps, &pool_id);
+    
+    let lp = Address::generate(env);
+    mint_tokens(env, token_a, &lp, liquidity_amount);
+    mint_tokens(env, token_b, &lp, liquidity_amount);
+    pool.add_liquidity(&lp, &-600, &600, &liquidity_amount, &liquidity_amount, &0, &0);
+    
+    pool
+}
+
+/// Create a test token
+pub fn create_token(env: &Env) -> Address {
+    let admin = Address::generate(env);
+    env.register_stellar_asset_contract_v2(admin).address()
+}
+
+/// Mint tokens to an address
+pub fn mint_tokens(env: &Env, token: &Address, to: &Address, amount: i128) {
+    StellarAssetClient::new(env, token).mint(to, &amount);
+}
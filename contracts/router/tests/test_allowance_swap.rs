@@ -0,0 +1,156 @@
+mod common;
+
+use belugaswap_router::{BelugaRouterClient, ExactInputParams, Hop, MultihopExactInputParams};
+use soroban_sdk::{
+    testutils::{Address as _, Events as _},
+    token::TokenClient,
+    vec, Address, Env, TryFromVal,
+};
+
+/// Router with a single default pool; returns (router, token_in, token_out)
+fn setup(env: &Env) -> (BelugaRouterClient<'_>, Address, Address) {
+    env.mock_all_auths();
+
+    let (router, factory) = common::setup_router(env);
+    let token_in = common::create_token(env);
+    let token_out = common::create_token(env);
+    common::create_pool(
+        env,
+        &factory,
+        &router.address,
+        &token_in,
+        &token_out,
+        common::DEFAULT_FEE_BPS,
+        common::DEFAULT_LIQUIDITY_AMOUNT,
+    );
+
+    (router, token_in, token_out)
+}
+
+fn params(env: &Env, token_in: &Address, token_out: &Address, recipient: &Address, amount_in: i128) -> ExactInputParams {
+    ExactInputParams {
+        token_in: token_in.clone(),
+        token_out: token_out.clone(),
+        amount_in,
+        amount_out_min: 0,
+        max_slippage_bps: 0,
+        fee_tiers: vec![env, common::DEFAULT_FEE_BPS],
+        recipient: recipient.clone(),
+        deadline: 0,
+    }
+}
+
+/// Whether any event published by `token` names `who` in its topics
+fn token_events_touch(env: &Env, token: &Address, who: &Address) -> bool {
+    env.events().all().iter().any(|(contract, topics, _)| {
+        &contract == token
+            && topics.iter().any(|t| Address::try_from_val(env, &t).is_ok_and(|a| &a == who))
+    })
+}
+
+#[test]
+fn test_allowance_swap_skips_router_custody() {
+    let env = Env::default();
+    let (router, token_in, token_out) = setup(&env);
+
+    let amount_in: i128 = 100_000_000;
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    common::mint_tokens(&env, &token_in, &sender, amount_in);
+    let token_in_client = TokenClient::new(&env, &token_in);
+    token_in_client.approve(&sender, &router.address, &amount_in, &(env.ledger().sequence() + 100));
+
+    let result = router.swap_exact_input(&sender, &params(&env, &token_in, &token_out, &recipient, amount_in));
+
+    // Neither token moved in or out of the router
+    assert!(!token_events_touch(&env, &token_in, &router.address));
+    assert!(!token_events_touch(&env, &token_out, &router.address));
+
+    // Settled straight to the recipient, allowance spent
+    assert_eq!(result.amount_in, amount_in);
+    assert!(result.amount_out > 0);
+    assert_eq!(TokenClient::new(&env, &token_out).balance(&recipient), result.amount_out);
+    assert_eq!(token_in_client.balance(&sender), 0);
+    assert_eq!(token_in_client.allowance(&sender, &router.address), 0);
+    assert_eq!(token_in_client.balance(&router.address), 0);
+    assert_eq!(TokenClient::new(&env, &token_out).balance(&router.address), 0);
+}
+
+#[test]
+fn test_allowance_swap_refunds_unspent_to_sender() {
+    let env = Env::default();
+    let (router, token_in, token_out) = setup(&env);
+
+    // Large enough to hit a 1% price limit
+    let amount_in: i128 = 20_000_000_000;
+    let sender = Address::generate(&env);
+    common::mint_tokens(&env, &token_in, &sender, amount_in);
+    let token_in_client = TokenClient::new(&env, &token_in);
+    token_in_client.approve(&sender, &router.address, &amount_in, &(env.ledger().sequence() + 100));
+
+    let mut p = params(&env, &token_in, &token_out, &sender, amount_in);
+    p.max_slippage_bps = 100;
+    let result = router.swap_exact_input(&sender, &p);
+
+    assert!(result.amount_in < amount_in);
+    assert!(!token_events_touch(&env, &token_in, &router.address));
+    assert_eq!(token_in_client.balance(&sender), amount_in - result.amount_in);
+    assert_eq!(TokenClient::new(&env, &token_out).balance(&sender), result.amount_out);
+}
+
+#[test]
+fn test_swap_without_allowance_still_settles() {
+    let env = Env::default();
+    let (router, token_in, token_out) = setup(&env);
+
+    let amount_in: i128 = 100_000_000;
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    common::mint_tokens(&env, &token_in, &sender, amount_in);
+
+    // Allowance below amount_in: the router takes custody instead
+    TokenClient::new(&env, &token_in).approve(&sender, &router.address, &(amount_in - 1), &(env.ledger().sequence() + 100));
+    let result = router.swap_exact_input(&sender, &params(&env, &token_in, &token_out, &recipient, amount_in));
+
+    assert!(token_events_touch(&env, &token_in, &router.address));
+    assert_eq!(TokenClient::new(&env, &token_out).balance(&recipient), result.amount_out);
+    assert_eq!(TokenClient::new(&env, &token_in).balance(&router.address), 0);
+    assert_eq!(TokenClient::new(&env, &token_out).balance(&router.address), 0);
+}
+
+#[test]
+fn test_allowance_multihop_first_hop_pulls_from_sender() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (router, factory) = common::setup_router(&env);
+    let token_a = common::create_token(&env);
+    let token_b = common::create_token(&env);
+    let token_c = common::create_token(&env);
+    for (x, y) in [(&token_a, &token_b), (&token_b, &token_c)] {
+        common::create_pool(&env, &factory, &router.address, x, y, common::DEFAULT_FEE_BPS, common::DEFAULT_LIQUIDITY_AMOUNT);
+    }
+
+    let amount_in: i128 = 100_000_000;
+    let sender = Address::generate(&env);
+    common::mint_tokens(&env, &token_a, &sender, amount_in);
+    TokenClient::new(&env, &token_a).approve(&sender, &router.address, &amount_in, &(env.ledger().sequence() + 100));
+
+    let result = router.swap_multihop(&sender, &MultihopExactInputParams {
+        token_in: token_a.clone(),
+        amount_in,
+        amount_out_min: 0,
+        path: vec![
+            &env,
+            Hop { token: token_b.clone(), fee_bps: common::DEFAULT_FEE_BPS },
+            Hop { token: token_c.clone(), fee_bps: common::DEFAULT_FEE_BPS },
+        ],
+        recipient: sender.clone(),
+        deadline: 0,
+    });
+
+    // token_a never reaches the router; the intermediate token still does
+    assert!(!token_events_touch(&env, &token_a, &router.address));
+    assert_eq!(TokenClient::new(&env, &token_c).balance(&sender), result.amount_out);
+    assert_eq!(TokenClient::new(&env, &token_b).balance(&router.address), 0);
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use belugaswap_router::{ExactInputParams, Hop, MultihopExactInputParams, SplitQuote};
+use soroban_sdk::{testutils::{Address as _, Ledger}, vec, Address, Env};
+
+const START_TS: u64 = 1_700_000_000;
+
+/// Router with a single funded pool, ledger clock at START_TS
+fn setup(env: &Env) -> (belugaswap_router::BelugaRouterClient<'_>, Address, Address, Address, Address) {
+    env.mock_all_auths();
+    env.ledger().set_timestamp(START_TS);
+    
+    let (router, factory) = common::setup_router(env);
+    let token_in = common::create_token(env);
+    let token_out = common::create_token(env);
+    let pool = common::create_pool(
+        env,
+        &factory,
+        &router.address,
+        &token_in,
+        &token_out,
+        common::DEFAULT_FEE_BPS,
+        common::DEFAULT_LIQUIDITY_AMOUNT,
+    );
+    
+    let sender = Address::generate(env);
+    common::mint_tokens(env, &token_in, &sender, 1_000_000_000);
+    
+    (router, pool.address, token_in, token_out, sender)
+}
+
+fn exact_input_params(env: &Env, token_in: &Address, token_out: &Address, recipient: &Address, deadline: u64) -> ExactInputParams {
+    ExactInputParams {
+        token_in: token_in.clone(),
+        token_out: token_out.clone(),
+        amount_in: 1_000_000,
+        amount_out_min: 0,
+        max_slippage_bps: 0,
+        fee_tiers: vec![env, common::DEFAULT_FEE_BPS],
+        recipient: recipient.clone(),
+        deadline,
+    }
+}
+
+#[test]
+fn test_exact_input_before_deadline() {
+    let env = Env::default();
+    let (router, _, token_in, token_out, sender) = setup(&env);
+    
+    let params = exact_input_params(&env, &token_in, &token_out, &sender, START_TS + 60);
+    let result = router.swap_exact_input(&sender, &params);
+    assert!(result.amount_out > 0);
+}
+
+#[test]
+fn test_exact_input_zero_deadline_never_expires() {
+    let env = Env::default();
+    let (router, _, token_in, token_out, sender) = setup(&env);
+    env.ledger().set_timestamp(u64::MAX / 2);
+    
+    let params = exact_input_params(&env, &token_in, &token_out, &sender, 0);
+    let result = router.swap_exact_input(&sender, &params);
+    assert!(result.amount_out > 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn test_exact_input_deadline_expired() {
+    let env = Env::default();
+    let (router, _, token_in, token_out, sender) = setup(&env);
+    env.ledger().set_timestamp(START_TS + 61);
+    
+    let params = exact_input_params(&env, &token_in, &token_out, &sender, START_TS + 60);
+    router.swap_exact_input(&sender, &params);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn test_split_deadline_expired() {
+    let env = Env::default();
+    let (router, pool, token_in, token_out, sender) = setup(&env);
+    env.ledger().set_timestamp(START_TS + 61);
+    
+    let splits = vec![
+        &env,
+        SplitQuote {
+            pool,
+            fee_bps: common::DEFAULT_FEE_BPS,
+            amount_in: 1_000_000,
+            amount_out: 0,
+        },
+    ];
+    router.swap_split(&sender, &token_in, &token_out, &1_000_000, &0, &splits, &vec![&env], &sender, &(START_TS + 60));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn test_multihop_deadline_expired() {
+    let env = Env::default();
+    let (router, _, token_in, token_out, sender) = setup(&env);
+    env.ledger().set_timestamp(START_TS + 61);
+    
+    router.swap_multihop(
+        &sender,
+        &MultihopExactInputParams {
+            token_in,
+            amount_in: 1_000_000,
+            path: vec![&env, Hop { token: token_out, fee_bps: common::DEFAULT_FEE_BPS }],
+            amount_out_min: 0,
+            recipient: sender.clone(),
+            deadline: START_TS + 60,
+        },
+    );
+}
@@ -0,0 +1,36 @@
+mod common;
+
+use soroban_sdk::{testutils::Address as _, vec, Address, Env};
+
+#[test]
+fn test_disabled_pool_skipped_by_quotes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (router, factory) = common::setup_router(&env);
+    let token_in = common::create_token(&env);
+    let token_out = common::create_token(&env);
+    let deep = common::create_pool(&env, &factory, &router.address, &token_in, &token_out, 5, 100_000_000_000);
+    common::create_pool(&env, &factory, &router.address, &token_in, &token_out, 30, 50_000_000_000);
+
+    // An LP with funds in the pool that is about to be disabled
+    let lp = Address::generate(&env);
+    common::mint_tokens(&env, &token_in, &lp, 1_000_000_000);
+    common::mint_tokens(&env, &token_out, &lp, 1_000_000_000);
+    let (liquidity, _, _) = deep.add_liquidity(&lp, &-600, &600, &1_000_000_000, &1_000_000_000, &0, &0);
+
+    let amount_in: i128 = 1_000_000;
+    let best = router.get_best_quote(&token_in, &token_out, &amount_in, &vec![&env]);
+    assert_eq!(best.pool, deep.address);
+
+    factory.set_pool_enabled(&deep.address, &false);
+
+    let best = router.get_best_quote(&token_in, &token_out, &amount_in, &vec![&env]);
+    assert_eq!(best.fee_bps, 30);
+    assert_ne!(best.pool, deep.address);
+    assert_eq!(best.all_quotes.len(), 1);
+
+    // LPs can still exit the disabled pool directly
+    let (amount0, amount1) = deep.remove_liquidity(&lp, &-600, &600, &liquidity, &0, &0);
+    assert!(amount0 > 0 && amount1 > 0);
+}
@@ -0,0 +1,119 @@
+mod common;
+
+use belugaswap_router::{ExactInputParams, RouterCall, RouterError, SwapResult};
+use soroban_sdk::{testutils::Address as _, token::TokenClient, vec, xdr::FromXdr, Address, Env};
+
+/// Pools A/B and B/C, plus a sender holding `amount` of A
+fn setup(env: &Env, amount: i128) -> (belugaswap_router::BelugaRouterClient<'_>, Address, Address, Address, Address) {
+    let (router, factory) = common::setup_router(env);
+    let token_a = common::create_token(env);
+    let token_b = common::create_token(env);
+    let token_c = common::create_token(env);
+    for (x, y) in [(&token_a, &token_b), (&token_b, &token_c)] {
+        common::create_pool(env, &factory, &router.address, x, y, common::DEFAULT_FEE_BPS, common::DEFAULT_LIQUIDITY_AMOUNT);
+    }
+
+    let sender = Address::generate(env);
+    common::mint_tokens(env, &token_a, &sender, amount);
+    (router, token_a, token_b, token_c, sender)
+}
+
+fn exact_input(env: &Env, token_in: &Address, token_out: &Address, amount_in: i128, amount_out_min: i128, recipient: &Address) -> ExactInputParams {
+    ExactInputParams {
+        token_in: token_in.clone(),
+        token_out: token_out.clone(),
+        amount_in,
+        amount_out_min,
+        max_slippage_bps: 0,
+        fee_tiers: vec![env, common::DEFAULT_FEE_BPS],
+        recipient: recipient.clone(),
+        deadline: 0,
+    }
+}
+
+#[test]
+fn test_multicall_chains_swap_output_into_next_swap() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let amount: i128 = 1_000_000_000;
+    let (router, token_a, token_b, token_c, sender) = setup(&env, amount);
+
+    // Second swap spends whatever the first one returned, whatever its own
+    // amount_in says
+    let calls = vec![
+        &env,
+        RouterCall::ExactInput(exact_input(&env, &token_a, &token_b, amount, 0, &sender)),
+        RouterCall::ExactInputChained(exact_input(&env, &token_b, &token_c, 1, 0, &sender)),
+    ];
+    let results = router.multicall(&sender, &calls);
+    assert_eq!(results.len(), 2);
+
+    let first = SwapResult::from_xdr(&env, &results.get(0).unwrap()).unwrap();
+    let second = SwapResult::from_xdr(&env, &results.get(1).unwrap()).unwrap();
+    assert_eq!(first.amount_in, amount);
+    assert_eq!(second.amount_in, first.amount_out);
+
+    assert_eq!(TokenClient::new(&env, &token_a).balance(&sender), 0);
+    assert_eq!(TokenClient::new(&env, &token_b).balance(&sender), 0);
+    assert_eq!(TokenClient::new(&env, &token_c).balance(&sender), second.amount_out);
+}
+
+#[test]
+fn test_multicall_reverts_whole_batch_on_failure() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let amount: i128 = 1_000_000_000;
+    let (router, token_a, token_b, token_c, sender) = setup(&env, amount);
+
+    // The second leg cannot meet its minimum
+    let calls = vec![
+        &env,
+        RouterCall::ExactInput(exact_input(&env, &token_a, &token_b, amount, 0, &sender)),
+        RouterCall::ExactInputChained(exact_input(&env, &token_b, &token_c, 0, amount * 2, &sender)),
+    ];
+    let result = router.try_multicall(&sender, &calls);
+    assert_eq!(result.err(), Some(Ok(RouterError::SlippageExceeded)));
+
+    // The first swap was rolled back with it
+    assert_eq!(TokenClient::new(&env, &token_a).balance(&sender), amount);
+    assert_eq!(TokenClient::new(&env, &token_b).balance(&sender), 0);
+    assert_eq!(TokenClient::new(&env, &token_c).balance(&sender), 0);
+}
+
+#[test]
+fn test_multicall_rejects_empty_batch_and_dangling_chain() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (router, token_a, token_b, _, sender) = setup(&env, 1_000_000_000);
+
+    let result = router.try_multicall(&sender, &vec![&env]);
+    assert_eq!(result.err(), Some(Ok(RouterError::EmptyCalls)));
+
+    // Nothing to chain from in the first call
+    let calls = vec![&env, RouterCall::ExactInputChained(exact_input(&env, &token_a, &token_b, 0, 0, &sender))];
+    let result = router.try_multicall(&sender, &calls);
+    assert_eq!(result.err(), Some(Ok(RouterError::InvalidAmount)));
+}
+
+#[test]
+fn test_multicall_zero_amount_does_not_chain() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let amount: i128 = 1_000_000_000;
+    let (router, token_a, token_b, token_c, sender) = setup(&env, amount);
+
+    // A plain exact-input call swaps exactly its amount_in, so 0 is rejected
+    // rather than read as "the previous output"
+    let calls = vec![
+        &env,
+        RouterCall::ExactInput(exact_input(&env, &token_a, &token_b, amount, 0, &sender)),
+        RouterCall::ExactInput(exact_input(&env, &token_b, &token_c, 0, 0, &sender)),
+    ];
+    let result = router.try_multicall(&sender, &calls);
+    assert_eq!(result.err(), Some(Ok(RouterError::InvalidAmount)));
+    assert_eq!(TokenClient::new(&env, &token_a).balance(&sender), amount);
+}
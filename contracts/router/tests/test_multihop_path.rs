@@ -0,0 +1,89 @@
+mod common;
+
+use belugaswap_router::{BelugaRouterClient, Hop, MultihopExactInputParams, RouterError};
+use soroban_sdk::{testutils::Address as _, token::TokenClient, vec, Address, Env, Vec};
+
+/// Tokens A, B, C with pools A/B and B/C at the default fee tier
+fn setup(env: &Env) -> (BelugaRouterClient<'_>, [Address; 3]) {
+    env.mock_all_auths();
+
+    let (router, factory) = common::setup_router(env);
+    let tokens = [common::create_token(env), common::create_token(env), common::create_token(env)];
+    for i in 0..2 {
+        common::create_pool(
+            env,
+            &factory,
+            &router.address,
+            &tokens[i],
+            &tokens[i + 1],
+            common::DEFAULT_FEE_BPS,
+            common::DEFAULT_LIQUIDITY_AMOUNT,
+        );
+    }
+    (router, tokens)
+}
+
+fn hop(token: &Address, fee_bps: u32) -> Hop {
+    Hop { token: token.clone(), fee_bps }
+}
+
+#[test]
+fn test_valid_path_has_no_invalid_hop() {
+    let env = Env::default();
+    let (router, [a, b, c]) = setup(&env);
+
+    let path = vec![&env, hop(&b, common::DEFAULT_FEE_BPS), hop(&c, common::DEFAULT_FEE_BPS)];
+    assert_eq!(router.find_invalid_hop(&a, &path), None);
+    assert!(router.quote_multihop(&a, &1_000_000, &path) > 0);
+}
+
+#[test]
+fn test_broken_path_rejected_before_any_hop_executes() {
+    let env = Env::default();
+    let (router, [a, b, c]) = setup(&env);
+
+    // A -> B -> A is fine, but there is no A/C pool for hop 2
+    let path = vec![
+        &env,
+        hop(&b, common::DEFAULT_FEE_BPS),
+        hop(&a, common::DEFAULT_FEE_BPS),
+        hop(&c, common::DEFAULT_FEE_BPS),
+    ];
+    assert_eq!(router.find_invalid_hop(&a, &path), Some(2));
+
+    let sender = Address::generate(&env);
+    let amount_in: i128 = 1_000_000;
+    common::mint_tokens(&env, &a, &sender, amount_in);
+    let result = router.try_swap_multihop(&sender, &MultihopExactInputParams {
+        token_in: a.clone(),
+        amount_in,
+        amount_out_min: 0,
+        path: path.clone(),
+        recipient: sender.clone(),
+        deadline: 0,
+    });
+    assert!(matches!(result, Err(Ok(RouterError::InvalidPath))));
+    assert_eq!(TokenClient::new(&env, &a).balance(&sender), amount_in);
+
+    assert_eq!(router.try_quote_multihop(&a, &amount_in, &path), Err(Ok(RouterError::InvalidPath)));
+}
+
+#[test]
+fn test_repeated_token_hop_is_invalid() {
+    let env = Env::default();
+    let (router, [a, b, _]) = setup(&env);
+
+    let path = vec![&env, hop(&b, common::DEFAULT_FEE_BPS), hop(&b, common::DEFAULT_FEE_BPS)];
+    assert_eq!(router.find_invalid_hop(&a, &path), Some(1));
+    assert_eq!(router.try_quote_multihop(&a, &1_000_000, &path), Err(Ok(RouterError::InvalidPath)));
+}
+
+#[test]
+fn test_missing_fee_tier_names_its_hop() {
+    let env = Env::default();
+    let (router, [a, b, c]) = setup(&env);
+
+    let path: Vec<Hop> = vec![&env, hop(&b, 100), hop(&c, common::DEFAULT_FEE_BPS)];
+    assert_eq!(router.find_invalid_hop(&a, &path), Some(0));
+    assert_eq!(router.try_quote_multihop(&a, &1_000_000, &path), Err(Ok(RouterError::InvalidPath)));
+}
@@ -0,0 +1,140 @@
+mod common;
+
+use belugaswap_router::BelugaRouterClient;
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, vec, Address, Env,
+    Symbol,
+};
+
+/// Same fields as the pool's `PreviewResult`
+#[contracttype]
+pub struct PreviewResult {
+    pub amount_in_used: i128,
+    pub amount_out_expected: i128,
+    pub fee_paid: i128,
+    pub price_impact_bps: i128,
+    pub is_valid: bool,
+    pub error_message: Option<Symbol>,
+    pub steps: u32,
+}
+
+/// Pool stub with a constant-product output curve and a fixed step count
+/// that counts its previews
+#[contract]
+pub struct CountingPool;
+
+#[contractimpl]
+impl CountingPool {
+    pub fn set_depth(env: Env, depth: i128) {
+        env.storage().instance().set(&symbol_short!("depth"), &depth);
+    }
+
+    pub fn set_steps(env: Env, steps: u32) {
+        env.storage().instance().set(&symbol_short!("steps"), &steps);
+    }
+
+    pub fn previews(env: Env) -> u32 {
+        env.storage().instance().get(&symbol_short!("calls")).unwrap_or(0)
+    }
+
+    pub fn preview_swap(
+        env: Env,
+        _token_in: Address,
+        amount_in: i128,
+        _min_amount_out: i128,
+        _sqrt_price_limit_x64: Option<u128>,
+    ) -> PreviewResult {
+        let storage = env.storage().instance();
+        storage.set(&symbol_short!("calls"), &(Self::previews(env.clone()) + 1));
+        let depth: i128 = storage.get(&symbol_short!("depth")).unwrap();
+        PreviewResult {
+            amount_in_used: amount_in,
+            amount_out_expected: amount_in * depth / (depth + amount_in),
+            fee_paid: 0,
+            price_impact_bps: 0,
+            is_valid: true,
+            error_message: None,
+            steps: storage.get(&symbol_short!("steps")).unwrap_or(0),
+        }
+    }
+}
+
+const FEE_TIERS: [u32; 2] = [5, 30];
+const SPLIT_CHUNKS: u32 = 10;
+
+/// Router over two counting pools of equal depth for one pair
+fn setup(env: &Env) -> (BelugaRouterClient<'_>, [CountingPoolClient<'_>; 2], Address, Address) {
+    env.mock_all_auths();
+
+    let (router, factory) = common::setup_router(env);
+    let token_in = common::create_token(env);
+    let token_out = common::create_token(env);
+    let pools = FEE_TIERS.map(|fee_bps| {
+        let pool = CountingPoolClient::new(env, &env.register(CountingPool, ()));
+        pool.set_depth(&1_000_000_000);
+        factory.register_pool(&token_in, &token_out, &fee_bps, &pool.address);
+        pool
+    });
+    (router, pools, token_in, token_out)
+}
+
+fn total_previews(pools: &[CountingPoolClient; 2]) -> u32 {
+    pools.iter().map(|p| p.previews()).sum()
+}
+
+#[test]
+fn test_split_quote_reuses_unchanged_chunk_quotes() {
+    let env = Env::default();
+    let (router, pools, token_in, token_out) = setup(&env);
+
+    let quote = router.get_split_quote(&token_in, &token_out, &1_000_000_000, &vec![&env, 5, 30]);
+    assert!(quote.is_split_recommended);
+
+    // Without reuse: one quote per tier, then every pool re-quoted every chunk
+    let pool_count = pools.len() as u32;
+    let naive = pool_count + SPLIT_CHUNKS * pool_count;
+    // With reuse: the first chunk quotes every pool, later chunks only the
+    // pool that won the previous one
+    let cached = pool_count + pool_count + (SPLIT_CHUNKS - 1);
+    assert!(total_previews(&pools) <= cached, "{} previews", total_previews(&pools));
+    assert!(total_previews(&pools) < naive);
+}
+
+#[test]
+fn test_split_quote_cached_legs_match_fresh_quotes() {
+    let env = Env::default();
+    let (router, pools, token_in, token_out) = setup(&env);
+    pools[1].set_depth(&3_000_000_000);
+
+    let quote = router.get_split_quote(&token_in, &token_out, &1_000_000_000, &vec![&env, 5, 30]);
+    assert_eq!(quote.splits.len(), 2);
+
+    let mut total_out = 0;
+    for split in quote.splits.iter() {
+        let pool = CountingPoolClient::new(&env, &split.pool);
+        let fresh = pool.preview_swap(&token_in, &split.amount_in, &0, &None);
+        assert_eq!(split.amount_out, fresh.amount_out_expected);
+        total_out += split.amount_out;
+    }
+    assert_eq!(quote.total_amount_out, total_out);
+}
+
+#[test]
+fn test_best_quote_prefers_fewer_steps_when_outputs_near_equal() {
+    let env = Env::default();
+    let (router, pools, token_in, token_out) = setup(&env);
+    pools[0].set_steps(&8);
+    pools[1].set_steps(&1);
+
+    // Slightly deeper but crossing more ticks: within a bp, so fewer steps wins
+    pools[0].set_depth(&1_000_010_000);
+    let quote = router.get_best_quote(&token_in, &token_out, &1_000_000, &vec![&env, 5, 30]);
+    assert_eq!(quote.pool, pools[1].address);
+    let steps: Vec<u32> = quote.all_quotes.iter().map(|q| q.steps).collect();
+    assert_eq!(steps, [8, 1]);
+
+    // A clearly better output still wins regardless of steps
+    pools[0].set_depth(&2_000_000_000);
+    let quote = router.get_best_quote(&token_in, &token_out, &1_000_000, &vec![&env, 5, 30]);
+    assert_eq!(quote.pool, pools[0].address);
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use belugaswap_pool::BelugaPoolClient;
+use belugaswap_router::{BelugaRouterClient, ExactInputParams, RouterError};
+use soroban_sdk::{testutils::Address as _, token::TokenClient, vec, Address, Env};
+
+/// Router with a single default pool; returns (router, pool, token_in, token_out)
+fn setup(env: &Env) -> (BelugaRouterClient<'_>, BelugaPoolClient<'static>, Address, Address) {
+    env.mock_all_auths();
+    
+    let (router, factory) = common::setup_router(env);
+    let token_in = common::create_token(env);
+    let token_out = common::create_token(env);
+    let pool = common::create_pool(
+        env,
+        &factory,
+        &router.address,
+        &token_in,
+        &token_out,
+        common::DEFAULT_FEE_BPS,
+        common::DEFAULT_LIQUIDITY_AMOUNT,
+    );
+    
+    (router, pool, token_in, token_out)
+}
+
+fn params(env: &Env, token_in: &Address, token_out: &Address, recipient: &Address, amount_in: i128, max_slippage_bps: u32) -> ExactInputParams {
+    ExactInputParams {
+        token_in: token_in.clone(),
+        token_out: token_out.clone(),
+        amount_in,
+        amount_out_min: 0,
+        max_slippage_bps,
+        fee_tiers: vec![env, common::DEFAULT_FEE_BPS],
+        recipient: recipient.clone(),
+        deadline: 0,
+    }
+}
+
+/// Price move in bps between two Q64.64 sqrt prices
+fn price_move_bps(sqrt_before: u128, sqrt_after: u128) -> f64 {
+    let ratio = sqrt_after as f64 / sqrt_before as f64;
+    (ratio * ratio - 1.0).abs() * 10_000.0
+}
+
+#[test]
+fn test_slippage_limit_stops_large_swap() {
+    let env = Env::default();
+    let (router, pool, token_in, token_out) = setup(&env);
+    
+    // Enough to push the price well past 1% on this pool
+    let amount_in: i128 = 20_000_000_000;
+    let sender = Address::generate(&env);
+    common::mint_tokens(&env, &token_in, &sender, amount_in);
+    
+    let sqrt_before = pool.get_sqrt_price();
+    let result = router.swap_exact_input(&sender, &params(&env, &token_in, &token_out, &sender, amount_in, 100));
+    let sqrt_after = pool.get_sqrt_price();
+    
+    assert!(result.amount_in < amount_in, "swap should stop at the limit");
+    assert!(result.amount_out > 0);
+    
+    // Stops right at the 1% limit (f64 noise aside)
+    let moved = price_move_bps(sqrt_before, sqrt_after);
+    assert!((moved - 100.0).abs() < 1e-6, "price moved {} bps", moved);
+    
+    // Unused input is back with the sender
+    assert_eq!(TokenClient::new(&env, &token_in).balance(&sender), amount_in - result.amount_in);
+    assert_eq!(TokenClient::new(&env, &token_out).balance(&sender), result.amount_out);
+    assert_eq!(TokenClient::new(&env, &token_in).balance(&router.address), 0);
+}
+
+#[test]
+fn test_slippage_limit_ignores_small_swap() {
+    let env = Env::default();
+    let (router, pool, token_in, token_out) = setup(&env);
+    
+    let amount_in: i128 = 100_000_000;
+    let sender = Address::generate(&env);
+    common::mint_tokens(&env, &token_in, &sender, amount_in);
+    
+    let sqrt_before = pool.get_sqrt_price();
+    let result = router.swap_exact_input(&sender, &params(&env, &token_in, &token_out, &sender, amount_in, 100));
+    
+    assert_eq!(result.amount_in, amount_in);
+    assert!(price_move_bps(sqrt_before, pool.get_sqrt_price()) < 100.0);
+    assert_eq!(TokenClient::new(&env, &token_in).balance(&sender), 0);
+}
+
+#[test]
+fn test_slippage_limit_rejects_full_range() {
+    let env = Env::default();
+    let (router, _, token_in, token_out) = setup(&env);
+    
+    let sender = Address::generate(&env);
+    common::mint_tokens(&env, &token_in, &sender, 1_000_000);
+    
+    let result = router.try_swap_exact_input(&sender, &params(&env, &token_in, &token_out, &sender, 1_000_000, 10_000));
+    assert_eq!(result.err(), Some(Ok(RouterError::InvalidSlippage)));
+}
@@ -0,0 +1,223 @@
+mod common;
+
+use belugaswap_pool::BelugaPoolClient;
+use belugaswap_router::{AggregatedQuote, RouterError};
+use soroban_sdk::{testutils::Address as _, token::TokenClient, vec, Address, Env};
+
+/// Three tiers of the same pair with decreasing depth
+fn setup_three_pools(env: &Env) -> (belugaswap_router::BelugaRouterClient<'_>, Address, Address) {
+    let (router, factory) = common::setup_router(env);
+    let token_in = common::create_token(env);
+    let token_out = common::create_token(env);
+
+    for (fee_bps, liquidity) in [(5u32, 100_000_000_000i128), (30, 60_000_000_000), (100, 30_000_000_000)] {
+        common::create_pool(env, &factory, &router.address, &token_in, &token_out, fee_bps, liquidity);
+    }
+
+    (router, token_in, token_out)
+}
+
+#[test]
+fn test_split_quote_beats_single_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (router, token_in, token_out) = setup_three_pools(&env);
+    let amount_in: i128 = 40_000_000_000;
+    let tiers = vec![&env];
+
+    let split = router.get_split_quote(&token_in, &token_out, &amount_in, &tiers);
+    let best = router.get_best_quote(&token_in, &token_out, &amount_in, &tiers);
+
+    assert!(split.is_split_recommended);
+    assert!(split.splits.len() >= 2);
+    assert!(
+        split.total_amount_out > best.amount_out,
+        "split {} should beat single pool {}",
+        split.total_amount_out,
+        best.amount_out
+    );
+
+    // Every chunk is accounted for
+    let mut sum_in: i128 = 0;
+    let mut sum_out: i128 = 0;
+    for s in split.splits.iter() {
+        assert!(s.amount_in > 0);
+        sum_in += s.amount_in;
+        sum_out += s.amount_out;
+    }
+    assert_eq!(sum_in, amount_in);
+    assert_eq!(split.total_amount_in, amount_in);
+    assert_eq!(sum_out, split.total_amount_out);
+}
+
+#[test]
+fn test_split_quote_executes_as_quoted() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (router, token_in, token_out) = setup_three_pools(&env);
+    let amount_in: i128 = 40_000_000_000;
+    let split = router.get_split_quote(&token_in, &token_out, &amount_in, &vec![&env]);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    common::mint_tokens(&env, &token_in, &sender, amount_in);
+
+    let result = router.swap_split(
+        &sender,
+        &token_in,
+        &token_out,
+        &amount_in,
+        &split.total_amount_out,
+        &split.splits,
+        &vec![&env],
+        &recipient,
+        &0,
+    );
+
+    assert_eq!(result.amount_out, split.total_amount_out);
+    assert_eq!(result.pools_used.len(), split.splits.len());
+    assert_eq!(TokenClient::new(&env, &token_out).balance(&recipient), split.total_amount_out);
+}
+
+#[test]
+fn test_split_quote_small_order_uses_single_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (router, token_in, token_out) = setup_three_pools(&env);
+    let amount_in: i128 = 1_000_000;
+    let split = router.get_split_quote(&token_in, &token_out, &amount_in, &vec![&env]);
+
+    assert!(!split.is_split_recommended);
+    assert_eq!(split.splits.len(), 1);
+    assert_eq!(split.splits.get(0).unwrap().amount_in, amount_in);
+}
+
+/// Two equal pools and a split quote across them, taken before any moves
+fn quoted_split(env: &Env) -> (
+    belugaswap_router::BelugaRouterClient<'_>,
+    BelugaPoolClient<'static>,
+    BelugaPoolClient<'static>,
+    Address,
+    Address,
+    AggregatedQuote,
+) {
+    let (router, factory) = common::setup_router(env);
+    let token_in = common::create_token(env);
+    let token_out = common::create_token(env);
+    let pool_a = common::create_pool(env, &factory, &router.address, &token_in, &token_out, 5, 50_000_000_000);
+    let pool_b = common::create_pool(env, &factory, &router.address, &token_in, &token_out, 30, 50_000_000_000);
+
+    let amount_in: i128 = 20_000_000_000;
+    let split = router.get_split_quote(&token_in, &token_out, &amount_in, &vec![env]);
+    assert_eq!(split.splits.len(), 2);
+
+    (router, pool_a, pool_b, token_in, token_out, split)
+}
+
+/// Sell `amount` of `token` into `pool` from a fresh account
+fn move_pool(env: &Env, pool: &BelugaPoolClient, token: &Address, amount: i128) {
+    let trader = Address::generate(env);
+    common::mint_tokens(env, token, &trader, amount);
+    pool.swap(&trader, token, &amount, &0, &None, &0);
+}
+
+#[test]
+fn test_swap_split_per_leg_min_reverts_moved_leg() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (router, pool_a, _, token_in, token_out, split) = quoted_split(&env);
+    move_pool(&env, &pool_a, &token_in, 40_000_000_000);
+
+    let sender = Address::generate(&env);
+    common::mint_tokens(&env, &token_in, &sender, split.total_amount_in);
+
+    // Loose aggregate bound, but each leg must return 99% of its quote
+    let mut leg_mins = vec![&env];
+    for s in split.splits.iter() {
+        leg_mins.push_back(s.amount_out * 99 / 100);
+    }
+    let result = router.try_swap_split(
+        &sender,
+        &token_in,
+        &token_out,
+        &split.total_amount_in,
+        &1,
+        &split.splits,
+        &leg_mins,
+        &sender,
+        &0,
+    );
+    assert_eq!(result.err(), Some(Ok(RouterError::SlippageExceeded)));
+    assert_eq!(TokenClient::new(&env, &token_in).balance(&sender), split.total_amount_in);
+}
+
+#[test]
+fn test_swap_split_default_leg_min_is_proportional() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    // One pool moves against the order, the other in its favour
+    let (router, pool_a, pool_b, token_in, token_out, split) = quoted_split(&env);
+    move_pool(&env, &pool_a, &token_in, 40_000_000_000);
+    move_pool(&env, &pool_b, &token_out, 40_000_000_000);
+
+    let sender = Address::generate(&env);
+    common::mint_tokens(&env, &token_in, &sender, split.total_amount_in);
+    let amount_out_min = split.total_amount_out * 95 / 100;
+
+    // The favourable leg covers the total, but the moved leg misses its share
+    let result = router.try_swap_split(
+        &sender,
+        &token_in,
+        &token_out,
+        &split.total_amount_in,
+        &amount_out_min,
+        &split.splits,
+        &vec![&env],
+        &sender,
+        &0,
+    );
+    assert_eq!(result.err(), Some(Ok(RouterError::SlippageExceeded)));
+
+    // Without leg bounds the same order clears the aggregate check
+    let unbounded = vec![&env, 1i128, 1i128];
+    let result = router.swap_split(
+        &sender,
+        &token_in,
+        &token_out,
+        &split.total_amount_in,
+        &amount_out_min,
+        &split.splits,
+        &unbounded,
+        &sender,
+        &0,
+    );
+    assert!(result.amount_out >= amount_out_min);
+}
+
+#[test]
+fn test_swap_split_leg_min_count_must_match() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (router, _, _, token_in, token_out, split) = quoted_split(&env);
+    let sender = Address::generate(&env);
+    common::mint_tokens(&env, &token_in, &sender, split.total_amount_in);
+
+    let result = router.try_swap_split(
+        &sender,
+        &token_in,
+        &token_out,
+        &split.total_amount_in,
+        &0,
+        &split.splits,
+        &vec![&env, 1i128],
+        &sender,
+        &0,
+    );
+    assert_eq!(result.err(), Some(Ok(RouterError::InvalidLegMinimums)));
+}
@@ -0,0 +1,154 @@
+mod common;
+
+use belugaswap_router::ExactOutputParams;
+use soroban_sdk::{testutils::Address as _, token::TokenClient, vec, Address, Env};
+
+#[test]
+fn test_swap_exact_output_delivers_requested_amount() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (router, factory) = common::setup_router(&env);
+    let token_in = common::create_token(&env);
+    let token_out = common::create_token(&env);
+    common::create_pool(
+        &env,
+        &factory,
+        &router.address,
+        &token_in,
+        &token_out,
+        common::DEFAULT_FEE_BPS,
+        common::DEFAULT_LIQUIDITY_AMOUNT,
+    );
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let amount_in_max: i128 = 2_000_000_000;
+    common::mint_tokens(&env, &token_in, &sender, amount_in_max);
+    
+    let amount_out: i128 = 1_000_000_000;
+    let result = router.swap_exact_output(
+        &sender,
+        &ExactOutputParams {
+            token_in: token_in.clone(),
+            token_out: token_out.clone(),
+            amount_out,
+            amount_in_max,
+            fee_tiers: vec![&env, common::DEFAULT_FEE_BPS],
+            recipient: recipient.clone(),
+            deadline: env.ledger().timestamp() + 100,
+        },
+    );
+    
+    let received = TokenClient::new(&env, &token_out).balance(&recipient);
+    assert_eq!(received, amount_out);
+    assert_eq!(result.amount_out, amount_out);
+    
+    // Unused input is refunded, nothing stays in the router
+    let sender_in = TokenClient::new(&env, &token_in).balance(&sender);
+    assert_eq!(sender_in, amount_in_max - result.amount_in);
+    assert_eq!(TokenClient::new(&env, &token_in).balance(&router.address), 0);
+    assert_eq!(TokenClient::new(&env, &token_out).balance(&router.address), 0);
+}
+
+#[test]
+fn test_swap_exact_output_picks_cheapest_tier() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (router, factory) = common::setup_router(&env);
+    let token_in = common::create_token(&env);
+    let token_out = common::create_token(&env);
+    for fee_bps in [5u32, 100] {
+        common::create_pool(
+            &env,
+            &factory,
+            &router.address,
+            &token_in,
+            &token_out,
+            fee_bps,
+            common::DEFAULT_LIQUIDITY_AMOUNT,
+        );
+    }
+    
+    let sender = Address::generate(&env);
+    let amount_in_max: i128 = 2_000_000_000;
+    common::mint_tokens(&env, &token_in, &sender, amount_in_max);
+    
+    let result = router.swap_exact_output(
+        &sender,
+        &ExactOutputParams {
+            token_in,
+            token_out,
+            amount_out: 1_000_000_000,
+            amount_in_max,
+            fee_tiers: vec![&env, 100, 5],
+            recipient: sender.clone(),
+            deadline: env.ledger().timestamp() + 100,
+        },
+    );
+    
+    assert_eq!(result.fee_tiers_used, vec![&env, 5u32]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #14)")]
+fn test_swap_exact_output_amount_in_max_too_small() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (router, factory) = common::setup_router(&env);
+    let token_in = common::create_token(&env);
+    let token_out = common::create_token(&env);
+    common::create_pool(
+        &env,
+        &factory,
+        &router.address,
+        &token_in,
+        &token_out,
+        common::DEFAULT_FEE_BPS,
+        common::DEFAULT_LIQUIDITY_AMOUNT,
+    );
+    
+    let sender = Address::generate(&env);
+    common::mint_tokens(&env, &token_in, &sender, 1_000_000_000);
+    
+    // At price 1.0 with a 0.3% fee, 1_000_000_000 input cannot buy the same output
+    router.swap_exact_output(
+        &sender,
+        &ExactOutputParams {
+            token_in,
+            token_out,
+            amount_out: 1_000_000_000,
+            amount_in_max: 1_000_000_000,
+            fee_tiers: vec![&env, common::DEFAULT_FEE_BPS],
+            recipient: sender.clone(),
+            deadline: env.ledger().timestamp() + 100,
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #12)")]
+fn test_swap_exact_output_no_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (router, _factory) = common::setup_router(&env);
+    let token_in = common::create_token(&env);
+    let token_out = common::create_token(&env);
+    let sender = Address::generate(&env);
+    
+    router.swap_exact_output(
+        &sender,
+        &ExactOutputParams {
+            token_in,
+            token_out,
+            amount_out: 1_000,
+            amount_in_max: 10_000,
+            fee_tiers: vec![&env, common::DEFAULT_FEE_BPS],
+            recipient: sender.clone(),
+            deadline: env.ledger().timestamp() + 100,
+        },
+    );
+}
@@ -0,0 +1,148 @@
+mod common;
+
+use belugaswap_router::{Hop, MultihopExactOutputParams};
+use soroban_sdk::{testutils::Address as _, token::TokenClient, vec, Address, Env, Vec};
+
+/// Create tokens A, B, C, D with pools A/B, B/C and C/D
+fn setup_three_hops(env: &Env) -> (belugaswap_router::BelugaRouterClient<'_>, Address, Vec<Hop>) {
+    let (router, factory) = common::setup_router(env);
+    let tokens = [
+        common::create_token(env),
+        common::create_token(env),
+        common::create_token(env),
+        common::create_token(env),
+    ];
+    
+    let mut path = Vec::new(env);
+    for i in 0..3 {
+        common::create_pool(
+            env,
+            &factory,
+            &router.address,
+            &tokens[i],
+            &tokens[i + 1],
+            common::DEFAULT_FEE_BPS,
+            common::DEFAULT_LIQUIDITY_AMOUNT,
+        );
+        path.push_back(Hop {
+            token: tokens[i + 1].clone(),
+            fee_bps: common::DEFAULT_FEE_BPS,
+        });
+    }
+    
+    (router, tokens[0].clone(), path)
+}
+
+#[test]
+fn test_multihop_exact_output_three_hops() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (router, token_a, path) = setup_three_hops(&env);
+    let token_d = path.get(2).unwrap().token;
+    
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let amount_in_max: i128 = 2_000_000_000;
+    common::mint_tokens(&env, &token_a, &sender, amount_in_max);
+    
+    let amount_out: i128 = 1_000_000_000;
+    let result = router.swap_multihop_exact_output(
+        &sender,
+        &MultihopExactOutputParams {
+            token_in: token_a.clone(),
+            amount_out,
+            path: path.clone(),
+            amount_in_max,
+            recipient: recipient.clone(),
+            deadline: env.ledger().timestamp() + 100,
+        },
+    );
+    
+    assert_eq!(TokenClient::new(&env, &token_d).balance(&recipient), amount_out);
+    assert_eq!(result.amount_out, amount_out);
+    assert!(result.amount_in < amount_in_max);
+    assert_eq!(result.pools_used.len(), 3);
+    
+    // Only the used input left the sender and no intermediate dust is stuck
+    assert_eq!(
+        TokenClient::new(&env, &token_a).balance(&sender),
+        amount_in_max - result.amount_in
+    );
+    for i in 0..path.len() {
+        let token = path.get(i).unwrap().token;
+        assert_eq!(TokenClient::new(&env, &token).balance(&router.address), 0);
+    }
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #14)")]
+fn test_multihop_exact_output_amount_in_max_too_small() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (router, token_a, path) = setup_three_hops(&env);
+    
+    let sender = Address::generate(&env);
+    common::mint_tokens(&env, &token_a, &sender, 1_000_000_000);
+    
+    // Three 0.3% hops at price 1.0 need more than 1:1 input
+    router.swap_multihop_exact_output(
+        &sender,
+        &MultihopExactOutputParams {
+            token_in: token_a,
+            amount_out: 1_000_000_000,
+            path,
+            amount_in_max: 1_000_000_000,
+            recipient: sender.clone(),
+            deadline: env.ledger().timestamp() + 100,
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #20)")]
+fn test_multihop_exact_output_missing_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (router, token_a, _path) = setup_three_hops(&env);
+    let unknown = common::create_token(&env);
+    let sender = Address::generate(&env);
+    
+    router.swap_multihop_exact_output(
+        &sender,
+        &MultihopExactOutputParams {
+            token_in: token_a,
+            amount_out: 1_000,
+            path: vec![&env, Hop { token: unknown, fee_bps: common::DEFAULT_FEE_BPS }],
+            amount_in_max: 10_000,
+            recipient: sender.clone(),
+            deadline: env.ledger().timestamp() + 100,
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #11)")]
+fn test_multihop_exact_output_path_too_long() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let (router, token_a, path) = setup_three_hops(&env);
+    let mut long_path = path.clone();
+    long_path.append(&path);
+    let sender = Address::generate(&env);
+    
+    router.swap_multihop_exact_output(
+        &sender,
+        &MultihopExactOutputParams {
+            token_in: token_a,
+            amount_out: 1_000,
+            path: long_path,
+            amount_in_max: 10_000,
+            recipient: sender.clone(),
+            deadline: env.ledger().timestamp() + 100,
+        },
+    );
+}
@@ -0,0 +1,212 @@
+mod common;
+
+use belugaswap_math::{get_sqrt_ratio_at_tick, mul_div, ONE_X64};
+use belugaswap_pool::{BelugaPool, BelugaPoolClient};
+use belugaswap_router::{BelugaRouterClient, RouterError};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, testutils::{Address as _, Ledger}, vec, Address, Env,
+    Symbol,
+};
+
+/// Same fields as the pool's `PreviewResult`
+#[contracttype]
+pub struct PreviewResult {
+    pub amount_in_used: i128,
+    pub amount_out_expected: i128,
+    pub fee_paid: i128,
+    pub price_impact_bps: i128,
+    pub is_valid: bool,
+    pub error_message: Option<Symbol>,
+    pub steps: u32,
+}
+
+/// Pool stub sitting at `STUB_TICK` for its whole history. It has no
+/// `get_swap_direction`, so a quote only succeeds if the router works out
+/// the direction itself.
+#[contract]
+pub struct FixedTickPool;
+
+const STUB_TICK: i32 = 6_960;
+
+#[contractimpl]
+impl FixedTickPool {
+    pub fn get_twap_tick(_env: Env, _window_secs: u32) -> i32 {
+        STUB_TICK
+    }
+
+    pub fn preview_swap(
+        _env: Env,
+        _token_in: Address,
+        amount_in: i128,
+        _min_amount_out: i128,
+        _sqrt_price_limit_x64: Option<u128>,
+    ) -> PreviewResult {
+        PreviewResult {
+            amount_in_used: amount_in,
+            amount_out_expected: 0,
+            fee_paid: 0,
+            price_impact_bps: 0,
+            is_valid: true,
+            error_message: None,
+            steps: 0,
+        }
+    }
+}
+
+const WINDOW_SECS: u32 = 1800;
+const QUOTE_AMOUNT: i128 = 1_000_000;
+
+/// One default-tier pool at price 1.0 with an hour of oracle history;
+/// returns (router, pool, token0, token1)
+fn setup(env: &Env) -> (BelugaRouterClient<'_>, BelugaPoolClient<'static>, Address, Address) {
+    env.mock_all_auths();
+
+    let (router, factory) = common::setup_router(env);
+    let token_a = common::create_token(env);
+    let token_b = common::create_token(env);
+    let pool = common::create_pool(
+        env,
+        &factory,
+        &router.address,
+        &token_a,
+        &token_b,
+        common::DEFAULT_FEE_BPS,
+        common::DEFAULT_LIQUIDITY_AMOUNT,
+    );
+    env.ledger().with_mut(|l| l.timestamp += 3600);
+
+    let state = pool.get_pool_state();
+    (router, pool, state.token0, state.token1)
+}
+
+/// Buy token0 with a large token1 swap, pushing the spot price up
+fn pump(env: &Env, pool: &BelugaPoolClient, token1: &Address) {
+    let whale = Address::generate(env);
+    common::mint_tokens(env, token1, &whale, 20_000_000_000);
+    pool.swap(&whale, token1, &20_000_000_000, &0, &None, &0);
+}
+
+#[test]
+fn test_twap_quote_prices_at_mean_tick() {
+    let env = Env::default();
+    let (router, pool, token0, token1) = setup(&env);
+
+    let quote = router.get_twap_quote(&token0, &token1, &QUOTE_AMOUNT, &WINDOW_SECS, &vec![&env]);
+    assert_eq!(quote.pool, pool.address);
+    assert_eq!(quote.all_quotes.len(), 1);
+    assert_eq!(quote.all_quotes.get(0).unwrap().twap_tick, 0);
+
+    // Price 1.0: input net of the 0.30% fee, with no price impact
+    assert_eq!(quote.twap_amount_out, QUOTE_AMOUNT * 9970 / 10000);
+    assert!(quote.spot_amount_out > 0 && quote.spot_amount_out <= quote.twap_amount_out);
+}
+
+#[test]
+fn test_twap_quote_resists_spot_manipulation() {
+    let env = Env::default();
+    let (router, pool, token0, token1) = setup(&env);
+
+    let before = router.get_twap_quote(&token0, &token1, &QUOTE_AMOUNT, &WINDOW_SECS, &vec![&env]);
+    pump(&env, &pool, &token1);
+    assert!(pool.get_pool_state().current_tick > 100);
+
+    // Same ledger: spot is inflated, the TWAP has not moved at all
+    let after = router.get_twap_quote(&token0, &token1, &QUOTE_AMOUNT, &WINDOW_SECS, &vec![&env]);
+    assert!(after.spot_amount_out > before.spot_amount_out * 101 / 100);
+    assert_eq!(after.twap_amount_out, before.twap_amount_out);
+
+    // A minute of the manipulated price barely moves a 30-minute TWAP
+    env.ledger().with_mut(|l| l.timestamp += 60);
+    let later = router.get_twap_quote(&token0, &token1, &QUOTE_AMOUNT, &WINDOW_SECS, &vec![&env]);
+    let drift = later.twap_amount_out - before.twap_amount_out;
+    assert!(drift > 0 && drift * 10_000 < before.twap_amount_out * 50, "drift {}", drift);
+    assert!(later.spot_amount_out > later.twap_amount_out);
+}
+
+#[test]
+fn test_twap_quote_window_longer_than_history() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    // Pool sitting at tick 6960 (price ~2.0) with an hour of history
+    let tick = 6_960;
+    let (router, factory) = common::setup_router(&env);
+    let token_a = common::create_token(&env);
+    let token_b = common::create_token(&env);
+    let pool = BelugaPoolClient::new(&env, &env.register(BelugaPool, ()));
+    pool.initialize(
+        &factory.address,
+        &router.address,
+        &Address::generate(&env),
+        &token_a,
+        &token_b,
+        &common::DEFAULT_FEE_BPS,
+        &100,
+        &get_sqrt_ratio_at_tick(tick),
+        &tick,
+        &common::DEFAULT_TICK_SPACING,
+    );
+    factory.register_pool(&token_a, &token_b, &common::DEFAULT_FEE_BPS, &pool.address);
+    let lp = Address::generate(&env);
+    common::mint_tokens(&env, &token_a, &lp, common::DEFAULT_LIQUIDITY_AMOUNT);
+    common::mint_tokens(&env, &token_b, &lp, common::DEFAULT_LIQUIDITY_AMOUNT);
+    pool.add_liquidity(
+        &lp,
+        &(tick - 600),
+        &(tick + 600),
+        &common::DEFAULT_LIQUIDITY_AMOUNT,
+        &common::DEFAULT_LIQUIDITY_AMOUNT,
+        &0,
+        &0,
+    );
+    env.ledger().with_mut(|l| l.timestamp += 3600);
+
+    // A day-long window averages over the hour the oracle has, so the mean
+    // tick is the pool's tick rather than 1/24 of it
+    let state = pool.get_pool_state();
+    let quote = router.get_twap_quote(&state.token0, &state.token1, &QUOTE_AMOUNT, &86_400, &vec![&env]);
+    assert_eq!(quote.all_quotes.get(0).unwrap().twap_tick, tick);
+    assert!(quote.twap_amount_out > QUOTE_AMOUNT * 19 / 10);
+}
+
+#[test]
+fn test_twap_quote_rejects_zero_window_and_missing_pools() {
+    let env = Env::default();
+    let (router, _, token0, token1) = setup(&env);
+
+    let result = router.try_get_twap_quote(&token0, &token1, &QUOTE_AMOUNT, &0, &vec![&env]);
+    assert!(matches!(result, Err(Ok(RouterError::InvalidTwapWindow))));
+
+    // No pool at the requested tier
+    let result = router.try_get_twap_quote(&token0, &token1, &QUOTE_AMOUNT, &WINDOW_SECS, &vec![&env, 100]);
+    assert!(matches!(result, Err(Ok(RouterError::NoPoolsFound))));
+}
+
+#[test]
+fn test_twap_quote_direction_needs_no_pool_call() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (router, factory) = common::setup_router(&env);
+    let token_a = common::create_token(&env);
+    let token_b = common::create_token(&env);
+    let (token0, token1) = if token_a < token_b { (token_a, token_b) } else { (token_b, token_a) };
+    let pool = env.register(FixedTickPool, ());
+    factory.register_pool(&token0, &token1, &common::DEFAULT_FEE_BPS, &pool);
+
+    // Converted at the stub's tick, net of the 0.30% fee
+    let net_in = (QUOTE_AMOUNT * 9970 / 10000) as u128;
+    let sqrt_price = get_sqrt_ratio_at_tick(STUB_TICK);
+
+    // Selling token0 (zero_for_one) multiplies by the price...
+    let sell0 = router.get_twap_quote(&token0, &token1, &QUOTE_AMOUNT, &WINDOW_SECS, &vec![&env]);
+    assert_eq!(sell0.all_quotes.get(0).unwrap().twap_tick, STUB_TICK);
+    let expected = mul_div(&env, mul_div(&env, net_in, sqrt_price, ONE_X64), sqrt_price, ONE_X64);
+    assert_eq!(sell0.twap_amount_out, expected as i128);
+
+    // ...selling token1 divides by it
+    let sell1 = router.get_twap_quote(&token1, &token0, &QUOTE_AMOUNT, &WINDOW_SECS, &vec![&env]);
+    let expected = mul_div(&env, mul_div(&env, net_in, ONE_X64, sqrt_price), ONE_X64, sqrt_price);
+    assert_eq!(sell1.twap_amount_out, expected as i128);
+    assert!(sell0.twap_amount_out > QUOTE_AMOUNT && sell1.twap_amount_out < QUOTE_AMOUNT);
+}
@@ -46,6 +46,11 @@ pub const SQRT_PRICE_1_1: u128 = 18446744073709551616_u128;
 /// Prevents dust positions and ensures meaningful LP participation
 pub const MIN_LIQUIDITY: i128 = 1000;
 
+/// Liquidity permanently locked by a pool's first deposit
+/// Keeps the pool from ever being emptied to a dust position the next
+/// depositor could manipulate (Uniswap V2 style minimum liquidity)
+pub const MINIMUM_LOCKED_LIQUIDITY: i128 = 1000;
+
 /// Maximum liquidity per tick to prevent overflow
 /// Set to half of i128::MAX for safety margin
 #[allow(dead_code)]
@@ -56,29 +61,53 @@ pub const MAX_LIQUIDITY_PER_TICK: i128 = i128::MAX / 2;
 // ============================================================
 
 /// Minimum amount for a swap
-/// Prevents gas waste on negligible trades
+/// Prevents gas waste on negligible trades; pools may raise it per token
 pub const MIN_SWAP_AMOUNT: i128 = 1;
 
 /// Minimum output amount (dust threshold)
-/// Outputs below this are considered dust
+/// Outputs below this are considered dust; pools may raise it per token
 pub const MIN_OUTPUT_AMOUNT: i128 = 1;
 
+/// Decimals of a native Stellar asset
+/// Tokens with more decimals get a dust threshold of one unit at this
+/// precision, e.g. 10^11 for an 18-decimal token
+pub const DUST_REFERENCE_DECIMALS: u32 = 7;
+
 /// Maximum slippage in basis points (50% = 5000 bps)
 /// Protection against extreme price impact
 pub const MAX_SLIPPAGE_BPS: i128 = 5000;
 
 /// Maximum iterations in swap loop (prevents infinite loops)
-/// Safety limit for complex multi-tick swaps
+/// Hard ceiling for a pool's configurable iteration budget
 pub const MAX_SWAP_ITERATIONS: u32 = 1024;
 
-/// Maximum steps when searching for next initialized tick
-/// Limits computation for tick traversal
+/// Swap loop iteration budget a new pool starts with
+/// Roughly one iteration per initialized tick crossed
+pub const DEFAULT_SWAP_ITERATIONS: u32 = 256;
+
+/// Maximum bitmap words scanned when searching for next initialized tick
+/// Limits computation for tick traversal (256 spaced ticks per word)
 pub const MAX_TICK_SEARCH_STEPS: i32 = 2000;
 
+/// Maximum buckets returned by `get_liquidity_distribution`
+/// Keeps the result vector and the per-bucket work bounded
+pub const MAX_LIQUIDITY_BUCKETS: u32 = 200;
+
+/// Maximum ticks priced by `amounts_to_reach_ticks`
+/// Each one is a separate dry-run swap from the current price
+pub const MAX_DEPTH_TICKS: u32 = 32;
+
+/// Input budget for each `amounts_to_reach_ticks` dry run
+/// The largest supply a classic Stellar asset can have
+pub const MAX_DEPTH_AMOUNT_IN: i128 = i64::MAX as i128;
+
 // ============================================================
 // FEE CONSTANTS
 // ============================================================
 
+/// Basis points in 100%
+pub const BPS_DENOMINATOR: u128 = 10_000;
+
 /// Maximum fee in basis points (100% = 10000 bps)
 pub const MAX_FEE_BPS: u32 = 10000;
 
@@ -88,6 +117,13 @@ pub const MIN_CREATOR_FEE_BPS: u32 = 1;
 /// Maximum creator fee in basis points (10% of swap = 1000 bps)
 pub const MAX_CREATOR_FEE_BPS: u32 = 1000;
 
+/// Ledgers a creator fee increase must wait before it can be applied
+/// (~7 days at 5s per ledger, same as the factory's minimum creator lock)
+pub const CREATOR_FEE_TIMELOCK_LEDGERS: u32 = 120_960;
+
+/// Maximum protocol fee in basis points (25% of the LP fee = 2500 bps)
+pub const MAX_PROTOCOL_FEE_BPS: u32 = 2500;
+
 /// Default LP fee (0.3% = 30 bps)
 #[allow(dead_code)]
 pub const DEFAULT_FEE_BPS: u32 = 30;
@@ -109,19 +145,20 @@ pub const Q64: u128 = 1u128 << 64;
 #[allow(dead_code)]
 pub const Q128: u128 = 1u128 << 64 << 64;
 
-/// Maximum reasonable fee delta (for overflow detection)
-/// Used to validate fee growth values
-#[allow(dead_code)]
-pub const MAX_REASONABLE_FEE_DELTA: u128 = 1u128 << 96;
-
 // ============================================================
-// TWAP CONSTANTS (Reserved for future use)
+// TWAP CONSTANTS
 // ============================================================
 
 /// Maximum TWAP observations stored
-/// Limits storage usage for price oracle
-#[allow(dead_code)]
-pub const MAX_TWAP_OBSERVATIONS: u32 = 100;
+/// Upper bound for `increase_observation_cardinality`
+pub const MAX_TWAP_OBSERVATIONS: u32 = 65_535;
+
+/// TWAP observations retained by a new pool
+/// Limits storage usage until someone pays to grow the buffer
+pub const DEFAULT_TWAP_CARDINALITY: u32 = 10;
+
+/// TWAP window (in seconds) the swap deviation guard compares against
+pub const TWAP_DEVIATION_WINDOW: u32 = 300;
 
 /// Minimum time between TWAP observations (in seconds)
 /// Prevents observation spam
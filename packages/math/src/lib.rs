@@ -6,6 +6,7 @@ pub mod constants;
 pub mod q64;
 pub mod sqrt_price;
 pub mod liquidity;
+pub mod tokens;
 
 // Re-export commonly used items from constants
 pub use constants::*;
@@ -15,6 +16,8 @@ pub use q64::{
     mul_q64, 
     div_q64, 
     mul_div,
+    mul_div_round_up,
+    mul_bps,
     div_round_up,           // <-- ADDED: was missing from export
     i128_to_u128_safe,      // <-- ADDED: helper function
     u128_to_i128_saturating, // <-- ADDED: helper function
@@ -41,6 +44,9 @@ pub use liquidity::{
     get_amount_1_delta,
 };
 
+// Re-export token pair ordering
+pub use tokens::sort_tokens;
+
 // Tick utility (kept in lib for backward compatibility)
 pub fn snap_tick_to_spacing(tick: i32, spacing: i32) -> i32 {
     if spacing <= 0 {
@@ -50,6 +56,13 @@ pub fn snap_tick_to_spacing(tick: i32, spacing: i32) -> i32 {
     tick - rem
 }
 
+/// Like `snap_tick_to_spacing` but rounds toward positive infinity, so an
+/// upper range bound is never narrowed
+pub fn snap_tick_to_spacing_ceil(tick: i32, spacing: i32) -> i32 {
+    let floor = snap_tick_to_spacing(tick, spacing);
+    if floor == tick { tick } else { floor + spacing }
+}
+
 // Export MIN_LIQUIDITY for backward compatibility
 pub const MIN_LIQUIDITY: i128 = constants::MIN_LIQUIDITY;
 
@@ -60,7 +73,10 @@ pub const MIN_LIQUIDITY: i128 = constants::MIN_LIQUIDITY;
 use soroban_sdk::Env;
 
 /// Compute a single swap step (without target price)
-/// This was in the original math.rs but missing from the refactored package
+///
+/// The step runs until `amount_remaining` is consumed, then goes through
+/// `compute_swap_step_with_target` with that price as the target, so both
+/// variants round the same way: input up, output down.
 pub fn compute_swap_step(
     env: &Env,
     sqrt_price_current: u128,
@@ -74,33 +90,27 @@ pub fn compute_swap_step(
         return (sqrt_price_current, 0, 0);
     }
 
-    let liq_u = q64::i128_to_u128_safe(liquidity);
-    let amt_in_remaining = q64::i128_to_u128_safe(amount_remaining);
-
     let next_sqrt_price = sqrt_price::get_next_sqrt_price_from_input(
-        env, sqrt_price_current, liq_u, amt_in_remaining, zero_for_one
+        env,
+        sqrt_price_current,
+        q64::i128_to_u128_safe(liquidity),
+        q64::i128_to_u128_safe(amount_remaining),
+        zero_for_one,
     );
 
-    let price_delta = next_sqrt_price.abs_diff(sqrt_price_current);
-
-    if price_delta < MIN_PRICE_DELTA {
+    if next_sqrt_price.abs_diff(sqrt_price_current) < MIN_PRICE_DELTA {
         return (sqrt_price_current, 0, 0);
     }
 
-    let amount_0 = liquidity::get_amount_0_delta(sqrt_price_current, next_sqrt_price, liq_u, true);
-    let amount_1 = liquidity::get_amount_1_delta(sqrt_price_current, next_sqrt_price, liq_u, true);
-
-    let (amount_in, amount_out) = if zero_for_one {
-        (amount_0, amount_1)
-    } else {
-        (amount_1, amount_0)
-    };
-
-    let final_amount_in = amount_in.min(amt_in_remaining);
-
-    (
+    let (sqrt_price_next, amount_in, amount_out) = sqrt_price::compute_swap_step_with_target(
+        env,
+        sqrt_price_current,
+        liquidity,
+        amount_remaining,
+        zero_for_one,
         next_sqrt_price,
-        q64::u128_to_i128_saturating(final_amount_in),
-        q64::u128_to_i128_saturating(amount_out)
-    )
-}
\ No newline at end of file
+    );
+
+    // Input rounding up can overshoot the remaining amount by a unit
+    (sqrt_price_next, amount_in.min(amount_remaining), amount_out)
+}
@@ -1,7 +1,7 @@
 // Q64.64 Fixed-Point Arithmetic Operations
 
 use soroban_sdk::{Env, U256};
-use crate::constants::Q64;
+use crate::constants::{BPS_DENOMINATOR, Q64};
 
 pub const ONE_X64: u128 = Q64;
 
@@ -17,8 +17,9 @@ pub fn u128_to_i128_saturating(x: u128) -> i128 {
 }
 
 /// Multiply two Q64.64 numbers, returning Q64.64 result
-/// Uses decomposition to avoid overflow
-/// FIXED: Now uses saturating_add to prevent overflow panics
+/// 
+/// The 256-bit product is split into 64-bit limb terms; each step is checked
+/// and the result saturates to `u128::MAX` when it does not fit in 128 bits.
 #[inline]
 pub fn mul_q64(a: u128, b: u128) -> u128 {
     let a_hi = a >> 64;
@@ -26,19 +27,28 @@ pub fn mul_q64(a: u128, b: u128) -> u128 {
     let b_hi = b >> 64;
     let b_lo = b & 0xFFFFFFFFFFFFFFFF;
 
+    // Each limb product fits in u128
     let term_hh = a_hi * b_hi;
     let term_hl = a_hi * b_lo;
     let term_lh = a_lo * b_hi;
     let term_ll = a_lo * b_lo;
 
-    // FIXED: Use saturating_add to prevent overflow
+    // (a * b) >> 64 = (term_hh << 64) + term_hl + term_lh + (term_ll >> 64)
+    if term_hh >> 64 != 0 {
+        return u128::MAX;
+    }
     (term_hh << 64)
-        .saturating_add(term_hl)
-        .saturating_add(term_lh)
-        .saturating_add(term_ll >> 64)
+        .checked_add(term_hl)
+        .and_then(|x| x.checked_add(term_lh))
+        .and_then(|x| x.checked_add(term_ll >> 64))
+        .unwrap_or(u128::MAX)
 }
 
 /// Divide in Q64.64 format: (a * 2^64) / b
+/// 
+/// Exact (floor) for the full input range. Large numerators fall back to a
+/// 256-by-128-bit long division; the result saturates to `u128::MAX` only
+/// when it does not fit in 128 bits (or `b` is zero).
 #[inline]
 pub fn div_q64(a: u128, b: u128) -> u128 {
     if b == 0 { return u128::MAX; }
@@ -47,17 +57,31 @@ pub fn div_q64(a: u128, b: u128) -> u128 {
         return (a << 64) / b;
     }
 
+    // (a << 64) / b = (a / b) << 64 + ((a % b) << 64) / b
     let q = a / b;
-    let r = a % b;
-
-    let q_part = q << 64;
-    let r_part = if r <= (u128::MAX >> 64) {
-        (r << 64) / b
-    } else {
-        ((r >> 32) << 32) / (b >> 32).max(1)
-    };
+    if q >> 64 != 0 {
+        return u128::MAX;
+    }
+    (q << 64) | shl64_div(a % b, b)
+}
 
-    q_part.saturating_add(r_part)
+/// `(r << 64) / b` for `r < b`, by restoring shift-subtract division
+///
+/// The running remainder stays below `b`, so each doubled remainder is at
+/// most 129 bits; the bit shifted out is tracked separately.
+fn shl64_div(r: u128, b: u128) -> u128 {
+    let mut rem = r;
+    let mut quotient: u128 = 0;
+    for _ in 0..64 {
+        let carry = rem >> 127 != 0;
+        rem <<= 1;
+        quotient <<= 1;
+        if carry || rem >= b {
+            rem = rem.wrapping_sub(b);
+            quotient |= 1;
+        }
+    }
+    quotient
 }
 
 /// Safe multiply-divide using U256 to prevent overflow
@@ -75,12 +99,38 @@ pub fn mul_div(env: &Env, a: u128, b: u128, denominator: u128) -> u128 {
     result.to_u128().unwrap_or(u128::MAX)
 }
 
+/// Basis-point share of a value: `value * bps / 10000`, rounded toward zero
+/// 
+/// The product is formed in 256 bits, so large values never saturate before
+/// the division. Only a result that does not fit in i128 (possible with bps
+/// above 10000) saturates.
+pub fn mul_bps(env: &Env, value: i128, bps: u32) -> i128 {
+    let share = mul_div(env, value.unsigned_abs(), bps as u128, BPS_DENOMINATOR);
+    let share = u128_to_i128_saturating(share);
+    if value < 0 { -share } else { share }
+}
+
+/// `mul_div` rounding up: ceil((a * b) / denominator)
+pub fn mul_div_round_up(env: &Env, a: u128, b: u128, denominator: u128) -> u128 {
+    if denominator == 0 { panic!("mul_div_round_up: divide by zero"); }
+
+    let product = U256::from_u128(env, a).mul(&U256::from_u128(env, b));
+    let den_256 = U256::from_u128(env, denominator);
+    let result = product.div(&den_256).to_u128().unwrap_or(u128::MAX);
+
+    if product.rem_euclid(&den_256) == U256::from_u32(env, 0) {
+        result
+    } else {
+        result.saturating_add(1)
+    }
+}
+
 /// Divide with rounding up - made public
 #[inline]
 pub fn div_round_up(numerator: u128, denominator: u128) -> u128 {
     if denominator == 0 { return 0; }
     let result = numerator / denominator;
-    if numerator % denominator != 0 {
+    if !numerator.is_multiple_of(denominator) {
         result.saturating_add(1)
     } else {
         result
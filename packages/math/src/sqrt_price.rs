@@ -4,7 +4,7 @@
 
 use soroban_sdk::Env;
 use crate::constants::{MIN_TICK, MAX_TICK};
-use crate::q64::{mul_q64, div_q64, mul_div, ONE_X64};
+use crate::q64::{mul_q64, div_q64, mul_div, mul_div_round_up, ONE_X64};
 
 /// Convert tick to sqrt price in Q64.64 format
 /// Formula: sqrt(1.0001^tick) * 2^64
@@ -72,7 +72,7 @@ pub fn tick_to_sqrt_price_x64(_env: &Env, tick: i32) -> u128 {
 /// tick far from the true price. Binary search over the full tick range (~21
 /// steps), exact against the same constants used for the forward conversion.
 pub fn get_tick_at_sqrt_ratio(sqrt_price_x64: u128) -> i32 {
-    if sqrt_price_x64 <= get_sqrt_ratio_at_tick(MIN_TICK) {
+    if sqrt_price_x64 < get_sqrt_ratio_at_tick(MIN_TICK) {
         return MIN_TICK;
     }
     if sqrt_price_x64 >= get_sqrt_ratio_at_tick(MAX_TICK) {
@@ -189,15 +189,46 @@ pub fn compute_swap_step_with_target(
         )
     };
 
-    let final_amount_in = if !target_reached && amount_in > amount_rem_u {
-        amount_rem_u
-    } else {
-        amount_in
-    };
+    if !target_reached && amount_in > amount_rem_u {
+        // The price was rounded past what the remaining input pays for.
+        // Clamp the input and pay out only for the move it achieves, so the
+        // output never exceeds what the consumed input is worth.
+        let sqrt_price_achieved = if zero_for_one {
+            next_sqrt_price_from_amount0_round_up(env, sqrt_price_current, liq_u, amount_rem_u)
+        } else {
+            sqrt_price_next
+        };
+        let achieved_out = if zero_for_one {
+            get_amount_1_delta(sqrt_price_current, sqrt_price_achieved, liq_u, false)
+        } else {
+            get_amount_0_delta(sqrt_price_current, sqrt_price_achieved, liq_u, false)
+        };
+        return (
+            sqrt_price_achieved,
+            u128_to_i128_saturating(amount_rem_u),
+            u128_to_i128_saturating(achieved_out.min(amount_out)),
+        );
+    }
 
     (
         sqrt_price_next,
-        u128_to_i128_saturating(final_amount_in),
+        u128_to_i128_saturating(amount_in),
         u128_to_i128_saturating(amount_out)
     )
+}
+
+/// Price after adding `amount_in` of token0, rounded up (toward the current
+/// price), so the move never exceeds what the input pays for
+fn next_sqrt_price_from_amount0_round_up(
+    env: &Env,
+    sqrt_price: u128,
+    liquidity: u128,
+    amount_in: u128,
+) -> u128 {
+    let product = amount_in.saturating_mul(sqrt_price);
+    let numerator = liquidity.saturating_mul(sqrt_price);
+    let denominator = (liquidity << 64).saturating_add(product);
+
+    if denominator == 0 { return sqrt_price; }
+    mul_div_round_up(env, numerator, ONE_X64, denominator).min(sqrt_price)
 }
\ No newline at end of file
@@ -0,0 +1,16 @@
+// Token Pair Ordering
+
+use soroban_sdk::Address;
+
+/// Canonical order of a token pair: `(token0, token1, swapped)`
+///
+/// `token0` is the lower address, the order factory and pool store pairs in.
+/// `swapped` is true when `token_b` came first, so a swap selling `token_a`
+/// has `zero_for_one = !swapped` without asking the pool.
+pub fn sort_tokens(token_a: &Address, token_b: &Address) -> (Address, Address, bool) {
+    if token_a < token_b {
+        (token_a.clone(), token_b.clone(), false)
+    } else {
+        (token_b.clone(), token_a.clone(), true)
+    }
+}
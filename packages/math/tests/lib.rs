@@ -9,4 +9,5 @@ mod test_liquidity;
 mod test_integration;
 
 // Re-export for convenience
+#[allow(unused_imports)]
 use belugaswap_math::*;
\ No newline at end of file
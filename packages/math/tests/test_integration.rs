@@ -33,6 +33,67 @@ fn test_integration_swap_calculation() {
     assert!(price_change_pct < 1000, "Price impact should be < 10%");
 }
 
+#[test]
+fn test_integration_swap_step_matches_target_variant() {
+    let env = Env::default();
+    let liquidity = 1_000_000i128;
+    
+    for zero_for_one in [true, false] {
+        for amount_in in [1i128, 7, 999, 10_000, 250_000] {
+            let (next_price, consumed, received) =
+                compute_swap_step(&env, ONE_X64, liquidity, amount_in, zero_for_one);
+            let (target_price, target_in, target_out) = compute_swap_step_with_target(
+                &env, ONE_X64, liquidity, amount_in, zero_for_one, next_price,
+            );
+            
+            assert_eq!(next_price, target_price);
+            assert!(consumed <= target_in);
+            assert!(received <= target_out, "no-target output {} > {}", received, target_out);
+            
+            // Output is rounded down, never up
+            let liq = liquidity as u128;
+            let exact_out = if zero_for_one {
+                get_amount_1_delta(ONE_X64, next_price, liq, false)
+            } else {
+                get_amount_0_delta(ONE_X64, next_price, liq, false)
+            };
+            assert_eq!(received as u128, exact_out);
+        }
+    }
+}
+
+#[test]
+fn test_integration_clamped_step_pays_only_for_consumed_input() {
+    let env = Env::default();
+    let far_target = get_sqrt_ratio_at_tick(-100_000);
+    let mut clamped = 0;
+    
+    for liquidity in [1_000i128, 77_777, 1_000_000, 123_456_789] {
+        for amount_in in [1i128, 3, 999, 10_001, 250_000] {
+            let liq = liquidity as u128;
+            // The unclamped move: the price the full input is rounded to
+            let full_price = get_next_sqrt_price_from_input(&env, ONE_X64, liq, amount_in as u128, true);
+            let full_in = get_amount_0_delta(ONE_X64, full_price, liq, true);
+            let full_out = get_amount_1_delta(ONE_X64, full_price, liq, false);
+            if full_in > amount_in as u128 {
+                clamped += 1;
+            }
+            
+            let (price, consumed, received) =
+                compute_swap_step_with_target(&env, ONE_X64, liquidity, amount_in, true, far_target);
+            assert!(consumed <= amount_in);
+            assert!(received as u128 <= full_out, "output {} over full move {}", received, full_out);
+            
+            // The output is what the returned price move pays, and reaching
+            // that price costs no more than the input consumed
+            assert!(price <= ONE_X64 && price >= full_price);
+            assert_eq!(received as u128, get_amount_1_delta(ONE_X64, price, liq, false));
+            assert!(get_amount_0_delta(ONE_X64, price, liq, true) <= consumed as u128);
+        }
+    }
+    assert!(clamped > 0, "no case exercised the clamp");
+}
+
 #[test]
 fn test_integration_add_liquidity() {
     let env = Env::default();
@@ -184,7 +245,7 @@ fn test_integration_swap_with_fees() {
     let fee_bps = 30u32; // 0.3% fee
     
     // Calculate swap
-    let (next_sqrt_price, consumed, received) = compute_swap_step(
+    let (_next_sqrt_price, consumed, received) = compute_swap_step(
         &env, current_sqrt_price, liquidity, amount_in, true
     );
     
@@ -224,6 +285,23 @@ fn test_integration_tick_spacing() {
     }
 }
 
+#[test]
+fn test_integration_tick_spacing_ceil() {
+    assert_eq!(snap_tick_to_spacing_ceil(601, 60), 660);
+    assert_eq!(snap_tick_to_spacing_ceil(600, 60), 600);
+    assert_eq!(snap_tick_to_spacing_ceil(-601, 60), -600);
+    assert_eq!(snap_tick_to_spacing_ceil(-75, 60), -60);
+
+    for spacing in [1, 10, 60, 200] {
+        let test_tick = 12345;
+        let snapped = snap_tick_to_spacing_ceil(test_tick, spacing);
+
+        assert_eq!(snapped % spacing, 0, "Tick should be aligned to spacing");
+        assert!(snapped >= test_tick, "Snapped tick should not be below original");
+        assert!(snapped - test_tick < spacing, "Should be within one spacing");
+    }
+}
+
 // ============================================================
 // INTEGRATION: POSITION VALUE
 // ============================================================
@@ -320,7 +398,7 @@ fn test_integration_no_arbitrage() {
     };
     
     assert!(
-        loss_pct >= 0 && loss_pct < 15, // Allow up to 15% loss due to precision
+        (0..15).contains(&loss_pct), // Allow up to 15% loss due to precision
         "Round-trip loss should be reasonable: {}%", loss_pct
     );
 }
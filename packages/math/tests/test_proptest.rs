@@ -2,7 +2,7 @@
 // Run with: cargo test -p belugaswap-math --test test_proptest
 
 use belugaswap_math::*;
-use soroban_sdk::Env;
+use soroban_sdk::{Env, U256};
 use proptest::prelude::*;
 
 // ============================================================
@@ -77,6 +77,94 @@ proptest! {
         let result = mul_div(&env, a, b, b);
         prop_assert_eq!(result, a);
     }
+
+    /// Property: mul_q64 matches a 256-bit reference, saturating above u128
+    #[test]
+    fn prop_mul_q64_matches_u256(
+        a in (u128::MAX >> 8)..=u128::MAX,
+        b in (u128::MAX >> 80)..=u128::MAX
+    ) {
+        prop_assert_eq!(mul_q64(a, b), mul_q64_reference(a, b));
+    }
+
+    /// Property: div_q64 is exact for large numerators (no precision loss)
+    #[test]
+    fn prop_div_q64_matches_u256_large(
+        a in (u128::MAX >> 64)..=u128::MAX,
+        b in 1u128..=u128::MAX
+    ) {
+        prop_assert_eq!(div_q64(a, b), div_q64_reference(a, b));
+    }
+
+    /// Property: div_q64 is exact across the full input range
+    #[test]
+    fn prop_div_q64_matches_u256_any(a in any::<u128>(), b in 1u128..=u128::MAX) {
+        prop_assert_eq!(div_q64(a, b), div_q64_reference(a, b));
+    }
+
+    /// Property: mul_q64 is exact across the full operand range
+    #[test]
+    fn prop_mul_q64_matches_u256_any(a in any::<u128>(), b in any::<u128>()) {
+        prop_assert_eq!(mul_q64(a, b), mul_q64_reference(a, b));
+    }
+}
+
+/// (a << 64) / b computed with U256, saturated to u128::MAX
+fn div_q64_reference(a: u128, b: u128) -> u128 {
+    let env = Env::default();
+    let numerator = U256::from_u128(&env, a).shl(64);
+    numerator.div(&U256::from_u128(&env, b)).to_u128().unwrap_or(u128::MAX)
+}
+
+/// (a * b) >> 64 computed with U256, saturated to u128::MAX
+fn mul_q64_reference(a: u128, b: u128) -> u128 {
+    let env = Env::default();
+    let product = U256::from_u128(&env, a).mul(&U256::from_u128(&env, b));
+    product.shr(64).to_u128().unwrap_or(u128::MAX)
+}
+
+// ============================================================
+// BASIS POINT PROPERTY TESTS
+// ============================================================
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(1000))]
+
+    /// Property: mul_bps matches a 256-bit reference for values whose
+    /// product with bps overflows i128
+    #[test]
+    fn prop_mul_bps_matches_u256_large(
+        value in (i128::MAX >> 16)..=i128::MAX,
+        bps in 0u32..=10_000
+    ) {
+        let env = Env::default();
+        prop_assert_eq!(mul_bps(&env, value, bps), mul_bps_reference(value, bps));
+    }
+
+    /// Property: mul_bps matches the reference for any sign and any bps
+    #[test]
+    fn prop_mul_bps_matches_u256_any(value in (i128::MIN + 1)..=i128::MAX, bps in any::<u32>()) {
+        let env = Env::default();
+        prop_assert_eq!(mul_bps(&env, value, bps), mul_bps_reference(value, bps));
+    }
+
+    /// Property: a share of at most 100% never exceeds the value
+    #[test]
+    fn prop_mul_bps_bounded(value in 0i128..=i128::MAX, bps in 0u32..=10_000) {
+        let env = Env::default();
+        let share = mul_bps(&env, value, bps);
+        prop_assert!(share >= 0 && share <= value);
+    }
+}
+
+/// |value| * bps / 10000 computed with U256, saturated to i128::MAX, then
+/// given the sign of `value`
+fn mul_bps_reference(value: i128, bps: u32) -> i128 {
+    let env = Env::default();
+    let product = U256::from_u128(&env, value.unsigned_abs()).mul(&U256::from_u32(&env, bps));
+    let share = product.div(&U256::from_u32(&env, 10_000)).to_u128().unwrap_or(u128::MAX);
+    let share = share.min(i128::MAX as u128) as i128;
+    if value < 0 { -share } else { share }
 }
 
 // ============================================================
@@ -104,10 +192,32 @@ proptest! {
         // If we get here, no panic occurred
     }
 
+    /// Property: get_tick_at_sqrt_ratio inverts get_sqrt_ratio_at_tick
+    /// NOTE: Near MIN_TICK adjacent ticks share the same Q64 ratio; there the
+    /// inverse returns the highest tick of that bucket.
+    #[test]
+    fn prop_tick_sqrt_ratio_roundtrip(tick in MIN_TICK..=MAX_TICK) {
+        let ratio = get_sqrt_ratio_at_tick(tick);
+        let back = get_tick_at_sqrt_ratio(ratio);
+        if tick == MAX_TICK || get_sqrt_ratio_at_tick(tick + 1) > ratio {
+            prop_assert_eq!(back, tick, "Roundtrip failed: tick={}, ratio={}", tick, ratio);
+        } else {
+            prop_assert!(back > tick && get_sqrt_ratio_at_tick(back) == ratio,
+                "Roundtrip left bucket: tick={}, back={}, ratio={}", tick, back, ratio);
+        }
+    }
+
+    /// Property: get_tick_at_sqrt_ratio is exact across the practical range
+    #[test]
+    fn prop_tick_sqrt_ratio_roundtrip_exact(tick in -400000i32..=MAX_TICK) {
+        let back = get_tick_at_sqrt_ratio(get_sqrt_ratio_at_tick(tick));
+        prop_assert_eq!(back, tick);
+    }
+
     /// Property: Tick symmetry - price(tick) * price(-tick) ≈ 1.0
     #[test]
     fn prop_tick_symmetry(tick in 1i32..100000) {
-        if tick > MAX_TICK || tick < MIN_TICK {
+        if !(MIN_TICK..=MAX_TICK).contains(&tick) {
             return Ok(());
         }
         
@@ -305,6 +415,28 @@ proptest! {
             consumed, amount_in);
     }
 
+    /// Property: Without a target, output never exceeds the targeted step
+    /// over the same price move
+    #[test]
+    fn prop_swap_step_output_not_above_target_variant(
+        sqrt_price in ONE_X64/2..ONE_X64*2,
+        liquidity in 1_000i128..1_000_000_000i128,
+        amount_in in 1i128..100_000_000i128,
+        zero_for_one: bool
+    ) {
+        let env = Env::default();
+        
+        let (next_price, _consumed, received) = compute_swap_step(
+            &env, sqrt_price, liquidity, amount_in, zero_for_one
+        );
+        let (_price, _in, target_out) = compute_swap_step_with_target(
+            &env, sqrt_price, liquidity, amount_in, zero_for_one, next_price
+        );
+        
+        prop_assert!(received <= target_out,
+            "no-target output {} exceeds targeted output {}", received, target_out);
+    }
+
     /// Property: Tick spacing works correctly
     #[test]
     fn prop_tick_spacing(
@@ -22,6 +22,20 @@ fn test_mul_q64_basic() {
     assert_eq!(mul_q64(half, two), one);
 }
 
+#[test]
+fn test_mul_q64_saturates_on_overflow() {
+    // Two near-max sqrt prices: the product is far above u128
+    assert_eq!(mul_q64(u128::MAX, u128::MAX), u128::MAX);
+    assert_eq!(mul_q64(u128::MAX - 1, 1u128 << 100), u128::MAX);
+    
+    // Largest operand that still fits: u128::MAX * 1.0
+    assert_eq!(mul_q64(u128::MAX, ONE_X64), u128::MAX);
+    assert_eq!(mul_q64(1u128 << 127, ONE_X64), 1u128 << 127);
+    
+    // term_hh = 2^64 fits in u128, but term_hh << 64 does not
+    assert_eq!(mul_q64(1u128 << 100, 1u128 << 92), u128::MAX);
+}
+
 #[test]
 fn test_mul_q64_zero() {
     let one = ONE_X64;
@@ -66,6 +80,23 @@ fn test_div_q64_basic() {
     assert_eq!(div_q64(10, 5), ONE_X64 * 2);
 }
 
+#[test]
+fn test_div_q64_large_numerator_exact() {
+    // 3 * 2^100 / 2^101 = 1.5; the remainder (2^100) is too large to shift
+    // left by 64 directly, which used to drop its low 32 bits
+    assert_eq!(div_q64(3u128 << 100, 1u128 << 101), 3u128 << 63);
+    
+    // 1/3 of a large numerator, exact to the last bit
+    let third = div_q64(1u128 << 100, 3u128 << 100);
+    assert_eq!(third, (u128::MAX / 3) >> 64);
+    
+    // Quotient above u128 saturates
+    assert_eq!(div_q64(u128::MAX, 1), u128::MAX);
+    assert_eq!(div_q64(1u128 << 64, 1), u128::MAX);
+    // Largest exact result
+    assert_eq!(div_q64((1u128 << 64) - 1, 1), ((1u128 << 64) - 1) << 64);
+}
+
 #[test]
 fn test_div_q64_zero_denominator() {
     let result = div_q64(ONE_X64, 0);
@@ -289,15 +320,15 @@ fn test_div_mul_roundtrip() {
     // Test: (a / b) * b ≈ a (in Q64 space)
     
     // Start with Q64 value
-    let a_q64 = ONE_X64;
-    let b_raw = 2;
+    let _a_q64 = ONE_X64;
+    let _b_raw = 2;
     
     // Divide Q64 by raw value (this requires converting back first)
     // Actually div_q64 expects raw inputs, so we need to rethink this test
     
     // Better approach: test that mul_q64 and div_q64 are inverses
     // Start with raw value
-    let raw = 100u128;
+    let _raw = 100u128;
     
     // Convert to Q64 using div_q64(raw * ONE_X64, 1) - but that's not how it works
     // div_q64 takes raw values and produces Q64 output
@@ -364,4 +395,21 @@ fn test_div_q64_monotonic() {
         );
         prev_result = result;
     }
-}
\ No newline at end of file
+}
+#[test]
+fn test_mul_bps_exact_where_saturating_math_clips() {
+    let env = Env::default();
+
+    assert_eq!(mul_bps(&env, 1_000_000, 30), 3_000);
+    assert_eq!(mul_bps(&env, 9_999, 1), 0);
+    assert_eq!(mul_bps(&env, -1_000_000, 30), -3_000);
+
+    // value * bps overflows i128, so saturating_mul would clip the result
+    let value = i128::MAX / 2;
+    assert_eq!(value.saturating_mul(10_000) / 10_000, i128::MAX / 10_000);
+    assert_eq!(mul_bps(&env, value, 10_000), value);
+    assert_eq!(mul_bps(&env, value, 5_000), value / 2);
+
+    // Only a result beyond i128 saturates
+    assert_eq!(mul_bps(&env, i128::MAX, 20_000), i128::MAX);
+}
@@ -104,7 +104,7 @@ fn test_sqrt_price_monotonically_increasing() {
     
     // Test every 1000 ticks
     for tick in (-100000..=100000).step_by(1000) {
-        if tick < MIN_TICK || tick > MAX_TICK {
+        if !(MIN_TICK..=MAX_TICK).contains(&tick) {
             continue;
         }
         
@@ -0,0 +1,18 @@
+use belugaswap_math::sort_tokens;
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+#[test]
+fn test_sort_tokens_is_order_independent() {
+    let env = Env::default();
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+
+    let (token0, token1, ab_swapped) = sort_tokens(&a, &b);
+    let (token0_ba, token1_ba, ba_swapped) = sort_tokens(&b, &a);
+    assert_eq!((&token0, &token1), (&token0_ba, &token1_ba));
+    assert!(token0 < token1);
+
+    // Exactly one of the two input orders is the canonical one
+    assert_ne!(ab_swapped, ba_swapped);
+    assert_eq!(ab_swapped, a != token0);
+}
@@ -5,12 +5,13 @@ pub mod manager;
 pub mod fees;
 
 // Re-export types
-pub use types::{Position, PositionInfo};
+pub use types::{Position, PositionInfo, PositionToken};
 
 // Re-export manager functions
 pub use manager::{
     modify_position, 
     update_position,
+    merge_into,
     has_liquidity,           // <-- ADDED: was not exported
     has_uncollected_fees,    // <-- ADDED: was missing
     is_empty,                // <-- ADDED: was missing
@@ -8,7 +8,7 @@ use crate::types::Position;
 /// 
 /// This is the core Uniswap V3 fee collection pattern:
 /// 1. Calculate delta = current_inside - last_inside (using wrapping arithmetic)
-/// 2. owed_tokens += liquidity * delta / 2^64
+/// 2. owed_tokens += liquidity * delta / 2^64 (and the lifetime totals)
 /// 3. Update last_inside = current_inside
 pub fn update_position(
     env: &Env,
@@ -33,6 +33,8 @@ pub fn update_position(
         // Accumulate owed tokens with saturation
         pos.tokens_owed_0 = pos.tokens_owed_0.saturating_add(fee_0);
         pos.tokens_owed_1 = pos.tokens_owed_1.saturating_add(fee_1);
+        pos.lifetime_fees_0 = pos.lifetime_fees_0.saturating_add(fee_0);
+        pos.lifetime_fees_1 = pos.lifetime_fees_1.saturating_add(fee_1);
     }
 
     // Always update checkpoints to current values
@@ -44,25 +46,84 @@ pub fn update_position(
 /// 
 /// This combines fee update with liquidity change:
 /// 1. First update fees based on current fee_growth_inside
-/// 2. Then adjust liquidity
+/// 2. Then adjust liquidity, folding `sqrt_price_x64` into the cost basis
+///    when liquidity is added
 pub fn modify_position(
     env: &Env,
     pos: &mut Position,
     liquidity_delta: i128,
     fee_growth_inside_0: u128,
     fee_growth_inside_1: u128,
+    sqrt_price_x64: u128,
 ) {
     // First update fees
     update_position(env, pos, fee_growth_inside_0, fee_growth_inside_1);
 
     // Then adjust liquidity
     if liquidity_delta > 0 {
+        pos.cost_basis_sqrt_price_x64 = weighted_cost_basis(
+            env,
+            pos.cost_basis_sqrt_price_x64,
+            pos.liquidity,
+            sqrt_price_x64,
+            liquidity_delta,
+        );
         pos.liquidity = pos.liquidity.saturating_add(liquidity_delta);
     } else if liquidity_delta < 0 {
         pos.liquidity = pos.liquidity.saturating_sub(liquidity_delta.abs());
     }
 }
 
+/// Fold `source` into `target` over the same tick range
+/// 
+/// Both positions are first settled at the current fee growth inside, so
+/// neither loses fees and the merged position shares one checkpoint. Then
+/// liquidity, owed tokens and lifetime fees are summed into `target`.
+pub fn merge_into(
+    env: &Env,
+    target: &mut Position,
+    source: &Position,
+    fee_growth_inside_0: u128,
+    fee_growth_inside_1: u128,
+) {
+    let mut source = source.clone();
+    update_position(env, target, fee_growth_inside_0, fee_growth_inside_1);
+    update_position(env, &mut source, fee_growth_inside_0, fee_growth_inside_1);
+
+    target.cost_basis_sqrt_price_x64 = weighted_cost_basis(
+        env,
+        target.cost_basis_sqrt_price_x64,
+        target.liquidity,
+        source.cost_basis_sqrt_price_x64,
+        source.liquidity,
+    );
+    target.liquidity = target.liquidity.saturating_add(source.liquidity);
+    target.tokens_owed_0 = target.tokens_owed_0.saturating_add(source.tokens_owed_0);
+    target.tokens_owed_1 = target.tokens_owed_1.saturating_add(source.tokens_owed_1);
+    target.lifetime_fees_0 = target.lifetime_fees_0.saturating_add(source.lifetime_fees_0);
+    target.lifetime_fees_1 = target.lifetime_fees_1.saturating_add(source.lifetime_fees_1);
+}
+
+/// Liquidity-weighted mean of two sqrt prices, in 256-bit math
+/// 
+/// Returns `price_b` when `liquidity_a` is zero, so an emptied position
+/// starts a fresh basis.
+fn weighted_cost_basis(
+    env: &Env,
+    price_a: u128,
+    liquidity_a: i128,
+    price_b: u128,
+    liquidity_b: i128,
+) -> u128 {
+    let liquidity_a = liquidity_a.max(0) as u128;
+    let liquidity_b = liquidity_b.max(0) as u128;
+    let total = liquidity_a.saturating_add(liquidity_b);
+    if liquidity_a == 0 || total == 0 {
+        return price_b;
+    }
+    mul_div(env, price_a, liquidity_a, total).saturating_add(mul_div(env, price_b, liquidity_b, total))
+}
+
 // ============================================================
 // POSITION HELPERS (were missing from refactored package)
 // ============================================================
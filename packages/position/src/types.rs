@@ -1,45 +1,40 @@
-use soroban_sdk::contracttype;
+use soroban_sdk::{contracttype, Address};
 
 #[contracttype]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct Position {
     pub liquidity: i128,
     pub fee_growth_inside_last_0: u128,
     pub fee_growth_inside_last_1: u128,
     pub tokens_owed_0: u128,
     pub tokens_owed_1: u128,
-}
-
-impl Default for Position {
-    fn default() -> Self {
-        Self {
-            liquidity: 0,
-            fee_growth_inside_last_0: 0,
-            fee_growth_inside_last_1: 0,
-            tokens_owed_0: 0,
-            tokens_owed_1: 0,
-        }
-    }
+    /// Liquidity-weighted average pool sqrt price (Q64.64) at which the
+    /// liquidity was added; removals leave it unchanged
+    pub cost_basis_sqrt_price_x64: u128,
+    /// Fees ever settled into `tokens_owed`; collects never reset them
+    pub lifetime_fees_0: u128,
+    pub lifetime_fees_1: u128,
 }
 
 #[contracttype]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct PositionInfo {
     pub liquidity: i128,
     pub amount0: i128,
     pub amount1: i128,
     pub fees_owed_0: u128,
     pub fees_owed_1: u128,
+    /// Average entry sqrt price (Q64.64), see `Position`
+    pub cost_basis_sqrt_price_x64: u128,
 }
 
-impl Default for PositionInfo {
-    fn default() -> Self {
-        Self {
-            liquidity: 0,
-            amount0: 0,
-            amount1: 0,
-            fees_owed_0: 0,
-            fees_owed_1: 0,
-        }
-    }
+/// Transferable position: a range and its state keyed by id rather than by
+/// owner, so ownership can move without touching the liquidity or fees
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PositionToken {
+    pub owner: Address,
+    pub lower_tick: i32,
+    pub upper_tick: i32,
+    pub position: Position,
 }
@@ -0,0 +1,41 @@
+use belugaswap_position::{merge_into, modify_position, Position};
+use soroban_sdk::Env;
+
+const P1: u128 = 1 << 64;
+const P2: u128 = 3 << 63;
+
+#[test]
+fn test_cost_basis_weights_additions_by_liquidity() {
+    let env = Env::default();
+    let mut pos = Position::default();
+
+    modify_position(&env, &mut pos, 1_000, 0, 0, P1);
+    assert_eq!(pos.cost_basis_sqrt_price_x64, P1);
+
+    // 1000 at 1.0 and 3000 at 1.5 average to 1.375
+    modify_position(&env, &mut pos, 3_000, 0, 0, P2);
+    assert_eq!(pos.cost_basis_sqrt_price_x64, 11 << 61);
+
+    // Removals keep the basis, even down to zero
+    modify_position(&env, &mut pos, -4_000, 0, 0, P1);
+    assert_eq!(pos.liquidity, 0);
+    assert_eq!(pos.cost_basis_sqrt_price_x64, 11 << 61);
+
+    // An emptied position starts a fresh basis
+    modify_position(&env, &mut pos, 500, 0, 0, P2);
+    assert_eq!(pos.cost_basis_sqrt_price_x64, P2);
+}
+
+#[test]
+fn test_merge_weights_cost_basis_by_liquidity() {
+    let env = Env::default();
+    let mut target = Position::default();
+    let mut source = Position::default();
+    modify_position(&env, &mut target, 3_000, 0, 0, P1);
+    modify_position(&env, &mut source, 1_000, 0, 0, P2);
+
+    merge_into(&env, &mut target, &source, 0, 0);
+    assert_eq!(target.liquidity, 4_000);
+    // 3000 at 1.0 and 1000 at 1.5 average to 1.125
+    assert_eq!(target.cost_basis_sqrt_price_x64, 9 << 61);
+}
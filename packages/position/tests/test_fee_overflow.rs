@@ -17,6 +17,9 @@ fn test_large_position_fees_not_dropped_on_overflow() {
         fee_growth_inside_last_1: 0,
         tokens_owed_0: 0,
         tokens_owed_1: 0,
+        cost_basis_sqrt_price_x64: 0,
+        lifetime_fees_0: 0,
+        lifetime_fees_1: 0,
     };
 
     // Act: fee growth delta of 1.0 (Q64.64) on token0.
@@ -37,6 +40,9 @@ fn test_update_position_credits_large_fee() {
         fee_growth_inside_last_1: 0,
         tokens_owed_0: 0,
         tokens_owed_1: 0,
+        cost_basis_sqrt_price_x64: 0,
+        lifetime_fees_0: 0,
+        lifetime_fees_1: 0,
     };
 
     // Act
@@ -46,3 +52,31 @@ fn test_update_position_credits_large_fee() {
     assert_eq!(pos.tokens_owed_0, 1u128 << 70);
     assert_eq!(pos.fee_growth_inside_last_0, 1u128 << 64);
 }
+
+#[test]
+fn test_fee_growth_delta_wraps_past_u128_max() {
+    // Arrange: the checkpoint sits just below u128::MAX and the accumulator
+    // has since wrapped around to a small value
+    let env = Env::default();
+    let mut pos = Position {
+        liquidity: 1 << 70,
+        fee_growth_inside_last_0: u128::MAX - (1u128 << 63) + 1,
+        fee_growth_inside_last_1: u128::MAX,
+        tokens_owed_0: 0,
+        tokens_owed_1: 0,
+        cost_basis_sqrt_price_x64: 0,
+        lifetime_fees_0: 0,
+        lifetime_fees_1: 0,
+    };
+
+    // Act: growth of 1.0 on token0 and 0.5 on token1, modulo 2^128
+    let inside_0 = 1u128 << 63;
+    let inside_1 = (1u128 << 63) - 1;
+    let (pending_0, pending_1) = calculate_pending_fees(&env, &pos, inside_0, inside_1);
+    update_position(&env, &mut pos, inside_0, inside_1);
+
+    // Assert: the wrapped delta is a real fee, not noise to discard
+    assert_eq!(pending_0, 1u128 << 70);
+    assert_eq!(pending_1, 1u128 << 69);
+    assert_eq!((pos.tokens_owed_0, pos.tokens_owed_1), (pending_0, pending_1));
+}
@@ -1,10 +1,12 @@
-use soroban_sdk::{Env, Symbol};
+use core::cell::Cell;
+use soroban_sdk::{panic_with_error, Env, Symbol};
 use belugaswap_math::{
-    constants::{MAX_SLIPPAGE_BPS, MAX_SWAP_ITERATIONS, MIN_OUTPUT_AMOUNT, MIN_SWAP_AMOUNT},
-    compute_swap_step_with_target, div_q64, get_sqrt_ratio_at_tick,
-    mul_div,
+    constants::{BPS_DENOMINATOR, MAX_SLIPPAGE_BPS, MAX_SWAP_ITERATIONS, MIN_OUTPUT_AMOUNT, MIN_SWAP_AMOUNT, Q64},
+    compute_swap_step_with_target, div_q64, mul_bps, mul_div, u128_to_i128_saturating, get_amount_0_delta, get_amount_1_delta,
+    get_next_sqrt_price_from_output, get_sqrt_ratio_at_tick, get_tick_at_sqrt_ratio,
 };
-use belugaswap_tick::TickInfo;
+use belugaswap_tick::{TickBitmapWord, TickInfo};
+use crate::types::SwapError;
 
 // ============================================================
 // SWAP STATE
@@ -20,6 +22,18 @@ pub struct SwapState {
     pub tick_spacing: i32,
     pub fee_growth_global_0: u128,
     pub fee_growth_global_1: u128,
+    /// Swap loop iteration budget, capped at MAX_SWAP_ITERATIONS
+    pub max_iterations: u32,
+    /// Smallest input accepted, in input token units (dust threshold)
+    pub min_amount_in: i128,
+    /// Smallest output delivered, in output token units (dust threshold)
+    pub min_amount_out: i128,
+    /// Cap on the creator's lifetime share of input-token fees, in bps (0 = no cap)
+    pub creator_fee_cap_bps: u32,
+    /// Fees charged in the input token over the pool's lifetime, before this swap
+    pub lifetime_fees_in: u128,
+    /// Creator fees taken in the input token over the pool's lifetime, before this swap
+    pub lifetime_creator_fees_in: u128,
 }
 
 // ============================================================
@@ -32,56 +46,74 @@ pub struct SwapState {
 /// * `env` - Soroban environment
 /// * `state` - Mutable swap state
 /// * `read_tick` - Callback to read tick info from storage
+/// * `read_word` - Callback to read a tick bitmap word from storage
 /// * `write_tick` - Callback to write tick info to storage
 /// * `emit_sync` - Callback to emit sync event
+/// * `emit_cross` - Callback run for every tick crossed, with the tick and the
+///   active liquidity after crossing it
 /// * `amount_specified` - Input amount
 /// * `zero_for_one` - Direction (true = token0 -> token1)
-/// * `sqrt_price_limit_x64` - Price limit (0 for no limit)
+/// * `sqrt_price_limit_x64` - Price limit (`None` for no limit)
 /// * `fee_bps` - Fee in basis points
 /// * `creator_fee_bps` - Creator fee in basis points
+/// * `protocol_fee_bps` - Protocol share of the LP fee in basis points
 /// 
 /// # Returns
-/// `(amount_in, amount_out)`
-pub fn engine_swap<F1, F2, F3>(
+/// `(amount_in, amount_out, creator_fee, protocol_fee, lp_fee)`
+///
+/// The three fee components are denominated in the input token and sum to
+/// the total fee charged on `amount_in`.
+///
+/// # Panics
+/// With `SwapError::SwapAmountTooSmall` or `SwapError::OutputDust` when the
+/// input or output falls below the state's dust thresholds, and with
+/// `SwapError::IterationLimit` when `state.max_iterations` runs out while
+/// input is left, rather than returning a partial fill.
+#[allow(clippy::too_many_arguments)]
+pub fn engine_swap<F1, F2, F3, F4, FW>(
     env: &Env,
     state: &mut SwapState,
     read_tick: F1,
+    read_word: FW,
     write_tick: F2,
     emit_sync: F3,
+    emit_cross: F4,
     amount_specified: i128,
     zero_for_one: bool,
-    sqrt_price_limit_x64: u128,
+    sqrt_price_limit_x64: Option<u128>,
     fee_bps: i128,
     creator_fee_bps: i128,
-) -> (i128, i128, i128)
+    protocol_fee_bps: i128,
+) -> (i128, i128, i128, i128, i128)
 where
     F1: Fn(&Env, i32) -> TickInfo,
     F2: Fn(&Env, i32, &TickInfo),
     F3: Fn(&Env, i32, u128),
+    F4: Fn(&Env, i32, i128),
+    FW: Fn(&Env, i32) -> TickBitmapWord,
 {
-    if amount_specified < MIN_SWAP_AMOUNT {
-        panic!("swap amount too small");
+    if amount_specified < state.min_amount_in.max(MIN_SWAP_AMOUNT) {
+        panic_with_error!(env, SwapError::SwapAmountTooSmall);
     }
 
     if amount_specified <= 0 {
-        return (0, 0, 0);
-    }
-
-    if state.liquidity <= 0 {
-        panic!("no liquidity available");
+        return (0, 0, 0, 0, 0);
     }
 
     engine_swap_internal(
         env,
         state,
         read_tick,
+        read_word,
         write_tick,
         emit_sync,
+        emit_cross,
         amount_specified,
         zero_for_one,
         sqrt_price_limit_x64,
         fee_bps,
         creator_fee_bps,
+        protocol_fee_bps,
         true,  
         false, 
     )
@@ -90,48 +122,185 @@ where
 /// Quote a swap without executing (read-only simulation)
 /// 
 /// # Returns
-/// `(amount_in_used, amount_out, final_sqrt_price)`
-pub fn quote_swap<F>(
+/// `(amount_in_used, amount_out, final_sqrt_price, final_tick, steps)`
+/// 
+/// `final_tick` is the tick the pool would end up at, derived from the final
+/// sqrt price (or the crossed boundary), not an estimate from tick spacing.
+/// `steps` counts the tick boundaries the swap would cross, a proxy for
+/// its execution cost.
+#[allow(clippy::too_many_arguments)]
+pub fn quote_swap<F, FW>(
     env: &Env,
     state: &SwapState,
     read_tick: F,
+    read_word: FW,
     amount_in: i128,
     zero_for_one: bool,
-    sqrt_price_limit_x64: u128,
+    sqrt_price_limit_x64: Option<u128>,
     fee_bps: i128,
-) -> (i128, i128, u128)
+) -> (i128, i128, u128, i32, u32)
 where
     F: Fn(&Env, i32) -> TickInfo,
+    FW: Fn(&Env, i32) -> TickBitmapWord,
 {
-    if amount_in < MIN_SWAP_AMOUNT || amount_in <= 0 || state.liquidity <= 0 {
-        return (0, 0, state.sqrt_price_x64);
+    if amount_in < state.min_amount_in.max(MIN_SWAP_AMOUNT) {
+        return (0, 0, state.sqrt_price_x64, state.current_tick, 0);
     }
 
     // Clone state for simulation (doesn't modify original)
     let mut sim_state = state.clone();
+    let steps = Cell::new(0u32);
 
-    let (amount_in_used, amount_out, _creator_fee) = engine_swap_internal(
+    let (amount_in_used, amount_out, _creator_fee, _protocol_fee, _lp_fee) = engine_swap_internal(
         env,
         &mut sim_state,
         read_tick,
+        read_word,
         |_, _, _| {}, // no-op write
         |_, _, _| {}, // no-op emit
+        |_, _, _| steps.set(steps.get() + 1),
         amount_in,
         zero_for_one,
         sqrt_price_limit_x64,
         fee_bps,
         0,     // No creator fee for quotes
+        0,     // No protocol fee for quotes
+        false, // don't panic
+        true,  // dry_run
+    );
+
+    (amount_in_used, amount_out, sim_state.sqrt_price_x64, sim_state.current_tick, steps.get())
+}
+
+/// Quote the fee split of a swap without executing (read-only simulation)
+/// 
+/// Runs the same dry run as `quote_swap` but charges the creator and
+/// protocol cuts, so the components match what `engine_swap` would take
+/// from the same state.
+/// 
+/// # Returns
+/// `(amount_in_used, creator_fee, protocol_fee, lp_fee)`, in the input token
+#[allow(clippy::too_many_arguments)]
+pub fn quote_swap_fees<F, FW>(
+    env: &Env,
+    state: &SwapState,
+    read_tick: F,
+    read_word: FW,
+    amount_in: i128,
+    zero_for_one: bool,
+    sqrt_price_limit_x64: Option<u128>,
+    fee_bps: i128,
+    creator_fee_bps: i128,
+    protocol_fee_bps: i128,
+) -> (i128, i128, i128, i128)
+where
+    F: Fn(&Env, i32) -> TickInfo,
+    FW: Fn(&Env, i32) -> TickBitmapWord,
+{
+    if amount_in < state.min_amount_in.max(MIN_SWAP_AMOUNT) {
+        return (0, 0, 0, 0);
+    }
+
+    let mut sim_state = state.clone();
+
+    let (amount_in_used, _amount_out, creator_fee, protocol_fee, lp_fee) = engine_swap_internal(
+        env,
+        &mut sim_state,
+        read_tick,
+        read_word,
+        |_, _, _| {}, // no-op write
+        |_, _, _| {}, // no-op emit
+        |_, _, _| {}, // no-op cross
+        amount_in,
+        zero_for_one,
+        sqrt_price_limit_x64,
+        fee_bps,
+        creator_fee_bps,
+        protocol_fee_bps,
         false, // don't panic
         true,  // dry_run
     );
 
-    (amount_in_used, amount_out, sim_state.sqrt_price_x64)
+    (amount_in_used, creator_fee, protocol_fee, lp_fee)
+}
+
+/// Quote the input needed to receive an exact output (read-only simulation)
+/// 
+/// Inverts each swap step from the output still owed to get the smallest
+/// input, then checks it once with an exact-input quote, adding a unit if the
+/// quote falls short. Executing `engine_swap` with that input therefore
+/// delivers at least `amount_out`.
+/// 
+/// # Returns
+/// `(amount_in_required, amount_out, final_sqrt_price, final_tick, steps)`,
+/// all zero (with the current price/tick) when the output cannot be reached.
+#[allow(clippy::too_many_arguments)]
+pub fn quote_swap_exact_output<F, FW>(
+    env: &Env,
+    state: &SwapState,
+    read_tick: F,
+    read_word: FW,
+    amount_out: i128,
+    zero_for_one: bool,
+    sqrt_price_limit_x64: Option<u128>,
+    fee_bps: i128,
+) -> (i128, i128, u128, i32, u32)
+where
+    F: Fn(&Env, i32) -> TickInfo + Clone,
+    FW: Fn(&Env, i32) -> TickBitmapWord + Clone,
+{
+    let unreachable = (0, 0, state.sqrt_price_x64, state.current_tick, 0);
+
+    if amount_out < state.min_amount_out.max(MIN_OUTPUT_AMOUNT) {
+        return unreachable;
+    }
+
+    let amount_in = estimate_input_for_output(
+        env,
+        state,
+        read_tick.clone(),
+        read_word.clone(),
+        amount_out,
+        zero_for_one,
+        sqrt_price_limit_x64,
+        fee_bps,
+    );
+    if amount_in <= 0 {
+        return unreachable;
+    }
+
+    let quote = |amount_in: i128| {
+        quote_swap(
+            env,
+            state,
+            read_tick.clone(),
+            read_word.clone(),
+            amount_in,
+            zero_for_one,
+            sqrt_price_limit_x64,
+            fee_bps,
+        )
+    };
+
+    let amount_in = amount_in.max(state.min_amount_in.max(MIN_SWAP_AMOUNT));
+    let mut result = quote(amount_in);
+
+    // Rounding the estimate cannot see (the per-step dust checks) may leave
+    // it a unit short
+    if result.1 < amount_out {
+        result = quote(amount_in.saturating_add(1));
+        if result.1 < amount_out {
+            return unreachable;
+        }
+    }
+
+    result
 }
 
 /// Validate and preview a swap
 /// 
 /// # Returns
-/// `Ok((amount_in_used, amount_out, fee_paid, price_impact_bps, final_price))` or `Err(Symbol)`
+/// `Ok((amount_in_used, amount_out, fee_paid, price_impact_bps, final_price, steps))` or `Err(Symbol)`
 /// 
 /// # Fee Calculation 
 /// fee_paid = amount_in_used * fee_bps / 10000
@@ -141,89 +310,111 @@ where
 /// price_impact = (expected_out - actual_out) / expected_out * 10000
 /// Where expected_out is calculated from spot price before swap
 /// Both values are in output token units (apples to apples)
-pub fn validate_and_preview_swap<F>(
+#[allow(clippy::too_many_arguments)]
+pub fn validate_and_preview_swap<F, FW>(
     env: &Env,
     state: &SwapState,
     read_tick: F,
+    read_word: FW,
     amount_in: i128,
     min_amount_out: i128,
     zero_for_one: bool,
-    sqrt_price_limit_x64: u128,
+    sqrt_price_limit_x64: Option<u128>,
     fee_bps: i128,
-) -> Result<(i128, i128, i128, i128, u128), Symbol>
+) -> Result<(i128, i128, i128, i128, u128, u32), Symbol>
 where
     F: Fn(&Env, i32) -> TickInfo + Clone,
+    FW: Fn(&Env, i32) -> TickBitmapWord + Clone,
 {
     // Validate input amount
-    if amount_in < MIN_SWAP_AMOUNT {
+    if amount_in < state.min_amount_in.max(MIN_SWAP_AMOUNT) {
         return Err(Symbol::new(env, "AMT_LOW"));
     }
 
-    // Validate liquidity
-    if state.liquidity <= 0 {
+    // Get actual quote (includes price impact from moving through ticks).
+    // With no active liquidity the quote first skips to the next initialized
+    // tick, so only a quote that fills nothing means there is none to reach.
+    let (amount_in_used, amount_out, final_price, _final_tick, steps) =
+        quote_swap(env, state, read_tick, read_word, amount_in, zero_for_one, sqrt_price_limit_x64, fee_bps);
+
+    if state.liquidity <= 0 && amount_in_used <= 0 {
         return Err(Symbol::new(env, "NO_LIQ"));
     }
 
-    // Calculate expected output at spot price (before swap)
-    // This gives us the "ideal" output without any price impact
-    let expected_out = calculate_expected_output(
-        state.sqrt_price_x64,
-        amount_in,
-        zero_for_one,
-        fee_bps,
-    );
-
-    // Get actual quote (includes price impact from moving through ticks)
-    let (amount_in_used, amount_out, final_price) =
-        quote_swap(env, state, read_tick, amount_in, zero_for_one, sqrt_price_limit_x64, fee_bps);
-
     // Check slippage against user's minimum
     if amount_out < min_amount_out {
         return Err(Symbol::new(env, "SLIP_HI"));
     }
 
     // Check minimum output (dust protection)
-    if amount_out < MIN_OUTPUT_AMOUNT {
+    if amount_out < state.min_amount_out.max(MIN_OUTPUT_AMOUNT) {
         return Err(Symbol::new(env, "OUT_DUST"));
     }
 
     // Calculate fee paid in INPUT token units
     // fee_paid = amount_in_used * fee_bps / 10000
-    let fee_paid = amount_in_used
-        .saturating_mul(fee_bps)
-        .saturating_div(10000);
+    let fee_paid = mul_bps(env, amount_in_used, fee_bps as u32);
+
+    let price_impact_bps = price_impact_bps(
+        env,
+        state.sqrt_price_x64,
+        amount_in_used,
+        amount_out,
+        zero_for_one,
+        fee_bps,
+    );
+
+    // Check maximum price impact
+    if price_impact_bps > MAX_SLIPPAGE_BPS {
+        return Err(Symbol::new(env, "SLIP_MAX"));
+    }
+
+    Ok((amount_in_used, amount_out, fee_paid, price_impact_bps, final_price, steps))
+}
+
+/// Price impact of a swap in basis points
+/// 
+/// price_impact = (expected_out - actual_out) / expected_out * 10000, where
+/// expected_out is what `amount_in_used` would get at the pre-swap spot price
+/// `sqrt_price_x64`. Measuring against the input actually used keeps a
+/// partial fill from reading as impact.
+pub fn price_impact_bps(
+    env: &Env,
+    sqrt_price_x64: u128,
+    amount_in_used: i128,
+    amount_out: i128,
+    zero_for_one: bool,
+    fee_bps: i128,
+) -> i128 {
+    let expected_out = calculate_expected_output(env, sqrt_price_x64, amount_in_used, zero_for_one, fee_bps);
 
-    // Calculate price impact in basis points
-    // price_impact = (expected_out - actual_out) / expected_out * 10000
     // Both are in OUTPUT token units (apples to apples)
-    let price_impact_bps = if expected_out > 0 && expected_out > amount_out {
+    if expected_out > 0 && expected_out > amount_out {
         (expected_out.saturating_sub(amount_out))
             .saturating_mul(10000)
             .saturating_div(expected_out)
     } else {
         0 // No impact or positive impact (unlikely but possible with rounding)
-    };
-
-    // Check maximum price impact
-    if price_impact_bps > MAX_SLIPPAGE_BPS {
-        return Err(Symbol::new(env, "SLIP_MAX"));
     }
-
-    Ok((amount_in_used, amount_out, fee_paid, price_impact_bps, final_price))
 }
 
 /// Calculate expected output at current spot price (no price impact)
 /// 
 /// This is used to measure price impact by comparing with actual output.
+/// The fee is deducted from the input first, so the comparison isolates the
+/// price movement and does not count the swap fee as impact.
 /// 
 /// Formula for token0 -> token1 (zero_for_one = true):
 ///   price = (sqrt_price_x64 / 2^64)^2
-///   output = input * price * (1 - fee)
+///   output = input * (1 - fee) * price
 /// 
 /// Formula for token1 -> token0 (zero_for_one = false):
-///   price = (2^64 / sqrt_price_x64)^2  
-///   output = input * price * (1 - fee)
+///   output = input * (1 - fee) / price
+/// 
+/// Intermediate products go through 256-bit `mul_div`, since the squared
+/// Q64.64 price alone overflows u128 at prices >= 1.
 fn calculate_expected_output(
+    env: &Env,
     sqrt_price_x64: u128,
     amount_in: i128,
     zero_for_one: bool,
@@ -233,108 +424,305 @@ fn calculate_expected_output(
         return 0;
     }
 
-    let amount_in_u = amount_in as u128;
-    const Q64: u128 = 1u128 << 64;
-    
     // Calculate amount after fee
-    let fee_multiplier = (10000 - fee_bps) as u128;
-    let amount_after_fee = amount_in_u
-        .saturating_mul(fee_multiplier)
-        .saturating_div(10000);
+    let amount_after_fee = mul_bps(env, amount_in, (10000 - fee_bps) as u32) as u128;
 
-    // Calculate output based on direction
+    // Apply the price one sqrt factor at a time
     let output = if zero_for_one {
-        // token0 -> token1: multiply by price
-        // output = amount * (sqrt_price / Q64)^2
-        // Simplified: output = amount * sqrt_price^2 / Q64^2
-        let price_squared = sqrt_price_x64.saturating_mul(sqrt_price_x64);
-        amount_after_fee
-            .saturating_mul(price_squared)
-            .saturating_div(Q64)
-            .saturating_div(Q64)
+        let partial = mul_div(env, amount_after_fee, sqrt_price_x64, Q64);
+        mul_div(env, partial, sqrt_price_x64, Q64)
     } else {
-        // token1 -> token0: divide by price
-        // output = amount / (sqrt_price / Q64)^2
-        // Simplified: output = amount * Q64^2 / sqrt_price^2
-        let price_squared = sqrt_price_x64.saturating_mul(sqrt_price_x64);
-        if price_squared == 0 {
-            return 0;
-        }
-        amount_after_fee
-            .saturating_mul(Q64)
-            .saturating_mul(Q64)
-            .saturating_div(price_squared)
+        let partial = mul_div(env, amount_after_fee, Q64, sqrt_price_x64);
+        mul_div(env, partial, Q64, sqrt_price_x64)
     };
 
-    // Cap at i128::MAX and return
-    if output > i128::MAX as u128 {
-        i128::MAX
-    } else {
-        output as i128
-    }
+    u128_to_i128_saturating(output)
 }
 
 // ============================================================
 // INTERNAL SWAP LOGIC
 // ============================================================
 
+/// Creator fee allowed under the state's lifetime cap
+/// 
+/// `swap_fees` and `swap_creator_fees` are what this swap has charged so far,
+/// including the current step's fee but not its creator fee. The creator may
+/// hold at most `creator_fee_cap_bps` of all input-token fees ever charged.
+fn capped_creator_fee(
+    env: &Env,
+    state: &SwapState,
+    creator_fee: i128,
+    swap_fees: i128,
+    swap_creator_fees: i128,
+) -> i128 {
+    if state.creator_fee_cap_bps == 0 || creator_fee <= 0 {
+        return creator_fee;
+    }
+    let fees = state.lifetime_fees_in.saturating_add(swap_fees as u128);
+    let allowed = mul_div(env, fees, state.creator_fee_cap_bps as u128, BPS_DENOMINATOR);
+    let taken = state.lifetime_creator_fees_in.saturating_add(swap_creator_fees as u128);
+    (creator_fee as u128).min(allowed.saturating_sub(taken)) as i128
+}
+
 /// Core swap logic following Uniswap V3 pattern
 /// 
 /// Creator fee is taken from LP fee (not from total swap amount)
 /// Formula: creator_fee = (lp_fee * creator_fee_bps) / 10000
-fn engine_swap_internal<F1, F2, F3>(
+/// 
+/// Protocol fee is then taken from what is left for LPs
+/// Formula: protocol_fee = ((step_fee - creator_fee) * protocol_fee_bps) / 10000
+#[allow(clippy::too_many_arguments)]
+fn engine_swap_internal<F1, F2, F3, F4, FW>(
     env: &Env,
     state: &mut SwapState,
     read_tick: F1,
+    read_word: FW,
     write_tick: F2,
     emit_sync: F3,
+    emit_cross: F4,
     amount_specified: i128,
     zero_for_one: bool,
-    sqrt_price_limit_x64: u128,
+    sqrt_price_limit_x64: Option<u128>,
     fee_bps: i128,
     creator_fee_bps: i128,
+    protocol_fee_bps: i128,
     allow_panic: bool,
     dry_run: bool,
-) -> (i128, i128, i128)
+) -> (i128, i128, i128, i128, i128)
 where
     F1: Fn(&Env, i32) -> TickInfo,
     F2: Fn(&Env, i32, &TickInfo),
     F3: Fn(&Env, i32, u128),
+    F4: Fn(&Env, i32, i128),
+    FW: Fn(&Env, i32) -> TickBitmapWord,
 {
     // Initialize swap state
     let mut amount_remaining = amount_specified;
     let mut amount_out_total: i128 = 0;
     let mut total_creator_fee: i128 = 0;
+    let mut total_protocol_fee: i128 = 0;
+    let mut total_lp_fee: i128 = 0;
+    // Input net of fees consumed by earlier steps
+    let mut net_in_total: i128 = 0;
+
+    let (sqrt_price, liquidity, current_tick) = walk_ticks(
+        env,
+        state,
+        read_tick,
+        read_word,
+        write_tick,
+        emit_cross,
+        zero_for_one,
+        sqrt_price_limit_x64,
+        allow_panic,
+        dry_run,
+        |state, sqrt_price, liquidity, sqrt_target| {
+            // Calculate fee divisor
+            let fee_divisor = 10000 - fee_bps;
+            if fee_divisor <= 0 {
+                if allow_panic {
+                    panic!("fee too high");
+                }
+                return None;
+            }
+
+            // Amount available after fee reservation
+            let amount_available =
+                available_after_fee(env, amount_remaining, net_in_total, fee_bps, fee_divisor);
+
+            if amount_available < MIN_OUTPUT_AMOUNT {
+                return None;
+            }
+
+            // Compute swap step
+            let (sqrt_next, amount_in, amount_out) = compute_swap_step_with_target(
+                env,
+                sqrt_price,
+                liquidity,
+                amount_available,
+                zero_for_one,
+                sqrt_target,
+            );
 
+            // Check minimum amounts
+            if amount_in < MIN_OUTPUT_AMOUNT || amount_out < MIN_OUTPUT_AMOUNT {
+                return None;
+            }
+
+            // Calculate step fee
+            let step_fee = calculate_step_fee(
+                amount_in,
+                amount_remaining,
+                amount_available,
+                net_in_total,
+                fee_bps,
+                fee_divisor,
+            );
+            net_in_total = net_in_total.saturating_add(amount_in);
+
+            // Validate fee
+            if step_fee < 0 || step_fee > amount_in {
+                if allow_panic {
+                    panic!("invalid fee calculation");
+                }
+                return None;
+            }
+
+            // Calculate creator fee (percentage of LP fee)
+            let uncapped_creator_fee = if creator_fee_bps > 0 && step_fee > 0 {
+                mul_bps(env, step_fee, creator_fee_bps as u32)
+            } else {
+                0
+            };
+
+            // Protocol fee (percentage of what remains for LPs)
+            let fee_after_creator = step_fee.saturating_sub(uncapped_creator_fee);
+            let protocol_fee = if protocol_fee_bps > 0 && fee_after_creator > 0 {
+                mul_bps(env, fee_after_creator, protocol_fee_bps as u32)
+            } else {
+                0
+            };
+
+            // Creator fee beyond the lifetime cap stays with the LPs
+            let swap_fees = total_creator_fee
+                .saturating_add(total_protocol_fee)
+                .saturating_add(total_lp_fee)
+                .saturating_add(step_fee);
+            let creator_fee = capped_creator_fee(env, state, uncapped_creator_fee, swap_fees, total_creator_fee);
+
+            // LP fee = total fee - creator fee - protocol fee
+            let lp_fee = step_fee.saturating_sub(creator_fee).saturating_sub(protocol_fee);
+
+            // Update amounts
+            amount_remaining = amount_remaining
+                .saturating_sub(amount_in)
+                .saturating_sub(step_fee);
+            amount_out_total = amount_out_total.saturating_add(amount_out);
+            total_creator_fee = total_creator_fee.saturating_add(creator_fee);
+            total_protocol_fee = total_protocol_fee.saturating_add(protocol_fee);
+            total_lp_fee = total_lp_fee.saturating_add(lp_fee);
+
+            // Update fee growth global for LP (Uniswap V3 style)
+            if liquidity > 0 && lp_fee > 0 {
+                let fee_u = lp_fee as u128;
+                let liq_u = liquidity as u128;
+                let growth_delta = div_q64(fee_u, liq_u);
+
+                if zero_for_one {
+                    state.fee_growth_global_0 = state.fee_growth_global_0.wrapping_add(growth_delta);
+                } else {
+                    state.fee_growth_global_1 = state.fee_growth_global_1.wrapping_add(growth_delta);
+                }
+            }
+
+            Some((sqrt_next, amount_remaining <= 0))
+        },
+    );
+
+    // Validate output
+    if amount_out_total < state.min_amount_out.max(MIN_OUTPUT_AMOUNT) {
+        if allow_panic {
+            panic_with_error!(env, SwapError::OutputDust);
+        } else {
+            return (0, 0, 0, 0, 0);
+        }
+    }
+
+    // Update state
+    state.sqrt_price_x64 = sqrt_price;
+    state.liquidity = liquidity;
+    state.current_tick = current_tick;
+
+    // Emit sync event (no-op in dry_run via callback)
+    emit_sync(env, state.current_tick, state.sqrt_price_x64);
+
+    let amount_in_total = amount_specified.saturating_sub(amount_remaining);
+    (amount_in_total, amount_out_total, total_creator_fee, total_protocol_fee, total_lp_fee)
+}
+
+/// Walk the price through tick ranges in the swap direction
+/// 
+/// The loop shared by exact-input swaps and exact-output estimates: it skips
+/// empty gaps, crosses initialized ticks and stops at the price limit or when
+/// the iteration budget runs out. `step` sizes each move inside a tick range;
+/// it gets the state, the current sqrt price, the active liquidity and the
+/// step's target price, and returns the price reached and whether the swap is
+/// filled, or `None` to stop.
+/// 
+/// # Returns
+/// `(sqrt_price, liquidity, current_tick)` where the walk stopped
+#[allow(clippy::too_many_arguments)]
+fn walk_ticks<F1, F2, F4, FW, S>(
+    env: &Env,
+    state: &mut SwapState,
+    read_tick: F1,
+    read_word: FW,
+    write_tick: F2,
+    emit_cross: F4,
+    zero_for_one: bool,
+    sqrt_price_limit_x64: Option<u128>,
+    allow_panic: bool,
+    dry_run: bool,
+    mut step: S,
+) -> (u128, i128, i32)
+where
+    F1: Fn(&Env, i32) -> TickInfo,
+    F2: Fn(&Env, i32, &TickInfo),
+    F4: Fn(&Env, i32, i128),
+    FW: Fn(&Env, i32) -> TickBitmapWord,
+    S: FnMut(&mut SwapState, u128, i128, u128) -> Option<(u128, bool)>,
+{
     let mut sqrt_price = state.sqrt_price_x64;
     let mut liquidity = state.liquidity;
     let mut current_tick = state.current_tick;
 
-    // Set default price limits
-    let sqrt_limit = if sqrt_price_limit_x64 == 0 {
-        if zero_for_one {
-            100 // Minimum price
-        } else {
-            u128::MAX - 1000 // Maximum price
-        }
-    } else {
-        sqrt_price_limit_x64
-    };
+    let sqrt_limit = resolve_sqrt_limit(sqrt_price_limit_x64, zero_for_one);
 
     // Main swap loop
+    let max_iterations = state.max_iterations.min(MAX_SWAP_ITERATIONS);
     let mut iterations = 0;
+    let mut filled = false;
 
-    while iterations < MAX_SWAP_ITERATIONS {
-        iterations += 1;
-
+    loop {
         // Exit conditions
-        if amount_remaining <= 0 {
+        if filled {
             break;
         }
 
-        if liquidity <= 0 {
+        // Out of budget with input left. Quotes report the partial fill;
+        // execution reverts instead of silently under-filling.
+        if iterations >= max_iterations {
+            if allow_panic {
+                panic_with_error!(env, SwapError::IterationLimit);
+            }
             break;
         }
+        iterations += 1;
+
+        // Out of range with input left: skip the empty gap to the next
+        // initialized tick instead of returning a silent partial fill
+        if liquidity <= 0 {
+            match skip_liquidity_gap(
+                env,
+                state,
+                &read_tick,
+                &read_word,
+                &write_tick,
+                current_tick,
+                zero_for_one,
+                sqrt_limit,
+                dry_run,
+            ) {
+                Some((crossed_tick, next_price, next_liquidity, next_tick)) => {
+                    emit_cross(env, crossed_tick, next_liquidity);
+                    sqrt_price = next_price;
+                    liquidity = next_liquidity;
+                    current_tick = next_tick;
+                    continue;
+                }
+                None => break,
+            }
+        }
 
         // Check price limit
         if zero_for_one && sqrt_price <= sqrt_limit {
@@ -347,14 +735,15 @@ where
         // Find next initialized tick
         let next_tick = belugaswap_tick::find_next_initialized_tick(
             env,
-            &read_tick,
+            &read_word,
             current_tick,
             state.tick_spacing,
             zero_for_one,
         );
 
         // Get sqrt price at next tick
-        let mut sqrt_target = get_sqrt_ratio_at_tick(next_tick);
+        let sqrt_tick = get_sqrt_ratio_at_tick(next_tick);
+        let mut sqrt_target = sqrt_tick;
 
         // Clamp target to user's price limit
         if zero_for_one {
@@ -365,176 +754,348 @@ where
             sqrt_target = sqrt_limit;
         }
 
-        // Calculate fee divisor
-        let fee_divisor = 10000 - fee_bps;
-        if fee_divisor <= 0 {
-            if allow_panic {
-                panic!("fee too high");
-            } else {
+        // The price already sits exactly on the next tick (pool initialized
+        // on it, or a previous swap ended on it from the other side). Cross
+        // it now; a zero-size step would otherwise end the swap before
+        // liquidity picks up the tick's net.
+        if sqrt_price == sqrt_target {
+            if sqrt_target != sqrt_tick {
+                // Clamped to the user's price limit
                 break;
             }
+            (liquidity, current_tick) = cross_initialized_tick(
+                env,
+                state,
+                &read_tick,
+                &write_tick,
+                next_tick,
+                liquidity,
+                zero_for_one,
+                dry_run,
+            );
+            emit_cross(env, next_tick, liquidity);
+            continue;
         }
 
-        // Amount available after fee reservation
-        let amount_available = amount_remaining
-            .saturating_mul(fee_divisor)
-            .saturating_div(10000);
-
-        if amount_available < MIN_OUTPUT_AMOUNT {
+        let Some((sqrt_next, step_filled)) = step(state, sqrt_price, liquidity, sqrt_target) else {
             break;
-        }
+        };
+        filled = step_filled;
 
-        // Compute swap step
-        let (sqrt_next, amount_in, amount_out) = if sqrt_price == sqrt_target {
-            (sqrt_price, 0, 0)
-        } else {
-            compute_swap_step_with_target(
+        // Handle tick crossing: only when the step landed on the tick itself.
+        // A user limit that coincides with the tick still crosses (Uniswap
+        // V3), so `current_tick` and `liquidity` never disagree at a boundary.
+        if sqrt_next == sqrt_tick {
+            sqrt_price = sqrt_tick;
+            (liquidity, current_tick) = cross_initialized_tick(
                 env,
-                sqrt_price,
+                state,
+                &read_tick,
+                &write_tick,
+                next_tick,
                 liquidity,
-                amount_available,
                 zero_for_one,
-                sqrt_target,
-            )
-        };
-
-        // Check minimum amounts
-        if amount_in < MIN_OUTPUT_AMOUNT || amount_out < MIN_OUTPUT_AMOUNT {
+                dry_run,
+            );
+            emit_cross(env, next_tick, liquidity);
+        } else if sqrt_next != sqrt_price {
+            sqrt_price = sqrt_next;
+            // Stopped inside a tick range: derive the tick from the price
+            // itself, the previous tick may be several ticks behind.
+            current_tick = get_tick_at_sqrt_ratio(sqrt_price);
+        } else {
             break;
         }
+    }
 
-        // Calculate step fee
-        let step_fee = calculate_step_fee(
-            amount_in,
-            amount_remaining,
-            amount_available,
-            fee_bps,
-            fee_divisor,
-        );
-
-        // Validate fee
-        if step_fee < 0 || step_fee > amount_in {
-            if allow_panic {
-                panic!("invalid fee calculation");
-            } else {
-                break;
-            }
-        }
+    (sqrt_price, liquidity, current_tick)
+}
 
-        // Calculate creator fee (percentage of LP fee)
-        let creator_fee = if creator_fee_bps > 0 && step_fee > 0 {
-            step_fee
-                .saturating_mul(creator_fee_bps)
-                .saturating_div(10000)
-        } else {
-            0
-        };
+// ============================================================
+// HELPER FUNCTIONS
+// ============================================================
 
-        // LP fee = total fee - creator fee
-        let lp_fee = step_fee.saturating_sub(creator_fee);
+/// Lowest sqrt price a swap may run down to
+const MIN_SQRT_LIMIT: u128 = 100;
 
-        // Update amounts
-        amount_remaining = amount_remaining
-            .saturating_sub(amount_in)
-            .saturating_sub(step_fee);
-        amount_out_total = amount_out_total.saturating_add(amount_out);
-        total_creator_fee = total_creator_fee.saturating_add(creator_fee);
+/// Highest sqrt price a swap may run up to
+const MAX_SQRT_LIMIT: u128 = u128::MAX - 1000;
 
-        // Update fee growth global for LP (Uniswap V3 style)
-        if liquidity > 0 && lp_fee > 0 {
-            let fee_u = lp_fee as u128;
-            let liq_u = liquidity as u128;
-            let growth_delta = div_q64(fee_u, liq_u);
+/// Price limit the swap loop runs against: the bound in the swap direction
+/// when there is no limit, otherwise the limit kept within those bounds
+fn resolve_sqrt_limit(sqrt_price_limit_x64: Option<u128>, zero_for_one: bool) -> u128 {
+    match sqrt_price_limit_x64 {
+        Some(limit) => limit.clamp(MIN_SQRT_LIMIT, MAX_SQRT_LIMIT),
+        None if zero_for_one => MIN_SQRT_LIMIT,
+        None => MAX_SQRT_LIMIT,
+    }
+}
 
-            if zero_for_one {
-                state.fee_growth_global_0 = state.fee_growth_global_0.wrapping_add(growth_delta);
-            } else {
-                state.fee_growth_global_1 = state.fee_growth_global_1.wrapping_add(growth_delta);
-            }
-        }
+/// Cross `tick` and return the new `(liquidity, current_tick)`
+/// 
+/// `liquidity_net` is the change when crossing left-to-right (added at a
+/// range's lower tick, subtracted at its upper tick), so it is added when
+/// the price rises and subtracted when it falls. After a leftward cross the
+/// pool sits just below the tick (`tick - 1`), as in Uniswap V3. Storage is
+/// only touched outside dry runs.
+#[allow(clippy::too_many_arguments)]
+fn cross_initialized_tick<F1, F2>(
+    env: &Env,
+    state: &SwapState,
+    read_tick: &F1,
+    write_tick: &F2,
+    tick: i32,
+    liquidity: i128,
+    zero_for_one: bool,
+    dry_run: bool,
+) -> (i128, i32)
+where
+    F1: Fn(&Env, i32) -> TickInfo,
+    F2: Fn(&Env, i32, &TickInfo),
+{
+    let liquidity_net = if dry_run {
+        read_tick(env, tick).liquidity_net
+    } else {
+        belugaswap_tick::cross_tick(
+            env,
+            read_tick,
+            write_tick,
+            tick,
+            state.fee_growth_global_0,
+            state.fee_growth_global_1,
+        )
+    };
 
-        // Handle tick crossing
-        let target_reached = sqrt_next == sqrt_target;
-        let should_cross = if zero_for_one {
-            sqrt_target <= sqrt_price
-        } else {
-            sqrt_target >= sqrt_price
-        };
-        let at_user_limit = sqrt_price_limit_x64 != 0 && sqrt_target == sqrt_limit;
+    if zero_for_one {
+        (liquidity.saturating_sub(liquidity_net), tick.saturating_sub(1))
+    } else {
+        (liquidity.saturating_add(liquidity_net), tick)
+    }
+}
 
-        if target_reached && should_cross && !at_user_limit {
-            sqrt_price = sqrt_target;
+/// Move the price across a range with no liquidity
+/// 
+/// Jumps straight to the next initialized tick in the swap direction and
+/// crosses it, returning `(crossed_tick, sqrt_price, liquidity, current_tick)`.
+/// Returns `None` when no initialized tick is left or the next one lies
+/// beyond the price limit; the swap then ends as a partial fill.
+#[allow(clippy::too_many_arguments)]
+fn skip_liquidity_gap<F1, F2, FW>(
+    env: &Env,
+    state: &SwapState,
+    read_tick: &F1,
+    read_word: &FW,
+    write_tick: &F2,
+    current_tick: i32,
+    zero_for_one: bool,
+    sqrt_limit: u128,
+    dry_run: bool,
+) -> Option<(i32, u128, i128, i32)>
+where
+    F1: Fn(&Env, i32) -> TickInfo,
+    F2: Fn(&Env, i32, &TickInfo),
+    FW: Fn(&Env, i32) -> TickBitmapWord,
+{
+    let next_tick = belugaswap_tick::find_next_initialized_tick(
+        env,
+        read_word,
+        current_tick,
+        state.tick_spacing,
+        zero_for_one,
+    );
 
-            // Cross tick - only modify storage if NOT dry_run
-            let liquidity_net = if dry_run {
-                let tick_info = read_tick(env, next_tick);
-                tick_info.liquidity_net
-            } else {
-                belugaswap_tick::cross_tick(
-                    env,
-                    &read_tick,
-                    &write_tick,
-                    next_tick,
-                    state.fee_growth_global_0,
-                    state.fee_growth_global_1,
-                )
-            };
+    // The search falls back to the starting tick when nothing is found, which
+    // is never a valid result when searching upwards
+    if (!zero_for_one && next_tick == current_tick) || !read_tick(env, next_tick).initialized {
+        return None;
+    }
 
-            // Update liquidity based on direction
-            if zero_for_one {
-                liquidity = liquidity.saturating_sub(liquidity_net);
-            } else {
-                liquidity = liquidity.saturating_add(liquidity_net);
-            }
+    let sqrt_tick = get_sqrt_ratio_at_tick(next_tick);
+    if (zero_for_one && sqrt_tick < sqrt_limit) || (!zero_for_one && sqrt_tick > sqrt_limit) {
+        return None;
+    }
 
-            // Update current tick
-            current_tick = if zero_for_one {
-                next_tick.saturating_sub(1)
-            } else {
-                next_tick
-            };
-        } else if sqrt_next != sqrt_price {
-            sqrt_price = sqrt_next;
+    let (liquidity, tick) = cross_initialized_tick(
+        env,
+        state,
+        read_tick,
+        write_tick,
+        next_tick,
+        0,
+        zero_for_one,
+        dry_run,
+    );
+    Some((next_tick, sqrt_tick, liquidity, tick))
+}
 
-            if amount_remaining <= 0 {
-                break;
-            }
-        } else {
-            break;
-        }
+/// Estimate the input (including fee) needed for `amount_out`
+/// 
+/// Walks the same ticks as the exact-input loop, inverting each step from the
+/// output still owed and rounding its input up. The fee is charged on the
+/// summed net input the way `available_after_fee` undoes it, so an exact-input
+/// swap with the estimate runs the same steps. Returns 0 when the output
+/// cannot be reached before liquidity or the price limit runs out.
+#[allow(clippy::too_many_arguments)]
+fn estimate_input_for_output<F, FW>(
+    env: &Env,
+    state: &SwapState,
+    read_tick: F,
+    read_word: FW,
+    amount_out: i128,
+    zero_for_one: bool,
+    sqrt_price_limit_x64: Option<u128>,
+    fee_bps: i128,
+) -> i128
+where
+    F: Fn(&Env, i32) -> TickInfo,
+    FW: Fn(&Env, i32) -> TickBitmapWord,
+{
+    let fee_divisor = 10000 - fee_bps;
+    if fee_divisor <= 0 {
+        return 0;
     }
 
-    // Validate output
-    if amount_out_total < MIN_OUTPUT_AMOUNT {
-        if allow_panic {
-            panic!("output amount too small");
-        } else {
-            return (0, 0, 0);
-        }
+    let mut remaining_out = amount_out;
+    let mut net_in: i128 = 0;
+
+    walk_ticks(
+        env,
+        &mut state.clone(),
+        read_tick,
+        read_word,
+        |_, _, _| {}, // no-op write
+        |_, _, _| {}, // no-op cross
+        zero_for_one,
+        sqrt_price_limit_x64,
+        false, // don't panic
+        true,  // dry_run
+        |_, sqrt_price, liquidity, sqrt_target| {
+            let (sqrt_next, step_in, step_out) =
+                invert_swap_step(env, sqrt_price, liquidity, remaining_out, zero_for_one, sqrt_target);
+            net_in = net_in.saturating_add(step_in);
+            remaining_out = remaining_out.saturating_sub(step_out);
+            Some((sqrt_next, remaining_out <= 0))
+        },
+    );
+
+    if remaining_out > 0 {
+        return 0;
     }
+    match net_in.checked_mul(fee_bps) {
+        Some(fee_num) => net_in.saturating_add(fee_num / fee_divisor),
+        None => 0,
+    }
+}
 
-    // Update state
-    state.sqrt_price_x64 = sqrt_price;
-    state.liquidity = liquidity;
-    state.current_tick = current_tick;
+/// Smallest step input (net of fee) that pays out `amount_out` within a range
+/// 
+/// Inverts the step from its output price and rounds the input up, then
+/// settles it against `compute_swap_step_with_target` so the exact-input step
+/// fed that input pays out at least `amount_out`. When the range holds less,
+/// the step runs to `sqrt_target`.
+/// 
+/// # Returns
+/// `(sqrt_price_next, amount_in, amount_out)`, with the price and output the
+/// exact-input step computes for `amount_in`
+fn invert_swap_step(
+    env: &Env,
+    sqrt_price: u128,
+    liquidity: i128,
+    amount_out: i128,
+    zero_for_one: bool,
+    sqrt_target: u128,
+) -> (u128, i128, i128) {
+    let liq_u = liquidity as u128;
+    let step = |amount_in: i128| {
+        let (sqrt_next, _, step_out) =
+            compute_swap_step_with_target(env, sqrt_price, liquidity, amount_in, zero_for_one, sqrt_target);
+        (sqrt_next, amount_in, step_out)
+    };
 
-    // Emit sync event (no-op in dry_run via callback)
-    emit_sync(env, state.current_tick, state.sqrt_price_x64);
+    let max_out = if zero_for_one {
+        get_amount_1_delta(sqrt_target, sqrt_price, liq_u, false)
+    } else {
+        get_amount_0_delta(sqrt_price, sqrt_target, liq_u, false)
+    };
+    let sqrt_next = if amount_out as u128 >= max_out {
+        sqrt_target
+    } else {
+        get_next_sqrt_price_from_output(env, sqrt_price, liq_u, amount_out as u128, zero_for_one)
+    };
+    let amount_in = u128_to_i128_saturating(if zero_for_one {
+        get_amount_0_delta(sqrt_next, sqrt_price, liq_u, true)
+    } else {
+        get_amount_1_delta(sqrt_price, sqrt_next, liq_u, true)
+    });
 
-    let amount_in_total = amount_specified.saturating_sub(amount_remaining);
-    (amount_in_total, amount_out_total, total_creator_fee)
+    let mut result = step(amount_in);
+    if sqrt_next == sqrt_target {
+        return result;
+    }
+
+    // The exact-input step rounds its price against the trader, so the
+    // inverted input can sit a unit or two off the smallest one that pays
+    while result.2 < amount_out && result.0 != sqrt_target && result.1 < i128::MAX {
+        result = step(result.1 + 1);
+    }
+    while result.1 > 1 {
+        let fewer = step(result.1 - 1);
+        if fewer.2 < amount_out {
+            break;
+        }
+        result = fewer;
+    }
+    result
 }
 
-// ============================================================
-// HELPER FUNCTIONS
-// ============================================================
+/// Fee owed on `amount_in` more net input after `net_in_before`
+/// 
+/// Fees are charged as the increase in `floor(net_in * fee_bps / fee_divisor)`
+/// over the whole swap, so rounding never accumulates across tick crossings.
+#[inline]
+fn fee_on_net_input(amount_in: i128, net_in_before: i128, fee_bps: i128, fee_divisor: i128) -> i128 {
+    let fee_before = net_in_before.saturating_mul(fee_bps) / fee_divisor;
+    let fee_after = net_in_before
+        .saturating_add(amount_in)
+        .saturating_mul(fee_bps)
+        / fee_divisor;
+    fee_after.saturating_sub(fee_before)
+}
+
+/// Largest net input whose fee still fits in `amount_remaining`
+/// 
+/// Starts from `amount_remaining * fee_divisor / 10000`, which always fits,
+/// and takes the one or two extra units the floor-rounded fee leaves room for.
+#[inline]
+fn available_after_fee(
+    env: &Env,
+    amount_remaining: i128,
+    net_in_before: i128,
+    fee_bps: i128,
+    fee_divisor: i128,
+) -> i128 {
+    let mut available = mul_bps(env, amount_remaining, fee_divisor as u32);
+    while available < amount_remaining {
+        let next = available + 1;
+        if next.saturating_add(fee_on_net_input(next, net_in_before, fee_bps, fee_divisor)) > amount_remaining {
+            break;
+        }
+        available = next;
+    }
+    available
+}
 
 /// Calculate the fee for a swap step
+/// 
+/// A step that consumes everything available takes the rest of the input as
+/// its fee. Otherwise it pays `fee_on_net_input`, which keeps the swap's total
+/// fee within one unit of `amount_in * fee_bps / 10000`.
 #[inline]
 fn calculate_step_fee(
     amount_in: i128,
     amount_remaining: i128,
     amount_available: i128,
+    net_in_before: i128,
     fee_bps: i128,
     fee_divisor: i128,
 ) -> i128 {
@@ -542,13 +1103,6 @@ fn calculate_step_fee(
         // Used all available amount
         amount_remaining.saturating_sub(amount_in)
     } else {
-        // Calculate fee on amount_in
-        let fee_num = amount_in.saturating_mul(fee_bps);
-        let fee = fee_num.saturating_div(fee_divisor);
-        if fee_num % fee_divisor != 0 {
-            fee.saturating_add(1) // Round up
-        } else {
-            fee
-        }
+        fee_on_net_input(amount_in, net_in_before, fee_bps, fee_divisor)
     }
 }
\ No newline at end of file
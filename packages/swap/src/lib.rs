@@ -4,13 +4,16 @@ pub mod types;
 pub mod engine;
 
 // Re-export types
-pub use types::{SwapResult, PreviewResult};
+pub use types::{SwapResult, PreviewResult, SwapError};
 
 // Re-export engine functions and types
 pub use engine::{
     SwapState, 
     engine_swap, 
     quote_swap, 
+    quote_swap_fees,
+    quote_swap_exact_output,
     validate_and_preview_swap,
+    price_impact_bps,
     // Note: engine_swap_safe is intentionally not exported (internal use only)
 };
\ No newline at end of file
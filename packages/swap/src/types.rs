@@ -1,27 +1,35 @@
-use soroban_sdk::{contracttype, Symbol};
+use soroban_sdk::{contracterror, contracttype, Symbol};
+
+/// Errors raised by the swap engine itself
+///
+/// Codes sit in the pool's swap range (500-599) so the pool's `PoolError`
+/// can mirror them and callers see a single typed error.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum SwapError {
+    /// Input below the state's `min_amount_in`
+    SwapAmountTooSmall = 500,
+    /// Output below the state's `min_amount_out`
+    OutputDust = 502,
+    /// Iteration budget exhausted with input left to swap
+    IterationLimit = 507,
+}
 
 #[contracttype]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct SwapResult {
     pub amount_in: i128,
     pub amount_out: i128,
     pub current_tick: i32,
     pub sqrt_price_x64: u128,
-}
-
-impl Default for SwapResult {
-    fn default() -> Self {
-        Self {
-            amount_in: 0,
-            amount_out: 0,
-            current_tick: 0,
-            sqrt_price_x64: 0,
-        }
-    }
+    /// Received input left unspent because the price limit (or liquidity)
+    /// ran out; the pool refunds it to the sender
+    pub amount_in_remaining: i128,
 }
 
 #[contracttype]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct PreviewResult {
     pub amount_in_used: i128,
     pub amount_out_expected: i128,
@@ -29,19 +37,8 @@ pub struct PreviewResult {
     pub price_impact_bps: i128,
     pub is_valid: bool,
     pub error_message: Option<Symbol>,
-}
-
-impl Default for PreviewResult {
-    fn default() -> Self {
-        Self {
-            amount_in_used: 0,
-            amount_out_expected: 0,
-            fee_paid: 0,
-            price_impact_bps: 0,
-            is_valid: false,
-            error_message: None,
-        }
-    }
+    /// Tick boundaries the swap would cross
+    pub steps: u32,
 }
 
 impl PreviewResult {
@@ -50,6 +47,7 @@ impl PreviewResult {
         amount_out_expected: i128,
         fee_paid: i128,
         price_impact_bps: i128,
+        steps: u32,
     ) -> Self {
         Self {
             amount_in_used,
@@ -58,6 +56,7 @@ impl PreviewResult {
             price_impact_bps,
             is_valid: true,
             error_message: None,
+            steps,
         }
     }
 
@@ -69,6 +68,7 @@ impl PreviewResult {
             price_impact_bps: 0,
             is_valid: false,
             error_message: Some(error),
+            steps: 0,
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,133 @@
+// Exact-output quotes across tick crossings
+// Run with: cargo test -p belugaswap-swap --test test_exact_output
+
+use belugaswap_math::{get_sqrt_ratio_at_tick, DEFAULT_SWAP_ITERATIONS};
+use belugaswap_swap::{quote_swap, quote_swap_exact_output, SwapState};
+use belugaswap_tick::{bitmap::position, TickBitmapWord, TickInfo};
+use proptest::prelude::*;
+use soroban_sdk::Env;
+
+const TICK_SPACING: i32 = 60;
+/// Nested ranges [-600k, 600k] for k = 1..=4, each holding the same liquidity
+const BOUNDARIES: [i32; 8] = [-2400, -1800, -1200, -600, 600, 1200, 1800, 2400];
+
+fn read_tick(liquidity: i128) -> impl Fn(&Env, i32) -> TickInfo + Clone {
+    move |_: &Env, tick: i32| {
+        if !BOUNDARIES.contains(&tick) {
+            return TickInfo::default();
+        }
+        TickInfo {
+            liquidity_gross: liquidity,
+            liquidity_net: if tick < 0 { liquidity } else { -liquidity },
+            initialized: true,
+            ..TickInfo::default()
+        }
+    }
+}
+
+fn read_word(_: &Env, word_pos: i32) -> TickBitmapWord {
+    let mut word = TickBitmapWord::default();
+    for tick in BOUNDARIES {
+        let (pos, bit) = position(tick / TICK_SPACING);
+        if pos == word_pos {
+            word.flip(bit);
+        }
+    }
+    word
+}
+
+fn state(liquidity: i128) -> SwapState {
+    SwapState {
+        sqrt_price_x64: get_sqrt_ratio_at_tick(0),
+        current_tick: 0,
+        liquidity: liquidity * 4,
+        tick_spacing: TICK_SPACING,
+        fee_growth_global_0: 0,
+        fee_growth_global_1: 0,
+        max_iterations: DEFAULT_SWAP_ITERATIONS,
+        min_amount_in: 1,
+        min_amount_out: 1,
+        creator_fee_cap_bps: 0,
+        lifetime_fees_in: 0,
+        lifetime_creator_fees_in: 0,
+    }
+}
+
+/// Exact-input quote output for `amount_in`
+fn quote_out(env: &Env, liquidity: i128, amount_in: i128, zero_for_one: bool, fee_bps: i128) -> i128 {
+    quote_swap(env, &state(liquidity), read_tick(liquidity), read_word, amount_in, zero_for_one, None, fee_bps).1
+}
+
+#[test]
+fn test_exact_output_across_crossings_is_smallest_input() {
+    let env = Env::default();
+    let liquidity = 1_000_000_000;
+    let amount_out = 200_000_000;
+
+    let (amount_in, quoted_out, _, final_tick, steps) = quote_swap_exact_output(
+        &env,
+        &state(liquidity),
+        read_tick(liquidity),
+        read_word,
+        amount_out,
+        true,
+        None,
+        30,
+    );
+    assert!(steps > 0 && final_tick < -600);
+    assert!(quoted_out >= amount_out);
+    assert!(quote_out(&env, liquidity, amount_in - 1, true, 30) < amount_out);
+}
+
+#[test]
+fn test_exact_output_beyond_liquidity_is_unreachable() {
+    let env = Env::default();
+    let liquidity = 1_000_000;
+
+    let (amount_in, quoted_out, _, _, steps) = quote_swap_exact_output(
+        &env,
+        &state(liquidity),
+        read_tick(liquidity),
+        read_word,
+        1_000_000_000,
+        false,
+        None,
+        30,
+    );
+    assert_eq!((amount_in, quoted_out, steps), (0, 0, 0));
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(200))]
+
+    /// Property: the quoted input covers the output and one unit less does not
+    #[test]
+    fn prop_exact_output_input_is_smallest(
+        liquidity in 1_000_000i128..1_000_000_000_000,
+        share in 1u32..1_000,
+        zero_for_one in any::<bool>(),
+        fee_bps in prop::sample::select(vec![1i128, 5, 30, 100]),
+    ) {
+        let env = Env::default();
+        // Up to roughly the output of the four ranges together
+        let amount_out = (liquidity / 1_000 * share as i128 / 10).max(1);
+
+        let (amount_in, quoted_out, _, _, _) = quote_swap_exact_output(
+            &env,
+            &state(liquidity),
+            read_tick(liquidity),
+            read_word,
+            amount_out,
+            zero_for_one,
+            None,
+            fee_bps,
+        );
+        prop_assume!(amount_in > 0);
+
+        prop_assert!(quoted_out >= amount_out);
+        prop_assert_eq!(quote_out(&env, liquidity, amount_in, zero_for_one, fee_bps), quoted_out);
+        if amount_in > 1 {
+            prop_assert!(quote_out(&env, liquidity, amount_in - 1, zero_for_one, fee_bps) < amount_out);
+        }
+    }
+}
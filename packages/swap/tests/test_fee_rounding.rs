@@ -0,0 +1,102 @@
+// Swap fee rounding across tick crossings
+// Run with: cargo test -p belugaswap-swap --test test_fee_rounding
+
+use belugaswap_math::{get_sqrt_ratio_at_tick, DEFAULT_SWAP_ITERATIONS};
+use belugaswap_swap::{engine_swap, SwapState};
+use belugaswap_tick::{bitmap::position, TickBitmapWord, TickInfo};
+use proptest::prelude::*;
+use soroban_sdk::Env;
+
+const TICK_SPACING: i32 = 60;
+/// Nested ranges [-600k, 600k] for k = 1..=4, each holding the same liquidity
+const BOUNDARIES: [i32; 8] = [-2400, -1800, -1200, -600, 600, 1200, 1800, 2400];
+
+/// Run a swap from tick 0 and return (state, amount_in, total_fee)
+fn run_swap(env: &Env, liquidity: i128, amount: i128, zero_for_one: bool, fee_bps: i128) -> (SwapState, i128, i128) {
+    let read_tick = move |_: &Env, tick: i32| {
+        if !BOUNDARIES.contains(&tick) {
+            return TickInfo::default();
+        }
+        TickInfo {
+            liquidity_gross: liquidity,
+            liquidity_net: if tick < 0 { liquidity } else { -liquidity },
+            initialized: true,
+            ..TickInfo::default()
+        }
+    };
+    let read_word = |_: &Env, word_pos: i32| {
+        let mut word = TickBitmapWord::default();
+        for tick in BOUNDARIES {
+            let (pos, bit) = position(tick / TICK_SPACING);
+            if pos == word_pos {
+                word.flip(bit);
+            }
+        }
+        word
+    };
+
+    let mut state = SwapState {
+        sqrt_price_x64: get_sqrt_ratio_at_tick(0),
+        current_tick: 0,
+        liquidity: liquidity * 4,
+        tick_spacing: TICK_SPACING,
+        fee_growth_global_0: 0,
+        fee_growth_global_1: 0,
+        max_iterations: DEFAULT_SWAP_ITERATIONS,
+        min_amount_in: 1,
+        min_amount_out: 1,
+        creator_fee_cap_bps: 0,
+        lifetime_fees_in: 0,
+        lifetime_creator_fees_in: 0,
+    };
+    let (amount_in, _, creator_fee, protocol_fee, lp_fee) = engine_swap(
+        env,
+        &mut state,
+        read_tick,
+        read_word,
+        |_, _, _| {},
+        |_, _, _| {},
+        |_, _, _| {},
+        amount,
+        zero_for_one,
+        None,
+        fee_bps,
+        1000,
+        1000,
+    );
+    (state, amount_in, creator_fee + protocol_fee + lp_fee)
+}
+
+#[test]
+fn test_fee_rounding_does_not_accumulate_per_crossing() {
+    let env = Env::default();
+    // Crosses all four lower boundaries
+    let (state, amount_in, total_fee) = run_swap(&env, 1_000_000_000, 1_000_000_000, true, 30);
+    assert!(state.current_tick < -2400);
+    assert!(amount_in < 1_000_000_000);
+
+    let closed_form = amount_in * 30 / 10_000;
+    assert!((total_fee - closed_form).abs() <= 1, "fee {} vs {}", total_fee, closed_form);
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(200))]
+
+    /// Property: the summed per-step fee stays within one unit of
+    /// amount_in * fee_bps / 10000, however many ticks the swap crosses
+    #[test]
+    fn prop_total_fee_matches_closed_form(
+        liquidity in 1_000_000i128..1_000_000_000_000,
+        amount in 1_000i128..100_000_000_000,
+        zero_for_one in any::<bool>(),
+        fee_bps in prop::sample::select(vec![1i128, 5, 30, 100]),
+    ) {
+        let env = Env::default();
+        let (_, amount_in, total_fee) = run_swap(&env, liquidity, amount, zero_for_one, fee_bps);
+
+        // Exact fee is amount_in * fee_bps / 10000; allow one unit either side
+        let diff = total_fee * 10_000 - amount_in * fee_bps;
+        prop_assert!(diff.abs() <= 10_000, "fee {} for input {} at {} bps", total_fee, amount_in, fee_bps);
+        prop_assert!(total_fee <= amount * fee_bps / 10_000 + 1);
+    }
+}
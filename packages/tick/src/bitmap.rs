@@ -0,0 +1,141 @@
+// Tick Bitmap
+//
+// Compressed bitmap of initialized ticks (Uniswap V3 TickBitmap). Each word
+// covers 256 consecutive multiples of the tick spacing, so the next
+// initialized tick is found with one storage read per word instead of one
+// per spaced tick.
+
+use soroban_sdk::Env;
+use crate::types::TickBitmapWord;
+
+// ============================================================
+// WORD OPERATIONS
+// ============================================================
+
+impl TickBitmapWord {
+    /// Whether `bit` (0-255) is set
+    #[inline]
+    pub fn is_set(&self, bit: u32) -> bool {
+        if bit < 128 {
+            self.low & (1u128 << bit) != 0
+        } else {
+            self.high & (1u128 << (bit - 128)) != 0
+        }
+    }
+
+    /// Toggle `bit` (0-255)
+    #[inline]
+    pub fn flip(&mut self, bit: u32) {
+        if bit < 128 {
+            self.low ^= 1u128 << bit;
+        } else {
+            self.high ^= 1u128 << (bit - 128);
+        }
+    }
+
+    /// Highest set bit at or below `bit`
+    fn highest_set_at_or_below(&self, bit: u32) -> Option<u32> {
+        if bit >= 128 {
+            let masked = self.high & mask_up_to(bit - 128);
+            if masked != 0 {
+                return Some(255 - masked.leading_zeros());
+            }
+            return (self.low != 0).then(|| 127 - self.low.leading_zeros());
+        }
+        let masked = self.low & mask_up_to(bit);
+        (masked != 0).then(|| 127 - masked.leading_zeros())
+    }
+
+    /// Lowest set bit at or above `bit`
+    fn lowest_set_at_or_above(&self, bit: u32) -> Option<u32> {
+        if bit < 128 {
+            let masked = self.low & (u128::MAX << bit);
+            if masked != 0 {
+                return Some(masked.trailing_zeros());
+            }
+            return (self.high != 0).then(|| 128 + self.high.trailing_zeros());
+        }
+        let masked = self.high & (u128::MAX << (bit - 128));
+        (masked != 0).then(|| 128 + masked.trailing_zeros())
+    }
+}
+
+/// Mask of bits 0..=bit within a 128-bit half
+#[inline]
+fn mask_up_to(bit: u32) -> u128 {
+    if bit >= 127 {
+        u128::MAX
+    } else {
+        (1u128 << (bit + 1)) - 1
+    }
+}
+
+/// Word index and bit index of a compressed tick
+#[inline]
+pub fn position(compressed: i32) -> (i32, u32) {
+    (compressed >> 8, (compressed & 0xff) as u32)
+}
+
+// ============================================================
+// BITMAP UPDATE
+// ============================================================
+
+/// Flip the initialized bit of `tick`
+///
+/// `tick` must be a multiple of `tick_spacing`.
+pub fn flip_tick<F1, F2>(
+    env: &Env,
+    read_word: F1,
+    write_word: F2,
+    tick: i32,
+    tick_spacing: i32,
+)
+where
+    F1: Fn(&Env, i32) -> TickBitmapWord,
+    F2: Fn(&Env, i32, &TickBitmapWord),
+{
+    let (word_pos, bit_pos) = position(tick.div_euclid(tick_spacing));
+    let mut word = read_word(env, word_pos);
+    word.flip(bit_pos);
+    write_word(env, word_pos, &word);
+}
+
+// ============================================================
+// BITMAP SEARCH
+// ============================================================
+
+/// Next initialized tick within the word holding `tick` (Uniswap V3
+/// `nextInitializedTickWithinOneWord`)
+///
+/// With `lte` the search covers ticks at or below `tick`, otherwise ticks
+/// strictly above it. Reads exactly one word.
+///
+/// # Returns
+/// `(next_tick, initialized)`. When nothing is set, `next_tick` is the last
+/// tick of the word in the search direction and `initialized` is false.
+pub fn next_initialized_tick_within_one_word<F>(
+    env: &Env,
+    read_word: F,
+    tick: i32,
+    tick_spacing: i32,
+    lte: bool,
+) -> (i32, bool)
+where
+    F: Fn(&Env, i32) -> TickBitmapWord,
+{
+    let compressed = tick.div_euclid(tick_spacing);
+
+    if lte {
+        let (word_pos, bit_pos) = position(compressed);
+        match read_word(env, word_pos).highest_set_at_or_below(bit_pos) {
+            Some(bit) => ((compressed - (bit_pos - bit) as i32).saturating_mul(tick_spacing), true),
+            None => ((compressed - bit_pos as i32).saturating_mul(tick_spacing), false),
+        }
+    } else {
+        let (word_pos, bit_pos) = position(compressed + 1);
+        match read_word(env, word_pos).lowest_set_at_or_above(bit_pos) {
+            Some(bit) => ((compressed + 1 + (bit - bit_pos) as i32).saturating_mul(tick_spacing), true),
+            None => ((compressed + 1 + (255 - bit_pos) as i32).saturating_mul(tick_spacing), false),
+        }
+    }
+}
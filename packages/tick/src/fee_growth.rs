@@ -15,24 +15,25 @@ pub fn get_fee_growth_inside(
     fee_growth_global_0: u128,
     fee_growth_global_1: u128,
 ) -> (u128, u128) {
-    let lower_info = read_tick(env, lower_tick);
-    let upper_info = read_tick(env, upper_tick);
+    let global = (fee_growth_global_0, fee_growth_global_1);
+    let lower_outside = fee_growth_outside(&read_tick(env, lower_tick), lower_tick, current_tick, global);
+    let upper_outside = fee_growth_outside(&read_tick(env, upper_tick), upper_tick, current_tick, global);
 
     let (fee_growth_below_0, fee_growth_below_1) = if current_tick >= lower_tick {
-        (lower_info.fee_growth_outside_0, lower_info.fee_growth_outside_1)
+        lower_outside
     } else {
         (
-            fee_growth_global_0.wrapping_sub(lower_info.fee_growth_outside_0),
-            fee_growth_global_1.wrapping_sub(lower_info.fee_growth_outside_1),
+            fee_growth_global_0.wrapping_sub(lower_outside.0),
+            fee_growth_global_1.wrapping_sub(lower_outside.1),
         )
     };
 
     let (fee_growth_above_0, fee_growth_above_1) = if current_tick < upper_tick {
-        (upper_info.fee_growth_outside_0, upper_info.fee_growth_outside_1)
+        upper_outside
     } else {
         (
-            fee_growth_global_0.wrapping_sub(upper_info.fee_growth_outside_0),
-            fee_growth_global_1.wrapping_sub(upper_info.fee_growth_outside_1),
+            fee_growth_global_0.wrapping_sub(upper_outside.0),
+            fee_growth_global_1.wrapping_sub(upper_outside.1),
         )
     };
 
@@ -46,3 +47,18 @@ pub fn get_fee_growth_inside(
 
     (fee_growth_inside_0, fee_growth_inside_1)
 }
+
+/// Fee growth outside a tick, reading an uninitialized tick as if it were
+/// initialized now (the seeding `update_tick` uses)
+///
+/// A default-zeroed tick would otherwise read as "all fees earned above
+/// it", crediting an empty range with fees it never earned.
+fn fee_growth_outside(info: &TickInfo, tick: i32, current_tick: i32, global: (u128, u128)) -> (u128, u128) {
+    if info.initialized {
+        (info.fee_growth_outside_0, info.fee_growth_outside_1)
+    } else if current_tick >= tick {
+        global
+    } else {
+        (0, 0)
+    }
+}
@@ -2,10 +2,12 @@
 
 pub mod types;
 pub mod update;
+pub mod bitmap;
 pub mod fee_growth;
+pub mod seconds;
 
 // Re-export types
-pub use types::TickInfo;
+pub use types::{TickInfo, TickBitmapWord};
 
 // Re-export update functions
 pub use update::{
@@ -17,8 +19,14 @@ pub use update::{
     align_tick,       // <-- ADDED: was missing
 };
 
+// Re-export bitmap functions
+pub use bitmap::{flip_tick, next_initialized_tick_within_one_word};
+
 // Re-export fee growth functions
 pub use fee_growth::get_fee_growth_inside;
 
+// Re-export seconds-inside functions
+pub use seconds::get_seconds_inside;
+
 // Re-export from math for convenience
-pub use belugaswap_math::snap_tick_to_spacing;
\ No newline at end of file
+pub use belugaswap_math::{snap_tick_to_spacing, snap_tick_to_spacing_ceil};
\ No newline at end of file
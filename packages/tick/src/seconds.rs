@@ -0,0 +1,36 @@
+// Seconds Inside Calculations
+
+use soroban_sdk::Env;
+use crate::types::TickInfo;
+
+/// Seconds the price has spent inside [lower_tick, upper_tick)
+/// 
+/// Same below/above decomposition as `get_fee_growth_inside`, with the
+/// ledger timestamp as the global value. Like fee growth inside, only the
+/// wrapping difference of two readings taken while both ticks stay
+/// initialized is meaningful: it is the in-range time for that period.
+pub fn get_seconds_inside(
+    env: &Env,
+    read_tick: impl Fn(&Env, i32) -> TickInfo,
+    lower_tick: i32,
+    upper_tick: i32,
+    current_tick: i32,
+) -> u64 {
+    let now = env.ledger().timestamp();
+    let lower_info = read_tick(env, lower_tick);
+    let upper_info = read_tick(env, upper_tick);
+
+    let seconds_below = if current_tick >= lower_tick {
+        lower_info.seconds_outside
+    } else {
+        now.wrapping_sub(lower_info.seconds_outside)
+    };
+
+    let seconds_above = if current_tick < upper_tick {
+        upper_info.seconds_outside
+    } else {
+        now.wrapping_sub(upper_info.seconds_outside)
+    };
+
+    now.wrapping_sub(seconds_below).wrapping_sub(seconds_above)
+}
@@ -4,7 +4,7 @@ use soroban_sdk::contracttype;
 
 /// Information stored for each initialized tick
 #[contracttype]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct TickInfo {
     /// Total liquidity referencing this tick
     pub liquidity_gross: i128,
@@ -14,18 +14,21 @@ pub struct TickInfo {
     pub fee_growth_outside_0: u128,
     /// Fee growth outside this tick for token1
     pub fee_growth_outside_1: u128,
+    /// Seconds spent on the other side of this tick (ledger timestamp based)
+    pub seconds_outside: u64,
     /// Whether this tick is initialized
     pub initialized: bool,
 }
 
-impl Default for TickInfo {
-    fn default() -> Self {
-        Self {
-            liquidity_gross: 0,
-            liquidity_net: 0,
-            fee_growth_outside_0: 0,
-            fee_growth_outside_1: 0,
-            initialized: false,
-        }
-    }
+/// One 256-tick word of the initialized-tick bitmap
+///
+/// Bit `i` is set when compressed tick `word_pos * 256 + i` is initialized,
+/// where a compressed tick is `tick / tick_spacing`.
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TickBitmapWord {
+    /// Bits 0-127
+    pub low: u128,
+    /// Bits 128-255
+    pub high: u128,
 }
@@ -5,7 +5,8 @@ use belugaswap_math::{
     constants::{MAX_TICK, MAX_TICK_SEARCH_STEPS, MIN_TICK}, 
     snap_tick_to_spacing
 };
-use crate::types::TickInfo;
+use crate::bitmap::{flip_tick, next_initialized_tick_within_one_word};
+use crate::types::{TickBitmapWord, TickInfo};
 
 // ============================================================
 // TICK UPDATE (Called when modifying liquidity)
@@ -14,15 +15,19 @@ use crate::types::TickInfo;
 /// Update a tick when liquidity is added or removed
 /// 
 /// This follows Uniswap V3's Tick.update() pattern:
-/// - Initializes fee_growth_outside based on current tick position
+/// - Initializes fee_growth_outside and seconds_outside based on current tick position
 /// - Updates liquidity_gross and liquidity_net
+/// - Flips the tick's bit in the tick bitmap when it is (un)initialized
 /// - Returns true if tick was flipped (initialized/uninitialized)
 /// 
 /// # Arguments
 /// * `env` - Soroban environment
 /// * `read_tick` - Callback to read tick info from storage
 /// * `write_tick` - Callback to write tick info to storage
+/// * `read_word` - Callback to read a tick bitmap word from storage
+/// * `write_word` - Callback to write a tick bitmap word to storage
 /// * `tick` - Tick index to update
+/// * `tick_spacing` - Pool's tick spacing
 /// * `current_tick` - Current pool tick
 /// * `liquidity_delta` - Liquidity change (positive = add, negative = remove)
 /// * `fee_growth_global_0` - Current global fee growth for token0
@@ -31,11 +36,15 @@ use crate::types::TickInfo;
 /// 
 /// # Returns
 /// `true` if the tick was flipped from uninitialized to initialized (or vice versa)
-pub fn update_tick<F1, F2>(
+#[allow(clippy::too_many_arguments)]
+pub fn update_tick<F1, F2, F3, F4>(
     env: &Env,
     read_tick: F1,
     write_tick: F2,
+    read_word: F3,
+    write_word: F4,
     tick: i32,
+    tick_spacing: i32,
     current_tick: i32,
     liquidity_delta: i128,
     fee_growth_global_0: u128,
@@ -45,6 +54,8 @@ pub fn update_tick<F1, F2>(
 where
     F1: Fn(&Env, i32) -> TickInfo,
     F2: Fn(&Env, i32, &TickInfo),
+    F3: Fn(&Env, i32) -> TickBitmapWord,
+    F4: Fn(&Env, i32, &TickBitmapWord),
 {
     let mut info = read_tick(env, tick);
 
@@ -62,13 +73,16 @@ where
     if liquidity_gross_before == 0 && liquidity_gross_after > 0 {
         // Initialize fee_growth_outside based on current tick position
         // Convention: if current_tick >= tick, assume all fees were earned BELOW this tick
+        // (and all time so far was spent below it)
         if current_tick >= tick {
             info.fee_growth_outside_0 = fee_growth_global_0;
             info.fee_growth_outside_1 = fee_growth_global_1;
+            info.seconds_outside = env.ledger().timestamp();
         } else {
             // All fees were earned ABOVE this tick
             info.fee_growth_outside_0 = 0;
             info.fee_growth_outside_1 = 0;
+            info.seconds_outside = 0;
         }
         info.initialized = true;
     }
@@ -91,6 +105,10 @@ where
 
     write_tick(env, tick, &info);
 
+    if flipped {
+        flip_tick(env, read_word, write_word, tick, tick_spacing);
+    }
+
     flipped
 }
 
@@ -103,6 +121,8 @@ where
 /// This is the core Uniswap V3 fee tracking mechanism:
 /// - `fee_growth_outside` represents fees accumulated on the "other side" of the tick
 /// - When crossing, we flip it: `new_outside = global - old_outside`
+/// - `seconds_outside` flips the same way, with the ledger timestamp as the
+///   global value
 /// 
 /// # Arguments
 /// * `env` - Soroban environment
@@ -132,6 +152,7 @@ where
     // After crossing: outside = global - previous_outside
     info.fee_growth_outside_0 = fee_growth_global_0.wrapping_sub(info.fee_growth_outside_0);
     info.fee_growth_outside_1 = fee_growth_global_1.wrapping_sub(info.fee_growth_outside_1);
+    info.seconds_outside = env.ledger().timestamp().wrapping_sub(info.seconds_outside);
 
     write_tick(env, tick, &info);
 
@@ -145,11 +166,18 @@ where
 /// Find the next initialized tick in the given direction
 /// 
 /// This is used during swaps to find the next tick boundary
-/// where liquidity changes.
+/// where liquidity changes. The search walks the tick bitmap one 256-tick
+/// word at a time, so sparse ranges cost one storage read per word rather
+/// than one per spaced tick.
+/// 
+/// Searching left includes the current tick's own boundary (`<= current_tick`):
+/// a pool at tick `t` has not crossed `t` yet, so it is the next boundary to
+/// cross. Searching right is strictly above (`> current_tick`). This matches
+/// Uniswap V3's `nextInitializedTickWithinOneWord(lte)`.
 /// 
 /// # Arguments
 /// * `env` - Soroban environment
-/// * `read_tick` - Callback to read tick info from storage
+/// * `read_word` - Callback to read a tick bitmap word from storage
 /// * `current_tick` - Starting tick
 /// * `tick_spacing` - Pool's tick spacing
 /// * `zero_for_one` - Direction (true = search left/down, false = search right/up)
@@ -158,44 +186,35 @@ where
 /// The next initialized tick index, or `current_tick` if none found within search limits
 pub fn find_next_initialized_tick<F>(
     env: &Env,
-    read_tick: F,
+    read_word: F,
     current_tick: i32,
     tick_spacing: i32,
     zero_for_one: bool,
 ) -> i32 
 where
-    F: Fn(&Env, i32) -> TickInfo,
+    F: Fn(&Env, i32) -> TickBitmapWord,
 {
     if tick_spacing <= 0 {
         return current_tick;
     }
 
-    let step = if zero_for_one {
-        -tick_spacing
-    } else {
-        tick_spacing
-    };
-
-    // Start from aligned tick
-    let mut tick = snap_tick_to_spacing(current_tick, tick_spacing);
-
-    // Move to next tick boundary
-    tick = tick.saturating_add(step);
+    let mut tick = current_tick;
 
     for _ in 0..MAX_TICK_SEARCH_STEPS {
+        let (next, initialized) =
+            next_initialized_tick_within_one_word(env, &read_word, tick, tick_spacing, zero_for_one);
+
         // Check bounds
-        if !(MIN_TICK..=MAX_TICK).contains(&tick) {
+        if !(MIN_TICK..=MAX_TICK).contains(&next) {
             return current_tick;
         }
 
-        let info = read_tick(env, tick);
-
-        // Found initialized tick with liquidity
-        if info.initialized && info.liquidity_gross > 0 {
-            return tick;
+        if initialized {
+            return next;
         }
 
-        tick = tick.saturating_add(step);
+        // Continue from the edge of the word just searched
+        tick = if zero_for_one { next - 1 } else { next };
     }
 
     current_tick
@@ -214,7 +233,7 @@ where
 /// `true` if tick is within [MIN_TICK, MAX_TICK]
 #[inline]
 pub fn is_valid_tick(tick: i32) -> bool {
-    tick >= MIN_TICK && tick <= MAX_TICK
+    (MIN_TICK..=MAX_TICK).contains(&tick)
 }
 
 /// Check if a tick is properly aligned to spacing
@@ -0,0 +1,145 @@
+// The bitmap search must agree with a tick-by-tick scan over the same
+// liquidity, while reading one bitmap word per 256 spaced ticks.
+
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+
+use belugaswap_math::constants::{MAX_TICK, MIN_TICK};
+use belugaswap_tick::{find_next_initialized_tick, update_tick, TickBitmapWord, TickInfo};
+use soroban_sdk::Env;
+
+const SPACING: i32 = 10;
+
+/// In-memory tick and bitmap storage with read counters
+#[derive(Default)]
+struct Store {
+    ticks: RefCell<BTreeMap<i32, TickInfo>>,
+    words: RefCell<BTreeMap<i32, TickBitmapWord>>,
+    tick_reads: Cell<u32>,
+    word_reads: Cell<u32>,
+}
+
+impl Store {
+    fn read_tick(&self, tick: i32) -> TickInfo {
+        self.tick_reads.set(self.tick_reads.get() + 1);
+        self.ticks.borrow().get(&tick).cloned().unwrap_or_default()
+    }
+
+    fn read_word(&self, word_pos: i32) -> TickBitmapWord {
+        self.word_reads.set(self.word_reads.get() + 1);
+        self.words.borrow().get(&word_pos).cloned().unwrap_or_default()
+    }
+
+    fn update(&self, env: &Env, tick: i32, liquidity_delta: i128, upper: bool) {
+        update_tick(
+            env,
+            |_, t| self.ticks.borrow().get(&t).cloned().unwrap_or_default(),
+            |_, t, info| {
+                self.ticks.borrow_mut().insert(t, info.clone());
+            },
+            |_, w| self.words.borrow().get(&w).cloned().unwrap_or_default(),
+            |_, w, word| {
+                self.words.borrow_mut().insert(w, word.clone());
+            },
+            tick,
+            SPACING,
+            0,
+            liquidity_delta,
+            0,
+            0,
+            upper,
+        );
+    }
+
+    fn bitmap_search(&self, env: &Env, current_tick: i32, zero_for_one: bool) -> i32 {
+        find_next_initialized_tick(env, |_, w| self.read_word(w), current_tick, SPACING, zero_for_one)
+    }
+
+    /// Reference: step through every spaced tick
+    fn linear_search(&self, current_tick: i32, zero_for_one: bool) -> i32 {
+        let step = if zero_for_one { -SPACING } else { SPACING };
+        let mut tick = current_tick.div_euclid(SPACING) * SPACING;
+        if !zero_for_one {
+            tick += step;
+        }
+        while (MIN_TICK..=MAX_TICK).contains(&tick) {
+            let info = self.read_tick(tick);
+            if info.initialized && info.liquidity_gross > 0 {
+                return tick;
+            }
+            tick += step;
+        }
+        current_tick
+    }
+}
+
+const SCATTERED: [i32; 10] = [-887_270, -50_000, -2_560, -600, -10, 0, 2_550, 2_560, 40_000, 887_270];
+
+fn scattered_store(env: &Env) -> Store {
+    let store = Store::default();
+    for (i, tick) in SCATTERED.iter().enumerate() {
+        store.update(env, *tick, 1_000, i % 2 == 1);
+    }
+    store
+}
+
+#[test]
+fn test_bitmap_search_matches_linear_scan() {
+    let env = Env::default();
+    let store = scattered_store(&env);
+
+    let mut queries = std::vec::Vec::new();
+    for tick in SCATTERED {
+        queries.extend([tick - 11, tick - 10, tick - 1, tick, tick + 1, tick + 9, tick + 10]);
+    }
+    queries.extend([-887_272, -300_000, -2_561, -2_305, -1, 5, 2_559, 100_000, 887_272]);
+
+    for tick in queries.into_iter().filter(|t| (MIN_TICK..=MAX_TICK).contains(t)) {
+        for zero_for_one in [true, false] {
+            assert_eq!(
+                store.bitmap_search(&env, tick, zero_for_one),
+                store.linear_search(tick, zero_for_one),
+                "tick {} zero_for_one {}",
+                tick,
+                zero_for_one
+            );
+        }
+    }
+}
+
+#[test]
+fn test_bitmap_search_reads_far_fewer_slots() {
+    let env = Env::default();
+    let store = Store::default();
+    store.update(&env, -100_000, 1_000, false);
+    store.update(&env, 100_000, 1_000, true);
+
+    for zero_for_one in [true, false] {
+        store.word_reads.set(0);
+        store.tick_reads.set(0);
+
+        let found = store.bitmap_search(&env, 0, zero_for_one);
+        assert_eq!(found, store.linear_search(0, zero_for_one));
+
+        // 10_000 spaced ticks away: one read per tick vs one per 256
+        assert!(store.tick_reads.get() >= 10_000);
+        assert!(store.word_reads.get() <= 41, "word reads {}", store.word_reads.get());
+    }
+}
+
+#[test]
+fn test_removed_tick_is_cleared_from_bitmap() {
+    let env = Env::default();
+    let store = scattered_store(&env);
+
+    assert_eq!(store.bitmap_search(&env, 1_000, false), 2_550);
+
+    // Fully removing the liquidity flips the bit back off
+    store.update(&env, 2_550, -1_000, true);
+    assert_eq!(store.bitmap_search(&env, 1_000, false), 2_560);
+    assert_eq!(store.linear_search(1_000, false), 2_560);
+
+    // A partial removal keeps it set
+    store.update(&env, 2_560, -400, false);
+    assert_eq!(store.bitmap_search(&env, 1_000, false), 2_560);
+}
@@ -0,0 +1,43 @@
+// Fee growth inside a range must not credit uninitialized (default-zeroed)
+// boundary ticks with fees earned before they existed.
+
+use belugaswap_tick::{get_fee_growth_inside, TickInfo};
+use soroban_sdk::Env;
+
+const GLOBAL: (u128, u128) = (5_000, 7_000);
+
+fn initialized(fee_growth_outside_0: u128, fee_growth_outside_1: u128) -> TickInfo {
+    TickInfo {
+        liquidity_gross: 1,
+        fee_growth_outside_0,
+        fee_growth_outside_1,
+        initialized: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_uninitialized_ticks_have_no_growth_inside() {
+    let env = Env::default();
+
+    // Current tick below, inside and above an untouched range
+    for current_tick in [-1200, 0, 1200] {
+        let inside = get_fee_growth_inside(&env, |_, _| TickInfo::default(), -600, 600, current_tick, GLOBAL.0, GLOBAL.1);
+        assert_eq!(inside, (0, 0), "current tick {}", current_tick);
+    }
+}
+
+#[test]
+fn test_initialized_ticks_keep_their_outside_growth() {
+    let env = Env::default();
+
+    // Lower seeded at 1_000/2_000 below the price, upper never crossed
+    let read = |_: &Env, tick: i32| if tick == -600 { initialized(1_000, 2_000) } else { initialized(0, 0) };
+    let inside = get_fee_growth_inside(&env, read, -600, 600, 0, GLOBAL.0, GLOBAL.1);
+    assert_eq!(inside, (4_000, 5_000));
+
+    // Same range with the upper tick uninitialized reads the same: an
+    // uninitialized tick above the price counts as zero outside growth
+    let read = |_: &Env, tick: i32| if tick == -600 { initialized(1_000, 2_000) } else { TickInfo::default() };
+    assert_eq!(get_fee_growth_inside(&env, read, -600, 600, 0, GLOBAL.0, GLOBAL.1), (4_000, 5_000));
+}